@@ -1,4 +1,4 @@
-#[cfg(not(target_os = "redox"))]
+#[cfg(all(feature = "alloc", not(target_os = "redox")))]
 pub(crate) mod dir;
 #[cfg(linux_kernel)]
 pub mod inotify;
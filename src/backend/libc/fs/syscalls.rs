@@ -31,8 +31,21 @@ use crate::fs::FallocateFlags;
 use crate::fs::FlockOperation;
 #[cfg(any(linux_kernel, target_os = "freebsd"))]
 use crate::fs::MemfdFlags;
+#[cfg(all(linux_kernel, feature = "alloc"))]
+use crate::fs::MntIdReq;
 #[cfg(any(linux_kernel, target_os = "freebsd", target_os = "fuchsia"))]
 use crate::fs::SealFlags;
+#[cfg(all(
+    linux_kernel,
+    any(
+        target_arch = "aarch64",
+        target_arch = "riscv32",
+        target_arch = "riscv64",
+        target_arch = "x86",
+        target_arch = "x86_64",
+    )
+))]
+use crate::fs::SecretFlags;
 #[cfg(not(any(
     solarish,
     target_os = "haiku",
@@ -42,6 +55,8 @@ use crate::fs::SealFlags;
 )))]
 use crate::fs::StatFs;
 use crate::fs::{Access, Mode, OFlags, SeekFrom, Stat, Timestamps};
+#[cfg(target_os = "linux")]
+use crate::fs::{Cachestat, SyncFileRangeFlags};
 #[cfg(not(any(apple, target_os = "redox", target_os = "wasi")))]
 use crate::fs::{Dev, FileType};
 #[cfg(not(any(target_os = "haiku", target_os = "redox", target_os = "wasi")))]
@@ -59,19 +74,37 @@ use crate::ugid::{Gid, Uid};
     target_env = "gnu",
 )))]
 use crate::utils::as_ptr;
+#[cfg(not(any(
+    target_os = "emscripten",
+    target_os = "fuchsia",
+    target_os = "redox",
+    target_os = "wasi"
+)))]
+use crate::{
+    fs::{Flock, FlockOffsetType, FlockType},
+    pid::Pid,
+};
 #[cfg(apple)]
 use alloc::vec;
 use core::mem::MaybeUninit;
-#[cfg(apple)]
-use {
-    crate::backend::conv::nonnegative_ret,
-    crate::fs::{copyfile_state_t, CloneFlags, CopyfileFlags},
-};
 #[cfg(linux_kernel)]
 use {
+    super::types::FsConfigCmd,
     crate::backend::conv::{syscall_ret, syscall_ret_owned_fd, syscall_ret_usize},
-    crate::fs::{RenameFlags, ResolveFlags, Statx, StatxFlags, CWD},
+    crate::fs::{
+        DnotifyFlags, FdOwner, FsXFlags, FsXattr, InodeFlags, LeaseType, RawFiemap, RenameFlags,
+        ResolveFlags, Statx, StatxFlags, CWD,
+    },
     core::ptr::null,
+    linux_raw_sys::general::{
+        __NR_fsconfig, __NR_fsmount, __NR_fsopen, __NR_fspick, __NR_mount_setattr, __NR_move_mount,
+        __NR_name_to_handle_at, __NR_open_by_handle_at, __NR_open_tree,
+    },
+};
+#[cfg(apple)]
+use {
+    crate::backend::conv::nonnegative_ret,
+    crate::fs::{copyfile_state_t, CloneFlags, CopyfileFlags},
 };
 #[cfg(any(apple, linux_kernel))]
 use {crate::fs::XattrFlags, core::mem::size_of, core::ptr::null_mut};
@@ -208,7 +241,7 @@ pub(crate) fn statvfs(filename: &CStr) -> io::Result<StatVfs> {
 }
 
 #[inline]
-pub(crate) fn readlink(path: &CStr, buf: &mut [u8]) -> io::Result<usize> {
+pub(crate) fn readlink(path: &CStr, buf: &mut [MaybeUninit<u8>]) -> io::Result<usize> {
     unsafe {
         ret_usize(c::readlink(
             c_str(path),
@@ -1098,6 +1131,197 @@ pub(crate) fn fcntl_lock(fd: BorrowedFd<'_>, operation: FlockOperation) -> io::R
     }
 }
 
+#[cfg(not(any(
+    target_os = "emscripten",
+    target_os = "fuchsia",
+    target_os = "redox",
+    target_os = "wasi"
+)))]
+#[inline]
+pub(crate) fn fcntl_setlk(fd: BorrowedFd<'_>, lock: &Flock, wait: bool) -> io::Result<()> {
+    // On Linux, use the `F_*LK64` commands, which take a `struct flock64`,
+    // so that 32-bit platforms can lock ranges beyond 2 GiB.
+    #[cfg(not(all(linux_kernel, target_pointer_width = "32")))]
+    use c::{F_SETLK, F_SETLKW};
+    #[cfg(all(linux_kernel, target_pointer_width = "32"))]
+    use linux_raw_sys::general::{F_SETLK64 as F_SETLK, F_SETLKW64 as F_SETLKW};
+
+    let cmd = if wait { F_SETLKW } else { F_SETLK };
+    fcntl_flock(fd, cmd as _, lock).map(|_| ())
+}
+
+#[cfg(not(any(
+    target_os = "emscripten",
+    target_os = "fuchsia",
+    target_os = "redox",
+    target_os = "wasi"
+)))]
+#[inline]
+pub(crate) fn fcntl_getlk(fd: BorrowedFd<'_>, lock: &Flock) -> io::Result<Option<Flock>> {
+    #[cfg(not(all(linux_kernel, target_pointer_width = "32")))]
+    use c::F_GETLK;
+    #[cfg(all(linux_kernel, target_pointer_width = "32"))]
+    use linux_raw_sys::general::F_GETLK64 as F_GETLK;
+
+    fcntl_flock(fd, F_GETLK as _, lock)
+}
+
+#[cfg(linux_kernel)]
+#[inline]
+pub(crate) fn fcntl_ofd_setlk(fd: BorrowedFd<'_>, lock: &Flock, wait: bool) -> io::Result<()> {
+    use linux_raw_sys::general::{F_OFD_SETLK, F_OFD_SETLKW};
+
+    let cmd = if wait { F_OFD_SETLKW } else { F_OFD_SETLK };
+    fcntl_flock(fd, cmd as _, lock).map(|_| ())
+}
+
+#[cfg(linux_kernel)]
+#[inline]
+pub(crate) fn fcntl_ofd_getlk(fd: BorrowedFd<'_>, lock: &Flock) -> io::Result<Option<Flock>> {
+    fcntl_flock(fd, linux_raw_sys::general::F_OFD_GETLK as _, lock)
+}
+
+/// Call `fcntl` with a `struct flock` argument, and convert the `struct flock`
+/// that it leaves behind back into a `Flock`.
+#[cfg(not(any(
+    target_os = "emscripten",
+    target_os = "fuchsia",
+    target_os = "redox",
+    target_os = "wasi"
+)))]
+fn fcntl_flock(fd: BorrowedFd<'_>, cmd: c::c_int, lock: &Flock) -> io::Result<Option<Flock>> {
+    unsafe {
+        // The kernel always uses `struct flock64` for the `F_*LK64` and
+        // `F_OFD_*` commands.
+        #[cfg(linux_kernel)]
+        let mut raw: c::flock64 = core::mem::zeroed();
+        #[cfg(not(linux_kernel))]
+        let mut raw: c::flock = core::mem::zeroed();
+        raw.l_type = lock.typ as _;
+        raw.l_whence = lock.offset_type as _;
+        raw.l_start = lock.start.try_into().map_err(|_| io::Errno::OVERFLOW)?;
+        raw.l_len = lock.length.try_into().map_err(|_| io::Errno::OVERFLOW)?;
+        // Open file description locks require `l_pid` to be zero, which
+        // `zeroed` has done for us.
+
+        ret(c::fcntl(borrowed_fd(fd), cmd, &mut raw))?;
+
+        let typ = match raw.l_type as i32 {
+            l_type if l_type == FlockType::ReadLock as i32 => FlockType::ReadLock,
+            l_type if l_type == FlockType::WriteLock as i32 => FlockType::WriteLock,
+            l_type if l_type == FlockType::Unlocked as i32 => return Ok(None),
+            _ => return Err(io::Errno::INVAL),
+        };
+        let offset_type = match raw.l_whence as i32 {
+            c::SEEK_SET => FlockOffsetType::Set,
+            c::SEEK_CUR => FlockOffsetType::Current,
+            c::SEEK_END => FlockOffsetType::End,
+            _ => return Err(io::Errno::INVAL),
+        };
+        Ok(Some(Flock {
+            start: raw.l_start.into(),
+            length: raw.l_len.into(),
+            // Open file description locks don't have a pid, and the kernel
+            // reports them with a pid of -1.
+            pid: if raw.l_pid > 0 {
+                Pid::from_raw(raw.l_pid)
+            } else {
+                None
+            },
+            typ,
+            offset_type,
+        }))
+    }
+}
+
+#[cfg(linux_kernel)]
+pub(crate) fn fcntl_getown_ex(fd: BorrowedFd<'_>) -> io::Result<Option<FdOwner>> {
+    use linux_raw_sys::general::{f_owner_ex, F_GETOWN_EX, F_OWNER_PGRP, F_OWNER_PID, F_OWNER_TID};
+
+    unsafe {
+        let mut raw: f_owner_ex = core::mem::zeroed();
+        ret(c::fcntl(borrowed_fd(fd), F_GETOWN_EX as _, &mut raw))?;
+
+        // A pid of zero means that no owner is set.
+        let pid = match Pid::from_raw(raw.pid) {
+            Some(pid) => pid,
+            None => return Ok(None),
+        };
+        match raw.type_ as u32 {
+            F_OWNER_TID => Ok(Some(FdOwner::Thread(pid))),
+            F_OWNER_PID => Ok(Some(FdOwner::Process(pid))),
+            F_OWNER_PGRP => Ok(Some(FdOwner::ProcessGroup(pid))),
+            _ => Err(io::Errno::INVAL),
+        }
+    }
+}
+
+#[cfg(linux_kernel)]
+pub(crate) fn fcntl_setown_ex(fd: BorrowedFd<'_>, owner: Option<FdOwner>) -> io::Result<()> {
+    use linux_raw_sys::general::{f_owner_ex, F_OWNER_PGRP, F_OWNER_PID, F_OWNER_TID, F_SETOWN_EX};
+
+    let (type_, pid) = match owner {
+        Some(FdOwner::Thread(pid)) => (F_OWNER_TID, Some(pid)),
+        Some(FdOwner::Process(pid)) => (F_OWNER_PID, Some(pid)),
+        Some(FdOwner::ProcessGroup(pid)) => (F_OWNER_PGRP, Some(pid)),
+        None => (F_OWNER_PID, None),
+    };
+    let raw = f_owner_ex {
+        type_: type_ as _,
+        pid: Pid::as_raw(pid),
+    };
+    unsafe { ret(c::fcntl(borrowed_fd(fd), F_SETOWN_EX as _, &raw)) }
+}
+
+#[cfg(linux_kernel)]
+pub(crate) fn fcntl_getsig(fd: BorrowedFd<'_>) -> io::Result<i32> {
+    unsafe {
+        ret_c_int(c::fcntl(
+            borrowed_fd(fd),
+            linux_raw_sys::general::F_GETSIG as _,
+        ))
+    }
+}
+
+#[cfg(linux_kernel)]
+pub(crate) fn fcntl_setsig(fd: BorrowedFd<'_>, sig: i32) -> io::Result<()> {
+    unsafe {
+        ret(c::fcntl(
+            borrowed_fd(fd),
+            linux_raw_sys::general::F_SETSIG as _,
+            sig,
+        ))
+    }
+}
+
+#[cfg(linux_kernel)]
+pub(crate) fn fcntl_getlease(fd: BorrowedFd<'_>) -> io::Result<LeaseType> {
+    unsafe {
+        match ret_c_int(c::fcntl(borrowed_fd(fd), c::F_GETLEASE))? {
+            lease if lease == LeaseType::Read as i32 => Ok(LeaseType::Read),
+            lease if lease == LeaseType::Write as i32 => Ok(LeaseType::Write),
+            lease if lease == LeaseType::Unlock as i32 => Ok(LeaseType::Unlock),
+            _ => Err(io::Errno::INVAL),
+        }
+    }
+}
+
+#[cfg(linux_kernel)]
+pub(crate) fn fcntl_setlease(fd: BorrowedFd<'_>, lease: LeaseType) -> io::Result<()> {
+    unsafe { ret(c::fcntl(borrowed_fd(fd), c::F_SETLEASE, lease as i32)) }
+}
+
+#[cfg(linux_kernel)]
+pub(crate) fn fcntl_notify(fd: BorrowedFd<'_>, flags: DnotifyFlags) -> io::Result<()> {
+    unsafe {
+        ret(c::fcntl(
+            borrowed_fd(fd),
+            c::F_NOTIFY,
+            flags.bits() as c::c_int,
+        ))
+    }
+}
+
 pub(crate) fn seek(fd: BorrowedFd<'_>, pos: SeekFrom) -> io::Result<u64> {
     let (whence, offset): (c::c_int, c::off_t) = match pos {
         SeekFrom::Start(pos) => {
@@ -1183,6 +1407,81 @@ pub(crate) fn syncfs(fd: BorrowedFd<'_>) -> io::Result<()> {
     unsafe { ret(syncfs(borrowed_fd(fd))) }
 }
 
+#[cfg(target_os = "linux")]
+pub(crate) fn sync_file_range(
+    fd: BorrowedFd<'_>,
+    offset: u64,
+    nbytes: u64,
+    flags: SyncFileRangeFlags,
+) -> io::Result<()> {
+    unsafe {
+        ret(c::sync_file_range(
+            borrowed_fd(fd),
+            offset as i64,
+            nbytes as i64,
+            flags.bits(),
+        ))
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn readahead(fd: BorrowedFd<'_>, offset: u64, count: usize) -> io::Result<()> {
+    unsafe {
+        ret_usize(c::readahead(borrowed_fd(fd), offset as i64, count))?;
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn cachestat(fd: BorrowedFd<'_>, offset: u64, len: u64) -> io::Result<Cachestat> {
+    #[repr(C)]
+    struct CachestatRange {
+        off: u64,
+        len: u64,
+    }
+
+    let range = CachestatRange { off: offset, len };
+    let mut cstat = MaybeUninit::<Cachestat>::uninit();
+    unsafe {
+        syscall_ret(c::syscall(
+            SYS_CACHESTAT,
+            borrowed_fd(fd),
+            &range,
+            cstat.as_mut_ptr(),
+            0 as c::c_uint,
+        ))?;
+        Ok(cstat.assume_init())
+    }
+}
+// TODO: Use `SYS_cachestat` from libc once it defines it on all platforms.
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "mips", target_arch = "mips32r6")
+))]
+const SYS_CACHESTAT: c::c_long = 4451;
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "mips64", target_arch = "mips64r6")
+))]
+const SYS_CACHESTAT: c::c_long = 5451;
+#[cfg(all(
+    target_os = "linux",
+    target_arch = "x86_64",
+    target_pointer_width = "32"
+))]
+const SYS_CACHESTAT: c::c_long = 0x4000_0000 + 451;
+#[cfg(all(
+    target_os = "linux",
+    not(any(
+        target_arch = "mips",
+        target_arch = "mips32r6",
+        target_arch = "mips64",
+        target_arch = "mips64r6",
+        all(target_arch = "x86_64", target_pointer_width = "32"),
+    ))
+))]
+const SYS_CACHESTAT: c::c_long = 451;
+
 #[cfg(not(any(target_os = "redox", target_os = "wasi")))]
 pub(crate) fn sync() {
     unsafe { c::sync() }
@@ -1472,6 +1771,67 @@ pub(crate) fn memfd_create(path: &CStr, flags: MemfdFlags) -> io::Result<OwnedFd
     unsafe { ret_owned_fd(memfd_create(c_str(path), flags.bits())) }
 }
 
+#[cfg(all(
+    linux_kernel,
+    any(
+        target_arch = "aarch64",
+        target_arch = "riscv32",
+        target_arch = "riscv64",
+        target_arch = "x86",
+        target_arch = "x86_64",
+    )
+))]
+pub(crate) fn memfd_secret(flags: SecretFlags) -> io::Result<OwnedFd> {
+    #[cfg(not(all(
+        target_env = "musl",
+        any(target_arch = "riscv32", target_arch = "riscv64")
+    )))]
+    use c::SYS_memfd_secret;
+
+    // libc doesn't define `SYS_memfd_secret` for musl on RISC-V, so use the
+    // number from the kernel's generic syscall table there.
+    #[cfg(all(
+        target_env = "musl",
+        any(target_arch = "riscv32", target_arch = "riscv64")
+    ))]
+    #[allow(non_upper_case_globals)]
+    const SYS_memfd_secret: c::c_long = 447;
+
+    unsafe { syscall_ret_owned_fd(c::syscall(SYS_memfd_secret, flags.bits())) }
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn fanotify_init(
+    flags: crate::fs::fanotify::InitFlags,
+    event_flags: OFlags,
+) -> io::Result<OwnedFd> {
+    unsafe {
+        ret_owned_fd(c::fanotify_init(
+            flags.bits(),
+            event_flags.bits() as c::c_uint,
+        ))
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn fanotify_mark(
+    fanotify: BorrowedFd<'_>,
+    flags: crate::fs::fanotify::MarkFlags,
+    mask: crate::fs::fanotify::EventFlags,
+    dirfd: BorrowedFd<'_>,
+    path: Option<&CStr>,
+) -> io::Result<()> {
+    unsafe {
+        ret(c::fanotify_mark(
+            borrowed_fd(fanotify),
+            flags.bits(),
+            mask.bits(),
+            borrowed_fd(dirfd),
+            path.map_or_else(null, c_str),
+        ))
+    }
+}
+
 #[cfg(linux_kernel)]
 pub(crate) fn openat2(
     dirfd: BorrowedFd<'_>,
@@ -1992,6 +2352,357 @@ pub(crate) fn unmount(target: &CStr, flags: super::types::UnmountFlags) -> io::R
     unsafe { ret(c::umount2(target.as_ptr(), flags.bits())) }
 }
 
+#[cfg(linux_kernel)]
+pub(crate) fn name_to_handle_at(
+    dirfd: BorrowedFd<'_>,
+    path: &CStr,
+    handle: &mut crate::fs::FileHandle,
+    flags: AtFlags,
+) -> io::Result<i32> {
+    let mut mount_id = MaybeUninit::<c::c_int>::uninit();
+    unsafe {
+        syscall_ret(c::syscall(
+            __NR_name_to_handle_at as _,
+            borrowed_fd(dirfd),
+            c_str(path),
+            handle,
+            mount_id.as_mut_ptr(),
+            flags.bits(),
+        ))?;
+        Ok(mount_id.assume_init())
+    }
+}
+
+#[cfg(linux_kernel)]
+pub(crate) fn open_by_handle_at(
+    mount_fd: BorrowedFd<'_>,
+    handle: &crate::fs::FileHandle,
+    flags: OFlags,
+) -> io::Result<OwnedFd> {
+    unsafe {
+        syscall_ret_owned_fd(c::syscall(
+            __NR_open_by_handle_at as _,
+            borrowed_fd(mount_fd),
+            handle,
+            flags.bits(),
+        ))
+    }
+}
+
+#[cfg(linux_kernel)]
+pub(crate) fn fsopen(fs_name: &CStr, flags: super::types::FsOpenFlags) -> io::Result<OwnedFd> {
+    unsafe { syscall_ret_owned_fd(c::syscall(__NR_fsopen as _, c_str(fs_name), flags.bits())) }
+}
+
+#[cfg(all(feature = "alloc", linux_kernel))]
+pub(crate) fn statmount(req: &MntIdReq, buf: &mut [u8]) -> io::Result<()> {
+    unsafe {
+        syscall_ret(c::syscall(
+            SYS_STATMOUNT,
+            req,
+            buf.as_mut_ptr(),
+            buf.len(),
+            0 as c::c_uint,
+        ))
+    }
+}
+
+#[cfg(all(feature = "alloc", linux_kernel))]
+pub(crate) fn listmount(req: &MntIdReq, mnt_ids: &mut [u64], flags: u32) -> io::Result<usize> {
+    unsafe {
+        syscall_ret_usize(c::syscall(
+            SYS_LISTMOUNT,
+            req,
+            mnt_ids.as_mut_ptr(),
+            mnt_ids.len(),
+            flags as c::c_uint,
+        ))
+    }
+}
+
+// TODO: Use `SYS_statmount` and `SYS_listmount` from libc once it defines
+// them on all platforms.
+#[cfg(all(
+    feature = "alloc",
+    linux_kernel,
+    any(target_arch = "mips", target_arch = "mips32r6")
+))]
+const SYS_STATMOUNT: c::c_long = 4457;
+#[cfg(all(
+    feature = "alloc",
+    linux_kernel,
+    any(target_arch = "mips", target_arch = "mips32r6")
+))]
+const SYS_LISTMOUNT: c::c_long = 4458;
+#[cfg(all(
+    feature = "alloc",
+    linux_kernel,
+    any(target_arch = "mips64", target_arch = "mips64r6")
+))]
+const SYS_STATMOUNT: c::c_long = 5457;
+#[cfg(all(
+    feature = "alloc",
+    linux_kernel,
+    any(target_arch = "mips64", target_arch = "mips64r6")
+))]
+const SYS_LISTMOUNT: c::c_long = 5458;
+#[cfg(all(
+    feature = "alloc",
+    linux_kernel,
+    target_arch = "x86_64",
+    target_pointer_width = "32"
+))]
+const SYS_STATMOUNT: c::c_long = 0x4000_0000 + 457;
+#[cfg(all(
+    feature = "alloc",
+    linux_kernel,
+    target_arch = "x86_64",
+    target_pointer_width = "32"
+))]
+const SYS_LISTMOUNT: c::c_long = 0x4000_0000 + 458;
+#[cfg(all(
+    feature = "alloc",
+    linux_kernel,
+    not(any(
+        target_arch = "mips",
+        target_arch = "mips32r6",
+        target_arch = "mips64",
+        target_arch = "mips64r6",
+        all(target_arch = "x86_64", target_pointer_width = "32"),
+    ))
+))]
+const SYS_STATMOUNT: c::c_long = 457;
+#[cfg(all(
+    feature = "alloc",
+    linux_kernel,
+    not(any(
+        target_arch = "mips",
+        target_arch = "mips32r6",
+        target_arch = "mips64",
+        target_arch = "mips64r6",
+        all(target_arch = "x86_64", target_pointer_width = "32"),
+    ))
+))]
+const SYS_LISTMOUNT: c::c_long = 458;
+
+#[cfg(linux_kernel)]
+pub(crate) fn fsmount(
+    fs_fd: BorrowedFd<'_>,
+    flags: super::types::FsMountFlags,
+    attr_flags: super::types::MountAttrFlags,
+) -> io::Result<OwnedFd> {
+    unsafe {
+        syscall_ret_owned_fd(c::syscall(
+            __NR_fsmount as _,
+            borrowed_fd(fs_fd),
+            flags.bits(),
+            attr_flags.bits(),
+        ))
+    }
+}
+
+#[cfg(linux_kernel)]
+pub(crate) fn move_mount(
+    from_dfd: BorrowedFd<'_>,
+    from_pathname: &CStr,
+    to_dfd: BorrowedFd<'_>,
+    to_pathname: &CStr,
+    flags: super::types::MoveMountFlags,
+) -> io::Result<()> {
+    unsafe {
+        syscall_ret(c::syscall(
+            __NR_move_mount as _,
+            borrowed_fd(from_dfd),
+            c_str(from_pathname),
+            borrowed_fd(to_dfd),
+            c_str(to_pathname),
+            flags.bits(),
+        ))
+    }
+}
+
+#[cfg(linux_kernel)]
+pub(crate) fn open_tree(
+    dfd: BorrowedFd<'_>,
+    filename: &CStr,
+    flags: super::types::OpenTreeFlags,
+) -> io::Result<OwnedFd> {
+    unsafe {
+        syscall_ret_owned_fd(c::syscall(
+            __NR_open_tree as _,
+            borrowed_fd(dfd),
+            c_str(filename),
+            flags.bits(),
+        ))
+    }
+}
+
+#[cfg(linux_kernel)]
+pub(crate) fn fspick(
+    dfd: BorrowedFd<'_>,
+    path: &CStr,
+    flags: super::types::FsPickFlags,
+) -> io::Result<OwnedFd> {
+    unsafe {
+        syscall_ret_owned_fd(c::syscall(
+            __NR_fspick as _,
+            borrowed_fd(dfd),
+            c_str(path),
+            flags.bits(),
+        ))
+    }
+}
+
+#[cfg(linux_kernel)]
+unsafe fn fsconfig(
+    fs_fd: BorrowedFd<'_>,
+    cmd: FsConfigCmd,
+    key: Option<&CStr>,
+    value: *const c::c_void,
+    aux: c::c_int,
+) -> io::Result<()> {
+    syscall_ret(c::syscall(
+        __NR_fsconfig as _,
+        borrowed_fd(fs_fd),
+        cmd as c::c_uint,
+        key.map_or_else(null, CStr::as_ptr),
+        value,
+        aux,
+    ))
+}
+
+#[cfg(linux_kernel)]
+pub(crate) fn fsconfig_set_flag(fs_fd: BorrowedFd<'_>, key: &CStr) -> io::Result<()> {
+    unsafe { fsconfig(fs_fd, FsConfigCmd::SetFlag, Some(key), null(), 0) }
+}
+
+#[cfg(linux_kernel)]
+pub(crate) fn fsconfig_set_string(
+    fs_fd: BorrowedFd<'_>,
+    key: &CStr,
+    value: &CStr,
+) -> io::Result<()> {
+    unsafe {
+        fsconfig(
+            fs_fd,
+            FsConfigCmd::SetString,
+            Some(key),
+            value.as_ptr().cast(),
+            0,
+        )
+    }
+}
+
+#[cfg(linux_kernel)]
+pub(crate) fn fsconfig_set_binary(
+    fs_fd: BorrowedFd<'_>,
+    key: &CStr,
+    value: &[u8],
+) -> io::Result<()> {
+    let len = value.len().try_into().map_err(|_| io::Errno::OVERFLOW)?;
+    unsafe {
+        fsconfig(
+            fs_fd,
+            FsConfigCmd::SetBinary,
+            Some(key),
+            value.as_ptr().cast(),
+            len,
+        )
+    }
+}
+
+#[cfg(linux_kernel)]
+pub(crate) fn fsconfig_set_path(
+    fs_fd: BorrowedFd<'_>,
+    key: &CStr,
+    path: &CStr,
+    fd: BorrowedFd<'_>,
+) -> io::Result<()> {
+    unsafe {
+        fsconfig(
+            fs_fd,
+            FsConfigCmd::SetPath,
+            Some(key),
+            path.as_ptr().cast(),
+            borrowed_fd(fd),
+        )
+    }
+}
+
+#[cfg(linux_kernel)]
+pub(crate) fn fsconfig_set_path_empty(
+    fs_fd: BorrowedFd<'_>,
+    key: &CStr,
+    fd: BorrowedFd<'_>,
+) -> io::Result<()> {
+    unsafe {
+        fsconfig(
+            fs_fd,
+            FsConfigCmd::SetPathEmpty,
+            Some(key),
+            cstr!("").as_ptr().cast(),
+            borrowed_fd(fd),
+        )
+    }
+}
+
+#[cfg(linux_kernel)]
+pub(crate) fn fsconfig_set_fd(
+    fs_fd: BorrowedFd<'_>,
+    key: &CStr,
+    fd: BorrowedFd<'_>,
+) -> io::Result<()> {
+    unsafe {
+        fsconfig(
+            fs_fd,
+            FsConfigCmd::SetFd,
+            Some(key),
+            null(),
+            borrowed_fd(fd),
+        )
+    }
+}
+
+#[cfg(linux_kernel)]
+pub(crate) fn fsconfig_create(fs_fd: BorrowedFd<'_>) -> io::Result<()> {
+    unsafe { fsconfig(fs_fd, FsConfigCmd::Create, None, null(), 0) }
+}
+
+#[cfg(linux_kernel)]
+pub(crate) fn fsconfig_reconfigure(fs_fd: BorrowedFd<'_>) -> io::Result<()> {
+    unsafe { fsconfig(fs_fd, FsConfigCmd::Reconfigure, None, null(), 0) }
+}
+
+#[cfg(linux_kernel)]
+pub(crate) fn mount_setattr(
+    dirfd: BorrowedFd<'_>,
+    path: &CStr,
+    flags: AtFlags,
+    attr: &crate::fs::MountAttr<'_>,
+) -> io::Result<()> {
+    use linux_raw_sys::general::mount_attr;
+
+    let attr = mount_attr {
+        attr_set: attr.attr_set.bits().into(),
+        attr_clr: attr.attr_clr.bits().into(),
+        propagation: attr.propagation.bits() as u64,
+        userns_fd: match attr.userns_fd {
+            Some(fd) => borrowed_fd(fd) as u64,
+            None => 0,
+        },
+    };
+    unsafe {
+        syscall_ret(c::syscall(
+            __NR_mount_setattr as _,
+            borrowed_fd(dirfd),
+            c_str(path),
+            flags.bits(),
+            &attr,
+            size_of::<mount_attr>(),
+        ))
+    }
+}
+
 #[cfg(any(apple, linux_kernel))]
 pub(crate) fn getxattr(path: &CStr, name: &CStr, value: &mut [u8]) -> io::Result<usize> {
     let value_ptr = value.as_mut_ptr();
@@ -2310,3 +3021,112 @@ pub(crate) fn ext4_ioc_resize_fs(fd: BorrowedFd<'_>, blocks: u64) -> io::Result<
 
     unsafe { ret(c::ioctl(borrowed_fd(fd), EXT4_IOC_RESIZE_FS as _, &blocks)) }
 }
+
+#[cfg(linux_kernel)]
+#[inline]
+pub(crate) fn ioctl_getflags(fd: BorrowedFd<'_>) -> io::Result<InodeFlags> {
+    // TODO: Fix linux-raw-sys to define ioctl codes for sparc.
+    #[cfg(target_arch = "sparc")]
+    const FS_IOC_GETFLAGS: u32 = 0x4004_6601;
+    #[cfg(target_arch = "sparc64")]
+    const FS_IOC_GETFLAGS: u32 = 0x4008_6601;
+
+    #[cfg(not(any(target_arch = "sparc", target_arch = "sparc64")))]
+    use linux_raw_sys::ioctl::FS_IOC_GETFLAGS;
+
+    // `FS_IOC_GETFLAGS` is declared with a `long` argument, but the kernel
+    // reads and writes an `int`.
+    let mut result = MaybeUninit::<c::c_uint>::uninit();
+    unsafe {
+        ret(c::ioctl(
+            borrowed_fd(fd),
+            FS_IOC_GETFLAGS as _,
+            result.as_mut_ptr(),
+        ))?;
+        Ok(InodeFlags::from_bits_retain(result.assume_init()))
+    }
+}
+
+#[cfg(linux_kernel)]
+#[inline]
+pub(crate) fn ioctl_setflags(fd: BorrowedFd<'_>, flags: InodeFlags) -> io::Result<()> {
+    // TODO: Fix linux-raw-sys to define ioctl codes for sparc.
+    #[cfg(target_arch = "sparc")]
+    const FS_IOC_SETFLAGS: u32 = 0x8004_6602;
+    #[cfg(target_arch = "sparc64")]
+    const FS_IOC_SETFLAGS: u32 = 0x8008_6602;
+
+    #[cfg(not(any(target_arch = "sparc", target_arch = "sparc64")))]
+    use linux_raw_sys::ioctl::FS_IOC_SETFLAGS;
+
+    let flags: c::c_uint = flags.bits();
+    unsafe { ret(c::ioctl(borrowed_fd(fd), FS_IOC_SETFLAGS as _, &flags)) }
+}
+
+#[cfg(linux_kernel)]
+#[inline]
+pub(crate) fn ioctl_fsgetxattr(fd: BorrowedFd<'_>) -> io::Result<FsXattr> {
+    // TODO: Fix linux-raw-sys to define ioctl codes for sparc.
+    #[cfg(any(target_arch = "sparc", target_arch = "sparc64"))]
+    const FS_IOC_FSGETXATTR: u32 = 0x401c_581f;
+
+    #[cfg(not(any(target_arch = "sparc", target_arch = "sparc64")))]
+    use linux_raw_sys::ioctl::FS_IOC_FSGETXATTR;
+
+    let mut result = MaybeUninit::<linux_raw_sys::general::fsxattr>::uninit();
+    unsafe {
+        ret(c::ioctl(
+            borrowed_fd(fd),
+            FS_IOC_FSGETXATTR as _,
+            result.as_mut_ptr(),
+        ))?;
+        let result = result.assume_init();
+        Ok(FsXattr {
+            xflags: FsXFlags::from_bits_retain(result.fsx_xflags),
+            extsize: result.fsx_extsize,
+            nextents: result.fsx_nextents,
+            projid: result.fsx_projid,
+            cowextsize: result.fsx_cowextsize,
+        })
+    }
+}
+
+#[cfg(linux_kernel)]
+#[inline]
+pub(crate) fn ioctl_fssetxattr(fd: BorrowedFd<'_>, xattr: &FsXattr) -> io::Result<()> {
+    // TODO: Fix linux-raw-sys to define ioctl codes for sparc.
+    #[cfg(any(target_arch = "sparc", target_arch = "sparc64"))]
+    const FS_IOC_FSSETXATTR: u32 = 0x801c_5820;
+
+    #[cfg(not(any(target_arch = "sparc", target_arch = "sparc64")))]
+    use linux_raw_sys::ioctl::FS_IOC_FSSETXATTR;
+
+    let xattr = linux_raw_sys::general::fsxattr {
+        fsx_xflags: xattr.xflags.bits(),
+        fsx_extsize: xattr.extsize,
+        fsx_nextents: xattr.nextents,
+        fsx_projid: xattr.projid,
+        fsx_cowextsize: xattr.cowextsize,
+        fsx_pad: [0; 8],
+    };
+    unsafe { ret(c::ioctl(borrowed_fd(fd), FS_IOC_FSSETXATTR as _, &xattr)) }
+}
+
+#[cfg(linux_kernel)]
+#[inline]
+pub(crate) fn ioctl_fiemap(fd: BorrowedFd<'_>, fiemap: &mut RawFiemap) -> io::Result<()> {
+    // TODO: Fix linux-raw-sys to define ioctl codes for sparc.
+    #[cfg(any(target_arch = "sparc", target_arch = "sparc64"))]
+    const FS_IOC_FIEMAP: u32 = 0xc020_660b;
+
+    #[cfg(not(any(target_arch = "sparc", target_arch = "sparc64")))]
+    use linux_raw_sys::ioctl::FS_IOC_FIEMAP;
+
+    unsafe {
+        ret(c::ioctl(
+            borrowed_fd(fd),
+            FS_IOC_FIEMAP as _,
+            fiemap as *mut RawFiemap,
+        ))
+    }
+}
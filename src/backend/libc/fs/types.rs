@@ -55,6 +55,14 @@ bitflags! {
         #[cfg(not(any(target_os = "emscripten", target_os = "android")))]
         const EACCESS = c::AT_EACCESS;
 
+        /// `AT_NO_AUTOMOUNT`
+        #[cfg(linux_kernel)]
+        const NO_AUTOMOUNT = linux_raw_sys::general::AT_NO_AUTOMOUNT as c::c_int;
+
+        /// `AT_RECURSIVE`
+        #[cfg(linux_kernel)]
+        const RECURSIVE = linux_raw_sys::general::AT_RECURSIVE as c::c_int;
+
         /// `AT_STATX_SYNC_AS_STAT`
         #[cfg(all(target_os = "linux", target_env = "gnu"))]
         const STATX_SYNC_AS_STAT = c::AT_STATX_SYNC_AS_STAT;
@@ -572,6 +580,27 @@ bitflags! {
     }
 }
 
+#[cfg(all(
+    linux_kernel,
+    any(
+        target_arch = "aarch64",
+        target_arch = "riscv32",
+        target_arch = "riscv64",
+        target_arch = "x86",
+        target_arch = "x86_64",
+    )
+))]
+bitflags! {
+    /// `O_*` constants for use with [`memfd_secret`].
+    ///
+    /// [`memfd_secret`]: crate::fs::memfd_secret
+    #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+    pub struct SecretFlags: c::c_uint {
+        /// `O_CLOEXEC`
+        const CLOEXEC = c::O_CLOEXEC as c::c_uint;
+    }
+}
+
 #[cfg(any(linux_kernel, target_os = "freebsd", target_os = "fuchsia"))]
 bitflags! {
     /// `F_SEAL_*` constants for use with [`fcntl_add_seals`] and
@@ -595,6 +624,24 @@ bitflags! {
     }
 }
 
+#[cfg(linux_kernel)]
+bitflags! {
+    /// `SYNC_FILE_RANGE_*` constants for use with [`sync_file_range`].
+    ///
+    /// [`sync_file_range`]: crate::fs::sync_file_range
+    #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+    pub struct SyncFileRangeFlags: u32 {
+        /// `SYNC_FILE_RANGE_WAIT_BEFORE`
+        const WAIT_BEFORE = linux_raw_sys::general::SYNC_FILE_RANGE_WAIT_BEFORE;
+
+        /// `SYNC_FILE_RANGE_WRITE`
+        const WRITE = linux_raw_sys::general::SYNC_FILE_RANGE_WRITE;
+
+        /// `SYNC_FILE_RANGE_WAIT_AFTER`
+        const WAIT_AFTER = linux_raw_sys::general::SYNC_FILE_RANGE_WAIT_AFTER;
+    }
+}
+
 #[cfg(all(target_os = "linux", target_env = "gnu"))]
 bitflags! {
     /// `STATX_*` constants for use with [`statx`].
@@ -644,6 +691,9 @@ bitflags! {
         /// `STATX_MNT_ID` (since Linux 5.8)
         const MNT_ID = c::STATX_MNT_ID;
 
+        /// `STATX_DIOALIGN` (since Linux 6.1)
+        const DIOALIGN = linux_raw_sys::general::STATX_DIOALIGN;
+
         /// `STATX_ALL`
         const ALL = c::STATX_ALL;
     }
@@ -701,11 +751,50 @@ bitflags! {
         /// `STATX_MNT_ID` (since Linux 5.8)
         const MNT_ID = 0x1000;
 
+        /// `STATX_DIOALIGN` (since Linux 6.1)
+        const DIOALIGN = 0x2000;
+
         /// `STATX_ALL`
         const ALL = 0xfff;
     }
 }
 
+#[cfg(linux_kernel)]
+bitflags! {
+    /// `STATX_ATTR_*` constants for use with [`Statx`].
+    ///
+    /// [`Statx`]: crate::fs::Statx
+    #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+    pub struct StatxAttributes: u64 {
+        /// `STATX_ATTR_COMPRESSED`
+        const COMPRESSED = 0x0000_0004;
+
+        /// `STATX_ATTR_IMMUTABLE`
+        const IMMUTABLE = 0x0000_0010;
+
+        /// `STATX_ATTR_APPEND`
+        const APPEND = 0x0000_0020;
+
+        /// `STATX_ATTR_NODUMP`
+        const NODUMP = 0x0000_0040;
+
+        /// `STATX_ATTR_ENCRYPTED`
+        const ENCRYPTED = 0x0000_0800;
+
+        /// `STATX_ATTR_AUTOMOUNT`
+        const AUTOMOUNT = 0x0000_1000;
+
+        /// `STATX_ATTR_MOUNT_ROOT` (since Linux 5.8)
+        const MOUNT_ROOT = 0x0000_2000;
+
+        /// `STATX_ATTR_VERITY` (since Linux 5.5)
+        const VERITY = 0x0010_0000;
+
+        /// `STATX_ATTR_DAX` (since Linux 5.8)
+        const DAX = 0x0020_0000;
+    }
+}
+
 #[cfg(not(any(netbsdlike, solarish, target_os = "aix", target_os = "redox")))]
 bitflags! {
     /// `FALLOC_FL_*` constants for use with [`fallocate`].
@@ -847,6 +936,87 @@ pub enum FlockOperation {
     NonBlockingUnlock = c::LOCK_UN | c::LOCK_NB,
 }
 
+/// `F_*LCK` constants for use with [`Flock`].
+///
+/// [`Flock`]: crate::fs::Flock
+#[cfg(not(any(
+    target_os = "emscripten",
+    target_os = "fuchsia",
+    target_os = "redox",
+    target_os = "wasi"
+)))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum FlockType {
+    /// `F_RDLCK`
+    ReadLock = c::F_RDLCK as i32,
+    /// `F_WRLCK`
+    WriteLock = c::F_WRLCK as i32,
+    /// `F_UNLCK`
+    Unlocked = c::F_UNLCK as i32,
+}
+
+/// `SEEK_*` constants for use with [`Flock`].
+///
+/// [`Flock`]: crate::fs::Flock
+#[cfg(not(any(
+    target_os = "emscripten",
+    target_os = "fuchsia",
+    target_os = "redox",
+    target_os = "wasi"
+)))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum FlockOffsetType {
+    /// `SEEK_SET`—The range starts at an absolute offset.
+    Set = c::SEEK_SET,
+    /// `SEEK_CUR`—The range starts relative to the current file offset.
+    Current = c::SEEK_CUR,
+    /// `SEEK_END`—The range starts relative to the end of the file.
+    End = c::SEEK_END,
+}
+
+/// `F_*LCK` constants for use with [`fcntl_setlease`] and
+/// [`fcntl_getlease`].
+///
+/// [`fcntl_setlease`]: crate::fs::fcntl_setlease
+/// [`fcntl_getlease`]: crate::fs::fcntl_getlease
+#[cfg(linux_kernel)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum LeaseType {
+    /// `F_RDLCK`—A read lease.
+    Read = c::F_RDLCK as i32,
+    /// `F_WRLCK`—A write lease.
+    Write = c::F_WRLCK as i32,
+    /// `F_UNLCK`—No lease.
+    Unlock = c::F_UNLCK as i32,
+}
+
+#[cfg(linux_kernel)]
+bitflags! {
+    /// `DN_*` constants for use with [`fcntl_notify`].
+    ///
+    /// [`fcntl_notify`]: crate::fs::fcntl_notify
+    #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+    pub struct DnotifyFlags: u32 {
+        /// `DN_ACCESS`
+        const ACCESS = linux_raw_sys::general::DN_ACCESS;
+        /// `DN_MODIFY`
+        const MODIFY = linux_raw_sys::general::DN_MODIFY;
+        /// `DN_CREATE`
+        const CREATE = linux_raw_sys::general::DN_CREATE;
+        /// `DN_DELETE`
+        const DELETE = linux_raw_sys::general::DN_DELETE;
+        /// `DN_RENAME`
+        const RENAME = linux_raw_sys::general::DN_RENAME;
+        /// `DN_ATTRIB`
+        const ATTRIB = linux_raw_sys::general::DN_ATTRIB;
+        /// `DN_MULTISHOT`
+        const MULTISHOT = linux_raw_sys::general::DN_MULTISHOT;
+    }
+}
+
 /// `struct stat` for use with [`statat`] and [`fstat`].
 ///
 /// [`statat`]: crate::fs::statat
@@ -937,70 +1107,6 @@ pub struct StatVfs {
     pub f_namemax: u64,
 }
 
-/// `struct statx` for use with [`statx`].
-///
-/// [`statx`]: crate::fs::statx
-#[cfg(all(target_os = "linux", target_env = "gnu"))]
-// Use the glibc `struct statx`.
-pub type Statx = c::statx;
-
-/// `struct statx_timestamp` for use with [`Statx`].
-#[cfg(all(target_os = "linux", target_env = "gnu"))]
-// Use the glibc `struct statx_timestamp`.
-pub type StatxTimestamp = c::statx;
-
-/// `struct statx` for use with [`statx`].
-///
-/// [`statx`]: crate::fs::statx
-// Non-glibc ABIs don't currently declare a `struct statx`, so we declare it
-// ourselves.
-#[cfg(any(
-    target_os = "android",
-    all(target_os = "linux", not(target_env = "gnu")),
-))]
-#[repr(C)]
-#[allow(missing_docs)]
-pub struct Statx {
-    pub stx_mask: u32,
-    pub stx_blksize: u32,
-    pub stx_attributes: u64,
-    pub stx_nlink: u32,
-    pub stx_uid: u32,
-    pub stx_gid: u32,
-    pub stx_mode: u16,
-    __statx_pad1: [u16; 1],
-    pub stx_ino: u64,
-    pub stx_size: u64,
-    pub stx_blocks: u64,
-    pub stx_attributes_mask: u64,
-    pub stx_atime: StatxTimestamp,
-    pub stx_btime: StatxTimestamp,
-    pub stx_ctime: StatxTimestamp,
-    pub stx_mtime: StatxTimestamp,
-    pub stx_rdev_major: u32,
-    pub stx_rdev_minor: u32,
-    pub stx_dev_major: u32,
-    pub stx_dev_minor: u32,
-    pub stx_mnt_id: u64,
-    __statx_pad2: u64,
-    __statx_pad3: [u64; 12],
-}
-
-/// `struct statx_timestamp` for use with [`Statx`].
-// Non-glibc ABIs don't currently declare a `struct statx_timestamp`, so we
-// declare it ourselves.
-#[cfg(any(
-    target_os = "android",
-    all(target_os = "linux", not(target_env = "gnu")),
-))]
-#[repr(C)]
-#[allow(missing_docs)]
-pub struct StatxTimestamp {
-    pub tv_sec: i64,
-    pub tv_nsec: u32,
-    pub __statx_timestamp_pad1: [i32; 1],
-}
-
 /// `mode_t`
 #[cfg(not(all(target_os = "android", target_pointer_width = "32")))]
 pub type RawMode = c::mode_t;
@@ -1157,3 +1263,365 @@ bitflags! {
         const NOFOLLOW = c::UMOUNT_NOFOLLOW;
     }
 }
+
+#[cfg(linux_kernel)]
+bitflags! {
+    /// `FSOPEN_*` constants for use with [`fsopen`].
+    ///
+    /// [`fsopen`]: crate::fs::fsopen
+    #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+    pub struct FsOpenFlags: c::c_uint {
+        /// `FSOPEN_CLOEXEC`
+        const CLOEXEC = linux_raw_sys::general::FSOPEN_CLOEXEC;
+    }
+}
+
+#[cfg(linux_kernel)]
+bitflags! {
+    /// `FSPICK_*` constants for use with [`fspick`].
+    ///
+    /// [`fspick`]: crate::fs::fspick
+    #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+    pub struct FsPickFlags: c::c_uint {
+        /// `FSPICK_CLOEXEC`
+        const CLOEXEC = linux_raw_sys::general::FSPICK_CLOEXEC;
+        /// `FSPICK_SYMLINK_NOFOLLOW`
+        const SYMLINK_NOFOLLOW = linux_raw_sys::general::FSPICK_SYMLINK_NOFOLLOW;
+        /// `FSPICK_NO_AUTOMOUNT`
+        const NO_AUTOMOUNT = linux_raw_sys::general::FSPICK_NO_AUTOMOUNT;
+        /// `FSPICK_EMPTY_PATH`
+        const EMPTY_PATH = linux_raw_sys::general::FSPICK_EMPTY_PATH;
+    }
+}
+
+#[cfg(linux_kernel)]
+bitflags! {
+    /// `FSMOUNT_*` constants for use with [`fsmount`].
+    ///
+    /// [`fsmount`]: crate::fs::fsmount
+    #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+    pub struct FsMountFlags: c::c_uint {
+        /// `FSMOUNT_CLOEXEC`
+        const CLOEXEC = linux_raw_sys::general::FSMOUNT_CLOEXEC;
+    }
+}
+
+#[cfg(linux_kernel)]
+bitflags! {
+    /// `MOUNT_ATTR_*` constants for use with [`fsmount`] and
+    /// [`mount_setattr`].
+    ///
+    /// [`fsmount`]: crate::fs::fsmount
+    /// [`mount_setattr`]: crate::fs::mount_setattr
+    #[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+    pub struct MountAttrFlags: c::c_uint {
+        /// `MOUNT_ATTR_RDONLY`
+        const RDONLY = linux_raw_sys::general::MOUNT_ATTR_RDONLY;
+        /// `MOUNT_ATTR_NOSUID`
+        const NOSUID = linux_raw_sys::general::MOUNT_ATTR_NOSUID;
+        /// `MOUNT_ATTR_NODEV`
+        const NODEV = linux_raw_sys::general::MOUNT_ATTR_NODEV;
+        /// `MOUNT_ATTR_NOEXEC`
+        const NOEXEC = linux_raw_sys::general::MOUNT_ATTR_NOEXEC;
+        /// `MOUNT_ATTR__ATIME`
+        const _ATIME = linux_raw_sys::general::MOUNT_ATTR__ATIME;
+        /// `MOUNT_ATTR_RELATIME`
+        const RELATIME = linux_raw_sys::general::MOUNT_ATTR_RELATIME;
+        /// `MOUNT_ATTR_NOATIME`
+        const NOATIME = linux_raw_sys::general::MOUNT_ATTR_NOATIME;
+        /// `MOUNT_ATTR_STRICTATIME`
+        const STRICTATIME = linux_raw_sys::general::MOUNT_ATTR_STRICTATIME;
+        /// `MOUNT_ATTR_NODIRATIME`
+        const NODIRATIME = linux_raw_sys::general::MOUNT_ATTR_NODIRATIME;
+        /// `MOUNT_ATTR_IDMAP` (since Linux 5.12)
+        const IDMAP = linux_raw_sys::general::MOUNT_ATTR_IDMAP;
+        /// `MOUNT_ATTR_NOSYMFOLLOW` (since Linux 5.14)
+        const NOSYMFOLLOW = linux_raw_sys::general::MOUNT_ATTR_NOSYMFOLLOW;
+    }
+}
+
+#[cfg(linux_kernel)]
+bitflags! {
+    /// `MOVE_MOUNT_*` constants for use with [`move_mount_at`].
+    ///
+    /// [`move_mount_at`]: crate::fs::move_mount_at
+    #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+    pub struct MoveMountFlags: c::c_uint {
+        /// `MOVE_MOUNT_F_SYMLINKS`
+        const F_SYMLINKS = linux_raw_sys::general::MOVE_MOUNT_F_SYMLINKS;
+        /// `MOVE_MOUNT_F_AUTOMOUNTS`
+        const F_AUTOMOUNTS = linux_raw_sys::general::MOVE_MOUNT_F_AUTOMOUNTS;
+        /// `MOVE_MOUNT_F_EMPTY_PATH`
+        const F_EMPTY_PATH = linux_raw_sys::general::MOVE_MOUNT_F_EMPTY_PATH;
+        /// `MOVE_MOUNT_T_SYMLINKS`
+        const T_SYMLINKS = linux_raw_sys::general::MOVE_MOUNT_T_SYMLINKS;
+        /// `MOVE_MOUNT_T_AUTOMOUNTS`
+        const T_AUTOMOUNTS = linux_raw_sys::general::MOVE_MOUNT_T_AUTOMOUNTS;
+        /// `MOVE_MOUNT_T_EMPTY_PATH`
+        const T_EMPTY_PATH = linux_raw_sys::general::MOVE_MOUNT_T_EMPTY_PATH;
+        /// `MOVE_MOUNT_SET_GROUP` (since Linux 5.15)
+        const SET_GROUP = linux_raw_sys::general::MOVE_MOUNT_SET_GROUP;
+    }
+}
+
+#[cfg(linux_kernel)]
+bitflags! {
+    /// `OPEN_TREE_*` and `AT_*` constants for use with [`open_tree`].
+    ///
+    /// [`open_tree`]: crate::fs::open_tree
+    #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+    pub struct OpenTreeFlags: c::c_uint {
+        /// `OPEN_TREE_CLONE`
+        const CLONE = linux_raw_sys::general::OPEN_TREE_CLONE;
+        /// `OPEN_TREE_CLOEXEC`
+        const CLOEXEC = linux_raw_sys::general::OPEN_TREE_CLOEXEC;
+        /// `AT_EMPTY_PATH`
+        const EMPTY_PATH = linux_raw_sys::general::AT_EMPTY_PATH;
+        /// `AT_NO_AUTOMOUNT`
+        const NO_AUTOMOUNT = linux_raw_sys::general::AT_NO_AUTOMOUNT;
+        /// `AT_RECURSIVE`
+        const RECURSIVE = linux_raw_sys::general::AT_RECURSIVE;
+        /// `AT_SYMLINK_NOFOLLOW`
+        const SYMLINK_NOFOLLOW = linux_raw_sys::general::AT_SYMLINK_NOFOLLOW;
+    }
+}
+
+/// `FSCONFIG_*` commands for use with `fsconfig`.
+#[cfg(linux_kernel)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[repr(u32)]
+pub(crate) enum FsConfigCmd {
+    /// `FSCONFIG_SET_FLAG`
+    SetFlag = linux_raw_sys::general::fsconfig_command::FSCONFIG_SET_FLAG as u32,
+    /// `FSCONFIG_SET_STRING`
+    SetString = linux_raw_sys::general::fsconfig_command::FSCONFIG_SET_STRING as u32,
+    /// `FSCONFIG_SET_BINARY`
+    SetBinary = linux_raw_sys::general::fsconfig_command::FSCONFIG_SET_BINARY as u32,
+    /// `FSCONFIG_SET_PATH`
+    SetPath = linux_raw_sys::general::fsconfig_command::FSCONFIG_SET_PATH as u32,
+    /// `FSCONFIG_SET_PATH_EMPTY`
+    SetPathEmpty = linux_raw_sys::general::fsconfig_command::FSCONFIG_SET_PATH_EMPTY as u32,
+    /// `FSCONFIG_SET_FD`
+    SetFd = linux_raw_sys::general::fsconfig_command::FSCONFIG_SET_FD as u32,
+    /// `FSCONFIG_CMD_CREATE`
+    Create = linux_raw_sys::general::fsconfig_command::FSCONFIG_CMD_CREATE as u32,
+    /// `FSCONFIG_CMD_RECONFIGURE`
+    Reconfigure = linux_raw_sys::general::fsconfig_command::FSCONFIG_CMD_RECONFIGURE as u32,
+}
+
+#[cfg(linux_kernel)]
+bitflags! {
+    /// `FS_*_FL` constants for use with [`ioctl_getflags`] and
+    /// [`ioctl_setflags`].
+    ///
+    /// These are the flags shown and set by `lsattr` and `chattr`.
+    ///
+    /// [`ioctl_getflags`]: crate::fs::ioctl_getflags
+    /// [`ioctl_setflags`]: crate::fs::ioctl_setflags
+    #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+    pub struct InodeFlags: c::c_uint {
+        /// `FS_SECRM_FL`—Secure deletion
+        const SECRM = linux_raw_sys::general::FS_SECRM_FL;
+
+        /// `FS_UNRM_FL`—Undelete
+        const UNRM = linux_raw_sys::general::FS_UNRM_FL;
+
+        /// `FS_COMPR_FL`—Compress file
+        const COMPR = linux_raw_sys::general::FS_COMPR_FL;
+
+        /// `FS_SYNC_FL`—Synchronous updates
+        const SYNC = linux_raw_sys::general::FS_SYNC_FL;
+
+        /// `FS_IMMUTABLE_FL`—Immutable file
+        const IMMUTABLE = linux_raw_sys::general::FS_IMMUTABLE_FL;
+
+        /// `FS_APPEND_FL`—Writes to file may only append
+        const APPEND = linux_raw_sys::general::FS_APPEND_FL;
+
+        /// `FS_NODUMP_FL`—Do not dump file
+        const NODUMP = linux_raw_sys::general::FS_NODUMP_FL;
+
+        /// `FS_NOATIME_FL`—Do not update atime
+        const NOATIME = linux_raw_sys::general::FS_NOATIME_FL;
+
+        /// `FS_DIRTY_FL`
+        const DIRTY = linux_raw_sys::general::FS_DIRTY_FL;
+
+        /// `FS_COMPRBLK_FL`
+        const COMPRBLK = linux_raw_sys::general::FS_COMPRBLK_FL;
+
+        /// `FS_NOCOMP_FL`—Don't compress
+        const NOCOMP = linux_raw_sys::general::FS_NOCOMP_FL;
+
+        /// `FS_ENCRYPT_FL`—Encrypted file
+        const ENCRYPT = linux_raw_sys::general::FS_ENCRYPT_FL;
+
+        /// `FS_INDEX_FL`—Hash-indexed directory
+        const INDEX = linux_raw_sys::general::FS_INDEX_FL;
+
+        /// `FS_IMAGIC_FL`—AFS directory
+        const IMAGIC = linux_raw_sys::general::FS_IMAGIC_FL;
+
+        /// `FS_JOURNAL_DATA_FL`—Journal file data
+        const JOURNAL_DATA = linux_raw_sys::general::FS_JOURNAL_DATA_FL;
+
+        /// `FS_NOTAIL_FL`—File tail should not be merged
+        const NOTAIL = linux_raw_sys::general::FS_NOTAIL_FL;
+
+        /// `FS_DIRSYNC_FL`—Synchronous directory modifications
+        const DIRSYNC = linux_raw_sys::general::FS_DIRSYNC_FL;
+
+        /// `FS_TOPDIR_FL`—Top of directory hierarchies
+        const TOPDIR = linux_raw_sys::general::FS_TOPDIR_FL;
+
+        /// `FS_HUGE_FILE_FL`
+        const HUGE_FILE = linux_raw_sys::general::FS_HUGE_FILE_FL;
+
+        /// `FS_EXTENT_FL`—Inode uses extents
+        const EXTENT = linux_raw_sys::general::FS_EXTENT_FL;
+
+        /// `FS_VERITY_FL`—Verity protected inode
+        const VERITY = linux_raw_sys::general::FS_VERITY_FL;
+
+        /// `FS_EA_INODE_FL`—Inode used for large extended attributes
+        const EA_INODE = linux_raw_sys::general::FS_EA_INODE_FL;
+
+        /// `FS_EOFBLOCKS_FL`
+        const EOFBLOCKS = linux_raw_sys::general::FS_EOFBLOCKS_FL;
+
+        /// `FS_NOCOW_FL`—Do not copy-on-write
+        const NOCOW = linux_raw_sys::general::FS_NOCOW_FL;
+
+        /// `FS_DAX_FL`—Inode is DAX
+        const DAX = linux_raw_sys::general::FS_DAX_FL;
+
+        /// `FS_INLINE_DATA_FL`—Inode has inline data
+        const INLINE_DATA = linux_raw_sys::general::FS_INLINE_DATA_FL;
+
+        /// `FS_PROJINHERIT_FL`—Create with parents projid
+        const PROJINHERIT = linux_raw_sys::general::FS_PROJINHERIT_FL;
+
+        /// `FS_CASEFOLD_FL`—Folder is case insensitive
+        const CASEFOLD = linux_raw_sys::general::FS_CASEFOLD_FL;
+    }
+}
+
+#[cfg(linux_kernel)]
+bitflags! {
+    /// `FS_XFLAG_*` constants for use with [`FsXattr`].
+    ///
+    /// [`FsXattr`]: crate::fs::FsXattr
+    #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+    pub struct FsXFlags: u32 {
+        /// `FS_XFLAG_REALTIME`—Data in realtime volume
+        const REALTIME = linux_raw_sys::general::FS_XFLAG_REALTIME;
+
+        /// `FS_XFLAG_PREALLOC`—Preallocated file extents
+        const PREALLOC = linux_raw_sys::general::FS_XFLAG_PREALLOC;
+
+        /// `FS_XFLAG_IMMUTABLE`—File cannot be modified
+        const IMMUTABLE = linux_raw_sys::general::FS_XFLAG_IMMUTABLE;
+
+        /// `FS_XFLAG_APPEND`—All writes append
+        const APPEND = linux_raw_sys::general::FS_XFLAG_APPEND;
+
+        /// `FS_XFLAG_SYNC`—All writes synchronous
+        const SYNC = linux_raw_sys::general::FS_XFLAG_SYNC;
+
+        /// `FS_XFLAG_NOATIME`—Do not update access time
+        const NOATIME = linux_raw_sys::general::FS_XFLAG_NOATIME;
+
+        /// `FS_XFLAG_NODUMP`—Do not include in backups
+        const NODUMP = linux_raw_sys::general::FS_XFLAG_NODUMP;
+
+        /// `FS_XFLAG_RTINHERIT`—Create with rt bit set
+        const RTINHERIT = linux_raw_sys::general::FS_XFLAG_RTINHERIT;
+
+        /// `FS_XFLAG_PROJINHERIT`—Create with parents projid
+        const PROJINHERIT = linux_raw_sys::general::FS_XFLAG_PROJINHERIT;
+
+        /// `FS_XFLAG_NOSYMLINKS`—Disallow symlink creation
+        const NOSYMLINKS = linux_raw_sys::general::FS_XFLAG_NOSYMLINKS;
+
+        /// `FS_XFLAG_EXTSIZE`—Extent size allocator hint
+        const EXTSIZE = linux_raw_sys::general::FS_XFLAG_EXTSIZE;
+
+        /// `FS_XFLAG_EXTSZINHERIT`—Inherit inode extent size
+        const EXTSZINHERIT = linux_raw_sys::general::FS_XFLAG_EXTSZINHERIT;
+
+        /// `FS_XFLAG_NODEFRAG`—Do not defragment
+        const NODEFRAG = linux_raw_sys::general::FS_XFLAG_NODEFRAG;
+
+        /// `FS_XFLAG_FILESTREAM`—Use filestream allocator
+        const FILESTREAM = linux_raw_sys::general::FS_XFLAG_FILESTREAM;
+
+        /// `FS_XFLAG_DAX`—Use DAX for I/O
+        const DAX = linux_raw_sys::general::FS_XFLAG_DAX;
+
+        /// `FS_XFLAG_COWEXTSIZE`—Copy-on-write extent size allocator hint
+        const COWEXTSIZE = linux_raw_sys::general::FS_XFLAG_COWEXTSIZE;
+
+        /// `FS_XFLAG_HASATTR`—No `DIFLAG` for this
+        const HASATTR = linux_raw_sys::general::FS_XFLAG_HASATTR;
+    }
+}
+
+#[cfg(linux_kernel)]
+bitflags! {
+    /// `FIEMAP_FLAG_*` constants for use with [`ioctl_fiemap`].
+    ///
+    /// [`ioctl_fiemap`]: crate::fs::ioctl_fiemap
+    #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+    pub struct FiemapFlags: u32 {
+        /// `FIEMAP_FLAG_SYNC`—Sync the file before mapping
+        const SYNC = linux_raw_sys::ioctl::FIEMAP_FLAG_SYNC;
+
+        /// `FIEMAP_FLAG_XATTR`—Map the extended attribute tree
+        const XATTR = linux_raw_sys::ioctl::FIEMAP_FLAG_XATTR;
+
+        /// `FIEMAP_FLAG_CACHE`—Request caching of the extents
+        const CACHE = linux_raw_sys::ioctl::FIEMAP_FLAG_CACHE;
+    }
+}
+
+#[cfg(linux_kernel)]
+bitflags! {
+    /// `FIEMAP_EXTENT_*` constants for use with [`FiemapExtent`].
+    ///
+    /// [`FiemapExtent`]: crate::fs::FiemapExtent
+    #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+    pub struct FiemapExtentFlags: u32 {
+        /// `FIEMAP_EXTENT_LAST`—Last extent in the file
+        const LAST = linux_raw_sys::ioctl::FIEMAP_EXTENT_LAST;
+
+        /// `FIEMAP_EXTENT_UNKNOWN`—Data location unknown
+        const UNKNOWN = linux_raw_sys::ioctl::FIEMAP_EXTENT_UNKNOWN;
+
+        /// `FIEMAP_EXTENT_DELALLOC`—Location still pending
+        const DELALLOC = linux_raw_sys::ioctl::FIEMAP_EXTENT_DELALLOC;
+
+        /// `FIEMAP_EXTENT_ENCODED`—Data can't be read while the filesystem is unmounted
+        const ENCODED = linux_raw_sys::ioctl::FIEMAP_EXTENT_ENCODED;
+
+        /// `FIEMAP_EXTENT_DATA_ENCRYPTED`—Data is encrypted by the filesystem
+        const DATA_ENCRYPTED = linux_raw_sys::ioctl::FIEMAP_EXTENT_DATA_ENCRYPTED;
+
+        /// `FIEMAP_EXTENT_NOT_ALIGNED`—Extent offsets may not be block-aligned
+        const NOT_ALIGNED = linux_raw_sys::ioctl::FIEMAP_EXTENT_NOT_ALIGNED;
+
+        /// `FIEMAP_EXTENT_DATA_INLINE`—Data mixed with metadata
+        const DATA_INLINE = linux_raw_sys::ioctl::FIEMAP_EXTENT_DATA_INLINE;
+
+        /// `FIEMAP_EXTENT_DATA_TAIL`—Multiple files in block
+        const DATA_TAIL = linux_raw_sys::ioctl::FIEMAP_EXTENT_DATA_TAIL;
+
+        /// `FIEMAP_EXTENT_UNWRITTEN`—Space allocated, but no data (i.e. zero)
+        const UNWRITTEN = linux_raw_sys::ioctl::FIEMAP_EXTENT_UNWRITTEN;
+
+        /// `FIEMAP_EXTENT_MERGED`—File does not natively support extents; result merged for efficiency
+        const MERGED = linux_raw_sys::ioctl::FIEMAP_EXTENT_MERGED;
+
+        /// `FIEMAP_EXTENT_SHARED`—Space shared with other files
+        const SHARED = linux_raw_sys::ioctl::FIEMAP_EXTENT_SHARED;
+    }
+}
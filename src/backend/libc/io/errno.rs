@@ -729,3 +729,674 @@ impl Errno {
         Self(errno().0)
     }
 }
+
+/// The name and description of each `Errno` constant, for [`Errno::name`],
+/// [`Errno::description`], and [`Errno::from_name`].
+///
+/// Where several names have the same value, such as `EAGAIN` and
+/// `EWOULDBLOCK`, the first one listed is the one `Errno::name` returns, so
+/// it should be the one the platform's headers treat as the primary name.
+pub(crate) static ERRNO_NAMES: &[(Errno, &str, &str)] = &[
+    (Errno::ACCESS, "EACCES", "Permission denied"),
+    (Errno::ADDRINUSE, "EADDRINUSE", "Address already in use"),
+    (
+        Errno::ADDRNOTAVAIL,
+        "EADDRNOTAVAIL",
+        "Cannot assign requested address",
+    ),
+    #[cfg(not(any(
+        bsd,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::ADV, "EADV", "Advertise error"),
+    (
+        Errno::AFNOSUPPORT,
+        "EAFNOSUPPORT",
+        "Address family not supported by protocol",
+    ),
+    (Errno::AGAIN, "EAGAIN", "Resource temporarily unavailable"),
+    (Errno::ALREADY, "EALREADY", "Operation already in progress"),
+    #[cfg(bsd)]
+    (Errno::AUTH, "EAUTH", "Authentication error"),
+    #[cfg(not(any(
+        bsd,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::BADE, "EBADE", "Invalid exchange"),
+    (Errno::BADF, "EBADF", "Bad file descriptor"),
+    #[cfg(not(any(
+        bsd,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::BADFD, "EBADFD", "File descriptor in bad state"),
+    #[cfg(not(windows))]
+    (Errno::BADMSG, "EBADMSG", "Bad message"),
+    #[cfg(not(any(
+        bsd,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::BADR, "EBADR", "Invalid request descriptor"),
+    #[cfg(bsd)]
+    (Errno::BADRPC, "EBADRPC", "RPC struct is bad"),
+    #[cfg(not(any(
+        bsd,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::BADRQC, "EBADRQC", "Invalid request code"),
+    #[cfg(not(any(
+        bsd,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::BADSLT, "EBADSLT", "Invalid slot"),
+    #[cfg(not(any(
+        bsd,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::BFONT, "EBFONT", "Bad font file format"),
+    #[cfg(not(windows))]
+    (Errno::BUSY, "EBUSY", "Device or resource busy"),
+    (Errno::CANCELED, "ECANCELED", "Operation canceled"),
+    #[cfg(target_os = "freebsd")]
+    (
+        Errno::CAPMODE,
+        "ECAPMODE",
+        "Not permitted in capability mode",
+    ),
+    #[cfg(not(windows))]
+    (Errno::CHILD, "ECHILD", "No child processes"),
+    #[cfg(not(any(bsd, windows, target_os = "haiku", target_os = "wasi")))]
+    (Errno::CHRNG, "ECHRNG", "Channel number out of range"),
+    #[cfg(not(any(
+        bsd,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::COMM, "ECOMM", "Communication error on send"),
+    (
+        Errno::CONNABORTED,
+        "ECONNABORTED",
+        "Software caused connection abort",
+    ),
+    (Errno::CONNREFUSED, "ECONNREFUSED", "Connection refused"),
+    (Errno::CONNRESET, "ECONNRESET", "Connection reset by peer"),
+    #[cfg(not(windows))]
+    (Errno::DEADLK, "EDEADLK", "Resource deadlock avoided"),
+    #[cfg(not(any(
+        bsd,
+        windows,
+        target_os = "aix",
+        target_os = "android",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::DEADLOCK, "EDEADLOCK", "Resource deadlock avoided"),
+    (
+        Errno::DESTADDRREQ,
+        "EDESTADDRREQ",
+        "Destination address required",
+    ),
+    #[cfg(windows)]
+    (Errno::DISCON, "EDISCON", "Graceful shutdown in progress"),
+    #[cfg(not(windows))]
+    (Errno::DOM, "EDOM", "Numerical argument out of domain"),
+    #[cfg(freebsdlike)]
+    (Errno::DOOFUS, "EDOOFUS", "Programming error"),
+    #[cfg(not(any(
+        bsd,
+        solarish,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::DOTDOT, "EDOTDOT", "RFS specific error"),
+    (Errno::DQUOT, "EDQUOT", "Disk quota exceeded"),
+    #[cfg(not(windows))]
+    (Errno::EXIST, "EEXIST", "File exists"),
+    (Errno::FAULT, "EFAULT", "Bad address"),
+    #[cfg(not(windows))]
+    (Errno::FBIG, "EFBIG", "File too large"),
+    #[cfg(any(bsd, target_env = "newlib"))]
+    (Errno::FTYPE, "EFTYPE", "Inappropriate file type or format"),
+    #[cfg(not(target_os = "wasi"))]
+    (Errno::HOSTDOWN, "EHOSTDOWN", "Host is down"),
+    (Errno::HOSTUNREACH, "EHOSTUNREACH", "No route to host"),
+    #[cfg(not(any(
+        bsd,
+        solarish,
+        windows,
+        target_os = "aix",
+        target_os = "android",
+        target_os = "haiku",
+        target_os = "redox",
+        target_os = "wasi",
+    )))]
+    (
+        Errno::HWPOISON,
+        "EHWPOISON",
+        "Memory page has hardware error",
+    ),
+    #[cfg(not(windows))]
+    (Errno::IDRM, "EIDRM", "Identifier removed"),
+    #[cfg(not(windows))]
+    (
+        Errno::ILSEQ,
+        "EILSEQ",
+        "Invalid or incomplete multibyte or wide character",
+    ),
+    (
+        Errno::INPROGRESS,
+        "EINPROGRESS",
+        "Operation now in progress",
+    ),
+    (Errno::INTR, "EINTR", "Interrupted system call"),
+    (Errno::INVAL, "EINVAL", "Invalid argument"),
+    #[cfg(windows)]
+    (
+        Errno::INVALIDPROCTABLE,
+        "EINVALIDPROCTABLE",
+        "Procedure table is invalid",
+    ),
+    #[cfg(windows)]
+    (
+        Errno::INVALIDPROVIDER,
+        "EINVALIDPROVIDER",
+        "Service provider is invalid",
+    ),
+    #[cfg(not(windows))]
+    (Errno::IO, "EIO", "Input/output error"),
+    (
+        Errno::ISCONN,
+        "EISCONN",
+        "Transport endpoint is already connected",
+    ),
+    #[cfg(not(windows))]
+    (Errno::ISDIR, "EISDIR", "Is a directory"),
+    #[cfg(not(any(
+        bsd,
+        solarish,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::ISNAM, "EISNAM", "Is a named type file"),
+    #[cfg(not(any(
+        bsd,
+        solarish,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::KEYEXPIRED, "EKEYEXPIRED", "Key has expired"),
+    #[cfg(not(any(
+        bsd,
+        solarish,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (
+        Errno::KEYREJECTED,
+        "EKEYREJECTED",
+        "Key was rejected by service",
+    ),
+    #[cfg(not(any(
+        bsd,
+        solarish,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::KEYREVOKED, "EKEYREVOKED", "Key has been revoked"),
+    #[cfg(not(any(bsd, windows, target_os = "haiku", target_os = "wasi")))]
+    (Errno::L2HLT, "EL2HLT", "Level 2 halted"),
+    #[cfg(not(any(bsd, windows, target_os = "haiku", target_os = "wasi")))]
+    (Errno::L2NSYNC, "EL2NSYNC", "Level 2 not synchronized"),
+    #[cfg(not(any(bsd, windows, target_os = "haiku", target_os = "wasi")))]
+    (Errno::L3HLT, "EL3HLT", "Level 3 halted"),
+    #[cfg(not(any(bsd, windows, target_os = "haiku", target_os = "wasi")))]
+    (Errno::L3RST, "EL3RST", "Level 3 reset"),
+    #[cfg(not(any(
+        bsd,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (
+        Errno::LIBACC,
+        "ELIBACC",
+        "Can not access a needed shared library",
+    ),
+    #[cfg(not(any(
+        bsd,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (
+        Errno::LIBBAD,
+        "ELIBBAD",
+        "Accessing a corrupted shared library",
+    ),
+    #[cfg(not(any(
+        bsd,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (
+        Errno::LIBEXEC,
+        "ELIBEXEC",
+        "Cannot exec a shared library directly",
+    ),
+    #[cfg(not(any(
+        bsd,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (
+        Errno::LIBMAX,
+        "ELIBMAX",
+        "Attempting to link in too many shared libraries",
+    ),
+    #[cfg(not(any(
+        bsd,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::LIBSCN, "ELIBSCN", ".lib section in a.out corrupted"),
+    #[cfg(not(any(bsd, windows, target_os = "haiku", target_os = "wasi")))]
+    (Errno::LNRNG, "ELNRNG", "Link number out of range"),
+    (Errno::LOOP, "ELOOP", "Too many levels of symbolic links"),
+    #[cfg(not(any(
+        bsd,
+        solarish,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::MEDIUMTYPE, "EMEDIUMTYPE", "Wrong medium type"),
+    (Errno::MFILE, "EMFILE", "Too many open files"),
+    #[cfg(not(windows))]
+    (Errno::MLINK, "EMLINK", "Too many links"),
+    (Errno::MSGSIZE, "EMSGSIZE", "Message too long"),
+    #[cfg(not(any(windows, target_os = "openbsd")))]
+    (Errno::MULTIHOP, "EMULTIHOP", "Multihop attempted"),
+    (Errno::NAMETOOLONG, "ENAMETOOLONG", "File name too long"),
+    #[cfg(not(any(
+        bsd,
+        solarish,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::NAVAIL, "ENAVAIL", "No XENIX semaphores available"),
+    #[cfg(bsd)]
+    (Errno::NEEDAUTH, "ENEEDAUTH", "Need authenticator"),
+    (Errno::NETDOWN, "ENETDOWN", "Network is down"),
+    (
+        Errno::NETRESET,
+        "ENETRESET",
+        "Network dropped connection on reset",
+    ),
+    (Errno::NETUNREACH, "ENETUNREACH", "Network is unreachable"),
+    #[cfg(not(windows))]
+    (Errno::NFILE, "ENFILE", "Too many open files in system"),
+    #[cfg(not(any(
+        bsd,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::NOANO, "ENOANO", "No anode"),
+    #[cfg(any(bsd, target_os = "haiku"))]
+    (Errno::NOATTR, "ENOATTR", "Attribute not found"),
+    (Errno::NOBUFS, "ENOBUFS", "No buffer space available"),
+    #[cfg(not(any(bsd, windows, target_os = "haiku", target_os = "wasi")))]
+    (Errno::NOCSI, "ENOCSI", "No CSI structure available"),
+    #[cfg(not(any(
+        freebsdlike,
+        windows,
+        target_os = "haiku",
+        target_os = "openbsd",
+        target_os = "wasi",
+    )))]
+    (Errno::NODATA, "ENODATA", "No data available"),
+    #[cfg(not(windows))]
+    (Errno::NODEV, "ENODEV", "No such device"),
+    #[cfg(not(windows))]
+    (Errno::NOENT, "ENOENT", "No such file or directory"),
+    #[cfg(not(windows))]
+    (Errno::NOEXEC, "ENOEXEC", "Exec format error"),
+    #[cfg(not(any(
+        solarish,
+        bsd,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::NOKEY, "ENOKEY", "Required key not available"),
+    #[cfg(not(windows))]
+    (Errno::NOLCK, "ENOLCK", "No locks available"),
+    #[cfg(not(any(windows, target_os = "openbsd")))]
+    (Errno::NOLINK, "ENOLINK", "Link has been severed"),
+    #[cfg(not(any(
+        bsd,
+        solarish,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::NOMEDIUM, "ENOMEDIUM", "No medium found"),
+    #[cfg(not(windows))]
+    (Errno::NOMEM, "ENOMEM", "Cannot allocate memory"),
+    #[cfg(windows)]
+    (Errno::NOMORE, "ENOMORE", "No more results"),
+    #[cfg(not(windows))]
+    (Errno::NOMSG, "ENOMSG", "No message of desired type"),
+    #[cfg(not(any(
+        bsd,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::NONET, "ENONET", "Machine is not on the network"),
+    #[cfg(not(any(
+        bsd,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::NOPKG, "ENOPKG", "Package not installed"),
+    (Errno::NOPROTOOPT, "ENOPROTOOPT", "Protocol not available"),
+    #[cfg(not(windows))]
+    (Errno::NOSPC, "ENOSPC", "No space left on device"),
+    #[cfg(not(any(
+        freebsdlike,
+        windows,
+        target_os = "haiku",
+        target_os = "openbsd",
+        target_os = "wasi",
+    )))]
+    (Errno::NOSR, "ENOSR", "Out of streams resources"),
+    #[cfg(not(any(
+        freebsdlike,
+        windows,
+        target_os = "haiku",
+        target_os = "openbsd",
+        target_os = "wasi",
+    )))]
+    (Errno::NOSTR, "ENOSTR", "Device not a stream"),
+    #[cfg(not(windows))]
+    (Errno::NOSYS, "ENOSYS", "Function not implemented"),
+    #[cfg(not(any(windows, target_os = "haiku", target_os = "wasi")))]
+    (Errno::NOTBLK, "ENOTBLK", "Block device required"),
+    #[cfg(any(target_os = "freebsd", target_os = "wasi"))]
+    (
+        Errno::NOTCAPABLE,
+        "ENOTCAPABLE",
+        "Capabilities insufficient",
+    ),
+    (
+        Errno::NOTCONN,
+        "ENOTCONN",
+        "Transport endpoint is not connected",
+    ),
+    #[cfg(not(windows))]
+    (Errno::NOTDIR, "ENOTDIR", "Not a directory"),
+    (Errno::NOTEMPTY, "ENOTEMPTY", "Directory not empty"),
+    #[cfg(not(any(
+        bsd,
+        solarish,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::NOTNAM, "ENOTNAM", "Not a XENIX named type file"),
+    #[cfg(not(any(freebsdlike, netbsdlike, windows, target_os = "haiku")))]
+    (
+        Errno::NOTRECOVERABLE,
+        "ENOTRECOVERABLE",
+        "State not recoverable",
+    ),
+    (Errno::NOTSOCK, "ENOTSOCK", "Socket operation on non-socket"),
+    #[cfg(not(any(linux_like, windows, target_os = "haiku", target_os = "redox")))]
+    (Errno::NOTSUP, "ENOTSUP", "Operation not supported"),
+    #[cfg(not(windows))]
+    (Errno::NOTTY, "ENOTTY", "Inappropriate ioctl for device"),
+    #[cfg(not(any(
+        bsd,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::NOTUNIQ, "ENOTUNIQ", "Name not unique on network"),
+    #[cfg(not(windows))]
+    (Errno::NXIO, "ENXIO", "No such device or address"),
+    (Errno::OPNOTSUPP, "EOPNOTSUPP", "Operation not supported"),
+    // On Linux, `ENOTSUP` is an alias for `EOPNOTSUPP`, which is the name
+    // the kernel uses, so it's listed second.
+    #[cfg(linux_like)]
+    (Errno::NOTSUP, "ENOTSUP", "Operation not supported"),
+    #[cfg(not(windows))]
+    (
+        Errno::OVERFLOW,
+        "EOVERFLOW",
+        "Value too large for defined data type",
+    ),
+    #[cfg(not(any(freebsdlike, netbsdlike, windows, target_os = "haiku")))]
+    (Errno::OWNERDEAD, "EOWNERDEAD", "Owner died"),
+    #[cfg(not(windows))]
+    (Errno::PERM, "EPERM", "Operation not permitted"),
+    #[cfg(not(target_os = "wasi"))]
+    (
+        Errno::PFNOSUPPORT,
+        "EPFNOSUPPORT",
+        "Protocol family not supported",
+    ),
+    #[cfg(not(windows))]
+    (Errno::PIPE, "EPIPE", "Broken pipe"),
+    #[cfg(bsd)]
+    (Errno::PROCLIM, "EPROCLIM", "Too many processes"),
+    #[cfg(bsd)]
+    (
+        Errno::PROCUNAVAIL,
+        "EPROCUNAVAIL",
+        "Bad procedure for program",
+    ),
+    #[cfg(bsd)]
+    (
+        Errno::PROGMISMATCH,
+        "EPROGMISMATCH",
+        "Program version wrong",
+    ),
+    #[cfg(bsd)]
+    (
+        Errno::PROGUNAVAIL,
+        "EPROGUNAVAIL",
+        "RPC program not available",
+    ),
+    #[cfg(not(windows))]
+    (Errno::PROTO, "EPROTO", "Protocol error"),
+    (
+        Errno::PROTONOSUPPORT,
+        "EPROTONOSUPPORT",
+        "Protocol not supported",
+    ),
+    (
+        Errno::PROTOTYPE,
+        "EPROTOTYPE",
+        "Protocol wrong type for socket",
+    ),
+    #[cfg(windows)]
+    (
+        Errno::PROVIDERFAILEDINIT,
+        "EPROVIDERFAILEDINIT",
+        "Service provider failed to initialize",
+    ),
+    #[cfg(not(windows))]
+    (Errno::RANGE, "ERANGE", "Numerical result out of range"),
+    #[cfg(windows)]
+    (Errno::REFUSED, "EREFUSED", "Query refused"),
+    #[cfg(not(any(
+        bsd,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::REMCHG, "EREMCHG", "Remote address changed"),
+    #[cfg(not(any(target_os = "haiku", target_os = "wasi")))]
+    (Errno::REMOTE, "EREMOTE", "Object is remote"),
+    #[cfg(not(any(
+        bsd,
+        solarish,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::REMOTEIO, "EREMOTEIO", "Remote I/O error"),
+    #[cfg(not(any(bsd, windows, target_os = "haiku", target_os = "wasi")))]
+    (
+        Errno::RESTART,
+        "ERESTART",
+        "Interrupted system call should be restarted",
+    ),
+    #[cfg(not(any(
+        bsd,
+        solarish,
+        windows,
+        target_os = "aix",
+        target_os = "android",
+        target_os = "haiku",
+        target_os = "redox",
+        target_os = "wasi",
+    )))]
+    (
+        Errno::RFKILL,
+        "ERFKILL",
+        "Operation not possible due to RF-kill",
+    ),
+    #[cfg(not(windows))]
+    (Errno::ROFS, "EROFS", "Read-only file system"),
+    #[cfg(bsd)]
+    (Errno::RPCMISMATCH, "ERPCMISMATCH", "RPC version wrong"),
+    #[cfg(not(target_os = "wasi"))]
+    (
+        Errno::SHUTDOWN,
+        "ESHUTDOWN",
+        "Cannot send after transport endpoint shutdown",
+    ),
+    #[cfg(not(any(target_os = "haiku", target_os = "wasi")))]
+    (
+        Errno::SOCKTNOSUPPORT,
+        "ESOCKTNOSUPPORT",
+        "Socket type not supported",
+    ),
+    #[cfg(not(windows))]
+    (Errno::SPIPE, "ESPIPE", "Illegal seek"),
+    #[cfg(not(windows))]
+    (Errno::SRCH, "ESRCH", "No such process"),
+    #[cfg(not(any(
+        bsd,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::SRMNT, "ESRMNT", "Srmount error"),
+    (Errno::STALE, "ESTALE", "Stale file handle"),
+    #[cfg(not(any(
+        bsd,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::STRPIPE, "ESTRPIPE", "Streams pipe error"),
+    #[cfg(not(any(freebsdlike, windows, target_os = "openbsd", target_os = "wasi")))]
+    (Errno::TIME, "ETIME", "Timer expired"),
+    (Errno::TIMEDOUT, "ETIMEDOUT", "Connection timed out"),
+    #[cfg(not(windows))]
+    (Errno::TOOBIG, "E2BIG", "Argument list too long"),
+    #[cfg(not(any(target_os = "haiku", target_os = "wasi")))]
+    (
+        Errno::TOOMANYREFS,
+        "ETOOMANYREFS",
+        "Too many references: cannot splice",
+    ),
+    #[cfg(not(windows))]
+    (Errno::TXTBSY, "ETXTBSY", "Text file busy"),
+    #[cfg(not(any(
+        bsd,
+        solarish,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::UCLEAN, "EUCLEAN", "Structure needs cleaning"),
+    #[cfg(not(any(bsd, windows, target_os = "haiku", target_os = "wasi")))]
+    (Errno::UNATCH, "EUNATCH", "Protocol driver not attached"),
+    #[cfg(not(any(target_os = "haiku", target_os = "wasi")))]
+    (Errno::USERS, "EUSERS", "Too many users"),
+    (
+        Errno::WOULDBLOCK,
+        "EWOULDBLOCK",
+        "Resource temporarily unavailable",
+    ),
+    #[cfg(not(windows))]
+    (Errno::XDEV, "EXDEV", "Invalid cross-device link"),
+    #[cfg(not(any(
+        bsd,
+        windows,
+        target_os = "aix",
+        target_os = "haiku",
+        target_os = "wasi",
+    )))]
+    (Errno::XFULL, "EXFULL", "Exchange full"),
+];
@@ -211,6 +211,19 @@ pub(crate) unsafe fn munlock(addr: *mut c::c_void, length: usize) -> io::Result<
     ret(c::munlock(addr, length))
 }
 
+/// # Safety
+///
+/// `mincore` operates on raw pointers.
+#[cfg(linux_kernel)]
+#[inline]
+pub(crate) unsafe fn mincore(
+    addr: *mut c::c_void,
+    length: usize,
+    vec: &mut [u8],
+) -> io::Result<()> {
+    ret(c::mincore(addr, length, vec.as_mut_ptr()))
+}
+
 #[cfg(linux_kernel)]
 pub(crate) unsafe fn userfaultfd(flags: UserfaultfdFlags) -> io::Result<OwnedFd> {
     syscall_ret_owned_fd(c::syscall(c::SYS_userfaultfd, flags.bits()))
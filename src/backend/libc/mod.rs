@@ -123,6 +123,7 @@ pub(crate) mod mm;
 pub(crate) mod net;
 #[cfg(not(windows))]
 #[cfg(any(
+    feature = "mm",
     feature = "param",
     feature = "runtime",
     feature = "time",
@@ -140,7 +141,7 @@ pub(crate) mod process;
 #[cfg(feature = "pty")]
 pub(crate) mod pty;
 #[cfg(not(windows))]
-#[cfg(feature = "rand")]
+#[cfg(any(all(feature = "fs", linux_kernel), feature = "rand"))]
 pub(crate) mod rand;
 #[cfg(not(windows))]
 #[cfg(not(target_os = "wasi"))]
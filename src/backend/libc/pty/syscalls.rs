@@ -7,12 +7,7 @@ use crate::io;
 #[cfg(not(target_os = "android"))]
 use {crate::backend::conv::ret_owned_fd, crate::fd::OwnedFd, crate::pty::OpenptFlags};
 #[cfg(any(apple, linux_like, target_os = "freebsd", target_os = "fuchsia"))]
-use {
-    crate::ffi::{CStr, CString},
-    crate::path::SMALL_PATH_BUFFER_SIZE,
-    alloc::borrow::ToOwned,
-    alloc::vec::Vec,
-};
+use {crate::ffi::CStr, core::mem::MaybeUninit};
 
 #[cfg(not(linux_kernel))]
 #[inline]
@@ -22,51 +17,49 @@ pub(crate) fn openpt(flags: OpenptFlags) -> io::Result<OwnedFd> {
 
 #[cfg(any(apple, linux_like, target_os = "freebsd", target_os = "fuchsia"))]
 #[inline]
-pub(crate) fn ptsname(fd: BorrowedFd, mut buffer: Vec<u8>) -> io::Result<CString> {
-    // This code would benefit from having a better way to read into
-    // uninitialized memory, but that requires `unsafe`.
-    buffer.clear();
-    buffer.reserve(SMALL_PATH_BUFFER_SIZE);
-    buffer.resize(buffer.capacity(), 0_u8);
+pub(crate) fn ptsname(fd: BorrowedFd, buf: &mut [MaybeUninit<u8>]) -> io::Result<usize> {
+    // On platforms with `ptsname_r`, use it.
+    #[cfg(any(target_os = "freebsd", linux_like, target_os = "fuchsia"))]
+    let r = unsafe { libc::ptsname_r(borrowed_fd(fd), buf.as_mut_ptr().cast(), buf.len()) };
 
-    loop {
-        // On platforms with `ptsname_r`, use it.
-        #[cfg(any(target_os = "freebsd", linux_like, target_os = "fuchsia"))]
-        let r =
-            unsafe { libc::ptsname_r(borrowed_fd(fd), buffer.as_mut_ptr().cast(), buffer.len()) };
+    // MacOS 10.13.4 has `ptsname_r`; use it if we have it, otherwise fall
+    // back to calling the underlying ioctl directly.
+    #[cfg(apple)]
+    let r = unsafe {
+        weak! { fn ptsname_r(c::c_int, *mut c::c_char, c::size_t) -> c::c_int }
 
-        // MacOS 10.13.4 has `ptsname_r`; use it if we have it, otherwise fall
-        // back to calling the underlying ioctl directly.
-        #[cfg(apple)]
-        let r = unsafe {
-            weak! { fn ptsname_r(c::c_int, *mut c::c_char, c::size_t) -> c::c_int }
-
-            if let Some(libc_ptsname_r) = ptsname_r.get() {
-                libc_ptsname_r(borrowed_fd(fd), buffer.as_mut_ptr().cast(), buffer.len())
-            } else {
-                // The size declared in the `TIOCPTYGNAME` macro in sys/ttycom.h is 128.
-                let mut name: [u8; 128] = [0_u8; 128];
-                match libc::ioctl(borrowed_fd(fd), libc::TIOCPTYGNAME as u64, &mut name) {
-                    0 => {
-                        let len = CStr::from_ptr(name.as_ptr().cast()).to_bytes().len();
-                        std::ptr::copy_nonoverlapping(name.as_ptr(), buffer.as_mut_ptr(), len + 1);
+        if let Some(libc_ptsname_r) = ptsname_r.get() {
+            libc_ptsname_r(borrowed_fd(fd), buf.as_mut_ptr().cast(), buf.len())
+        } else {
+            // The size declared in the `TIOCPTYGNAME` macro in sys/ttycom.h is 128.
+            let mut name: [u8; 128] = [0_u8; 128];
+            match libc::ioctl(borrowed_fd(fd), libc::TIOCPTYGNAME as u64, &mut name) {
+                0 => {
+                    let len = CStr::from_ptr(name.as_ptr().cast()).to_bytes().len();
+                    if len < buf.len() {
+                        core::ptr::copy_nonoverlapping(
+                            name.as_ptr(),
+                            buf.as_mut_ptr().cast::<u8>(),
+                            len + 1,
+                        );
                         0
+                    } else {
+                        libc::ERANGE
                     }
-                    _ => libc_errno::errno().0,
                 }
+                _ => libc_errno::errno().0,
             }
-        };
-
-        if r == 0 {
-            return Ok(unsafe { CStr::from_ptr(buffer.as_ptr().cast()).to_owned() });
-        }
-        if r != libc::ERANGE {
-            return Err(io::Errno::from_raw_os_error(r));
         }
+    };
 
-        buffer.reserve(1); // use `Vec` reallocation strategy to grow capacity exponentially
-        buffer.resize(buffer.capacity(), 0_u8);
+    if r != 0 {
+        return Err(io::Errno::from_raw_os_error(r));
     }
+
+    // SAFETY: `ptsname_r` wrote a NUL-terminated string into `buf`.
+    Ok(unsafe { CStr::from_ptr(buf.as_ptr().cast()) }
+        .to_bytes()
+        .len())
 }
 
 #[inline]
@@ -1,14 +1,21 @@
 //! libc syscalls supporting `rustix::rand`.
 
 #[cfg(linux_kernel)]
-use {crate::backend::c, crate::backend::conv::ret_usize, crate::io, crate::rand::GetRandomFlags};
+use {
+    crate::backend::c, crate::backend::conv::ret_usize,
+    crate::backend::rand::types::GetRandomFlags, crate::io,
+};
 
 #[cfg(linux_kernel)]
-pub(crate) fn getrandom(buf: &mut [u8], flags: GetRandomFlags) -> io::Result<usize> {
+pub(crate) unsafe fn getrandom(
+    buf: *mut u8,
+    len: usize,
+    flags: GetRandomFlags,
+) -> io::Result<usize> {
     // `getrandom` wasn't supported in glibc until 2.25.
     weak_or_syscall! {
         fn getrandom(buf: *mut c::c_void, buflen: c::size_t, flags: c::c_uint) via SYS_getrandom -> c::ssize_t
     }
 
-    unsafe { ret_usize(getrandom(buf.as_mut_ptr().cast(), buf.len(), flags.bits())) }
+    ret_usize(getrandom(buf.cast(), len, flags.bits()))
 }
@@ -357,6 +357,78 @@ impl<'a, Num: ArgNumber> From<crate::fs::MemfdFlags> for ArgReg<'a, Num> {
     }
 }
 
+#[cfg(all(feature = "fs", target_os = "linux"))]
+impl<'a, Num: ArgNumber> From<crate::fs::fanotify::InitFlags> for ArgReg<'a, Num> {
+    #[inline]
+    fn from(flags: crate::fs::fanotify::InitFlags) -> Self {
+        c_uint(flags.bits())
+    }
+}
+
+#[cfg(all(feature = "fs", target_os = "linux"))]
+impl<'a, Num: ArgNumber> From<crate::fs::fanotify::MarkFlags> for ArgReg<'a, Num> {
+    #[inline]
+    fn from(flags: crate::fs::fanotify::MarkFlags) -> Self {
+        c_uint(flags.bits())
+    }
+}
+
+#[cfg(feature = "fs")]
+impl<'a, Num: ArgNumber> From<crate::fs::FsOpenFlags> for ArgReg<'a, Num> {
+    #[inline]
+    fn from(flags: crate::fs::FsOpenFlags) -> Self {
+        c_uint(flags.bits())
+    }
+}
+
+#[cfg(feature = "fs")]
+impl<'a, Num: ArgNumber> From<crate::fs::FsPickFlags> for ArgReg<'a, Num> {
+    #[inline]
+    fn from(flags: crate::fs::FsPickFlags) -> Self {
+        c_uint(flags.bits())
+    }
+}
+
+#[cfg(feature = "fs")]
+impl<'a, Num: ArgNumber> From<crate::fs::FsMountFlags> for ArgReg<'a, Num> {
+    #[inline]
+    fn from(flags: crate::fs::FsMountFlags) -> Self {
+        c_uint(flags.bits())
+    }
+}
+
+#[cfg(feature = "fs")]
+impl<'a, Num: ArgNumber> From<crate::fs::MountAttrFlags> for ArgReg<'a, Num> {
+    #[inline]
+    fn from(flags: crate::fs::MountAttrFlags) -> Self {
+        c_uint(flags.bits())
+    }
+}
+
+#[cfg(feature = "fs")]
+impl<'a, Num: ArgNumber> From<crate::fs::MoveMountFlags> for ArgReg<'a, Num> {
+    #[inline]
+    fn from(flags: crate::fs::MoveMountFlags) -> Self {
+        c_uint(flags.bits())
+    }
+}
+
+#[cfg(feature = "fs")]
+impl<'a, Num: ArgNumber> From<crate::fs::OpenTreeFlags> for ArgReg<'a, Num> {
+    #[inline]
+    fn from(flags: crate::fs::OpenTreeFlags) -> Self {
+        c_uint(flags.bits())
+    }
+}
+
+#[cfg(feature = "fs")]
+impl<'a, Num: ArgNumber> From<crate::backend::fs::types::FsConfigCmd> for ArgReg<'a, Num> {
+    #[inline]
+    fn from(cmd: crate::backend::fs::types::FsConfigCmd) -> Self {
+        c_uint(cmd as c::c_uint)
+    }
+}
+
 #[cfg(feature = "fs")]
 impl<'a, Num: ArgNumber> From<crate::fs::RenameFlags> for ArgReg<'a, Num> {
     #[inline]
@@ -403,6 +475,13 @@ impl<'a, Num: ArgNumber> From<crate::io::DupFlags> for ArgReg<'a, Num> {
     }
 }
 
+impl<'a, Num: ArgNumber> From<crate::io::CloseRangeFlags> for ArgReg<'a, Num> {
+    #[inline]
+    fn from(flags: crate::io::CloseRangeFlags) -> Self {
+        c_uint(flags.bits())
+    }
+}
+
 impl<'a, Num: ArgNumber> From<crate::io::ReadWriteFlags> for ArgReg<'a, Num> {
     #[inline]
     fn from(flags: crate::io::ReadWriteFlags) -> Self {
@@ -650,10 +729,10 @@ impl<'a, Num: ArgNumber> From<crate::time::TimerfdTimerFlags> for ArgReg<'a, Num
     }
 }
 
-#[cfg(feature = "rand")]
-impl<'a, Num: ArgNumber> From<crate::rand::GetRandomFlags> for ArgReg<'a, Num> {
+#[cfg(any(feature = "fs", feature = "rand"))]
+impl<'a, Num: ArgNumber> From<crate::backend::rand::types::GetRandomFlags> for ArgReg<'a, Num> {
     #[inline]
-    fn from(flags: crate::rand::GetRandomFlags) -> Self {
+    fn from(flags: crate::backend::rand::types::GetRandomFlags) -> Self {
         c_uint(flags.bits())
     }
 }
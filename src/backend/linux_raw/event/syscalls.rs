@@ -7,8 +7,10 @@
 #![allow(clippy::undocumented_unsafe_blocks)]
 
 use crate::backend::c;
+#[cfg(feature = "alloc")]
+use crate::backend::conv::pass_usize;
 use crate::backend::conv::{
-    by_ref, c_int, c_uint, pass_usize, raw_fd, ret, ret_owned_fd, ret_usize, slice_mut, zero,
+    by_ref, c_int, c_uint, raw_fd, ret, ret_owned_fd, ret_usize, slice_mut, zero,
 };
 use crate::event::{epoll, EventfdFlags, PollFd};
 use crate::fd::{BorrowedFd, OwnedFd};
@@ -95,6 +97,7 @@ pub(crate) unsafe fn epoll_del(epfd: BorrowedFd<'_>, fd: c::c_int) -> io::Result
     ))
 }
 
+#[cfg(feature = "alloc")]
 #[inline]
 pub(crate) fn epoll_wait(
     epfd: BorrowedFd<'_>,
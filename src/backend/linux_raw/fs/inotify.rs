@@ -72,6 +72,48 @@ bitflags! {
     }
 }
 
+bitflags! {
+    /// `IN*` for use with [`Reader`].
+    ///
+    /// [`Reader`]: crate::fs::inotify::Reader
+    #[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+    pub struct ReadFlags: c::c_uint {
+        /// `IN_ACCESS`
+        const ACCESS = linux_raw_sys::general::IN_ACCESS;
+        /// `IN_ATTRIB`
+        const ATTRIB = linux_raw_sys::general::IN_ATTRIB;
+        /// `IN_CLOSE_NOWRITE`
+        const CLOSE_NOWRITE = linux_raw_sys::general::IN_CLOSE_NOWRITE;
+        /// `IN_CLOSE_WRITE`
+        const CLOSE_WRITE = linux_raw_sys::general::IN_CLOSE_WRITE;
+        /// `IN_CREATE`
+        const CREATE = linux_raw_sys::general::IN_CREATE;
+        /// `IN_DELETE`
+        const DELETE = linux_raw_sys::general::IN_DELETE;
+        /// `IN_DELETE_SELF`
+        const DELETE_SELF = linux_raw_sys::general::IN_DELETE_SELF;
+        /// `IN_MODIFY`
+        const MODIFY = linux_raw_sys::general::IN_MODIFY;
+        /// `IN_MOVE_SELF`
+        const MOVE_SELF = linux_raw_sys::general::IN_MOVE_SELF;
+        /// `IN_MOVED_FROM`
+        const MOVED_FROM = linux_raw_sys::general::IN_MOVED_FROM;
+        /// `IN_MOVED_TO`
+        const MOVED_TO = linux_raw_sys::general::IN_MOVED_TO;
+        /// `IN_OPEN`
+        const OPEN = linux_raw_sys::general::IN_OPEN;
+
+        /// `IN_IGNORED`
+        const IGNORED = linux_raw_sys::general::IN_IGNORED;
+        /// `IN_ISDIR`
+        const ISDIR = linux_raw_sys::general::IN_ISDIR;
+        /// `IN_Q_OVERFLOW`
+        const Q_OVERFLOW = linux_raw_sys::general::IN_Q_OVERFLOW;
+        /// `IN_UNMOUNT`
+        const UNMOUNT = linux_raw_sys::general::IN_UNMOUNT;
+    }
+}
+
 /// `inotify_init1(flags)`—Creates a new inotify object.
 ///
 /// Use the [`CreateFlags::CLOEXEC`] flag to prevent the resulting file
@@ -97,8 +139,7 @@ pub fn inotify_add_watch<P: crate::path::Arg>(
     path: P,
     flags: WatchFlags,
 ) -> io::Result<i32> {
-    let path = path.as_cow_c_str().unwrap();
-    syscalls::inotify_add_watch(inot, &path, flags)
+    path.into_with_c_str(|path| syscalls::inotify_add_watch(inot, path, flags))
 }
 
 /// `inotify_rm_watch(self, wd)`—Removes a watch from this inotify
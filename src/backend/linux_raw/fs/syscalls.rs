@@ -8,11 +8,13 @@
 
 use crate::backend::c;
 use crate::backend::conv::{
-    by_ref, c_int, c_uint, dev_t, oflags_for_open_how, opt_mut, pass_usize, raw_fd, ret, ret_c_int,
-    ret_c_uint, ret_infallible, ret_owned_fd, ret_usize, size_of, slice, slice_mut, zero,
+    by_mut, by_ref, c_int, c_uint, dev_t, oflags_for_open_how, opt_mut, pass_usize, raw_fd, ret,
+    ret_c_int, ret_c_uint, ret_infallible, ret_owned_fd, ret_usize, size_of, slice, slice_mut,
+    zero,
 };
 #[cfg(target_pointer_width = "64")]
 use crate::backend::conv::{loff_t, loff_t_from_u64, ret_u64};
+use crate::backend::fs::types::FsConfigCmd;
 #[cfg(any(
     target_arch = "aarch64",
     target_arch = "riscv64",
@@ -20,25 +22,45 @@ use crate::backend::conv::{loff_t, loff_t_from_u64, ret_u64};
     target_pointer_width = "32",
 ))]
 use crate::fd::AsFd;
-use crate::fd::{BorrowedFd, OwnedFd};
+use crate::fd::{AsRawFd, BorrowedFd, OwnedFd};
 use crate::ffi::CStr;
+#[cfg(target_os = "linux")]
+use crate::fs::fanotify;
+#[cfg(feature = "alloc")]
+use crate::fs::MntIdReq;
+#[cfg(any(
+    target_arch = "aarch64",
+    target_arch = "riscv32",
+    target_arch = "riscv64",
+    target_arch = "x86",
+    target_arch = "x86_64",
+))]
+use crate::fs::SecretFlags;
 #[cfg(any(target_arch = "aarch64", target_arch = "riscv64"))]
 use crate::fs::CWD;
 use crate::fs::{
-    inotify, Access, Advice, AtFlags, FallocateFlags, FileType, FlockOperation, Gid, MemfdFlags,
-    Mode, OFlags, RenameFlags, ResolveFlags, SealFlags, SeekFrom, Stat, StatFs, StatVfs,
-    StatVfsMountFlags, StatxFlags, Timestamps, Uid, XattrFlags,
+    inotify, Access, Advice, AtFlags, DnotifyFlags, FallocateFlags, FdOwner, FileHandle, FileType,
+    Flock, FlockOffsetType, FlockOperation, FlockType, FsXFlags, FsXattr, Gid, InodeFlags,
+    LeaseType, MemfdFlags, Mode, MountAttr, OFlags, RawFiemap, RenameFlags, ResolveFlags,
+    SealFlags, SeekFrom, Stat, StatFs, StatVfs, StatVfsMountFlags, Statx, StatxFlags, Timestamps,
+    Uid, XattrFlags,
 };
+#[cfg(target_os = "linux")]
+use crate::fs::{Cachestat, SyncFileRangeFlags};
 use crate::io;
+use crate::pid::Pid;
 use core::mem::{transmute, zeroed, MaybeUninit};
 #[cfg(target_arch = "mips64")]
 use linux_raw_sys::general::stat as linux_stat64;
 use linux_raw_sys::general::{
-    __kernel_fsid_t, __kernel_timespec, open_how, statx, AT_EACCESS, AT_FDCWD, AT_REMOVEDIR,
-    AT_SYMLINK_NOFOLLOW, F_ADD_SEALS, F_GETFL, F_GET_SEALS, F_SETFL, SEEK_CUR, SEEK_DATA, SEEK_END,
-    SEEK_HOLE, SEEK_SET, STATX__RESERVED,
+    __kernel_fsid_t, __kernel_timespec, fsxattr, mount_attr, open_how, AT_EACCESS, AT_FDCWD,
+    AT_REMOVEDIR, AT_SYMLINK_NOFOLLOW, F_ADD_SEALS, F_GETFL, F_GET_SEALS, F_SETFL, SEEK_CUR,
+    SEEK_DATA, SEEK_END, SEEK_HOLE, SEEK_SET, STATX__RESERVED,
+};
+use linux_raw_sys::ioctl::{
+    BLKPBSZGET, BLKSSZGET, EXT4_IOC_RESIZE_FS, FICLONE, FS_IOC_FIEMAP, FS_IOC_FSGETXATTR,
+    FS_IOC_FSSETXATTR, FS_IOC_GETFLAGS, FS_IOC_SETFLAGS,
 };
-use linux_raw_sys::ioctl::{BLKPBSZGET, BLKSSZGET, EXT4_IOC_RESIZE_FS, FICLONE};
 #[cfg(target_pointer_width = "32")]
 use {
     crate::backend::conv::{hi, lo, slice_just_addr},
@@ -401,6 +423,164 @@ pub(crate) fn syncfs(fd: BorrowedFd<'_>) -> io::Result<()> {
     unsafe { ret(syscall_readonly!(__NR_syncfs, fd)) }
 }
 
+#[cfg(target_os = "linux")]
+#[inline]
+pub(crate) fn sync_file_range(
+    fd: BorrowedFd<'_>,
+    offset: u64,
+    nbytes: u64,
+    flags: SyncFileRangeFlags,
+) -> io::Result<()> {
+    // On ARM, the arguments are reordered so that the offset and nbytes
+    // argument pairs are aligned. And ARM has a custom syscall code for this.
+    #[cfg(target_arch = "arm")]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_arm_sync_file_range,
+            fd,
+            c_uint(flags.bits()),
+            hi(offset),
+            lo(offset),
+            hi(nbytes),
+            lo(nbytes)
+        ))
+    }
+    // On powerpc, the arguments are reordered as on ARM.
+    #[cfg(target_arch = "powerpc")]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_sync_file_range2,
+            fd,
+            c_uint(flags.bits()),
+            hi(offset),
+            lo(offset),
+            hi(nbytes),
+            lo(nbytes)
+        ))
+    }
+    // On mips, the arguments are not reordered, and padding is inserted
+    // instead to ensure alignment.
+    #[cfg(target_arch = "mips")]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_sync_file_range,
+            fd,
+            zero(),
+            hi(offset),
+            lo(offset),
+            hi(nbytes),
+            lo(nbytes),
+            c_uint(flags.bits())
+        ))
+    }
+    #[cfg(all(
+        target_pointer_width = "32",
+        not(any(target_arch = "arm", target_arch = "mips", target_arch = "powerpc")),
+    ))]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_sync_file_range,
+            fd,
+            hi(offset),
+            lo(offset),
+            hi(nbytes),
+            lo(nbytes),
+            c_uint(flags.bits())
+        ))
+    }
+    // On powerpc64, only the reordered form is available.
+    #[cfg(target_arch = "powerpc64")]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_sync_file_range2,
+            fd,
+            c_uint(flags.bits()),
+            loff_t_from_u64(offset),
+            loff_t_from_u64(nbytes)
+        ))
+    }
+    #[cfg(all(target_pointer_width = "64", not(target_arch = "powerpc64")))]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_sync_file_range,
+            fd,
+            loff_t_from_u64(offset),
+            loff_t_from_u64(nbytes),
+            c_uint(flags.bits())
+        ))
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[inline]
+pub(crate) fn readahead(fd: BorrowedFd<'_>, offset: u64, count: usize) -> io::Result<()> {
+    // On ARM, mips, and powerpc, padding is inserted so that the offset
+    // argument pair is aligned.
+    #[cfg(any(target_arch = "arm", target_arch = "mips", target_arch = "powerpc"))]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_readahead,
+            fd,
+            zero(),
+            hi(offset),
+            lo(offset),
+            pass_usize(count)
+        ))
+    }
+    #[cfg(all(
+        target_pointer_width = "32",
+        not(any(target_arch = "arm", target_arch = "mips", target_arch = "powerpc")),
+    ))]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_readahead,
+            fd,
+            hi(offset),
+            lo(offset),
+            pass_usize(count)
+        ))
+    }
+    #[cfg(target_pointer_width = "64")]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_readahead,
+            fd,
+            loff_t_from_u64(offset),
+            pass_usize(count)
+        ))
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn cachestat(fd: BorrowedFd<'_>, offset: u64, len: u64) -> io::Result<Cachestat> {
+    // TODO: Use `__NR_cachestat` from linux-raw-sys once it defines it.
+    #[cfg(target_arch = "mips")]
+    const NR_CACHESTAT: u32 = 4451;
+    #[cfg(target_arch = "mips64")]
+    const NR_CACHESTAT: u32 = 5451;
+    #[cfg(not(any(target_arch = "mips", target_arch = "mips64")))]
+    const NR_CACHESTAT: u32 = 451;
+
+    #[repr(C)]
+    struct CachestatRange {
+        off: u64,
+        len: u64,
+    }
+
+    let range = CachestatRange { off: offset, len };
+    let mut cstat = MaybeUninit::<Cachestat>::uninit();
+    unsafe {
+        ret(crate::backend::arch::choose::syscall4(
+            crate::backend::reg::nr(NR_CACHESTAT),
+            fd.into(),
+            by_ref(&range),
+            by_mut(&mut cstat),
+            c_uint(0),
+        ))?;
+        Ok(cstat.assume_init())
+    }
+}
+
 #[inline]
 pub(crate) fn sync() {
     unsafe { ret_infallible(syscall_readonly!(__NR_sync)) }
@@ -710,7 +890,7 @@ pub(crate) fn statx(
     path: &CStr,
     flags: AtFlags,
     mask: StatxFlags,
-) -> io::Result<statx> {
+) -> io::Result<Statx> {
     // If a future Linux kernel adds more fields to `struct statx` and users
     // passing flags unknown to rustix in `StatxFlags`, we could end up
     // writing outside of the buffer. To prevent this possibility, we mask off
@@ -730,7 +910,7 @@ pub(crate) fn statx(
     let mask = mask & StatxFlags::all();
 
     unsafe {
-        let mut statx_buf = MaybeUninit::<statx>::uninit();
+        let mut statx_buf = MaybeUninit::<Statx>::uninit();
         ret(syscall!(
             __NR_statx,
             dirfd,
@@ -847,7 +1027,7 @@ fn statfs_to_statvfs(statfs: StatFs) -> StatVfs {
 }
 
 #[inline]
-pub(crate) fn readlink(path: &CStr, buf: &mut [u8]) -> io::Result<usize> {
+pub(crate) fn readlink(path: &CStr, buf: &mut [MaybeUninit<u8>]) -> io::Result<usize> {
     let (buf_addr_mut, buf_len) = slice_mut(buf);
     unsafe {
         ret_usize(syscall!(
@@ -992,6 +1172,265 @@ pub(crate) fn fcntl_lock(fd: BorrowedFd<'_>, operation: FlockOperation) -> io::R
     }
 }
 
+#[inline]
+pub(crate) fn fcntl_setlk(fd: BorrowedFd<'_>, lock: &Flock, wait: bool) -> io::Result<()> {
+    #[cfg(target_pointer_width = "64")]
+    use linux_raw_sys::general::{F_SETLK, F_SETLKW};
+    #[cfg(target_pointer_width = "32")]
+    use linux_raw_sys::general::{F_SETLK64 as F_SETLK, F_SETLKW64 as F_SETLKW};
+
+    fcntl_flock(fd, if wait { F_SETLKW } else { F_SETLK }, lock).map(|_| ())
+}
+
+#[inline]
+pub(crate) fn fcntl_getlk(fd: BorrowedFd<'_>, lock: &Flock) -> io::Result<Option<Flock>> {
+    #[cfg(target_pointer_width = "64")]
+    use linux_raw_sys::general::F_GETLK;
+    #[cfg(target_pointer_width = "32")]
+    use linux_raw_sys::general::F_GETLK64 as F_GETLK;
+
+    fcntl_flock(fd, F_GETLK, lock)
+}
+
+#[inline]
+pub(crate) fn fcntl_ofd_setlk(fd: BorrowedFd<'_>, lock: &Flock, wait: bool) -> io::Result<()> {
+    use linux_raw_sys::general::{F_OFD_SETLK, F_OFD_SETLKW};
+
+    fcntl_flock(fd, if wait { F_OFD_SETLKW } else { F_OFD_SETLK }, lock).map(|_| ())
+}
+
+#[inline]
+pub(crate) fn fcntl_ofd_getlk(fd: BorrowedFd<'_>, lock: &Flock) -> io::Result<Option<Flock>> {
+    fcntl_flock(fd, linux_raw_sys::general::F_OFD_GETLK, lock)
+}
+
+/// Call `fcntl` with a `struct flock` argument, and convert the `struct flock`
+/// that it leaves behind back into a `Flock`.
+fn fcntl_flock(fd: BorrowedFd<'_>, cmd: u32, lock: &Flock) -> io::Result<Option<Flock>> {
+    #[cfg(target_pointer_width = "64")]
+    use linux_raw_sys::general::flock;
+    #[cfg(target_pointer_width = "32")]
+    use linux_raw_sys::general::flock64 as flock;
+    use linux_raw_sys::general::{F_RDLCK, F_UNLCK, F_WRLCK};
+
+    unsafe {
+        let mut raw = flock {
+            l_type: lock.typ as _,
+            l_whence: lock.offset_type as _,
+            l_start: lock.start,
+            l_len: lock.length,
+
+            // Open file description locks require `l_pid` to be zero.
+            ..zeroed()
+        };
+
+        #[cfg(target_pointer_width = "32")]
+        ret(syscall!(__NR_fcntl64, fd, c_uint(cmd), by_mut(&mut raw)))?;
+        #[cfg(target_pointer_width = "64")]
+        ret(syscall!(__NR_fcntl, fd, c_uint(cmd), by_mut(&mut raw)))?;
+
+        let typ = match raw.l_type as u32 {
+            F_RDLCK => FlockType::ReadLock,
+            F_WRLCK => FlockType::WriteLock,
+            F_UNLCK => return Ok(None),
+            _ => return Err(io::Errno::INVAL),
+        };
+        let offset_type = match raw.l_whence as u32 {
+            SEEK_SET => FlockOffsetType::Set,
+            SEEK_CUR => FlockOffsetType::Current,
+            SEEK_END => FlockOffsetType::End,
+            _ => return Err(io::Errno::INVAL),
+        };
+        Ok(Some(Flock {
+            start: raw.l_start,
+            length: raw.l_len,
+            // Open file description locks don't have a pid, and the kernel
+            // reports them with a pid of -1.
+            pid: if raw.l_pid > 0 {
+                Pid::from_raw(raw.l_pid)
+            } else {
+                None
+            },
+            typ,
+            offset_type,
+        }))
+    }
+}
+
+#[inline]
+pub(crate) fn fcntl_getown_ex(fd: BorrowedFd<'_>) -> io::Result<Option<FdOwner>> {
+    use linux_raw_sys::general::{f_owner_ex, F_GETOWN_EX, F_OWNER_PGRP, F_OWNER_PID, F_OWNER_TID};
+
+    unsafe {
+        let mut raw: f_owner_ex = zeroed();
+
+        #[cfg(target_pointer_width = "32")]
+        ret(syscall!(
+            __NR_fcntl64,
+            fd,
+            c_uint(F_GETOWN_EX),
+            by_mut(&mut raw)
+        ))?;
+        #[cfg(target_pointer_width = "64")]
+        ret(syscall!(
+            __NR_fcntl,
+            fd,
+            c_uint(F_GETOWN_EX),
+            by_mut(&mut raw)
+        ))?;
+
+        // A pid of zero means that no owner is set.
+        let pid = match Pid::from_raw(raw.pid) {
+            Some(pid) => pid,
+            None => return Ok(None),
+        };
+        match raw.type_ as u32 {
+            F_OWNER_TID => Ok(Some(FdOwner::Thread(pid))),
+            F_OWNER_PID => Ok(Some(FdOwner::Process(pid))),
+            F_OWNER_PGRP => Ok(Some(FdOwner::ProcessGroup(pid))),
+            _ => Err(io::Errno::INVAL),
+        }
+    }
+}
+
+#[inline]
+pub(crate) fn fcntl_setown_ex(fd: BorrowedFd<'_>, owner: Option<FdOwner>) -> io::Result<()> {
+    use linux_raw_sys::general::{f_owner_ex, F_OWNER_PGRP, F_OWNER_PID, F_OWNER_TID, F_SETOWN_EX};
+
+    let (type_, pid) = match owner {
+        Some(FdOwner::Thread(pid)) => (F_OWNER_TID, Some(pid)),
+        Some(FdOwner::Process(pid)) => (F_OWNER_PID, Some(pid)),
+        Some(FdOwner::ProcessGroup(pid)) => (F_OWNER_PGRP, Some(pid)),
+        None => (F_OWNER_PID, None),
+    };
+    let raw = f_owner_ex {
+        type_: type_ as _,
+        pid: Pid::as_raw(pid),
+    };
+
+    #[cfg(target_pointer_width = "32")]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_fcntl64,
+            fd,
+            c_uint(F_SETOWN_EX),
+            by_ref(&raw)
+        ))
+    }
+    #[cfg(target_pointer_width = "64")]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_fcntl,
+            fd,
+            c_uint(F_SETOWN_EX),
+            by_ref(&raw)
+        ))
+    }
+}
+
+#[inline]
+pub(crate) fn fcntl_getsig(fd: BorrowedFd<'_>) -> io::Result<i32> {
+    use linux_raw_sys::general::F_GETSIG;
+
+    #[cfg(target_pointer_width = "32")]
+    unsafe {
+        ret_c_int(syscall_readonly!(__NR_fcntl64, fd, c_uint(F_GETSIG)))
+    }
+    #[cfg(target_pointer_width = "64")]
+    unsafe {
+        ret_c_int(syscall_readonly!(__NR_fcntl, fd, c_uint(F_GETSIG)))
+    }
+}
+
+#[inline]
+pub(crate) fn fcntl_setsig(fd: BorrowedFd<'_>, sig: i32) -> io::Result<()> {
+    use linux_raw_sys::general::F_SETSIG;
+
+    #[cfg(target_pointer_width = "32")]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_fcntl64,
+            fd,
+            c_uint(F_SETSIG),
+            c_int(sig)
+        ))
+    }
+    #[cfg(target_pointer_width = "64")]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_fcntl,
+            fd,
+            c_uint(F_SETSIG),
+            c_int(sig)
+        ))
+    }
+}
+
+#[inline]
+pub(crate) fn fcntl_getlease(fd: BorrowedFd<'_>) -> io::Result<LeaseType> {
+    use linux_raw_sys::general::{F_GETLEASE, F_RDLCK, F_UNLCK, F_WRLCK};
+
+    #[cfg(target_pointer_width = "32")]
+    let lease = unsafe { ret_c_uint(syscall_readonly!(__NR_fcntl64, fd, c_uint(F_GETLEASE)))? };
+    #[cfg(target_pointer_width = "64")]
+    let lease = unsafe { ret_c_uint(syscall_readonly!(__NR_fcntl, fd, c_uint(F_GETLEASE)))? };
+
+    match lease {
+        F_RDLCK => Ok(LeaseType::Read),
+        F_WRLCK => Ok(LeaseType::Write),
+        F_UNLCK => Ok(LeaseType::Unlock),
+        _ => Err(io::Errno::INVAL),
+    }
+}
+
+#[inline]
+pub(crate) fn fcntl_setlease(fd: BorrowedFd<'_>, lease: LeaseType) -> io::Result<()> {
+    use linux_raw_sys::general::F_SETLEASE;
+
+    #[cfg(target_pointer_width = "32")]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_fcntl64,
+            fd,
+            c_uint(F_SETLEASE),
+            c_uint(lease as u32)
+        ))
+    }
+    #[cfg(target_pointer_width = "64")]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_fcntl,
+            fd,
+            c_uint(F_SETLEASE),
+            c_uint(lease as u32)
+        ))
+    }
+}
+
+#[inline]
+pub(crate) fn fcntl_notify(fd: BorrowedFd<'_>, flags: DnotifyFlags) -> io::Result<()> {
+    use linux_raw_sys::general::F_NOTIFY;
+
+    #[cfg(target_pointer_width = "32")]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_fcntl64,
+            fd,
+            c_uint(F_NOTIFY),
+            c_uint(flags.bits())
+        ))
+    }
+    #[cfg(target_pointer_width = "64")]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_fcntl,
+            fd,
+            c_uint(F_NOTIFY),
+            c_uint(flags.bits())
+        ))
+    }
+}
+
 #[inline]
 pub(crate) fn rename(old_path: &CStr, new_path: &CStr) -> io::Result<()> {
     #[cfg(target_arch = "riscv64")]
@@ -1164,6 +1603,7 @@ pub(crate) fn mkdirat(dirfd: BorrowedFd<'_>, path: &CStr, mode: Mode) -> io::Res
     unsafe { ret(syscall_readonly!(__NR_mkdirat, dirfd, path, mode)) }
 }
 
+#[cfg(feature = "alloc")]
 #[inline]
 pub(crate) fn getdents(fd: BorrowedFd<'_>, dirent: &mut [u8]) -> io::Result<usize> {
     let (dirent_addr_mut, dirent_len) = slice_mut(dirent);
@@ -1372,6 +1812,61 @@ pub(crate) fn memfd_create(name: &CStr, flags: MemfdFlags) -> io::Result<OwnedFd
     unsafe { ret_owned_fd(syscall_readonly!(__NR_memfd_create, name, flags)) }
 }
 
+#[cfg(any(
+    target_arch = "aarch64",
+    target_arch = "riscv32",
+    target_arch = "riscv64",
+    target_arch = "x86",
+    target_arch = "x86_64",
+))]
+#[inline]
+pub(crate) fn memfd_secret(flags: SecretFlags) -> io::Result<OwnedFd> {
+    unsafe { ret_owned_fd(syscall_readonly!(__NR_memfd_secret, c_uint(flags.bits()))) }
+}
+
+#[cfg(target_os = "linux")]
+#[inline]
+pub(crate) fn fanotify_init(
+    flags: fanotify::InitFlags,
+    event_flags: OFlags,
+) -> io::Result<OwnedFd> {
+    unsafe { ret_owned_fd(syscall_readonly!(__NR_fanotify_init, flags, event_flags)) }
+}
+
+#[cfg(target_os = "linux")]
+#[inline]
+pub(crate) fn fanotify_mark(
+    fanotify: BorrowedFd<'_>,
+    flags: fanotify::MarkFlags,
+    mask: fanotify::EventFlags,
+    dirfd: BorrowedFd<'_>,
+    path: Option<&CStr>,
+) -> io::Result<()> {
+    #[cfg(target_pointer_width = "32")]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_fanotify_mark,
+            fanotify,
+            flags,
+            hi(mask.bits()),
+            lo(mask.bits()),
+            dirfd,
+            path
+        ))
+    }
+    #[cfg(target_pointer_width = "64")]
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_fanotify_mark,
+            fanotify,
+            flags,
+            pass_usize(mask.bits() as usize),
+            dirfd,
+            path
+        ))
+    }
+}
+
 #[inline]
 pub(crate) fn sendfile(
     out_fd: BorrowedFd<'_>,
@@ -1426,6 +1921,301 @@ pub(crate) fn unmount(target: &CStr, flags: super::types::UnmountFlags) -> io::R
     unsafe { ret(syscall_readonly!(__NR_umount2, target, flags)) }
 }
 
+#[inline]
+pub(crate) fn name_to_handle_at(
+    dirfd: BorrowedFd<'_>,
+    path: &CStr,
+    handle: &mut FileHandle,
+    flags: AtFlags,
+) -> io::Result<i32> {
+    let mut mount_id = MaybeUninit::<c::c_int>::uninit();
+    unsafe {
+        ret(syscall!(
+            __NR_name_to_handle_at,
+            dirfd,
+            path,
+            by_mut(handle),
+            &mut mount_id,
+            flags
+        ))?;
+        Ok(mount_id.assume_init())
+    }
+}
+
+#[inline]
+pub(crate) fn open_by_handle_at(
+    mount_fd: BorrowedFd<'_>,
+    handle: &FileHandle,
+    flags: OFlags,
+) -> io::Result<OwnedFd> {
+    unsafe {
+        ret_owned_fd(syscall_readonly!(
+            __NR_open_by_handle_at,
+            mount_fd,
+            by_ref(handle),
+            flags
+        ))
+    }
+}
+
+#[inline]
+pub(crate) fn fsopen(fs_name: &CStr, flags: super::types::FsOpenFlags) -> io::Result<OwnedFd> {
+    unsafe { ret_owned_fd(syscall_readonly!(__NR_fsopen, fs_name, flags)) }
+}
+
+#[inline]
+pub(crate) fn fsmount(
+    fs_fd: BorrowedFd<'_>,
+    flags: super::types::FsMountFlags,
+    attr_flags: super::types::MountAttrFlags,
+) -> io::Result<OwnedFd> {
+    unsafe { ret_owned_fd(syscall_readonly!(__NR_fsmount, fs_fd, flags, attr_flags)) }
+}
+
+#[cfg(feature = "alloc")]
+pub(crate) fn statmount(req: &MntIdReq, buf: &mut [u8]) -> io::Result<()> {
+    // TODO: Use `__NR_statmount` from linux-raw-sys once it defines it.
+    #[cfg(target_arch = "mips")]
+    const NR_STATMOUNT: u32 = 4457;
+    #[cfg(target_arch = "mips64")]
+    const NR_STATMOUNT: u32 = 5457;
+    #[cfg(not(any(target_arch = "mips", target_arch = "mips64")))]
+    const NR_STATMOUNT: u32 = 457;
+
+    let (buf_addr_mut, buf_len) = slice_mut(buf);
+    unsafe {
+        ret(crate::backend::arch::choose::syscall4(
+            crate::backend::reg::nr(NR_STATMOUNT),
+            by_ref(req),
+            buf_addr_mut,
+            buf_len,
+            c_uint(0),
+        ))
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub(crate) fn listmount(req: &MntIdReq, mnt_ids: &mut [u64], flags: u32) -> io::Result<usize> {
+    // TODO: Use `__NR_listmount` from linux-raw-sys once it defines it.
+    #[cfg(target_arch = "mips")]
+    const NR_LISTMOUNT: u32 = 4458;
+    #[cfg(target_arch = "mips64")]
+    const NR_LISTMOUNT: u32 = 5458;
+    #[cfg(not(any(target_arch = "mips", target_arch = "mips64")))]
+    const NR_LISTMOUNT: u32 = 458;
+
+    let (mnt_ids_addr_mut, mnt_ids_len) = slice_mut(mnt_ids);
+    unsafe {
+        ret_usize(crate::backend::arch::choose::syscall4(
+            crate::backend::reg::nr(NR_LISTMOUNT),
+            by_ref(req),
+            mnt_ids_addr_mut,
+            mnt_ids_len,
+            c_uint(flags),
+        ))
+    }
+}
+
+#[inline]
+pub(crate) fn move_mount(
+    from_dfd: BorrowedFd<'_>,
+    from_pathname: &CStr,
+    to_dfd: BorrowedFd<'_>,
+    to_pathname: &CStr,
+    flags: super::types::MoveMountFlags,
+) -> io::Result<()> {
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_move_mount,
+            from_dfd,
+            from_pathname,
+            to_dfd,
+            to_pathname,
+            flags
+        ))
+    }
+}
+
+#[inline]
+pub(crate) fn open_tree(
+    dfd: BorrowedFd<'_>,
+    filename: &CStr,
+    flags: super::types::OpenTreeFlags,
+) -> io::Result<OwnedFd> {
+    unsafe { ret_owned_fd(syscall_readonly!(__NR_open_tree, dfd, filename, flags)) }
+}
+
+#[inline]
+pub(crate) fn fspick(
+    dfd: BorrowedFd<'_>,
+    path: &CStr,
+    flags: super::types::FsPickFlags,
+) -> io::Result<OwnedFd> {
+    unsafe { ret_owned_fd(syscall_readonly!(__NR_fspick, dfd, path, flags)) }
+}
+
+#[inline]
+pub(crate) fn fsconfig_set_flag(fs_fd: BorrowedFd<'_>, key: &CStr) -> io::Result<()> {
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_fsconfig,
+            fs_fd,
+            FsConfigCmd::SetFlag,
+            key,
+            zero(),
+            zero()
+        ))
+    }
+}
+
+#[inline]
+pub(crate) fn fsconfig_set_string(
+    fs_fd: BorrowedFd<'_>,
+    key: &CStr,
+    value: &CStr,
+) -> io::Result<()> {
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_fsconfig,
+            fs_fd,
+            FsConfigCmd::SetString,
+            key,
+            value,
+            zero()
+        ))
+    }
+}
+
+#[inline]
+pub(crate) fn fsconfig_set_binary(
+    fs_fd: BorrowedFd<'_>,
+    key: &CStr,
+    value: &[u8],
+) -> io::Result<()> {
+    let (value_addr, value_len) = slice(value);
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_fsconfig,
+            fs_fd,
+            FsConfigCmd::SetBinary,
+            key,
+            value_addr,
+            value_len
+        ))
+    }
+}
+
+#[inline]
+pub(crate) fn fsconfig_set_path(
+    fs_fd: BorrowedFd<'_>,
+    key: &CStr,
+    path: &CStr,
+    fd: BorrowedFd<'_>,
+) -> io::Result<()> {
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_fsconfig,
+            fs_fd,
+            FsConfigCmd::SetPath,
+            key,
+            path,
+            fd
+        ))
+    }
+}
+
+#[inline]
+pub(crate) fn fsconfig_set_path_empty(
+    fs_fd: BorrowedFd<'_>,
+    key: &CStr,
+    fd: BorrowedFd<'_>,
+) -> io::Result<()> {
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_fsconfig,
+            fs_fd,
+            FsConfigCmd::SetPathEmpty,
+            key,
+            cstr!(""),
+            fd
+        ))
+    }
+}
+
+#[inline]
+pub(crate) fn fsconfig_set_fd(
+    fs_fd: BorrowedFd<'_>,
+    key: &CStr,
+    fd: BorrowedFd<'_>,
+) -> io::Result<()> {
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_fsconfig,
+            fs_fd,
+            FsConfigCmd::SetFd,
+            key,
+            zero(),
+            fd
+        ))
+    }
+}
+
+#[inline]
+pub(crate) fn fsconfig_create(fs_fd: BorrowedFd<'_>) -> io::Result<()> {
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_fsconfig,
+            fs_fd,
+            FsConfigCmd::Create,
+            zero(),
+            zero(),
+            zero()
+        ))
+    }
+}
+
+#[inline]
+pub(crate) fn fsconfig_reconfigure(fs_fd: BorrowedFd<'_>) -> io::Result<()> {
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_fsconfig,
+            fs_fd,
+            FsConfigCmd::Reconfigure,
+            zero(),
+            zero(),
+            zero()
+        ))
+    }
+}
+
+#[inline]
+pub(crate) fn mount_setattr(
+    dirfd: BorrowedFd<'_>,
+    path: &CStr,
+    flags: AtFlags,
+    attr: &MountAttr<'_>,
+) -> io::Result<()> {
+    let attr = mount_attr {
+        attr_set: attr.attr_set.bits().into(),
+        attr_clr: attr.attr_clr.bits().into(),
+        propagation: attr.propagation.bits().into(),
+        userns_fd: match attr.userns_fd {
+            Some(fd) => fd.as_raw_fd() as u64,
+            None => 0,
+        },
+    };
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_mount_setattr,
+            dirfd,
+            path,
+            flags,
+            by_ref(&attr),
+            size_of::<mount_attr, _>()
+        ))
+    }
+}
+
 #[inline]
 pub(crate) fn inotify_init1(flags: inotify::CreateFlags) -> io::Result<OwnedFd> {
     unsafe { ret_owned_fd(syscall_readonly!(__NR_inotify_init1, flags)) }
@@ -1614,3 +2404,85 @@ pub(crate) fn ext4_ioc_resize_fs(fd: BorrowedFd<'_>, blocks: u64) -> io::Result<
         ))
     }
 }
+
+#[inline]
+pub(crate) fn ioctl_getflags(fd: BorrowedFd<'_>) -> io::Result<InodeFlags> {
+    // `FS_IOC_GETFLAGS` is declared with a `long` argument, but the kernel
+    // reads and writes an `int`.
+    let mut result = MaybeUninit::<c::c_uint>::uninit();
+    unsafe {
+        ret(syscall!(
+            __NR_ioctl,
+            fd,
+            c_uint(FS_IOC_GETFLAGS),
+            &mut result
+        ))?;
+        Ok(InodeFlags::from_bits_retain(result.assume_init()))
+    }
+}
+
+#[inline]
+pub(crate) fn ioctl_setflags(fd: BorrowedFd<'_>, flags: InodeFlags) -> io::Result<()> {
+    let flags = flags.bits();
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_ioctl,
+            fd,
+            c_uint(FS_IOC_SETFLAGS),
+            by_ref(&flags)
+        ))
+    }
+}
+
+#[inline]
+pub(crate) fn ioctl_fsgetxattr(fd: BorrowedFd<'_>) -> io::Result<FsXattr> {
+    let mut result = MaybeUninit::<fsxattr>::uninit();
+    unsafe {
+        ret(syscall!(
+            __NR_ioctl,
+            fd,
+            c_uint(FS_IOC_FSGETXATTR),
+            &mut result
+        ))?;
+        let result = result.assume_init();
+        Ok(FsXattr {
+            xflags: FsXFlags::from_bits_retain(result.fsx_xflags),
+            extsize: result.fsx_extsize,
+            nextents: result.fsx_nextents,
+            projid: result.fsx_projid,
+            cowextsize: result.fsx_cowextsize,
+        })
+    }
+}
+
+#[inline]
+pub(crate) fn ioctl_fssetxattr(fd: BorrowedFd<'_>, xattr: &FsXattr) -> io::Result<()> {
+    let xattr = fsxattr {
+        fsx_xflags: xattr.xflags.bits(),
+        fsx_extsize: xattr.extsize,
+        fsx_nextents: xattr.nextents,
+        fsx_projid: xattr.projid,
+        fsx_cowextsize: xattr.cowextsize,
+        fsx_pad: [0; 8],
+    };
+    unsafe {
+        ret(syscall_readonly!(
+            __NR_ioctl,
+            fd,
+            c_uint(FS_IOC_FSSETXATTR),
+            by_ref(&xattr)
+        ))
+    }
+}
+
+#[inline]
+pub(crate) fn ioctl_fiemap(fd: BorrowedFd<'_>, fiemap: &mut RawFiemap) -> io::Result<()> {
+    unsafe {
+        ret(syscall!(
+            __NR_ioctl,
+            fd,
+            c_uint(FS_IOC_FIEMAP),
+            by_mut(fiemap)
+        ))
+    }
+}
@@ -529,3 +529,252 @@ impl Errno {
     /// `EXFULL`
     pub const XFULL: Self = Self::from_errno(errno::EXFULL);
 }
+
+/// The name and description of each `Errno` constant, for [`Errno::name`],
+/// [`Errno::description`], and [`Errno::from_name`].
+///
+/// Where several names have the same value, such as `EAGAIN` and
+/// `EWOULDBLOCK`, the first one listed is the one `Errno::name` returns, so
+/// it should be the one the platform's headers treat as the primary name.
+pub(crate) static ERRNO_NAMES: &[(Errno, &str, &str)] = &[
+    (Errno::ACCESS, "EACCES", "Permission denied"),
+    (Errno::ADDRINUSE, "EADDRINUSE", "Address already in use"),
+    (
+        Errno::ADDRNOTAVAIL,
+        "EADDRNOTAVAIL",
+        "Cannot assign requested address",
+    ),
+    (Errno::ADV, "EADV", "Advertise error"),
+    (
+        Errno::AFNOSUPPORT,
+        "EAFNOSUPPORT",
+        "Address family not supported by protocol",
+    ),
+    (Errno::AGAIN, "EAGAIN", "Resource temporarily unavailable"),
+    (Errno::ALREADY, "EALREADY", "Operation already in progress"),
+    (Errno::BADE, "EBADE", "Invalid exchange"),
+    (Errno::BADF, "EBADF", "Bad file descriptor"),
+    (Errno::BADFD, "EBADFD", "File descriptor in bad state"),
+    (Errno::BADMSG, "EBADMSG", "Bad message"),
+    (Errno::BADR, "EBADR", "Invalid request descriptor"),
+    (Errno::BADRQC, "EBADRQC", "Invalid request code"),
+    (Errno::BADSLT, "EBADSLT", "Invalid slot"),
+    (Errno::BFONT, "EBFONT", "Bad font file format"),
+    (Errno::BUSY, "EBUSY", "Device or resource busy"),
+    (Errno::CANCELED, "ECANCELED", "Operation canceled"),
+    (Errno::CHILD, "ECHILD", "No child processes"),
+    (Errno::CHRNG, "ECHRNG", "Channel number out of range"),
+    (Errno::COMM, "ECOMM", "Communication error on send"),
+    (
+        Errno::CONNABORTED,
+        "ECONNABORTED",
+        "Software caused connection abort",
+    ),
+    (Errno::CONNREFUSED, "ECONNREFUSED", "Connection refused"),
+    (Errno::CONNRESET, "ECONNRESET", "Connection reset by peer"),
+    (Errno::DEADLK, "EDEADLK", "Resource deadlock avoided"),
+    (Errno::DEADLOCK, "EDEADLOCK", "Resource deadlock avoided"),
+    (
+        Errno::DESTADDRREQ,
+        "EDESTADDRREQ",
+        "Destination address required",
+    ),
+    (Errno::DOM, "EDOM", "Numerical argument out of domain"),
+    (Errno::DOTDOT, "EDOTDOT", "RFS specific error"),
+    (Errno::DQUOT, "EDQUOT", "Disk quota exceeded"),
+    (Errno::EXIST, "EEXIST", "File exists"),
+    (Errno::FAULT, "EFAULT", "Bad address"),
+    (Errno::FBIG, "EFBIG", "File too large"),
+    (Errno::HOSTDOWN, "EHOSTDOWN", "Host is down"),
+    (Errno::HOSTUNREACH, "EHOSTUNREACH", "No route to host"),
+    (
+        Errno::HWPOISON,
+        "EHWPOISON",
+        "Memory page has hardware error",
+    ),
+    (Errno::IDRM, "EIDRM", "Identifier removed"),
+    (
+        Errno::ILSEQ,
+        "EILSEQ",
+        "Invalid or incomplete multibyte or wide character",
+    ),
+    (
+        Errno::INPROGRESS,
+        "EINPROGRESS",
+        "Operation now in progress",
+    ),
+    (Errno::INTR, "EINTR", "Interrupted system call"),
+    (Errno::INVAL, "EINVAL", "Invalid argument"),
+    (Errno::IO, "EIO", "Input/output error"),
+    (
+        Errno::ISCONN,
+        "EISCONN",
+        "Transport endpoint is already connected",
+    ),
+    (Errno::ISDIR, "EISDIR", "Is a directory"),
+    (Errno::ISNAM, "EISNAM", "Is a named type file"),
+    (Errno::KEYEXPIRED, "EKEYEXPIRED", "Key has expired"),
+    (
+        Errno::KEYREJECTED,
+        "EKEYREJECTED",
+        "Key was rejected by service",
+    ),
+    (Errno::KEYREVOKED, "EKEYREVOKED", "Key has been revoked"),
+    (Errno::L2HLT, "EL2HLT", "Level 2 halted"),
+    (Errno::L2NSYNC, "EL2NSYNC", "Level 2 not synchronized"),
+    (Errno::L3HLT, "EL3HLT", "Level 3 halted"),
+    (Errno::L3RST, "EL3RST", "Level 3 reset"),
+    (
+        Errno::LIBACC,
+        "ELIBACC",
+        "Can not access a needed shared library",
+    ),
+    (
+        Errno::LIBBAD,
+        "ELIBBAD",
+        "Accessing a corrupted shared library",
+    ),
+    (
+        Errno::LIBEXEC,
+        "ELIBEXEC",
+        "Cannot exec a shared library directly",
+    ),
+    (
+        Errno::LIBMAX,
+        "ELIBMAX",
+        "Attempting to link in too many shared libraries",
+    ),
+    (Errno::LIBSCN, "ELIBSCN", ".lib section in a.out corrupted"),
+    (Errno::LNRNG, "ELNRNG", "Link number out of range"),
+    (Errno::LOOP, "ELOOP", "Too many levels of symbolic links"),
+    (Errno::MEDIUMTYPE, "EMEDIUMTYPE", "Wrong medium type"),
+    (Errno::MFILE, "EMFILE", "Too many open files"),
+    (Errno::MLINK, "EMLINK", "Too many links"),
+    (Errno::MSGSIZE, "EMSGSIZE", "Message too long"),
+    (Errno::MULTIHOP, "EMULTIHOP", "Multihop attempted"),
+    (Errno::NAMETOOLONG, "ENAMETOOLONG", "File name too long"),
+    (Errno::NAVAIL, "ENAVAIL", "No XENIX semaphores available"),
+    (Errno::NETDOWN, "ENETDOWN", "Network is down"),
+    (
+        Errno::NETRESET,
+        "ENETRESET",
+        "Network dropped connection on reset",
+    ),
+    (Errno::NETUNREACH, "ENETUNREACH", "Network is unreachable"),
+    (Errno::NFILE, "ENFILE", "Too many open files in system"),
+    (Errno::NOANO, "ENOANO", "No anode"),
+    (Errno::NOBUFS, "ENOBUFS", "No buffer space available"),
+    (Errno::NOCSI, "ENOCSI", "No CSI structure available"),
+    (Errno::NODATA, "ENODATA", "No data available"),
+    (Errno::NODEV, "ENODEV", "No such device"),
+    (Errno::NOENT, "ENOENT", "No such file or directory"),
+    (Errno::NOEXEC, "ENOEXEC", "Exec format error"),
+    (Errno::NOKEY, "ENOKEY", "Required key not available"),
+    (Errno::NOLCK, "ENOLCK", "No locks available"),
+    (Errno::NOLINK, "ENOLINK", "Link has been severed"),
+    (Errno::NOMEDIUM, "ENOMEDIUM", "No medium found"),
+    (Errno::NOMEM, "ENOMEM", "Cannot allocate memory"),
+    (Errno::NOMSG, "ENOMSG", "No message of desired type"),
+    (Errno::NONET, "ENONET", "Machine is not on the network"),
+    (Errno::NOPKG, "ENOPKG", "Package not installed"),
+    (Errno::NOPROTOOPT, "ENOPROTOOPT", "Protocol not available"),
+    (Errno::NOSPC, "ENOSPC", "No space left on device"),
+    (Errno::NOSR, "ENOSR", "Out of streams resources"),
+    (Errno::NOSTR, "ENOSTR", "Device not a stream"),
+    (Errno::NOSYS, "ENOSYS", "Function not implemented"),
+    (Errno::NOTBLK, "ENOTBLK", "Block device required"),
+    (
+        Errno::NOTCONN,
+        "ENOTCONN",
+        "Transport endpoint is not connected",
+    ),
+    (Errno::NOTDIR, "ENOTDIR", "Not a directory"),
+    (Errno::NOTEMPTY, "ENOTEMPTY", "Directory not empty"),
+    (Errno::NOTNAM, "ENOTNAM", "Not a XENIX named type file"),
+    (
+        Errno::NOTRECOVERABLE,
+        "ENOTRECOVERABLE",
+        "State not recoverable",
+    ),
+    (Errno::NOTSOCK, "ENOTSOCK", "Socket operation on non-socket"),
+    (Errno::NOTTY, "ENOTTY", "Inappropriate ioctl for device"),
+    (Errno::NOTUNIQ, "ENOTUNIQ", "Name not unique on network"),
+    (Errno::NXIO, "ENXIO", "No such device or address"),
+    (Errno::OPNOTSUPP, "EOPNOTSUPP", "Operation not supported"),
+    // `ENOTSUP` is an alias for `EOPNOTSUPP`, which is the name the kernel
+    // uses, so it's listed second.
+    (Errno::NOTSUP, "ENOTSUP", "Operation not supported"),
+    (
+        Errno::OVERFLOW,
+        "EOVERFLOW",
+        "Value too large for defined data type",
+    ),
+    (Errno::OWNERDEAD, "EOWNERDEAD", "Owner died"),
+    (Errno::PERM, "EPERM", "Operation not permitted"),
+    (
+        Errno::PFNOSUPPORT,
+        "EPFNOSUPPORT",
+        "Protocol family not supported",
+    ),
+    (Errno::PIPE, "EPIPE", "Broken pipe"),
+    (Errno::PROTO, "EPROTO", "Protocol error"),
+    (
+        Errno::PROTONOSUPPORT,
+        "EPROTONOSUPPORT",
+        "Protocol not supported",
+    ),
+    (
+        Errno::PROTOTYPE,
+        "EPROTOTYPE",
+        "Protocol wrong type for socket",
+    ),
+    (Errno::RANGE, "ERANGE", "Numerical result out of range"),
+    (Errno::REMCHG, "EREMCHG", "Remote address changed"),
+    (Errno::REMOTE, "EREMOTE", "Object is remote"),
+    (Errno::REMOTEIO, "EREMOTEIO", "Remote I/O error"),
+    (
+        Errno::RESTART,
+        "ERESTART",
+        "Interrupted system call should be restarted",
+    ),
+    (
+        Errno::RFKILL,
+        "ERFKILL",
+        "Operation not possible due to RF-kill",
+    ),
+    (Errno::ROFS, "EROFS", "Read-only file system"),
+    (
+        Errno::SHUTDOWN,
+        "ESHUTDOWN",
+        "Cannot send after transport endpoint shutdown",
+    ),
+    (
+        Errno::SOCKTNOSUPPORT,
+        "ESOCKTNOSUPPORT",
+        "Socket type not supported",
+    ),
+    (Errno::SPIPE, "ESPIPE", "Illegal seek"),
+    (Errno::SRCH, "ESRCH", "No such process"),
+    (Errno::SRMNT, "ESRMNT", "Srmount error"),
+    (Errno::STALE, "ESTALE", "Stale file handle"),
+    (Errno::STRPIPE, "ESTRPIPE", "Streams pipe error"),
+    (Errno::TIME, "ETIME", "Timer expired"),
+    (Errno::TIMEDOUT, "ETIMEDOUT", "Connection timed out"),
+    (Errno::TOOBIG, "E2BIG", "Argument list too long"),
+    (
+        Errno::TOOMANYREFS,
+        "ETOOMANYREFS",
+        "Too many references: cannot splice",
+    ),
+    (Errno::TXTBSY, "ETXTBSY", "Text file busy"),
+    (Errno::UCLEAN, "EUCLEAN", "Structure needs cleaning"),
+    (Errno::UNATCH, "EUNATCH", "Protocol driver not attached"),
+    (Errno::USERS, "EUSERS", "Too many users"),
+    (
+        Errno::WOULDBLOCK,
+        "EWOULDBLOCK",
+        "Resource temporarily unavailable",
+    ),
+    (Errno::XDEV, "EXDEV", "Invalid cross-device link"),
+    (Errno::XFULL, "EXFULL", "Exchange full"),
+];
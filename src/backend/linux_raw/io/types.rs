@@ -43,3 +43,16 @@ bitflags! {
         const CLOEXEC = linux_raw_sys::general::O_CLOEXEC;
     }
 }
+
+bitflags! {
+    /// `CLOSE_RANGE_*` constants for use with [`close_range`].
+    ///
+    /// [`close_range`]: crate::io::close_range
+    #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+    pub struct CloseRangeFlags: c::c_uint {
+        /// `CLOSE_RANGE_UNSHARE` (since Linux 5.9)
+        const UNSHARE = 1 << 1;
+        /// `CLOSE_RANGE_CLOEXEC` (since Linux 5.11)
+        const CLOEXEC = 1 << 2;
+    }
+}
@@ -206,6 +206,23 @@ pub(crate) unsafe fn munlock(addr: *mut c::c_void, length: usize) -> io::Result<
     ret(syscall!(__NR_munlock, addr, pass_usize(length)))
 }
 
+/// # Safety
+///
+/// `mincore` operates on raw pointers.
+#[inline]
+pub(crate) unsafe fn mincore(
+    addr: *mut c::c_void,
+    length: usize,
+    vec: &mut [u8],
+) -> io::Result<()> {
+    ret(syscall!(
+        __NR_mincore,
+        addr,
+        pass_usize(length),
+        vec.as_mut_ptr()
+    ))
+}
+
 #[inline]
 pub(crate) unsafe fn userfaultfd(flags: UserfaultfdFlags) -> io::Result<OwnedFd> {
     ret_owned_fd(syscall_readonly!(__NR_userfaultfd, flags))
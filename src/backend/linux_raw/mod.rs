@@ -18,6 +18,7 @@
 mod arch;
 mod conv;
 #[cfg(any(
+    feature = "mm",
     feature = "param",
     feature = "runtime",
     feature = "time",
@@ -42,6 +43,7 @@ pub(crate) mod mm;
 #[cfg(feature = "net")]
 pub(crate) mod net;
 #[cfg(any(
+    feature = "mm",
     feature = "param",
     feature = "runtime",
     feature = "time",
@@ -54,7 +56,7 @@ pub(crate) mod pipe;
 pub(crate) mod process;
 #[cfg(feature = "pty")]
 pub(crate) mod pty;
-#[cfg(feature = "rand")]
+#[cfg(any(feature = "fs", feature = "rand"))]
 pub(crate) mod rand;
 #[cfg(feature = "runtime")]
 pub(crate) mod runtime;
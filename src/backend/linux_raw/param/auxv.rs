@@ -6,14 +6,13 @@
 #![allow(unsafe_code)]
 
 use crate::backend::c;
+#[cfg(not(target_vendor = "mustang"))]
+use crate::backend::conv::{c_uint, raw_fd, ret_owned_fd, zero};
 use crate::backend::elf::*;
 use crate::fd::OwnedFd;
 #[cfg(feature = "param")]
 use crate::ffi::CStr;
-#[cfg(not(target_vendor = "mustang"))]
-use crate::fs::{Mode, OFlags};
 use crate::utils::{as_ptr, check_raw_pointer};
-use alloc::vec::Vec;
 use core::ffi::c_void;
 use core::mem::size_of;
 use core::ptr::{null_mut, read_unaligned, NonNull};
@@ -26,7 +25,7 @@ use linux_raw_sys::general::{
     AT_PHNUM, AT_SYSINFO_EHDR,
 };
 
-#[cfg(feature = "param")]
+#[cfg(any(feature = "param", feature = "mm"))]
 #[inline]
 pub(crate) fn page_size() -> usize {
     let mut page_size = PAGE_SIZE.load(Relaxed);
@@ -136,8 +135,18 @@ fn init_from_proc_self_auxv() {
     // Open "/proc/self/auxv", either because we trust "/proc", or because
     // we're running inside QEMU and `proc_self_auxv`'s extra checking foils
     // QEMU's emulation so we need to do a plain open to get the right
-    // auxv records.
-    let file = crate::fs::open("/proc/self/auxv", OFlags::RDONLY, Mode::empty()).unwrap();
+    // auxv records. Use the syscall directly, so that this doesn't depend
+    // on the `fs` feature.
+    let file = unsafe {
+        ret_owned_fd(syscall_readonly!(
+            __NR_openat,
+            raw_fd(c::AT_FDCWD),
+            cstr!("/proc/self/auxv"),
+            c_uint(linux_raw_sys::general::O_RDONLY),
+            zero()
+        ))
+    }
+    .unwrap();
 
     let _ = init_from_auxv_file(file);
 }
@@ -149,26 +158,25 @@ fn init_from_proc_self_auxv() {
 
 /// Process auxv entries from the open file `auxv`.
 fn init_from_auxv_file(auxv: OwnedFd) -> Option<()> {
-    let mut buffer = Vec::<u8>::with_capacity(512);
+    // The kernel's auxv has a fixed maximum size, well under this, so read it
+    // into a buffer on the stack, so that this doesn't depend on `alloc`.
+    // The buffer starts out zeroed, and we make sure at least one entry's
+    // worth of it stays that way, so the entries end with an `AT_NULL` entry.
+    let mut buffer = [0_u8; 4096];
+    let limit = buffer.len() - size_of::<Elf_auxv_t>();
+    let mut len = 0;
     loop {
-        let cur = buffer.len();
-
-        // Request one extra byte; `Vec` will often allocate more.
-        buffer.reserve(1);
-
-        // Use all the space it allocated.
-        buffer.resize(buffer.capacity(), 0);
+        if len == limit {
+            return None;
+        }
 
-        // Read up to that many bytes.
-        let n = match crate::io::read(&auxv, &mut buffer[cur..]) {
-            Err(crate::io::Errno::INTR) => 0,
+        // Read as many bytes as will fit.
+        match crate::io::read(&auxv, &mut buffer[len..limit]) {
+            Err(crate::io::Errno::INTR) => (),
             Err(_err) => panic!(),
             Ok(0) => break,
-            Ok(n) => n,
-        };
-
-        // Account for the number of bytes actually read.
-        buffer.resize(cur + n, 0_u8);
+            Ok(n) => len += n,
+        }
     }
 
     // SAFETY: We loaded from an auxv file into the buffer.
@@ -64,7 +64,7 @@ fn test_abi() {
     assert_eq!(self::AT_SYSINFO_EHDR, ::libc::AT_SYSINFO_EHDR);
 }
 
-#[cfg(feature = "param")]
+#[cfg(any(feature = "param", feature = "mm"))]
 #[inline]
 pub(crate) fn page_size() -> usize {
     unsafe { sysconf(_SC_PAGESIZE) as usize }
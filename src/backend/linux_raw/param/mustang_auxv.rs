@@ -19,7 +19,7 @@ use linux_raw_sys::general::{
     AT_SYSINFO_EHDR,
 };
 
-#[cfg(feature = "param")]
+#[cfg(any(feature = "param", feature = "mm"))]
 #[inline]
 pub(crate) fn page_size() -> usize {
     // SAFETY: This is initialized during program startup.
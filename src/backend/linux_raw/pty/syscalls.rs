@@ -9,28 +9,32 @@
 use crate::backend::c;
 use crate::backend::conv::{by_ref, c_uint, ret, ret_owned_fd};
 use crate::fd::{BorrowedFd, OwnedFd};
-use crate::ffi::CString;
 use crate::io;
 use crate::path::DecInt;
 use crate::pty::OpenptFlags;
-#[cfg(any(apple, freebsdlike, linux_like, target_os = "fuchsia"))]
-use alloc::vec::Vec;
 use core::mem::MaybeUninit;
 use linux_raw_sys::ioctl::{TIOCGPTN, TIOCGPTPEER, TIOCSPTLCK};
 
 #[cfg(any(apple, freebsdlike, linux_like, target_os = "fuchsia"))]
 #[inline]
-pub(crate) fn ptsname(fd: BorrowedFd, mut buffer: Vec<u8>) -> io::Result<CString> {
-    unsafe {
-        let mut n = MaybeUninit::<c::c_int>::uninit();
+pub(crate) fn ptsname(fd: BorrowedFd, buf: &mut [MaybeUninit<u8>]) -> io::Result<usize> {
+    let mut n = MaybeUninit::<c::c_int>::uninit();
+    let n = unsafe {
         ret(syscall!(__NR_ioctl, fd, c_uint(TIOCGPTN), &mut n))?;
+        n.assume_init()
+    };
 
-        buffer.clear();
-        buffer.extend_from_slice(b"/dev/pts/");
-        buffer.extend_from_slice(DecInt::new(n.assume_init()).as_bytes());
-        buffer.push(b'\0');
-        Ok(CString::from_vec_with_nul_unchecked(buffer))
+    let prefix = b"/dev/pts/";
+    let n = DecInt::new(n);
+    let len = prefix.len() + n.as_bytes().len();
+    if len >= buf.len() {
+        return Err(io::Errno::RANGE);
+    }
+    for (dst, src) in buf.iter_mut().zip(prefix.iter().chain(n.as_bytes())) {
+        dst.write(*src);
     }
+    buf[len].write(b'\0');
+    Ok(len)
 }
 
 #[inline]
@@ -6,12 +6,15 @@
 #![allow(unsafe_code)]
 #![allow(clippy::undocumented_unsafe_blocks)]
 
-use crate::backend::conv::{ret_usize, slice_mut};
+use crate::backend::conv::{pass_usize, ret_usize};
 use crate::io;
-use crate::rand::GetRandomFlags;
+use crate::backend::rand::types::GetRandomFlags;
 
 #[inline]
-pub(crate) fn getrandom(buf: &mut [u8], flags: GetRandomFlags) -> io::Result<usize> {
-    let (buf_addr_mut, buf_len) = slice_mut(buf);
-    unsafe { ret_usize(syscall!(__NR_getrandom, buf_addr_mut, buf_len, flags)) }
+pub(crate) unsafe fn getrandom(
+    buf: *mut u8,
+    len: usize,
+    flags: GetRandomFlags,
+) -> io::Result<usize> {
+    ret_usize(syscall!(__NR_getrandom, buf, pass_usize(len), flags))
 }
@@ -1,7 +1,9 @@
 //! POSIX-style filesystem functions which operate on bare paths.
 
 use crate::fd::OwnedFd;
-use crate::ffi::{CStr, CString};
+use crate::ffi::CStr;
+#[cfg(feature = "alloc")]
+use crate::ffi::CString;
 #[cfg(not(any(
     solarish,
     target_os = "haiku",
@@ -13,9 +15,13 @@ use crate::fs::StatFs;
 #[cfg(not(any(target_os = "haiku", target_os = "redox", target_os = "wasi")))]
 use crate::fs::StatVfs;
 use crate::fs::{Access, Mode, OFlags, Stat};
+#[cfg(feature = "alloc")]
 use crate::path::SMALL_PATH_BUFFER_SIZE;
 use crate::{backend, io, path};
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
+use core::mem::MaybeUninit;
+use core::slice;
 
 /// `open(path, oflags, mode)`—Opens a file.
 ///
@@ -97,29 +103,73 @@ pub fn lstat<P: path::Arg>(path: P) -> io::Result<Stat> {
 ///
 /// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/readlink.html
 /// [Linux]: https://man7.org/linux/man-pages/man2/readlink.2.html
+#[cfg(feature = "alloc")]
 #[inline]
 pub fn readlink<P: path::Arg, B: Into<Vec<u8>>>(path: P, reuse: B) -> io::Result<CString> {
     path.into_with_c_str(|path| _readlink(path, reuse.into()))
 }
 
+#[cfg(feature = "alloc")]
+#[allow(unsafe_code)]
 fn _readlink(path: &CStr, mut buffer: Vec<u8>) -> io::Result<CString> {
-    // This code would benefit from having a better way to read into
-    // uninitialized memory, but that requires `unsafe`.
     buffer.clear();
     buffer.reserve(SMALL_PATH_BUFFER_SIZE);
-    buffer.resize(buffer.capacity(), 0_u8);
 
     loop {
-        let nread = backend::fs::syscalls::readlink(path, &mut buffer)?;
+        let nread = backend::fs::syscalls::readlink(path, buffer.spare_capacity_mut())?;
 
-        let nread = nread as usize;
-        assert!(nread <= buffer.len());
-        if nread < buffer.len() {
-            buffer.resize(nread, 0_u8);
-            return Ok(CString::new(buffer).unwrap());
+        debug_assert!(nread <= buffer.capacity());
+        if nread < buffer.capacity() {
+            // SAFETY: `readlink` initialized the first `nread` bytes, which
+            // don't contain a NUL byte, as explained in `_readlinkat`.
+            unsafe {
+                buffer.set_len(nread);
+                return Ok(CString::from_vec_unchecked(buffer));
+            }
         }
-        buffer.reserve(1); // use `Vec` reallocation strategy to grow capacity exponentially
-        buffer.resize(buffer.capacity(), 0_u8);
+
+        buffer.reserve(buffer.capacity() + 1); // use `Vec` reallocation strategy to grow capacity exponentially
+    }
+}
+
+/// `readlink(path)`—Reads the contents of a symlink, without allocating.
+///
+/// The contents are written to `buf`, followed by a NUL terminator, and
+/// returned as a `CStr` borrowed from `buf`. If `buf` is too small, this
+/// fails with [`io::Errno::RANGE`]; the symlink may be retried with a larger
+/// buffer.
+///
+/// # References
+///  - [POSIX]
+///  - [Linux]
+///
+/// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/readlink.html
+/// [Linux]: https://man7.org/linux/man-pages/man2/readlink.2.html
+#[inline]
+pub fn readlink_into<P: path::Arg>(path: P, buf: &mut [MaybeUninit<u8>]) -> io::Result<&CStr> {
+    path.into_with_c_str(|path| _readlink_into(path, buf))
+}
+
+#[allow(unsafe_code)]
+fn _readlink_into<'buf>(path: &CStr, buf: &'buf mut [MaybeUninit<u8>]) -> io::Result<&'buf CStr> {
+    let nread = backend::fs::syscalls::readlink(path, buf)?;
+
+    // If the number of bytes is equal to the buffer length, truncation may
+    // have occurred. This check also ensures that we have enough space for
+    // adding a NUL terminator.
+    debug_assert!(nread <= buf.len());
+    if nread == buf.len() {
+        return Err(io::Errno::RANGE);
+    }
+    buf[nread].write(b'\0');
+
+    // SAFETY: `readlink` initialized the first `nread` bytes, which don't
+    // contain a NUL byte, and we just wrote the NUL terminator after them.
+    unsafe {
+        Ok(CStr::from_bytes_with_nul_unchecked(slice::from_raw_parts(
+            buf.as_ptr().cast::<u8>(),
+            nread + 1,
+        )))
     }
 }
 
@@ -0,0 +1,611 @@
+//! POSIX access control lists, stored in the `system.posix_acl_access` and
+//! `system.posix_acl_default` extended attributes.
+//!
+//! This implements the encoding the Linux kernel uses for these attributes
+//! directly, so it doesn't depend on libacl.
+#![allow(unsafe_code)]
+
+use crate::ffi::CStr;
+use crate::fs::{
+    fgetxattr_vec, fremovexattr, fsetxattr, fstat, getxattr_vec, lgetxattr_vec, lremovexattr,
+    lsetxattr, lstat, removexattr, setxattr, stat, Gid, Mode, Stat, Uid, XattrFlags,
+};
+use crate::{backend, io, path};
+use alloc::vec;
+use alloc::vec::Vec;
+use backend::fd::AsFd;
+use bitflags::bitflags;
+use core::fmt;
+use core::str::FromStr;
+
+/// `POSIX_ACL_XATTR_VERSION`
+const ACL_XATTR_VERSION: u32 = 2;
+
+/// `ACL_UNDEFINED_ID`
+const ACL_UNDEFINED_ID: u32 = u32::MAX;
+
+/// The size of the `posix_acl_xattr_header`.
+const HEADER_SIZE: usize = 4;
+
+/// The size of a `posix_acl_xattr_entry`.
+const ENTRY_SIZE: usize = 8;
+
+/// Which of a file's ACLs to access.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum AclType {
+    /// `system.posix_acl_access`—The ACL which controls access to the file.
+    Access,
+
+    /// `system.posix_acl_default`—The ACL which new files in a directory
+    /// inherit.
+    Default,
+}
+
+impl AclType {
+    /// The name of the extended attribute this ACL is stored in.
+    #[inline]
+    pub fn xattr_name(self) -> &'static CStr {
+        match self {
+            Self::Access => cstr!("system.posix_acl_access"),
+            Self::Default => cstr!("system.posix_acl_default"),
+        }
+    }
+}
+
+bitflags! {
+    /// `ACL_READ`, `ACL_WRITE`, and `ACL_EXECUTE` constants for use in
+    /// [`AclEntry`].
+    #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+    pub struct AclPerms: u16 {
+        /// `ACL_READ`
+        const READ = 0x04;
+
+        /// `ACL_WRITE`
+        const WRITE = 0x02;
+
+        /// `ACL_EXECUTE`
+        const EXECUTE = 0x01;
+    }
+}
+
+impl AclPerms {
+    /// Convert the low three bits of a file mode, as in `S_IRWXO`, into
+    /// permissions.
+    #[inline]
+    const fn from_mode_bits(bits: u32) -> Self {
+        Self::from_bits_truncate((bits & 0o7) as u16)
+    }
+}
+
+/// What an [`AclEntry`] applies to.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum AclTag {
+    /// `ACL_USER_OBJ`—The file's owner.
+    UserObj,
+    /// `ACL_USER`—A specific user.
+    User(Uid),
+    /// `ACL_GROUP_OBJ`—The file's group.
+    GroupObj,
+    /// `ACL_GROUP`—A specific group.
+    Group(Gid),
+    /// `ACL_MASK`—The maximum permissions granted by any of the `User`,
+    /// `GroupObj`, and `Group` entries.
+    Mask,
+    /// `ACL_OTHER`—Everyone else.
+    Other,
+}
+
+impl AclTag {
+    /// The kernel's tag value and qualifier for this tag.
+    fn to_raw(self) -> (u16, u32) {
+        match self {
+            Self::UserObj => (0x01, ACL_UNDEFINED_ID),
+            Self::User(uid) => (0x02, uid.as_raw()),
+            Self::GroupObj => (0x04, ACL_UNDEFINED_ID),
+            Self::Group(gid) => (0x08, gid.as_raw()),
+            Self::Mask => (0x10, ACL_UNDEFINED_ID),
+            Self::Other => (0x20, ACL_UNDEFINED_ID),
+        }
+    }
+
+    fn from_raw(tag: u16, id: u32) -> io::Result<Self> {
+        // `ACL_UNDEFINED_ID` is -1, which isn't a valid user or group ID.
+        let id = || {
+            if id == ACL_UNDEFINED_ID {
+                Err(io::Errno::INVAL)
+            } else {
+                Ok(id)
+            }
+        };
+        // SAFETY: Every ID other than -1, which `id` rejects, is a valid
+        // user or group ID.
+        Ok(match tag {
+            0x01 => Self::UserObj,
+            0x02 => Self::User(unsafe { Uid::from_raw(id()?) }),
+            0x04 => Self::GroupObj,
+            0x08 => Self::Group(unsafe { Gid::from_raw(id()?) }),
+            0x10 => Self::Mask,
+            0x20 => Self::Other,
+            _ => return Err(io::Errno::INVAL),
+        })
+    }
+}
+
+/// An entry in an [`Acl`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct AclEntry {
+    /// What this entry applies to.
+    pub tag: AclTag,
+    /// The permissions it grants.
+    pub perms: AclPerms,
+}
+
+/// A POSIX access control list.
+///
+/// The entries are kept in the order the kernel requires: by tag, and then by
+/// user or group ID, with at most one entry for each tag.
+///
+/// The [`Display`] and [`FromStr`] implementations use the long text form
+/// that `getfacl -n --omit-header` prints, with numeric user and group IDs:
+///
+/// ```text
+/// user::rw-
+/// user:1000:rw-
+/// group::r--
+/// mask::rw-
+/// other::r--
+/// ```
+///
+/// [`Display`]: fmt::Display
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Acl {
+    entries: Vec<AclEntry>,
+}
+
+impl Acl {
+    /// An empty ACL.
+    ///
+    /// Setting an empty ACL as a directory's default ACL removes it. An empty
+    /// ACL isn't valid as an access ACL.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// The minimal ACL equivalent to the permission bits of `mode`.
+    pub fn from_mode(mode: Mode) -> Self {
+        let bits = mode.bits() as u32;
+        Self {
+            entries: vec![
+                AclEntry {
+                    tag: AclTag::UserObj,
+                    perms: AclPerms::from_mode_bits(bits >> 6),
+                },
+                AclEntry {
+                    tag: AclTag::GroupObj,
+                    perms: AclPerms::from_mode_bits(bits >> 3),
+                },
+                AclEntry {
+                    tag: AclTag::Other,
+                    perms: AclPerms::from_mode_bits(bits),
+                },
+            ],
+        }
+    }
+
+    /// The permission bits equivalent to this ACL, as the kernel sets them
+    /// when it's set as an access ACL.
+    ///
+    /// The group bits come from the `Mask` entry if there is one, and from
+    /// the `GroupObj` entry otherwise. Returns `None` if the ACL is missing
+    /// any of the entries it needs.
+    pub fn to_mode(&self) -> Option<Mode> {
+        let user = self.get(AclTag::UserObj)?;
+        let group = self
+            .get(AclTag::Mask)
+            .or_else(|| self.get(AclTag::GroupObj))?;
+        let other = self.get(AclTag::Other)?;
+        let bits = (user.bits() << 6) | (group.bits() << 3) | other.bits();
+        Some(Mode::from_bits_truncate(bits.into()))
+    }
+
+    /// The entries of this ACL.
+    #[inline]
+    pub fn entries(&self) -> &[AclEntry] {
+        &self.entries
+    }
+
+    /// Is this ACL empty?
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Is this ACL equivalent to a file mode, with only the `UserObj`,
+    /// `GroupObj`, and `Other` entries?
+    #[inline]
+    pub fn is_minimal(&self) -> bool {
+        self.entries.len() == 3 && self.validate().is_ok()
+    }
+
+    /// The permissions of the entry for `tag`, if there is one.
+    pub fn get(&self, tag: AclTag) -> Option<AclPerms> {
+        self.position(tag).ok().map(|i| self.entries[i].perms)
+    }
+
+    /// Set the permissions of the entry for `tag`, adding it if needed.
+    pub fn set(&mut self, tag: AclTag, perms: AclPerms) {
+        match self.position(tag) {
+            Ok(i) => self.entries[i].perms = perms,
+            Err(i) => self.entries.insert(i, AclEntry { tag, perms }),
+        }
+    }
+
+    /// Remove the entry for `tag`, and return its permissions, if there was
+    /// one.
+    pub fn remove(&mut self, tag: AclTag) -> Option<AclPerms> {
+        self.position(tag)
+            .ok()
+            .map(|i| self.entries.remove(i).perms)
+    }
+
+    /// Set the `Mask` entry to the union of the permissions of the `User`,
+    /// `GroupObj`, and `Group` entries, as `setfacl` does by default.
+    ///
+    /// If the ACL is minimal, any `Mask` entry is removed instead, since
+    /// it's not needed.
+    pub fn calc_mask(&mut self) {
+        let mut has_named = false;
+        let mut mask = AclPerms::empty();
+        for entry in &self.entries {
+            match entry.tag {
+                AclTag::User(_) | AclTag::Group(_) => {
+                    has_named = true;
+                    mask |= entry.perms;
+                }
+                AclTag::GroupObj => mask |= entry.perms,
+                AclTag::UserObj | AclTag::Mask | AclTag::Other => (),
+            }
+        }
+        if has_named {
+            self.set(AclTag::Mask, mask);
+        } else {
+            self.remove(AclTag::Mask);
+        }
+    }
+
+    /// Check that this is a valid access ACL, or a valid non-empty default
+    /// ACL, in the way that the kernel does.
+    ///
+    /// A valid ACL has exactly one each of the `UserObj`, `GroupObj`, and
+    /// `Other` entries, and it has a `Mask` entry if and only if it has any
+    /// `User` or `Group` entries.
+    pub fn validate(&self) -> io::Result<()> {
+        let mut required = 0;
+        let mut has_named = false;
+        let mut has_mask = false;
+        for entry in &self.entries {
+            match entry.tag {
+                AclTag::UserObj | AclTag::GroupObj | AclTag::Other => required += 1,
+                AclTag::User(_) | AclTag::Group(_) => has_named = true,
+                AclTag::Mask => has_mask = true,
+            }
+        }
+        if required == 3 && has_named == has_mask {
+            Ok(())
+        } else {
+            Err(io::Errno::INVAL)
+        }
+    }
+
+    /// Decode an ACL from the value of a `system.posix_acl_*` extended
+    /// attribute.
+    pub fn from_xattr(value: &[u8]) -> io::Result<Self> {
+        if value.len() < HEADER_SIZE || (value.len() - HEADER_SIZE) % ENTRY_SIZE != 0 {
+            return Err(io::Errno::INVAL);
+        }
+        let (header, entries) = value.split_at(HEADER_SIZE);
+        if u32::from_le_bytes(header.try_into().unwrap()) != ACL_XATTR_VERSION {
+            return Err(io::Errno::OPNOTSUPP);
+        }
+
+        let mut acl = Self {
+            entries: Vec::with_capacity(entries.len() / ENTRY_SIZE),
+        };
+        for entry in entries.chunks_exact(ENTRY_SIZE) {
+            let tag = u16::from_le_bytes([entry[0], entry[1]]);
+            let perms = u16::from_le_bytes([entry[2], entry[3]]);
+            let id = u32::from_le_bytes([entry[4], entry[5], entry[6], entry[7]]);
+            let tag = AclTag::from_raw(tag, id)?;
+            let perms = AclPerms::from_bits(perms).ok_or(io::Errno::INVAL)?;
+            match acl.position(tag) {
+                Ok(_) => return Err(io::Errno::INVAL),
+                Err(i) => acl.entries.insert(i, AclEntry { tag, perms }),
+            }
+        }
+        Ok(acl)
+    }
+
+    /// Encode this ACL as the value of a `system.posix_acl_*` extended
+    /// attribute.
+    pub fn to_xattr(&self) -> Vec<u8> {
+        let mut value = Vec::with_capacity(HEADER_SIZE + self.entries.len() * ENTRY_SIZE);
+        value.extend_from_slice(&ACL_XATTR_VERSION.to_le_bytes());
+        for entry in &self.entries {
+            let (tag, id) = entry.tag.to_raw();
+            value.extend_from_slice(&tag.to_le_bytes());
+            value.extend_from_slice(&entry.perms.bits().to_le_bytes());
+            value.extend_from_slice(&id.to_le_bytes());
+        }
+        value
+    }
+
+    /// Find the index of the entry for `tag`, or where it would be inserted.
+    fn position(&self, tag: AclTag) -> Result<usize, usize> {
+        let key = tag.to_raw();
+        self.entries
+            .binary_search_by_key(&key, |entry| entry.tag.to_raw())
+    }
+}
+
+impl fmt::Display for AclPerms {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flag = |perm, c| if self.contains(perm) { c } else { '-' };
+        write!(
+            f,
+            "{}{}{}",
+            flag(Self::READ, 'r'),
+            flag(Self::WRITE, 'w'),
+            flag(Self::EXECUTE, 'x')
+        )
+    }
+}
+
+impl fmt::Display for AclEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.tag {
+            AclTag::UserObj => write!(f, "user::{}", self.perms),
+            AclTag::User(uid) => write!(f, "user:{}:{}", uid.as_raw(), self.perms),
+            AclTag::GroupObj => write!(f, "group::{}", self.perms),
+            AclTag::Group(gid) => write!(f, "group:{}:{}", gid.as_raw(), self.perms),
+            AclTag::Mask => write!(f, "mask::{}", self.perms),
+            AclTag::Other => write!(f, "other::{}", self.perms),
+        }
+    }
+}
+
+impl fmt::Display for Acl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            writeln!(f, "{}", entry)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for AclPerms {
+    type Err = io::Errno;
+
+    /// Parse permissions in the form `getfacl` prints them, such as `r-x`,
+    /// or in the shorter forms `setfacl` accepts, such as `rx`.
+    fn from_str(s: &str) -> io::Result<Self> {
+        if s.is_empty() {
+            return Err(io::Errno::INVAL);
+        }
+        let mut perms = Self::empty();
+        for c in s.chars() {
+            perms |= match c {
+                'r' => Self::READ,
+                'w' => Self::WRITE,
+                'x' => Self::EXECUTE,
+                '-' => Self::empty(),
+                _ => return Err(io::Errno::INVAL),
+            };
+        }
+        Ok(perms)
+    }
+}
+
+impl FromStr for AclEntry {
+    type Err = io::Errno;
+
+    /// Parse an entry such as `user:1000:rw-`. The short tag names `u`, `g`,
+    /// `m`, and `o` are accepted, and so is omitting the empty qualifier of
+    /// `mask` and `other` entries, as in `o:r`.
+    ///
+    /// User and group names aren't supported; qualifiers must be numeric
+    /// IDs.
+    fn from_str(s: &str) -> io::Result<Self> {
+        let mut fields = s.trim().split(':');
+        let tag = fields.next().ok_or(io::Errno::INVAL)?;
+        let (qualifier, perms) = match (fields.next(), fields.next(), fields.next()) {
+            (Some(qualifier), Some(perms), None) => (qualifier.trim(), perms),
+            (Some(perms), None, None) if matches!(tag, "mask" | "m" | "other" | "o") => ("", perms),
+            _ => return Err(io::Errno::INVAL),
+        };
+        let id = || -> io::Result<u32> {
+            match qualifier.parse() {
+                Ok(ACL_UNDEFINED_ID) | Err(_) => Err(io::Errno::INVAL),
+                Ok(id) => Ok(id),
+            }
+        };
+        // SAFETY: Every ID other than -1, which `id` rejects, is a valid
+        // user or group ID.
+        let tag = match (tag.trim(), qualifier.is_empty()) {
+            ("user" | "u", true) => AclTag::UserObj,
+            ("user" | "u", false) => AclTag::User(unsafe { Uid::from_raw(id()?) }),
+            ("group" | "g", true) => AclTag::GroupObj,
+            ("group" | "g", false) => AclTag::Group(unsafe { Gid::from_raw(id()?) }),
+            ("mask" | "m", true) => AclTag::Mask,
+            ("other" | "o", true) => AclTag::Other,
+            _ => return Err(io::Errno::INVAL),
+        };
+        Ok(Self {
+            tag,
+            perms: perms.trim().parse()?,
+        })
+    }
+}
+
+impl FromStr for Acl {
+    type Err = io::Errno;
+
+    /// Parse an ACL in the form `getfacl` prints, with one entry per line, or
+    /// with entries separated by commas as `setfacl` accepts them.
+    ///
+    /// Blank lines and comments starting with `#`, including the header and
+    /// effective rights comments `getfacl` prints, are ignored. Entries for
+    /// a default ACL, starting with `default:`, aren't accepted.
+    fn from_str(s: &str) -> io::Result<Self> {
+        let mut acl = Self::new();
+        for line in s.lines() {
+            let line = match line.find('#') {
+                Some(comment) => &line[..comment],
+                None => line,
+            };
+            for entry in line.split(',') {
+                if entry.trim().is_empty() {
+                    continue;
+                }
+                let entry: AclEntry = entry.parse()?;
+                match acl.position(entry.tag) {
+                    Ok(_) => return Err(io::Errno::INVAL),
+                    Err(i) => acl.entries.insert(i, entry),
+                }
+            }
+        }
+        Ok(acl)
+    }
+}
+
+/// Get the ACL of type `typ` of the file at `path`.
+///
+/// If the file doesn't have an access ACL, this returns the minimal ACL
+/// equivalent to its mode. If it doesn't have a default ACL, this returns an
+/// empty ACL.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man5/acl.5.html
+pub fn get_acl<P: path::Arg>(path: P, typ: AclType) -> io::Result<Acl> {
+    path.into_with_c_str(|path| decode(getxattr_vec(path, typ.xattr_name()), typ, || stat(path)))
+}
+
+/// Get the ACL of type `typ` of the file at `path`, without following
+/// symlinks in the last path component.
+///
+/// See [`get_acl`] for details.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man5/acl.5.html
+pub fn lget_acl<P: path::Arg>(path: P, typ: AclType) -> io::Result<Acl> {
+    path.into_with_c_str(|path| decode(lgetxattr_vec(path, typ.xattr_name()), typ, || lstat(path)))
+}
+
+/// Get the ACL of type `typ` of an open file.
+///
+/// See [`get_acl`] for details.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man5/acl.5.html
+pub fn fget_acl<Fd: AsFd>(fd: Fd, typ: AclType) -> io::Result<Acl> {
+    let fd = fd.as_fd();
+    decode(fgetxattr_vec(fd, typ.xattr_name()), typ, || fstat(fd))
+}
+
+/// Set the ACL of type `typ` of the file at `path`.
+///
+/// The ACL is checked with [`Acl::validate`] first. Setting an access ACL
+/// also sets the permission bits of the file's mode. Setting an empty
+/// default ACL removes the default ACL.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man5/acl.5.html
+pub fn set_acl<P: path::Arg>(path: P, typ: AclType, acl: &Acl) -> io::Result<()> {
+    path.into_with_c_str(|path| {
+        encode(
+            typ,
+            acl,
+            |value| setxattr(path, typ.xattr_name(), value, XattrFlags::empty()),
+            || removexattr(path, typ.xattr_name()),
+        )
+    })
+}
+
+/// Set the ACL of type `typ` of the file at `path`, without following
+/// symlinks in the last path component.
+///
+/// See [`set_acl`] for details.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man5/acl.5.html
+pub fn lset_acl<P: path::Arg>(path: P, typ: AclType, acl: &Acl) -> io::Result<()> {
+    path.into_with_c_str(|path| {
+        encode(
+            typ,
+            acl,
+            |value| lsetxattr(path, typ.xattr_name(), value, XattrFlags::empty()),
+            || lremovexattr(path, typ.xattr_name()),
+        )
+    })
+}
+
+/// Set the ACL of type `typ` of an open file.
+///
+/// See [`set_acl`] for details.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man5/acl.5.html
+pub fn fset_acl<Fd: AsFd>(fd: Fd, typ: AclType, acl: &Acl) -> io::Result<()> {
+    let fd = fd.as_fd();
+    encode(
+        typ,
+        acl,
+        |value| fsetxattr(fd, typ.xattr_name(), value, XattrFlags::empty()),
+        || fremovexattr(fd, typ.xattr_name()),
+    )
+}
+
+fn decode(
+    value: io::Result<Vec<u8>>,
+    typ: AclType,
+    stat: impl FnOnce() -> io::Result<Stat>,
+) -> io::Result<Acl> {
+    match value {
+        Ok(value) => Acl::from_xattr(&value),
+        Err(io::Errno::NODATA) => match typ {
+            AclType::Access => Ok(Acl::from_mode(Mode::from_raw_mode(stat()?.st_mode))),
+            AclType::Default => Ok(Acl::new()),
+        },
+        Err(err) => Err(err),
+    }
+}
+
+fn encode(
+    typ: AclType,
+    acl: &Acl,
+    set: impl FnOnce(&[u8]) -> io::Result<()>,
+    remove: impl FnOnce() -> io::Result<()>,
+) -> io::Result<()> {
+    if typ == AclType::Default && acl.is_empty() {
+        return match remove() {
+            Ok(()) | Err(io::Errno::NODATA) => Ok(()),
+            Err(err) => Err(err),
+        };
+    }
+    acl.validate()?;
+    set(&acl.to_xattr())
+}
@@ -6,7 +6,9 @@
 //! [`cwd`]: crate::fs::cwd::CWD
 
 use crate::fd::OwnedFd;
-use crate::ffi::{CStr, CString};
+use crate::ffi::CStr;
+#[cfg(feature = "alloc")]
+use crate::ffi::CString;
 #[cfg(apple)]
 use crate::fs::CloneFlags;
 #[cfg(not(any(apple, target_os = "wasi")))]
@@ -16,11 +18,15 @@ use crate::fs::RenameFlags;
 use crate::fs::{Access, AtFlags, Mode, OFlags, Stat, Timestamps};
 #[cfg(not(target_os = "wasi"))]
 use crate::fs::{Gid, Uid};
+#[cfg(feature = "alloc")]
 use crate::path::SMALL_PATH_BUFFER_SIZE;
 use crate::timespec::Nsecs;
 use crate::{backend, io, path};
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 use backend::fd::{AsFd, BorrowedFd};
+use core::mem::MaybeUninit;
+use core::slice;
 
 pub use backend::fs::types::{Dev, RawMode};
 
@@ -72,6 +78,7 @@ pub fn openat<P: path::Arg, Fd: AsFd>(
 ///
 /// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/readlinkat.html
 /// [Linux]: https://man7.org/linux/man-pages/man2/readlinkat.2.html
+#[cfg(feature = "alloc")]
 #[inline]
 pub fn readlinkat<P: path::Arg, Fd: AsFd, B: Into<Vec<u8>>>(
     dirfd: Fd,
@@ -81,6 +88,7 @@ pub fn readlinkat<P: path::Arg, Fd: AsFd, B: Into<Vec<u8>>>(
     path.into_with_c_str(|path| _readlinkat(dirfd.as_fd(), path, reuse.into()))
 }
 
+#[cfg(feature = "alloc")]
 #[allow(unsafe_code)]
 fn _readlinkat(dirfd: BorrowedFd<'_>, path: &CStr, mut buffer: Vec<u8>) -> io::Result<CString> {
     buffer.clear();
@@ -116,6 +124,57 @@ fn _readlinkat(dirfd: BorrowedFd<'_>, path: &CStr, mut buffer: Vec<u8>) -> io::R
     }
 }
 
+/// `readlinkat(fd, path)`—Reads the contents of a symlink, without
+/// allocating.
+///
+/// The contents are written to `buf`, followed by a NUL terminator, and
+/// returned as a `CStr` borrowed from `buf`. If `buf` is too small, this
+/// fails with [`io::Errno::RANGE`]; the symlink may be retried with a larger
+/// buffer.
+///
+/// # References
+///  - [POSIX]
+///  - [Linux]
+///
+/// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/readlinkat.html
+/// [Linux]: https://man7.org/linux/man-pages/man2/readlinkat.2.html
+#[inline]
+pub fn readlinkat_into<P: path::Arg, Fd: AsFd>(
+    dirfd: Fd,
+    path: P,
+    buf: &mut [MaybeUninit<u8>],
+) -> io::Result<&CStr> {
+    path.into_with_c_str(|path| _readlinkat_into(dirfd.as_fd(), path, buf))
+}
+
+#[allow(unsafe_code)]
+fn _readlinkat_into<'buf>(
+    dirfd: BorrowedFd<'_>,
+    path: &CStr,
+    buf: &'buf mut [MaybeUninit<u8>],
+) -> io::Result<&'buf CStr> {
+    let nread = backend::fs::syscalls::readlinkat(dirfd, path, buf)?;
+
+    // If the number of bytes is equal to the buffer length, truncation may
+    // have occurred. This check also ensures that we have enough space for
+    // adding a NUL terminator.
+    debug_assert!(nread <= buf.len());
+    if nread == buf.len() {
+        return Err(io::Errno::RANGE);
+    }
+    buf[nread].write(b'\0');
+
+    // SAFETY: `readlinkat` initialized the first `nread` bytes, which don't
+    // contain a NUL byte, as in `_readlinkat`, and we just wrote the NUL
+    // terminator after them.
+    unsafe {
+        Ok(CStr::from_bytes_with_nul_unchecked(slice::from_raw_parts(
+            buf.as_ptr().cast::<u8>(),
+            nread + 1,
+        )))
+    }
+}
+
 /// `mkdirat(fd, path, mode)`—Creates a directory.
 ///
 /// # References
@@ -0,0 +1,325 @@
+//! `atomic_write_at`, which replaces a file so that after a crash, the file
+//! has either its old contents or its new contents, and never a mix of the
+//! two.
+
+use crate::fd::{AsFd, AsRawFd, BorrowedFd, OwnedFd};
+use crate::fs::random_name::fill_random_name;
+use crate::fs::{
+    fsync, linkat, openat, renameat, renameat_with, unlinkat, AtFlags, Mode, OFlags, RenameFlags,
+    CWD,
+};
+use crate::{io, path};
+use alloc::vec::Vec;
+
+/// The number of temporary names to try before giving up.
+const MAX_ATTEMPTS: usize = 100;
+
+/// The longest prefix of the final name to include in a temporary name, which
+/// leaves room for the random suffix within `NAME_MAX`.
+const MAX_PREFIX: usize = 200;
+
+/// Options for [`atomic_write_at`] and [`atomic_write_at_with`].
+#[derive(Clone, Debug)]
+pub struct AtomicWriteOptions {
+    /// The mode to create the file with, which is masked by the process's
+    /// umask. The default is `0o644`.
+    pub mode: Mode,
+
+    /// Replace the file if it already exists. The default is `true`.
+    ///
+    /// If this is `false`, and the file already exists, the write fails with
+    /// [`io::Errno::EXIST`] and the existing file is left unmodified.
+    pub replace: bool,
+}
+
+impl Default for AtomicWriteOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            mode: Mode::from_raw_mode(0o644),
+            replace: true,
+        }
+    }
+}
+
+/// Atomically create or replace the file `name` in `dirfd`, with `contents`.
+///
+/// See [`atomic_write_at_with`] for details.
+pub fn atomic_write_at<Fd: AsFd, P: path::Arg>(
+    dirfd: Fd,
+    name: P,
+    contents: &[u8],
+    options: &AtomicWriteOptions,
+) -> io::Result<()> {
+    atomic_write_at_with(dirfd, name, options, |fd| write_all(fd, contents))
+}
+
+/// Atomically create or replace the file `name` in `dirfd`, with the data
+/// that `write` writes to the file descriptor it's passed.
+///
+/// This creates an anonymous file with `O_TMPFILE` in the directory that will
+/// contain `name`, or, if the filesystem doesn't support that, a file with a
+/// random hidden name created with `O_EXCL`. Once `write` has written the
+/// data, the file is `fsync`ed, and then published under `name`:
+///
+///  - An anonymous file is linked into the directory with
+///    `linkat(fd, "", dir, name, AT_EMPTY_PATH)`, or, without the
+///    `CAP_DAC_READ_SEARCH` capability that needs, through its
+///    `/proc/self/fd` entry. When replacing an existing file, it's linked
+///    under a temporary name first, and then renamed over `name`.
+///  - A named temporary file is renamed over `name` with `renameat`, or with
+///    `renameat2(…, RENAME_NOREPLACE)` when [`AtomicWriteOptions::replace`]
+///    is `false`.
+///
+/// Finally, the directory is `fsync`ed, so that the new directory entry is
+/// durable when this returns.
+///
+/// If anything fails, including `write`, `name` is left unmodified and any
+/// temporary file is removed.
+///
+/// `name` may contain `/`s, in which case the file is created in the
+/// directory its parent path refers to. It may not end in `/`, `.`, or `..`.
+///
+/// # References
+///  - [Linux `open`]
+///  - [Linux `linkat`]
+///  - [Linux `rename`]
+///
+/// [Linux `open`]: https://man7.org/linux/man-pages/man2/open.2.html
+/// [Linux `linkat`]: https://man7.org/linux/man-pages/man2/linkat.2.html
+/// [Linux `rename`]: https://man7.org/linux/man-pages/man2/rename.2.html
+pub fn atomic_write_at_with<Fd: AsFd, P: path::Arg, F>(
+    dirfd: Fd,
+    name: P,
+    options: &AtomicWriteOptions,
+    write: F,
+) -> io::Result<()>
+where
+    F: FnOnce(BorrowedFd<'_>) -> io::Result<()>,
+{
+    let dirfd = dirfd.as_fd();
+    name.into_with_c_str(|name| {
+        let (parent, base) = split_name(name.to_bytes())?;
+        let dir = open_dir(dirfd, parent)?;
+        let mut temp = TempFile::create(dir.as_fd(), base, options.mode)?;
+        write(temp.fd.as_fd())?;
+        fsync(&temp.fd)?;
+        temp.publish(base, options.replace)?;
+        fsync(&dir)
+    })
+}
+
+/// Atomically exchange the files or directories `old` and `new` in `dirfd`,
+/// and `fsync` the directories containing them.
+///
+/// This uses `renameat2(…, RENAME_EXCHANGE)`, so both must already exist.
+/// To swap in a new directory tree, build and sync it under a temporary name
+/// next to the one it's replacing, exchange the two, and then remove the old
+/// tree, which is now under the temporary name.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/rename.2.html
+pub fn atomic_exchange_at<Fd: AsFd, P: path::Arg, Q: path::Arg>(
+    dirfd: Fd,
+    old: P,
+    new: Q,
+) -> io::Result<()> {
+    let dirfd = dirfd.as_fd();
+    old.into_with_c_str(|old| {
+        new.into_with_c_str(|new| {
+            let (old_parent, old_base) = split_name(old.to_bytes())?;
+            let (new_parent, new_base) = split_name(new.to_bytes())?;
+            let old_dir = open_dir(dirfd, old_parent)?;
+            let new_dir = open_dir(dirfd, new_parent)?;
+            renameat_with(
+                &old_dir,
+                old_base,
+                &new_dir,
+                new_base,
+                RenameFlags::EXCHANGE,
+            )?;
+            fsync(&old_dir)?;
+            fsync(&new_dir)
+        })
+    })
+}
+
+/// A file that isn't visible under its final name yet, which is removed if
+/// it's dropped before it's published.
+struct TempFile<'a> {
+    dir: BorrowedFd<'a>,
+    fd: OwnedFd,
+    /// The name the file is linked under in `dir`, or `None` if it's
+    /// anonymous or has been published.
+    name: Option<Vec<u8>>,
+}
+
+impl<'a> TempFile<'a> {
+    fn create(dir: BorrowedFd<'a>, base: &[u8], mode: Mode) -> io::Result<Self> {
+        match openat(
+            dir,
+            ".",
+            OFlags::WRONLY | OFlags::TMPFILE | OFlags::CLOEXEC,
+            mode,
+        ) {
+            Ok(fd) => {
+                return Ok(Self {
+                    dir,
+                    fd,
+                    name: None,
+                })
+            }
+            // Kernels that predate `O_TMPFILE` see its `O_DIRECTORY` bit and
+            // fail with `EISDIR`, and filesystems that don't support it fail
+            // with `EOPNOTSUPP`.
+            Err(io::Errno::ISDIR) | Err(io::Errno::OPNOTSUPP) | Err(io::Errno::INVAL) => (),
+            Err(err) => return Err(err),
+        }
+
+        for _ in 0..MAX_ATTEMPTS {
+            let name = temp_name(base)?;
+            match openat(
+                dir,
+                name.as_slice(),
+                OFlags::WRONLY | OFlags::CREATE | OFlags::EXCL | OFlags::NOFOLLOW | OFlags::CLOEXEC,
+                mode,
+            ) {
+                Ok(fd) => {
+                    return Ok(Self {
+                        dir,
+                        fd,
+                        name: Some(name),
+                    })
+                }
+                Err(io::Errno::EXIST) => continue,
+                Err(err) => return Err(err),
+            }
+        }
+        Err(io::Errno::EXIST)
+    }
+
+    /// Make the file visible as `base`.
+    fn publish(&mut self, base: &[u8], replace: bool) -> io::Result<()> {
+        if self.name.is_none() {
+            if !replace {
+                // `linkat` never replaces an existing file.
+                return self.link(base);
+            }
+
+            // Give the file a temporary name, so that it can be renamed over
+            // `base`.
+            let mut attempts = 0;
+            loop {
+                let name = temp_name(base)?;
+                match self.link(&name) {
+                    Ok(()) => {
+                        self.name = Some(name);
+                        break;
+                    }
+                    Err(io::Errno::EXIST) if attempts < MAX_ATTEMPTS => attempts += 1,
+                    Err(err) => return Err(err),
+                }
+            }
+        }
+
+        let name = self.name.as_deref().unwrap();
+        if replace {
+            renameat(self.dir, name, self.dir, base)?;
+        } else {
+            match renameat_with(self.dir, name, self.dir, base, RenameFlags::NOREPLACE) {
+                Ok(()) => (),
+                // The filesystem doesn't support `RENAME_NOREPLACE`, so link
+                // the file under its final name, which fails if it exists,
+                // and leave the temporary name to be removed.
+                Err(io::Errno::INVAL) => {
+                    return linkat(self.dir, name, self.dir, base, AtFlags::empty())
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        self.name = None;
+        Ok(())
+    }
+
+    /// Link the anonymous file into the directory as `name`.
+    fn link(&self, name: &[u8]) -> io::Result<()> {
+        match linkat(&self.fd, "", self.dir, name, AtFlags::EMPTY_PATH) {
+            // `AT_EMPTY_PATH` requires `CAP_DAC_READ_SEARCH`, and fails with
+            // `ENOENT` without it.
+            Err(io::Errno::NOENT) | Err(io::Errno::PERM) => {
+                let mut proc_path = b"/proc/self/fd/".to_vec();
+                push_decimal(&mut proc_path, self.fd.as_raw_fd() as u32);
+                linkat(CWD, proc_path, self.dir, name, AtFlags::SYMLINK_FOLLOW)
+            }
+            result => result,
+        }
+    }
+}
+
+impl Drop for TempFile<'_> {
+    fn drop(&mut self) {
+        if let Some(name) = self.name.take() {
+            let _ = unlinkat(self.dir, name, AtFlags::empty());
+        }
+    }
+}
+
+/// Split `name` into the path of its parent directory and its last
+/// component.
+fn split_name(name: &[u8]) -> io::Result<(&[u8], &[u8])> {
+    let (parent, base) = match name.iter().rposition(|b| *b == b'/') {
+        Some(0) => (&b"/"[..], &name[1..]),
+        Some(slash) => (&name[..slash], &name[slash + 1..]),
+        None => (&b"."[..], name),
+    };
+    if base.is_empty() || base == b"." || base == b".." {
+        return Err(io::Errno::INVAL);
+    }
+    Ok((parent, base))
+}
+
+/// Open the directory `parent` in `dirfd`, so that it can be `fsync`ed.
+fn open_dir(dirfd: BorrowedFd<'_>, parent: &[u8]) -> io::Result<OwnedFd> {
+    openat(
+        dirfd,
+        parent,
+        OFlags::RDONLY | OFlags::DIRECTORY | OFlags::CLOEXEC,
+        Mode::empty(),
+    )
+}
+
+/// Return a hidden name to use for a temporary file that will be renamed to
+/// `base`.
+fn temp_name(base: &[u8]) -> io::Result<Vec<u8>> {
+    let base = &base[..base.len().min(MAX_PREFIX)];
+    let mut name = Vec::with_capacity(base.len() + 14);
+    name.push(b'.');
+    name.extend_from_slice(base);
+    name.push(b'.');
+    name.resize(base.len() + 14, 0);
+    fill_random_name(&mut name[base.len() + 2..])?;
+    Ok(name)
+}
+
+/// Append the decimal digits of `n` to `buf`.
+fn push_decimal(buf: &mut Vec<u8>, n: u32) {
+    if n >= 10 {
+        push_decimal(buf, n / 10);
+    }
+    buf.push(b'0' + (n % 10) as u8);
+}
+
+/// Write all of `contents` to `fd`.
+fn write_all(fd: BorrowedFd<'_>, mut contents: &[u8]) -> io::Result<()> {
+    while !contents.is_empty() {
+        match io::write(fd, contents) {
+            Ok(0) => return Err(io::Errno::NOSPC),
+            Ok(n) => contents = &contents[n..],
+            Err(io::Errno::INTR) => (),
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,109 @@
+//! Page cache residency queries.
+
+use crate::{backend, io};
+use backend::fd::AsFd;
+
+/// `struct cachestat`—Page cache statistics for a range of a file, as
+/// returned by [`cachestat`].
+///
+/// All of the counts are in pages.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Cachestat {
+    /// The number of pages in the page cache.
+    pub nr_cache: u64,
+    /// The number of dirty pages.
+    pub nr_dirty: u64,
+    /// The number of pages under writeback.
+    pub nr_writeback: u64,
+    /// The number of pages which have been evicted from the page cache.
+    pub nr_evicted: u64,
+    /// The number of evicted pages which would still be in the page cache if
+    /// they had been accessed recently enough to be kept.
+    pub nr_recently_evicted: u64,
+}
+
+/// `cachestat(fd, {offset, len}, cstat, 0)`—Query the page cache status of a
+/// range of a file.
+///
+/// A `len` of 0 means the range extends to the end of the file.
+///
+/// This requires Linux 6.5. On older kernels, it fails with
+/// [`io::Errno::NOSYS`]; see [`cached_pages`] for a query that works there
+/// too.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/cachestat.2.html
+#[inline]
+pub fn cachestat<Fd: AsFd>(fd: Fd, offset: u64, len: u64) -> io::Result<Cachestat> {
+    backend::fs::syscalls::cachestat(fd.as_fd(), offset, len)
+}
+
+/// Count the pages of a range of a file which are in the page cache.
+///
+/// A `len` of 0 means the range extends to the end of the file.
+///
+/// This uses [`cachestat`] if it's available. Otherwise, it maps the range
+/// and uses [`mincore`] to ask which of its pages are resident, which requires
+/// `fd` to be open for reading.
+///
+/// [`mincore`]: crate::mm::mincore
+#[cfg(all(feature = "alloc", feature = "mm", feature = "param"))]
+pub fn cached_pages<Fd: AsFd>(fd: Fd, offset: u64, len: u64) -> io::Result<u64> {
+    let fd = fd.as_fd();
+    match cachestat(fd, offset, len) {
+        Ok(cstat) => Ok(cstat.nr_cache),
+        Err(io::Errno::NOSYS) => mincore_pages(fd, offset, len),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "mm", feature = "param"))]
+#[allow(unsafe_code)]
+fn mincore_pages(fd: backend::fd::BorrowedFd<'_>, offset: u64, len: u64) -> io::Result<u64> {
+    use crate::mm::{mincore, mmap, munmap, MapFlags, ProtFlags};
+    use alloc::vec;
+    use core::ptr::null_mut;
+
+    /// The most to map at once, so that large files don't need a large
+    /// mapping, or a large `mincore` vector.
+    const CHUNK: u64 = 64 << 20;
+
+    let page_size = crate::param::page_size() as u64;
+    let size = super::fstat(fd)?.st_size as u64;
+    let end = if len == 0 {
+        size
+    } else {
+        offset.saturating_add(len).min(size)
+    };
+
+    // `mmap` needs a page-aligned offset.
+    let mut pos = offset - offset % page_size;
+    let mut vec = vec![0_u8; (CHUNK / page_size) as usize];
+    let mut count = 0;
+    while pos < end {
+        let chunk = (end - pos).min(CHUNK) as usize;
+        let pages = (chunk as u64 + page_size - 1) / page_size;
+        unsafe {
+            let addr = mmap(
+                null_mut(),
+                chunk,
+                ProtFlags::READ,
+                MapFlags::SHARED,
+                fd,
+                pos,
+            )?;
+            let result = mincore(addr, chunk, &mut vec[..pages as usize]);
+            munmap(addr, chunk)?;
+            result?;
+        }
+        count += vec[..pages as usize]
+            .iter()
+            .filter(|&&page| page & 1 != 0)
+            .count() as u64;
+        pos += chunk as u64;
+    }
+    Ok(count)
+}
@@ -0,0 +1,341 @@
+//! `copy_fd`, which copies the contents of one file to another using the
+//! fastest method the kernel and filesystems support.
+
+use crate::fd::{AsFd, BorrowedFd};
+use crate::fs::{
+    copy_file_range, fstat, ftruncate, ioctl_ficlone, seek, sendfile, FileType, SeekFrom,
+};
+use crate::io;
+#[cfg(feature = "pipe")]
+use crate::pipe::{splice, SpliceFlags};
+use alloc::vec::Vec;
+
+/// The largest amount of data to ask the kernel to copy in a single call.
+///
+/// `copy_file_range`, `sendfile`, and `splice` copy at most `0x7fff_f000`
+/// bytes per call anyway; asking for less lets us respond to errors sooner.
+const MAX_CHUNK: usize = 1 << 30;
+
+/// The size of the buffer used when falling back to `read` and `write`.
+const BUFFER_SIZE: usize = 128 * 1024;
+
+/// How [`copy_fd`] copied the data.
+///
+/// These are ordered from fastest to slowest. When `copy_fd` has to fall
+/// back part way through a copy, it reports the slowest method it used.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum CopyMethod {
+    /// `ioctl(dst, FICLONE, src)`—The destination shares the source's
+    /// extents, and no data was copied.
+    Reflink,
+    /// `copy_file_range`—The kernel copied the data, possibly by sharing
+    /// extents or by copying on the storage device.
+    CopyFileRange,
+    /// `sendfile`—The kernel copied the data, through the page cache.
+    Sendfile,
+    /// `splice`—The kernel moved the data to or from a pipe. This is only
+    /// used when one of the files is a pipe, and the `pipe` feature is
+    /// enabled.
+    Splice,
+    /// `read` and `write`—The data was copied through a userspace buffer.
+    ReadWrite,
+}
+
+/// Options for [`copy_fd`].
+#[derive(Clone, Debug)]
+pub struct CopyFdOptions {
+    /// Try `ioctl(dst, FICLONE, src)` first. The default is `true`.
+    ///
+    /// Reflinked files share storage until one of them is modified, which
+    /// may be undesirable when the copy is meant to protect against
+    /// corruption of the underlying storage.
+    pub reflink: bool,
+
+    /// Preserve holes in the source, by only copying the ranges that
+    /// `SEEK_DATA` and `SEEK_HOLE` report as containing data. The default is
+    /// `true`.
+    ///
+    /// This only applies when both files are regular files.
+    pub sparse: bool,
+}
+
+impl Default for CopyFdOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            reflink: true,
+            sparse: true,
+        }
+    }
+}
+
+/// Copy the contents of `src` to `dst`.
+///
+/// This tries each of the methods in [`CopyMethod`] in turn, falling back to
+/// the next one when the kernel or filesystem reports that a method isn't
+/// supported for these files, with [`io::Errno::XDEV`], [`io::Errno::INVAL`],
+/// [`io::Errno::NOSYS`], or similar. It returns the length of the copy,
+/// including any holes, and the slowest method that was used.
+///
+/// If `src` is a regular file, its whole contents are copied, regardless of
+/// its current file offset, which is preserved. Otherwise, such as when it's
+/// a pipe, it's read until it reports end-of-file.
+///
+/// If `dst` is a regular file, it's truncated, and then the data is written
+/// from offset 0. Its file offset afterwards is unspecified. Otherwise, the
+/// data is written to it sequentially.
+///
+/// When [`CopyFdOptions::sparse`] is set, holes in `src` are recreated in
+/// `dst` by skipping over them, rather than by filling them with zeros.
+pub fn copy_fd<SrcFd: AsFd, DstFd: AsFd>(
+    src: SrcFd,
+    dst: DstFd,
+    options: &CopyFdOptions,
+) -> io::Result<(u64, CopyMethod)> {
+    let src = src.as_fd();
+    let dst = dst.as_fd();
+
+    let src_stat = fstat(src)?;
+    let dst_stat = fstat(dst)?;
+    let src_regular = FileType::from_raw_mode(src_stat.st_mode) == FileType::RegularFile;
+    let dst_regular = FileType::from_raw_mode(dst_stat.st_mode) == FileType::RegularFile;
+    let either_pipe = FileType::from_raw_mode(src_stat.st_mode) == FileType::Fifo
+        || FileType::from_raw_mode(dst_stat.st_mode) == FileType::Fifo;
+
+    // Truncating `dst` would destroy the data we're about to copy.
+    if src_stat.st_dev == dst_stat.st_dev && src_stat.st_ino == dst_stat.st_ino {
+        return Err(io::Errno::INVAL);
+    }
+
+    // Files in procfs and sysfs report a size of 0, but have contents, so
+    // only trust a size that isn't 0.
+    let src_len = if src_regular && src_stat.st_size > 0 {
+        Some(src_stat.st_size as u64)
+    } else {
+        None
+    };
+
+    if options.reflink && src_regular && dst_regular {
+        match ioctl_ficlone(dst, src) {
+            Ok(()) => return Ok((src_stat.st_size as u64, CopyMethod::Reflink)),
+            Err(io::Errno::OPNOTSUPP)
+            | Err(io::Errno::XDEV)
+            | Err(io::Errno::INVAL)
+            | Err(io::Errno::NOTTY)
+            | Err(io::Errno::NOSYS)
+            | Err(io::Errno::PERM) => (),
+            Err(err) => return Err(err),
+        }
+    }
+
+    let mut copier = Copier {
+        src,
+        dst,
+        src_regular,
+        dst_regular,
+        method: if src_regular && dst_regular {
+            CopyMethod::CopyFileRange
+        } else if either_pipe && cfg!(feature = "pipe") {
+            CopyMethod::Splice
+        } else {
+            CopyMethod::Sendfile
+        },
+        copied: 0,
+        buffer: Vec::new(),
+    };
+
+    if !dst_regular {
+        copier.copy_range(0, None)?;
+        return Ok((copier.copied, copier.method));
+    }
+
+    ftruncate(dst, 0)?;
+
+    let end = match src_len {
+        Some(len) if options.sparse => {
+            // Walking the data segments moves `src`'s file offset, so put it
+            // back afterwards.
+            let saved = seek(src, SeekFrom::Current(0))?;
+            let result = copier.copy_data_segments(len);
+            seek(src, SeekFrom::Start(saved))?;
+            result?
+        }
+        _ => copier.copy_range(0, src_len)?,
+    };
+
+    // Extend `dst` to the full size, which recreates any hole at the end.
+    if let Some(len) = src_len {
+        if len > end {
+            ftruncate(dst, len)?;
+            return Ok((len, copier.method));
+        }
+    }
+    Ok((end, copier.method))
+}
+
+struct Copier<'a> {
+    src: BorrowedFd<'a>,
+    dst: BorrowedFd<'a>,
+    src_regular: bool,
+    dst_regular: bool,
+    method: CopyMethod,
+    copied: u64,
+    buffer: Vec<u8>,
+}
+
+impl<'a> Copier<'a> {
+    /// Copy the ranges of `src` below `len` that contain data, and return the
+    /// offset of the end of the last one.
+    fn copy_data_segments(&mut self, len: u64) -> io::Result<u64> {
+        let mut pos = 0;
+        let mut end = 0;
+        while pos < len {
+            let data = match seek(self.src, SeekFrom::Data(pos as i64)) {
+                Ok(data) => data,
+                // There's no more data before the end of the file.
+                Err(io::Errno::NXIO) => break,
+                // The filesystem doesn't support `SEEK_DATA`, so copy the
+                // rest as if it were all data.
+                Err(io::Errno::INVAL) => return self.copy_range(pos, Some(len)),
+                Err(err) => return Err(err),
+            };
+            let hole = match seek(self.src, SeekFrom::Hole(data as i64)) {
+                Ok(hole) => hole.min(len),
+                Err(io::Errno::NXIO) => break,
+                Err(err) => return Err(err),
+            };
+            if data >= hole {
+                break;
+            }
+            end = self.copy_range(data, Some(hole))?;
+            if end < hole {
+                // The file was truncated while we were copying it.
+                break;
+            }
+            pos = hole;
+        }
+        Ok(end)
+    }
+
+    /// Copy the data in `src` from `start` to `end`, or to the end of the
+    /// file if `end` is `None`, and return the offset it stopped at.
+    ///
+    /// If `src` isn't a regular file, `start` is ignored, and it's read from
+    /// its current position.
+    fn copy_range(&mut self, start: u64, end: Option<u64>) -> io::Result<u64> {
+        let mut pos = start;
+
+        // `sendfile`, `splice`, and `write` write at `dst`'s file offset.
+        if self.dst_regular && self.method > CopyMethod::CopyFileRange {
+            seek(self.dst, SeekFrom::Start(pos))?;
+        }
+
+        loop {
+            let len = match end {
+                Some(end) if pos >= end => break,
+                Some(end) => (end - pos).min(MAX_CHUNK as u64) as usize,
+                None => MAX_CHUNK,
+            };
+            let result = match self.method {
+                CopyMethod::Reflink => unreachable!(),
+                CopyMethod::CopyFileRange => {
+                    let mut off_in = pos;
+                    let mut off_out = pos;
+                    copy_file_range(
+                        self.src,
+                        Some(&mut off_in),
+                        self.dst,
+                        Some(&mut off_out),
+                        len,
+                    )
+                }
+                CopyMethod::Sendfile => {
+                    let mut offset = pos;
+                    let offset = if self.src_regular {
+                        Some(&mut offset)
+                    } else {
+                        None
+                    };
+                    sendfile(self.dst, self.src, offset, len)
+                }
+                #[cfg(feature = "pipe")]
+                CopyMethod::Splice => {
+                    let mut offset = pos;
+                    let offset = if self.src_regular {
+                        Some(&mut offset)
+                    } else {
+                        None
+                    };
+                    splice(self.src, offset, self.dst, None, len, SpliceFlags::empty())
+                }
+                #[cfg(not(feature = "pipe"))]
+                CopyMethod::Splice => unreachable!(),
+                CopyMethod::ReadWrite => self.read_write(pos, len),
+            };
+            match result {
+                // The kernel copied nothing, but this may be a file in procfs
+                // or sysfs that it doesn't know how to copy, so try the next
+                // method before concluding that we're at the end.
+                Ok(0) if self.copied == 0 && self.method < CopyMethod::ReadWrite => {
+                    self.fall_back(pos)?;
+                }
+                Ok(0) => break,
+                Ok(n) => {
+                    pos += n as u64;
+                    self.copied += n as u64;
+                }
+                Err(io::Errno::INTR) => (),
+                Err(io::Errno::XDEV)
+                | Err(io::Errno::INVAL)
+                | Err(io::Errno::NOSYS)
+                | Err(io::Errno::OPNOTSUPP)
+                | Err(io::Errno::PERM)
+                    if self.method < CopyMethod::ReadWrite =>
+                {
+                    self.fall_back(pos)?;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(pos)
+    }
+
+    /// Switch to the next slower method, continuing from `pos`.
+    fn fall_back(&mut self, pos: u64) -> io::Result<()> {
+        self.method = match self.method {
+            CopyMethod::Reflink | CopyMethod::CopyFileRange => CopyMethod::Sendfile,
+            CopyMethod::Sendfile | CopyMethod::Splice | CopyMethod::ReadWrite => {
+                CopyMethod::ReadWrite
+            }
+        };
+        if self.dst_regular {
+            seek(self.dst, SeekFrom::Start(pos))?;
+        }
+        Ok(())
+    }
+
+    /// Read up to `len` bytes from `src` at `pos`, and write all of them to
+    /// `dst`.
+    fn read_write(&mut self, pos: u64, len: usize) -> io::Result<usize> {
+        if self.buffer.is_empty() {
+            self.buffer.resize(BUFFER_SIZE, 0);
+        }
+        let len = len.min(self.buffer.len());
+        let n = if self.src_regular {
+            io::pread(self.src, &mut self.buffer[..len], pos)?
+        } else {
+            io::read(self.src, &mut self.buffer[..len])?
+        };
+
+        let mut written = 0;
+        while written < n {
+            match io::write(self.dst, &self.buffer[written..n]) {
+                Ok(0) => return Err(io::Errno::NOSPC),
+                Ok(m) => written += m,
+                Err(io::Errno::INTR) => (),
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(n)
+    }
+}
@@ -0,0 +1,787 @@
+//! fanotify support for working with filesystem-wide notifications.
+//!
+//! Unlike inotify, fanotify can watch entire mounts and filesystems, and
+//! it can report the objects involved in an event as open file descriptors
+//! or as file handles.
+//!
+//! # Safety
+//!
+//! fanotify events carry file descriptors which the kernel installs in the
+//! reading process' file descriptor table. [`Reader`] takes ownership of
+//! them, so it must only be used with fanotify file descriptors.
+#![allow(unsafe_code)]
+
+use crate::backend::fs::syscalls;
+use crate::fd::{AsFd, BorrowedFd, FromRawFd, OwnedFd, RawFd};
+use crate::ffi::CStr;
+use crate::fs::OFlags;
+use crate::{io, path};
+use bitflags::bitflags;
+use core::fmt;
+use core::mem::MaybeUninit;
+
+// linux-raw-sys doesn't yet provide the fanotify definitions. These values
+// are the same on all architectures.
+const FAN_EVENT_METADATA_LEN: usize = 24;
+const FANOTIFY_METADATA_VERSION: u8 = 3;
+const FAN_EVENT_INFO_HDR_LEN: usize = 4;
+const FAN_EVENT_INFO_TYPE_FID: u8 = 1;
+const FAN_EVENT_INFO_TYPE_DFID_NAME: u8 = 2;
+const FAN_EVENT_INFO_TYPE_DFID: u8 = 3;
+const FAN_EVENT_INFO_TYPE_PIDFD: u8 = 4;
+const FAN_EVENT_INFO_TYPE_ERROR: u8 = 5;
+const FAN_EVENT_INFO_TYPE_OLD_DFID_NAME: u8 = 10;
+const FAN_EVENT_INFO_TYPE_NEW_DFID_NAME: u8 = 12;
+
+bitflags! {
+    /// `FAN_*` constants for use with [`fanotify_init`].
+    #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+    pub struct InitFlags: u32 {
+        /// `FAN_CLOEXEC`
+        const CLOEXEC = 0x0000_0001;
+        /// `FAN_NONBLOCK`
+        const NONBLOCK = 0x0000_0002;
+
+        /// `FAN_CLASS_NOTIF`
+        const CLASS_NOTIF = 0x0000_0000;
+        /// `FAN_CLASS_CONTENT`
+        const CLASS_CONTENT = 0x0000_0004;
+        /// `FAN_CLASS_PRE_CONTENT`
+        const CLASS_PRE_CONTENT = 0x0000_0008;
+
+        /// `FAN_UNLIMITED_QUEUE`
+        const UNLIMITED_QUEUE = 0x0000_0010;
+        /// `FAN_UNLIMITED_MARKS`
+        const UNLIMITED_MARKS = 0x0000_0020;
+        /// `FAN_ENABLE_AUDIT` (since Linux 4.15)
+        const ENABLE_AUDIT = 0x0000_0040;
+
+        /// `FAN_REPORT_PIDFD` (since Linux 5.15)
+        const REPORT_PIDFD = 0x0000_0080;
+        /// `FAN_REPORT_TID` (since Linux 4.20)
+        const REPORT_TID = 0x0000_0100;
+        /// `FAN_REPORT_FID` (since Linux 5.1)
+        const REPORT_FID = 0x0000_0200;
+        /// `FAN_REPORT_DIR_FID` (since Linux 5.9)
+        const REPORT_DIR_FID = 0x0000_0400;
+        /// `FAN_REPORT_NAME` (since Linux 5.9)
+        const REPORT_NAME = 0x0000_0800;
+        /// `FAN_REPORT_TARGET_FID` (since Linux 5.17)
+        const REPORT_TARGET_FID = 0x0000_1000;
+
+        /// `FAN_REPORT_DFID_NAME`
+        const REPORT_DFID_NAME = Self::REPORT_DIR_FID.bits() | Self::REPORT_NAME.bits();
+        /// `FAN_REPORT_DFID_NAME_TARGET`
+        const REPORT_DFID_NAME_TARGET = Self::REPORT_DFID_NAME.bits()
+            | Self::REPORT_FID.bits()
+            | Self::REPORT_TARGET_FID.bits();
+    }
+}
+
+bitflags! {
+    /// `FAN_MARK_*` constants for use with [`fanotify_mark`].
+    #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+    pub struct MarkFlags: u32 {
+        /// `FAN_MARK_ADD`
+        const ADD = 0x0000_0001;
+        /// `FAN_MARK_REMOVE`
+        const REMOVE = 0x0000_0002;
+        /// `FAN_MARK_FLUSH`
+        const FLUSH = 0x0000_0080;
+
+        /// `FAN_MARK_DONT_FOLLOW`
+        const DONT_FOLLOW = 0x0000_0004;
+        /// `FAN_MARK_ONLYDIR`
+        const ONLYDIR = 0x0000_0008;
+
+        /// `FAN_MARK_INODE`
+        const INODE = 0x0000_0000;
+        /// `FAN_MARK_MOUNT`
+        const MOUNT = 0x0000_0010;
+        /// `FAN_MARK_FILESYSTEM` (since Linux 4.20)
+        const FILESYSTEM = 0x0000_0100;
+
+        /// `FAN_MARK_IGNORED_MASK`
+        const IGNORED_MASK = 0x0000_0020;
+        /// `FAN_MARK_IGNORED_SURV_MODIFY`
+        const IGNORED_SURV_MODIFY = 0x0000_0040;
+        /// `FAN_MARK_EVICTABLE` (since Linux 5.19)
+        const EVICTABLE = 0x0000_0200;
+        /// `FAN_MARK_IGNORE` (since Linux 6.0)
+        const IGNORE = 0x0000_0400;
+        /// `FAN_MARK_IGNORE_SURV`
+        const IGNORE_SURV = Self::IGNORE.bits() | Self::IGNORED_SURV_MODIFY.bits();
+    }
+}
+
+bitflags! {
+    /// `FAN_*` event mask constants for use with [`fanotify_mark`] and
+    /// [`Event::mask`].
+    #[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+    pub struct EventFlags: u64 {
+        /// `FAN_ACCESS`
+        const ACCESS = 0x0000_0001;
+        /// `FAN_MODIFY`
+        const MODIFY = 0x0000_0002;
+        /// `FAN_ATTRIB` (since Linux 5.1)
+        const ATTRIB = 0x0000_0004;
+        /// `FAN_CLOSE_WRITE`
+        const CLOSE_WRITE = 0x0000_0008;
+        /// `FAN_CLOSE_NOWRITE`
+        const CLOSE_NOWRITE = 0x0000_0010;
+        /// `FAN_OPEN`
+        const OPEN = 0x0000_0020;
+        /// `FAN_MOVED_FROM` (since Linux 5.1)
+        const MOVED_FROM = 0x0000_0040;
+        /// `FAN_MOVED_TO` (since Linux 5.1)
+        const MOVED_TO = 0x0000_0080;
+        /// `FAN_CREATE` (since Linux 5.1)
+        const CREATE = 0x0000_0100;
+        /// `FAN_DELETE` (since Linux 5.1)
+        const DELETE = 0x0000_0200;
+        /// `FAN_DELETE_SELF` (since Linux 5.1)
+        const DELETE_SELF = 0x0000_0400;
+        /// `FAN_MOVE_SELF` (since Linux 5.1)
+        const MOVE_SELF = 0x0000_0800;
+        /// `FAN_OPEN_EXEC` (since Linux 5.0)
+        const OPEN_EXEC = 0x0000_1000;
+
+        /// `FAN_Q_OVERFLOW`
+        const Q_OVERFLOW = 0x0000_4000;
+        /// `FAN_FS_ERROR` (since Linux 5.16)
+        const FS_ERROR = 0x0000_8000;
+
+        /// `FAN_OPEN_PERM`
+        const OPEN_PERM = 0x0001_0000;
+        /// `FAN_ACCESS_PERM`
+        const ACCESS_PERM = 0x0002_0000;
+        /// `FAN_OPEN_EXEC_PERM` (since Linux 5.0)
+        const OPEN_EXEC_PERM = 0x0004_0000;
+
+        /// `FAN_EVENT_ON_CHILD`
+        const EVENT_ON_CHILD = 0x0800_0000;
+        /// `FAN_RENAME` (since Linux 5.17)
+        const RENAME = 0x1000_0000;
+        /// `FAN_ONDIR`
+        const ONDIR = 0x4000_0000;
+
+        /// `FAN_CLOSE`
+        const CLOSE = Self::CLOSE_WRITE.bits() | Self::CLOSE_NOWRITE.bits();
+        /// `FAN_MOVE`
+        const MOVE = Self::MOVED_FROM.bits() | Self::MOVED_TO.bits();
+    }
+}
+
+/// `FAN_ALLOW`, `FAN_DENY`—Responses for use with [`fanotify_respond`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[repr(u32)]
+pub enum Response {
+    /// `FAN_ALLOW`
+    Allow = 0x01,
+    /// `FAN_DENY`
+    Deny = 0x02,
+}
+
+/// `fanotify_init(flags, event_f_flags)`—Creates a new fanotify object.
+///
+/// `event_flags` are the `O_*` flags used for the file descriptors returned
+/// in events, such as [`OFlags::RDONLY`] and [`OFlags::CLOEXEC`].
+///
+/// Use the [`InitFlags::CLOEXEC`] flag to prevent the resulting file
+/// descriptor from being implicitly passed across `exec` boundaries.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fanotify_init.2.html
+#[inline]
+pub fn fanotify_init(flags: InitFlags, event_flags: OFlags) -> io::Result<OwnedFd> {
+    syscalls::fanotify_init(flags, event_flags)
+}
+
+/// `fanotify_mark(fanotify, flags, mask, dirfd, path)`—Adds, removes, or
+/// modifies a mark.
+///
+/// With [`MarkFlags::MOUNT`] or [`MarkFlags::FILESYSTEM`], this marks the
+/// mount or the filesystem containing `path`, respectively.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fanotify_mark.2.html
+#[inline]
+pub fn fanotify_mark<Fd: AsFd, DirFd: AsFd, P: path::Arg>(
+    fanotify: Fd,
+    flags: MarkFlags,
+    mask: EventFlags,
+    dirfd: DirFd,
+    path: P,
+) -> io::Result<()> {
+    path.into_with_c_str(|path| {
+        syscalls::fanotify_mark(fanotify.as_fd(), flags, mask, dirfd.as_fd(), Some(path))
+    })
+}
+
+/// `fanotify_mark(fanotify, flags, mask, fd, NULL)`—Adds, removes, or
+/// modifies a mark on the object referred to by `fd`.
+///
+/// This is also the form to use with [`MarkFlags::FLUSH`].
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fanotify_mark.2.html
+#[inline]
+#[doc(alias = "fanotify_mark")]
+pub fn fanotify_mark_fd<Fd: AsFd, MarkFd: AsFd>(
+    fanotify: Fd,
+    flags: MarkFlags,
+    mask: EventFlags,
+    fd: MarkFd,
+) -> io::Result<()> {
+    syscalls::fanotify_mark(fanotify.as_fd(), flags, mask, fd.as_fd(), None)
+}
+
+/// `write(fanotify, &fanotify_response { fd, response })`—Responds to a
+/// permission event.
+///
+/// `event_fd` is the file descriptor of the event being responded to, as
+/// returned by [`Event::fd`].
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man7/fanotify.7.html
+#[doc(alias = "fanotify_response")]
+pub fn fanotify_respond<Fd: AsFd, EventFd: AsFd>(
+    fanotify: Fd,
+    event_fd: EventFd,
+    response: Response,
+) -> io::Result<()> {
+    use crate::fd::AsRawFd;
+
+    // `struct fanotify_response { __s32 fd; __u32 response; }`
+    let mut buf = [0_u8; 8];
+    buf[..4].copy_from_slice(&event_fd.as_fd().as_raw_fd().to_ne_bytes());
+    buf[4..].copy_from_slice(&(response as u32).to_ne_bytes());
+
+    let n = crate::backend::io::syscalls::write(fanotify.as_fd(), &buf)?;
+    if n != buf.len() {
+        return Err(io::Errno::IO);
+    }
+    Ok(())
+}
+
+/// A buffered reader of fanotify events.
+///
+/// fanotify never returns partial events; if the buffer is too small to hold
+/// the next event, reading fails with [`io::Errno::INVAL`]. A buffer of 4096
+/// bytes or more is enough to hold any single event.
+///
+/// Events which are still buffered when the `Reader` is dropped have their
+/// file descriptors closed.
+pub struct Reader<'buf, Fd: AsFd> {
+    fd: Fd,
+    buf: &'buf mut [MaybeUninit<u8>],
+    initialized: usize,
+    offset: usize,
+}
+
+impl<'buf, Fd: AsFd> Reader<'buf, Fd> {
+    /// Create a new reader from the given fanotify file descriptor and
+    /// buffer.
+    ///
+    /// # Safety
+    ///
+    /// `fd` must be a file descriptor returned by [`fanotify_init`] (or a
+    /// duplicate of one), and nothing else may read from it while the
+    /// `Reader` is in use, because the `Reader` takes ownership of the file
+    /// descriptors contained in the events it reads.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::mem::MaybeUninit;
+    /// # use rustix::fs::fanotify::{self, EventFlags, InitFlags, MarkFlags, Reader};
+    /// # use rustix::fs::{OFlags, CWD};
+    /// let fan = fanotify::fanotify_init(
+    ///     InitFlags::CLOEXEC | InitFlags::CLASS_NOTIF,
+    ///     OFlags::RDONLY | OFlags::CLOEXEC,
+    /// )
+    /// .unwrap();
+    /// fanotify::fanotify_mark(
+    ///     &fan,
+    ///     MarkFlags::ADD | MarkFlags::MOUNT,
+    ///     EventFlags::CLOSE_WRITE,
+    ///     CWD,
+    ///     "/",
+    /// )
+    /// .unwrap();
+    ///
+    /// let mut buf = [MaybeUninit::uninit(); 4096];
+    /// let mut reader = unsafe { Reader::new(&fan, &mut buf) };
+    /// loop {
+    ///     let event = reader.next().unwrap();
+    ///     dbg!(&event);
+    /// }
+    /// ```
+    #[inline]
+    pub unsafe fn new(fd: Fd, buf: &'buf mut [MaybeUninit<u8>]) -> Self {
+        Self {
+            fd,
+            buf,
+            initialized: 0,
+            offset: 0,
+        }
+    }
+
+    /// Read the next fanotify event.
+    ///
+    /// If the buffer is empty, this reads a new batch of events from the
+    /// fanotify file descriptor, which blocks unless the file descriptor is
+    /// in non-blocking mode.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> io::Result<Event<'_>> {
+        if self.is_buffer_empty() {
+            match io::read_uninit(self.fd.as_fd(), self.buf).map(|(init, _uninit)| init.len()) {
+                Ok(0) => return Err(io::Errno::INVAL),
+                Ok(bytes_read) => {
+                    self.initialized = bytes_read;
+                    self.offset = 0;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        // SAFETY: The bytes up to `self.initialized` were written by `read`.
+        let bytes = unsafe { assume_init(&self.buf[self.offset..self.initialized]) };
+        match parse_event(bytes) {
+            Ok((event, len)) => {
+                self.offset += len;
+                Ok(event)
+            }
+            Err(e) => {
+                // Skip just the malformed event, so that the events after it
+                // are still returned. If its length can't be trusted, the
+                // events after it can't be found, so discard the rest of the
+                // buffer.
+                self.offset = match discard_event(bytes) {
+                    Some(len) => self.offset + len,
+                    None => self.initialized,
+                };
+                Err(e)
+            }
+        }
+    }
+
+    /// Returns true if the internal buffer is empty and will be refilled when
+    /// calling [`next`].
+    ///
+    /// [`next`]: Self::next
+    #[inline]
+    pub fn is_buffer_empty(&self) -> bool {
+        self.offset >= self.initialized
+    }
+}
+
+impl<'buf, Fd: AsFd> Drop for Reader<'buf, Fd> {
+    fn drop(&mut self) {
+        // Close the file descriptors of any events we haven't returned.
+        while !self.is_buffer_empty() {
+            // SAFETY: See `next`.
+            let bytes = unsafe { assume_init(&self.buf[self.offset..self.initialized]) };
+            match parse_event(bytes) {
+                Ok((_event, len)) => self.offset += len,
+                Err(_) => match discard_event(bytes) {
+                    Some(len) => self.offset += len,
+                    None => break,
+                },
+            }
+        }
+    }
+}
+
+/// An fanotify event, as returned by [`Reader::next`].
+pub struct Event<'a> {
+    mask: EventFlags,
+    fd: Option<OwnedFd>,
+    pid: i32,
+    pidfd: Option<OwnedFd>,
+    info: &'a [u8],
+}
+
+impl<'a> Event<'a> {
+    /// Returns the event mask, describing what happened.
+    ///
+    /// [`EventFlags::Q_OVERFLOW`] indicates that events were dropped because
+    /// the queue overflowed.
+    #[inline]
+    pub fn mask(&self) -> EventFlags {
+        self.mask
+    }
+
+    /// Returns the file descriptor of the object the event is about, if any.
+    ///
+    /// This is `None` for queue overflow events, and for fanotify instances
+    /// that report file handles instead, with [`InitFlags::REPORT_FID`] and
+    /// related flags.
+    #[inline]
+    pub fn fd(&self) -> Option<BorrowedFd<'_>> {
+        self.fd.as_ref().map(AsFd::as_fd)
+    }
+
+    /// Takes ownership of the file descriptor of the object the event is
+    /// about, if any.
+    #[inline]
+    pub fn into_fd(self) -> Option<OwnedFd> {
+        self.fd
+    }
+
+    /// Returns the process ID, or the thread ID with
+    /// [`InitFlags::REPORT_TID`], that caused the event.
+    #[inline]
+    pub fn pid(&self) -> i32 {
+        self.pid
+    }
+
+    /// Returns the pidfd of the process that caused the event, if the
+    /// fanotify instance was created with [`InitFlags::REPORT_PIDFD`] and a
+    /// pidfd could be created.
+    #[inline]
+    pub fn pidfd(&self) -> Option<BorrowedFd<'_>> {
+        self.pidfd.as_ref().map(AsFd::as_fd)
+    }
+
+    /// Returns an iterator over the information records attached to this
+    /// event.
+    ///
+    /// pidfd records are not included; see [`Event::pidfd`].
+    #[inline]
+    pub fn info(&self) -> InfoRecords<'a> {
+        InfoRecords { bytes: self.info }
+    }
+}
+
+impl<'a> fmt::Debug for Event<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Event")
+            .field("mask", &self.mask)
+            .field("fd", &self.fd)
+            .field("pid", &self.pid)
+            .field("pidfd", &self.pidfd)
+            .field("info", &self.info())
+            .finish()
+    }
+}
+
+/// An iterator over the information records of an fanotify [`Event`].
+#[derive(Clone)]
+pub struct InfoRecords<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for InfoRecords<'a> {
+    type Item = InfoRecord<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.bytes.len() < FAN_EVENT_INFO_HDR_LEN {
+                return None;
+            }
+
+            // `struct fanotify_event_info_header { __u8 info_type; __u8 pad;
+            // __u16 len; }`
+            let info_type = self.bytes[0];
+            let len = usize::from(read_u16(self.bytes, 2));
+            if len < FAN_EVENT_INFO_HDR_LEN || len > self.bytes.len() {
+                self.bytes = &[];
+                return None;
+            }
+            let record = &self.bytes[FAN_EVENT_INFO_HDR_LEN..len];
+            self.bytes = &self.bytes[len..];
+
+            match info_type {
+                FAN_EVENT_INFO_TYPE_PIDFD => continue,
+                FAN_EVENT_INFO_TYPE_ERROR if record.len() >= 8 => {
+                    return Some(InfoRecord::Error {
+                        error: io::Errno::from_raw_os_error(read_i32(record, 0)),
+                        error_count: read_u32(record, 4),
+                    })
+                }
+                FAN_EVENT_INFO_TYPE_FID
+                | FAN_EVENT_INFO_TYPE_DFID
+                | FAN_EVENT_INFO_TYPE_DFID_NAME
+                | FAN_EVENT_INFO_TYPE_OLD_DFID_NAME
+                | FAN_EVENT_INFO_TYPE_NEW_DFID_NAME => {
+                    if let Some(fid) = FidInfo::parse(info_type, record) {
+                        return Some(InfoRecord::Fid(fid));
+                    }
+                }
+                _ => {}
+            }
+
+            return Some(InfoRecord::Other {
+                info_type,
+                data: record,
+            });
+        }
+    }
+}
+
+impl<'a> fmt::Debug for InfoRecords<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+/// An information record attached to an fanotify [`Event`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum InfoRecord<'a> {
+    /// A file handle record, reported with [`InitFlags::REPORT_FID`],
+    /// [`InitFlags::REPORT_DIR_FID`], or [`InitFlags::REPORT_NAME`].
+    Fid(FidInfo<'a>),
+
+    /// `FAN_EVENT_INFO_TYPE_ERROR`—An error record, reported with
+    /// [`EventFlags::FS_ERROR`] events.
+    Error {
+        /// The error that occurred.
+        error: io::Errno,
+        /// The number of errors that occurred since the last error event.
+        error_count: u32,
+    },
+
+    /// A record of a type that rustix doesn't know how to decode.
+    Other {
+        /// The `info_type` field.
+        info_type: u8,
+        /// The contents of the record, following the header.
+        data: &'a [u8],
+    },
+}
+
+/// The kind of object a [`FidInfo`] record identifies.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum FidKind {
+    /// `FAN_EVENT_INFO_TYPE_FID`—The object the event is about.
+    Fid,
+    /// `FAN_EVENT_INFO_TYPE_DFID`—The directory containing the object.
+    Dfid,
+    /// `FAN_EVENT_INFO_TYPE_DFID_NAME`—The directory containing the object,
+    /// and the object's name within it.
+    DfidName,
+    /// `FAN_EVENT_INFO_TYPE_OLD_DFID_NAME`—The directory and name an object
+    /// was renamed from.
+    OldDfidName,
+    /// `FAN_EVENT_INFO_TYPE_NEW_DFID_NAME`—The directory and name an object
+    /// was renamed to.
+    NewDfidName,
+}
+
+/// `struct fanotify_event_info_fid`—A file handle information record.
+#[derive(Clone)]
+pub struct FidInfo<'a> {
+    kind: FidKind,
+    fsid: [i32; 2],
+    handle_type: i32,
+    handle: &'a [u8],
+    name: Option<&'a CStr>,
+}
+
+impl<'a> FidInfo<'a> {
+    fn parse(info_type: u8, record: &'a [u8]) -> Option<Self> {
+        let kind = match info_type {
+            FAN_EVENT_INFO_TYPE_FID => FidKind::Fid,
+            FAN_EVENT_INFO_TYPE_DFID => FidKind::Dfid,
+            FAN_EVENT_INFO_TYPE_DFID_NAME => FidKind::DfidName,
+            FAN_EVENT_INFO_TYPE_OLD_DFID_NAME => FidKind::OldDfidName,
+            FAN_EVENT_INFO_TYPE_NEW_DFID_NAME => FidKind::NewDfidName,
+            _ => return None,
+        };
+
+        // `__kernel_fsid_t fsid;` followed by a `struct file_handle`, which
+        // is `__u32 handle_bytes; int handle_type; unsigned char f_handle[];`.
+        if record.len() < 16 {
+            return None;
+        }
+        let fsid = [read_i32(record, 0), read_i32(record, 4)];
+        let handle_bytes = read_u32(record, 8) as usize;
+        let handle_type = read_i32(record, 12);
+        let handle = record.get(16..16_usize.checked_add(handle_bytes)?)?;
+
+        // Records with names have a NUL-terminated name following the
+        // handle, and then padding.
+        let name = match kind {
+            FidKind::DfidName | FidKind::OldDfidName | FidKind::NewDfidName => {
+                let rest = &record[16 + handle_bytes..];
+                let nul = rest.iter().position(|b| *b == b'\0')?;
+                Some(CStr::from_bytes_with_nul(&rest[..=nul]).ok()?)
+            }
+            FidKind::Fid | FidKind::Dfid => None,
+        };
+
+        Some(Self {
+            kind,
+            fsid,
+            handle_type,
+            handle,
+            name,
+        })
+    }
+
+    /// Returns what this record identifies.
+    #[inline]
+    pub fn kind(&self) -> FidKind {
+        self.kind
+    }
+
+    /// Returns the `fsid` of the filesystem containing the object, as
+    /// reported by `statfs`'s `f_fsid`.
+    #[inline]
+    pub fn fsid(&self) -> [i32; 2] {
+        self.fsid
+    }
+
+    /// Returns the `handle_type` field of the file handle.
+    #[inline]
+    pub fn handle_type(&self) -> i32 {
+        self.handle_type
+    }
+
+    /// Returns the opaque bytes of the file handle.
+    #[inline]
+    pub fn handle(&self) -> &'a [u8] {
+        self.handle
+    }
+
+    /// Returns the name of the object within the directory identified by the
+    /// file handle, for [`FidKind::DfidName`], [`FidKind::OldDfidName`], and
+    /// [`FidKind::NewDfidName`] records.
+    #[inline]
+    pub fn name(&self) -> Option<&'a CStr> {
+        self.name
+    }
+}
+
+impl<'a> fmt::Debug for FidInfo<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FidInfo")
+            .field("kind", &self.kind)
+            .field("fsid", &self.fsid)
+            .field("handle_type", &self.handle_type)
+            .field("handle", &self.handle)
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+/// Parse one event from the start of `bytes`, returning it and its length.
+fn parse_event(bytes: &[u8]) -> io::Result<(Event<'_>, usize)> {
+    // `struct fanotify_event_metadata { __u32 event_len; __u8 vers;
+    // __u8 reserved; __u16 metadata_len; __aligned_u64 mask; __s32 fd;
+    // __s32 pid; }`
+    if bytes.len() < FAN_EVENT_METADATA_LEN {
+        return Err(io::Errno::INVAL);
+    }
+    let event_len = read_u32(bytes, 0) as usize;
+    let vers = bytes[4];
+    let metadata_len = usize::from(read_u16(bytes, 6));
+    if vers != FANOTIFY_METADATA_VERSION {
+        return Err(io::Errno::NOTSUP);
+    }
+    if metadata_len < FAN_EVENT_METADATA_LEN || event_len < metadata_len || event_len > bytes.len()
+    {
+        return Err(io::Errno::INVAL);
+    }
+
+    let mask = EventFlags::from_bits_retain(read_u64(bytes, 8));
+    let fd = read_i32(bytes, 16);
+    let pid = read_i32(bytes, 20);
+    let info = &bytes[metadata_len..event_len];
+
+    // SAFETY: The kernel installed these file descriptors for us, and the
+    // caller of `Reader::new` promised that we're the only one reading
+    // them.
+    let fd = (fd >= 0).then(|| unsafe { OwnedFd::from_raw_fd(fd as RawFd) });
+    let pidfd = find_pidfd(info).map(|pidfd| unsafe { OwnedFd::from_raw_fd(pidfd) });
+
+    Ok((
+        Event {
+            mask,
+            fd,
+            pid,
+            pidfd,
+            info,
+        },
+        event_len,
+    ))
+}
+
+/// Close the file descriptor of a malformed event at the start of `bytes`,
+/// and return the event's length, if its header is intact enough to find
+/// them.
+fn discard_event(bytes: &[u8]) -> Option<usize> {
+    if bytes.len() < FAN_EVENT_METADATA_LEN || bytes[4] != FANOTIFY_METADATA_VERSION {
+        return None;
+    }
+    let event_len = read_u32(bytes, 0) as usize;
+    if event_len < FAN_EVENT_METADATA_LEN || event_len > bytes.len() {
+        return None;
+    }
+
+    let fd = read_i32(bytes, 16);
+    if fd >= 0 {
+        // SAFETY: See `parse_event`.
+        drop(unsafe { OwnedFd::from_raw_fd(fd as RawFd) });
+    }
+    Some(event_len)
+}
+
+/// Find a `FAN_EVENT_INFO_TYPE_PIDFD` record and return its pidfd, if it
+/// holds a valid one.
+fn find_pidfd(mut info: &[u8]) -> Option<RawFd> {
+    while info.len() >= FAN_EVENT_INFO_HDR_LEN {
+        let info_type = info[0];
+        let len = usize::from(read_u16(info, 2));
+        if len < FAN_EVENT_INFO_HDR_LEN || len > info.len() {
+            break;
+        }
+        if info_type == FAN_EVENT_INFO_TYPE_PIDFD && len >= FAN_EVENT_INFO_HDR_LEN + 4 {
+            // `FAN_NOPIDFD` and `FAN_EPIDFD` are negative.
+            let pidfd = read_i32(info, FAN_EVENT_INFO_HDR_LEN);
+            return (pidfd >= 0).then_some(pidfd as RawFd);
+        }
+        info = &info[len..];
+    }
+    None
+}
+
+/// Reinterpret a slice of initialized `MaybeUninit<u8>` as a `&[u8]`.
+///
+/// # Safety
+///
+/// All the bytes in `buf` must be initialized.
+#[inline]
+unsafe fn assume_init(buf: &[MaybeUninit<u8>]) -> &[u8] {
+    core::slice::from_raw_parts(buf.as_ptr().cast::<u8>(), buf.len())
+}
+
+#[inline]
+fn read_u16(bytes: &[u8], at: usize) -> u16 {
+    u16::from_ne_bytes(bytes[at..at + 2].try_into().unwrap())
+}
+
+#[inline]
+fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_ne_bytes(bytes[at..at + 4].try_into().unwrap())
+}
+
+#[inline]
+fn read_i32(bytes: &[u8], at: usize) -> i32 {
+    i32::from_ne_bytes(bytes[at..at + 4].try_into().unwrap())
+}
+
+#[inline]
+fn read_u64(bytes: &[u8], at: usize) -> u64 {
+    u64::from_ne_bytes(bytes[at..at + 8].try_into().unwrap())
+}
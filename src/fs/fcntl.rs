@@ -10,10 +10,25 @@
     target_os = "wasi"
 )))]
 use crate::fs::FlockOperation;
+#[cfg(not(any(
+    target_os = "emscripten",
+    target_os = "fuchsia",
+    target_os = "redox",
+    target_os = "wasi"
+)))]
+use crate::pid::Pid;
 use crate::{backend, io};
 use backend::fd::AsFd;
 use backend::fs::types::OFlags;
 
+#[cfg(not(any(
+    target_os = "emscripten",
+    target_os = "fuchsia",
+    target_os = "redox",
+    target_os = "wasi"
+)))]
+pub use backend::fs::types::{FlockOffsetType, FlockType};
+
 // These `fcntl` functions like in the `io` module because they're not specific
 // to files, directories, or memfd objects. We re-export them here in the `fs`
 // module because the other the `fcntl` functions are here.
@@ -65,6 +80,9 @@ pub fn fcntl_get_seals<Fd: AsFd>(fd: Fd) -> io::Result<SealFlags> {
 #[cfg(any(linux_kernel, target_os = "freebsd", target_os = "fuchsia"))]
 pub use backend::fs::types::SealFlags;
 
+#[cfg(linux_kernel)]
+pub use backend::fs::types::{DnotifyFlags, LeaseType};
+
 /// `fcntl(fd, F_ADD_SEALS)`
 ///
 /// # References
@@ -80,9 +98,9 @@ pub fn fcntl_add_seals<Fd: AsFd>(fd: Fd, seals: SealFlags) -> io::Result<()> {
 
 /// `fcntl(fd, F_SETLK)`—Acquire or release an `fcntl`-style lock.
 ///
-/// This function doesn't currently have an offset or len; it currently always
-/// sets the `l_len` field to 0, which is a special case that means the entire
-/// file should be locked.
+/// This function doesn't have an offset or len; it always sets the `l_len`
+/// field to 0, which is a special case that means the entire file should be
+/// locked. To lock a byte range, use [`fcntl_setlk`].
 ///
 /// Unlike `flock`-style locks, `fcntl`-style locks are process-associated,
 /// meaning that they don't guard against being acquired by two threads in
@@ -106,3 +124,318 @@ pub fn fcntl_add_seals<Fd: AsFd>(fd: Fd, seals: SealFlags) -> io::Result<()> {
 pub fn fcntl_lock<Fd: AsFd>(fd: Fd, operation: FlockOperation) -> io::Result<()> {
     backend::fs::syscalls::fcntl_lock(fd.as_fd(), operation)
 }
+
+/// `struct flock`—A byte-range lock, for use with [`fcntl_setlk`],
+/// [`fcntl_getlk`], and related functions.
+#[cfg(not(any(
+    target_os = "emscripten",
+    target_os = "fuchsia",
+    target_os = "redox",
+    target_os = "wasi"
+)))]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Flock {
+    /// `l_start`—The offset of the start of the range, relative to the
+    /// position indicated by `offset_type`.
+    pub start: i64,
+    /// `l_len`—The length of the range. 0 means the range extends to the end
+    /// of the file, however large it grows. A negative length means the range
+    /// ends at `start` and extends backwards.
+    pub length: i64,
+    /// `l_pid`—The process holding a conflicting lock, as returned by
+    /// [`fcntl_getlk`]. This is `None` for open file description locks, and
+    /// is ignored when setting locks.
+    pub pid: Option<Pid>,
+    /// `l_type`—The type of the lock.
+    pub typ: FlockType,
+    /// `l_whence`—What `start` is relative to.
+    pub offset_type: FlockOffsetType,
+}
+
+#[cfg(not(any(
+    target_os = "emscripten",
+    target_os = "fuchsia",
+    target_os = "redox",
+    target_os = "wasi"
+)))]
+impl Flock {
+    /// A lock of type `typ` covering `length` bytes from the absolute offset
+    /// `start`.
+    ///
+    /// A `length` of 0 covers everything from `start` onwards, and a negative
+    /// `length` covers the bytes before `start`.
+    #[inline]
+    pub const fn new(typ: FlockType, start: i64, length: i64) -> Self {
+        Self {
+            start,
+            length,
+            pid: None,
+            typ,
+            offset_type: FlockOffsetType::Set,
+        }
+    }
+}
+
+/// `fcntl(fd, F_SETLK, lock)`—Acquire or release a byte-range lock, failing
+/// with [`io::Errno::AGAIN`] or [`io::Errno::ACCESS`] if a conflicting lock is
+/// held.
+///
+/// Like [`fcntl_lock`], these locks are process-associated: they're shared
+/// by all threads in the process, and they're released when the process
+/// closes *any* file descriptor for the file. Use [`fcntl_ofd_setlk`] for
+/// locks which belong to an open file description instead.
+///
+/// # References
+///  - [POSIX]
+///  - [Linux]
+///
+/// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/fcntl.html
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(not(any(
+    target_os = "emscripten",
+    target_os = "fuchsia",
+    target_os = "redox",
+    target_os = "wasi"
+)))]
+#[inline]
+#[doc(alias = "F_SETLK")]
+pub fn fcntl_setlk<Fd: AsFd>(fd: Fd, lock: &Flock) -> io::Result<()> {
+    backend::fs::syscalls::fcntl_setlk(fd.as_fd(), lock, false)
+}
+
+/// `fcntl(fd, F_SETLKW, lock)`—Acquire or release a byte-range lock, waiting
+/// for any conflicting lock to be released.
+///
+/// See [`fcntl_setlk`] for details.
+///
+/// # References
+///  - [POSIX]
+///  - [Linux]
+///
+/// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/fcntl.html
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(not(any(
+    target_os = "emscripten",
+    target_os = "fuchsia",
+    target_os = "redox",
+    target_os = "wasi"
+)))]
+#[inline]
+#[doc(alias = "F_SETLKW")]
+pub fn fcntl_setlkw<Fd: AsFd>(fd: Fd, lock: &Flock) -> io::Result<()> {
+    backend::fs::syscalls::fcntl_setlk(fd.as_fd(), lock, true)
+}
+
+/// `fcntl(fd, F_GETLK, lock)`—Test whether a byte-range lock could be
+/// acquired.
+///
+/// If `lock` could be placed, this returns `None`. Otherwise, it returns one
+/// of the locks which conflicts with it, including the pid of the process
+/// which holds it. Locks held by the calling process never conflict.
+///
+/// # References
+///  - [POSIX]
+///  - [Linux]
+///
+/// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/fcntl.html
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(not(any(
+    target_os = "emscripten",
+    target_os = "fuchsia",
+    target_os = "redox",
+    target_os = "wasi"
+)))]
+#[inline]
+#[doc(alias = "F_GETLK")]
+pub fn fcntl_getlk<Fd: AsFd>(fd: Fd, lock: &Flock) -> io::Result<Option<Flock>> {
+    backend::fs::syscalls::fcntl_getlk(fd.as_fd(), lock)
+}
+
+/// `fcntl(fd, F_OFD_SETLK, lock)`—Acquire or release an open file description
+/// byte-range lock, failing with [`io::Errno::AGAIN`] if a conflicting lock
+/// is held.
+///
+/// Open file description locks belong to the open file description that
+/// `fd` refers to, rather than to the process, so they conflict with locks
+/// taken through other `open` calls, even in the same process, and they're
+/// only released when the last file descriptor referring to the open file
+/// description is closed. They conflict with process-associated locks too.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(linux_kernel)]
+#[inline]
+#[doc(alias = "F_OFD_SETLK")]
+pub fn fcntl_ofd_setlk<Fd: AsFd>(fd: Fd, lock: &Flock) -> io::Result<()> {
+    backend::fs::syscalls::fcntl_ofd_setlk(fd.as_fd(), lock, false)
+}
+
+/// `fcntl(fd, F_OFD_SETLKW, lock)`—Acquire or release an open file
+/// description byte-range lock, waiting for any conflicting lock to be
+/// released.
+///
+/// See [`fcntl_ofd_setlk`] for details.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(linux_kernel)]
+#[inline]
+#[doc(alias = "F_OFD_SETLKW")]
+pub fn fcntl_ofd_setlkw<Fd: AsFd>(fd: Fd, lock: &Flock) -> io::Result<()> {
+    backend::fs::syscalls::fcntl_ofd_setlk(fd.as_fd(), lock, true)
+}
+
+/// `fcntl(fd, F_OFD_GETLK, lock)`—Test whether an open file description
+/// byte-range lock could be acquired.
+///
+/// This is like [`fcntl_getlk`], except that locks held through other open
+/// file descriptions in the calling process do conflict. Conflicting open
+/// file description locks are returned with a `pid` of `None`.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(linux_kernel)]
+#[inline]
+#[doc(alias = "F_OFD_GETLK")]
+pub fn fcntl_ofd_getlk<Fd: AsFd>(fd: Fd, lock: &Flock) -> io::Result<Option<Flock>> {
+    backend::fs::syscalls::fcntl_ofd_getlk(fd.as_fd(), lock)
+}
+
+/// The owner of a file descriptor, which is sent `SIGIO` and `SIGURG`
+/// signals for it, for use with [`fcntl_setown_ex`] and [`fcntl_getown_ex`].
+#[cfg(linux_kernel)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum FdOwner {
+    /// `F_OWNER_TID`—A single thread.
+    Thread(Pid),
+    /// `F_OWNER_PID`—A process.
+    Process(Pid),
+    /// `F_OWNER_PGRP`—A process group.
+    ProcessGroup(Pid),
+}
+
+/// `fcntl(fd, F_GETOWN_EX)`—Returns the owner which receives I/O signals for
+/// a file descriptor.
+///
+/// This returns `None` if no owner is set.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(linux_kernel)]
+#[inline]
+#[doc(alias = "F_GETOWN_EX")]
+pub fn fcntl_getown_ex<Fd: AsFd>(fd: Fd) -> io::Result<Option<FdOwner>> {
+    backend::fs::syscalls::fcntl_getown_ex(fd.as_fd())
+}
+
+/// `fcntl(fd, F_SETOWN_EX, owner)`—Sets the owner which receives I/O signals
+/// for a file descriptor.
+///
+/// Passing `None` removes the owner. Signals are only sent once
+/// [`OFlags::ASYNC`] is set with [`fcntl_setfl`].
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(linux_kernel)]
+#[inline]
+#[doc(alias = "F_SETOWN_EX")]
+pub fn fcntl_setown_ex<Fd: AsFd>(fd: Fd, owner: Option<FdOwner>) -> io::Result<()> {
+    backend::fs::syscalls::fcntl_setown_ex(fd.as_fd(), owner)
+}
+
+/// `fcntl(fd, F_GETSIG)`—Returns the signal sent when I/O is possible on a
+/// file descriptor.
+///
+/// A return value of 0 means that the default, `SIGIO`, is sent.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(linux_kernel)]
+#[inline]
+#[doc(alias = "F_GETSIG")]
+pub fn fcntl_getsig<Fd: AsFd>(fd: Fd) -> io::Result<i32> {
+    backend::fs::syscalls::fcntl_getsig(fd.as_fd())
+}
+
+/// `fcntl(fd, F_SETSIG, sig)`—Sets the signal sent when I/O is possible on a
+/// file descriptor.
+///
+/// `sig` is a raw signal number, so that real-time signals can be used. A
+/// value of 0 restores the default, `SIGIO`. When any other signal is set,
+/// handlers installed with `SA_SIGINFO` receive the file descriptor in
+/// `si_fd`.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(linux_kernel)]
+#[inline]
+#[doc(alias = "F_SETSIG")]
+pub fn fcntl_setsig<Fd: AsFd>(fd: Fd, sig: i32) -> io::Result<()> {
+    backend::fs::syscalls::fcntl_setsig(fd.as_fd(), sig)
+}
+
+/// `fcntl(fd, F_GETLEASE)`—Returns the type of lease held on a file.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(linux_kernel)]
+#[inline]
+#[doc(alias = "F_GETLEASE")]
+pub fn fcntl_getlease<Fd: AsFd>(fd: Fd) -> io::Result<LeaseType> {
+    backend::fs::syscalls::fcntl_getlease(fd.as_fd())
+}
+
+/// `fcntl(fd, F_SETLEASE, lease)`—Acquire or release a lease on a file.
+///
+/// The lease holder is sent a signal, `SIGIO` by default, when another
+/// process opens or truncates the file in a way that conflicts with the
+/// lease, and must release or downgrade it with this function. A lease can
+/// only be taken on a regular file that the caller owns, or with
+/// `CAP_LEASE`.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(linux_kernel)]
+#[inline]
+#[doc(alias = "F_SETLEASE")]
+pub fn fcntl_setlease<Fd: AsFd>(fd: Fd, lease: LeaseType) -> io::Result<()> {
+    backend::fs::syscalls::fcntl_setlease(fd.as_fd(), lease)
+}
+
+/// `fcntl(fd, F_NOTIFY, flags)`—Request a signal when the directory `fd`, or
+/// the files in it, change.
+///
+/// Unless [`DnotifyFlags::MULTISHOT`] is set, the request is removed after
+/// the first notification. Passing empty flags removes all requests. New
+/// code should use [`inotify`] instead.
+///
+/// [`inotify`]: crate::fs::inotify
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fcntl.2.html
+#[cfg(linux_kernel)]
+#[inline]
+#[doc(alias = "F_NOTIFY")]
+pub fn fcntl_notify<Fd: AsFd>(fd: Fd, flags: DnotifyFlags) -> io::Result<()> {
+    backend::fs::syscalls::fcntl_notify(fd.as_fd(), flags)
+}
@@ -36,6 +36,8 @@ pub use backend::fs::types::{StatVfs, StatVfsMountFlags};
 
 #[cfg(linux_kernel)]
 pub use backend::fs::types::FsWord;
+#[cfg(target_os = "linux")]
+pub use backend::fs::types::SyncFileRangeFlags;
 
 /// Timestamps used by [`utimensat`] and [`futimens`].
 ///
@@ -336,3 +338,45 @@ pub fn flock<Fd: AsFd>(fd: Fd, operation: FlockOperation) -> io::Result<()> {
 pub fn syncfs<Fd: AsFd>(fd: Fd) -> io::Result<()> {
     backend::fs::syscalls::syncfs(fd.as_fd())
 }
+
+/// `sync_file_range(fd, offset, nbytes, flags)`—Start or wait for writeback
+/// of a range of a file.
+///
+/// An `nbytes` of 0 means the range extends to the end of the file.
+///
+/// This doesn't write out the file's metadata, or flush the disk's write
+/// cache, so it isn't a substitute for [`fsync`] or [`fdatasync`] when data
+/// needs to be durable. It's useful for controlling the pace of writeback,
+/// for example by starting writeback of a range with
+/// [`SyncFileRangeFlags::WRITE`] after writing it, and waiting for it with
+/// all three flags later.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/sync_file_range.2.html
+#[cfg(target_os = "linux")]
+#[inline]
+pub fn sync_file_range<Fd: AsFd>(
+    fd: Fd,
+    offset: u64,
+    nbytes: u64,
+    flags: SyncFileRangeFlags,
+) -> io::Result<()> {
+    backend::fs::syscalls::sync_file_range(fd.as_fd(), offset, nbytes, flags)
+}
+
+/// `readahead(fd, offset, count)`—Read a range of a file into the page
+/// cache.
+///
+/// This blocks until the data has been read.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/readahead.2.html
+#[cfg(target_os = "linux")]
+#[inline]
+pub fn readahead<Fd: AsFd>(fd: Fd, offset: u64, count: usize) -> io::Result<()> {
+    backend::fs::syscalls::readahead(fd.as_fd(), offset, count)
+}
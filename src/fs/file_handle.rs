@@ -0,0 +1,143 @@
+//! Linux file handles.
+
+use crate::fd::{AsFd, OwnedFd};
+use crate::fs::{AtFlags, OFlags};
+use crate::{backend, io, path};
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+/// `MAX_HANDLE_SZ`—The largest file handle size the kernel supports.
+const MAX_HANDLE_SZ: usize = 128;
+
+/// `struct file_handle`—An opaque, persistent reference to a file.
+///
+/// A file handle identifies a file independently of its name, so it stays
+/// valid across renames, and it can be stored and used from another process
+/// as long as the file still exists.
+///
+/// The handle is stored inline, with enough room for any handle the kernel
+/// can produce.
+#[repr(C)]
+#[derive(Clone)]
+#[doc(alias = "file_handle")]
+pub struct FileHandle {
+    handle_bytes: u32,
+    handle_type: i32,
+    f_handle: [u8; MAX_HANDLE_SZ],
+}
+
+impl FileHandle {
+    /// Reconstruct a file handle from its type and bytes, as previously
+    /// returned by [`FileHandle::handle_type`] and
+    /// [`FileHandle::as_bytes`].
+    ///
+    /// Returns `None` if `bytes` is larger than any valid handle.
+    pub fn new(handle_type: i32, bytes: &[u8]) -> Option<Self> {
+        let mut handle = Self::empty();
+        handle
+            .f_handle
+            .get_mut(..bytes.len())?
+            .copy_from_slice(bytes);
+        handle.handle_bytes = bytes.len() as u32;
+        handle.handle_type = handle_type;
+        Some(handle)
+    }
+
+    /// Returns the filesystem-specific type of this handle.
+    #[inline]
+    pub fn handle_type(&self) -> i32 {
+        self.handle_type
+    }
+
+    /// Returns the opaque contents of this handle.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.f_handle[..self.handle_bytes as usize]
+    }
+
+    #[inline]
+    fn empty() -> Self {
+        Self {
+            handle_bytes: 0,
+            handle_type: 0,
+            f_handle: [0; MAX_HANDLE_SZ],
+        }
+    }
+}
+
+impl PartialEq for FileHandle {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.handle_type == other.handle_type && self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl Eq for FileHandle {}
+
+impl Hash for FileHandle {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.handle_type.hash(state);
+        self.as_bytes().hash(state);
+    }
+}
+
+impl fmt::Debug for FileHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileHandle")
+            .field("handle_type", &self.handle_type)
+            .field("handle", &self.as_bytes())
+            .finish()
+    }
+}
+
+/// `name_to_handle_at(dirfd, path, handle, mount_id, flags)`—Obtains a file
+/// handle and mount ID for a file.
+///
+/// The returned mount ID identifies the mount containing the file; a file
+/// descriptor for that mount is needed to open the handle with
+/// [`open_by_handle_at`].
+///
+/// `flags` may contain [`AtFlags::SYMLINK_FOLLOW`] and
+/// [`AtFlags::EMPTY_PATH`].
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/name_to_handle_at.2.html
+pub fn name_to_handle_at<Fd: AsFd, P: path::Arg>(
+    dirfd: Fd,
+    path: P,
+    flags: AtFlags,
+) -> io::Result<(FileHandle, i32)> {
+    path.into_with_c_str(|path| {
+        let mut handle = FileHandle::empty();
+        handle.handle_bytes = MAX_HANDLE_SZ as u32;
+
+        // `MAX_HANDLE_SZ` is the largest handle the kernel will produce, so
+        // this can't fail with `EOVERFLOW`, and we don't need to retry with
+        // the size the kernel asks for.
+        let mount_id =
+            backend::fs::syscalls::name_to_handle_at(dirfd.as_fd(), path, &mut handle, flags)?;
+        Ok((handle, mount_id))
+    })
+}
+
+/// `open_by_handle_at(mount_fd, handle, flags)`—Opens a file from a file
+/// handle.
+///
+/// `mount_fd` may be any file descriptor on the mount the handle was
+/// obtained from. This requires the `CAP_DAC_READ_SEARCH` capability.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/open_by_handle_at.2.html
+#[inline]
+pub fn open_by_handle_at<Fd: AsFd>(
+    mount_fd: Fd,
+    handle: &FileHandle,
+    flags: OFlags,
+) -> io::Result<OwnedFd> {
+    backend::fs::syscalls::open_by_handle_at(mount_fd.as_fd(), handle, flags)
+}
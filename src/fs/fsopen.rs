@@ -0,0 +1,369 @@
+//! Linux's file-descriptor-based mount API.
+//!
+//! With this API, a filesystem is configured through a filesystem context
+//! created by [`fsopen`] or [`fspick`], instantiated with [`fsmount`] and
+//! then attached to the filesystem tree with [`move_mount_at`].
+
+use crate::backend::fs::types::{
+    FsMountFlags, FsOpenFlags, FsPickFlags, MountAttrFlags, MountPropagationFlags, MoveMountFlags,
+    OpenTreeFlags,
+};
+use crate::fd::{AsFd, BorrowedFd, OwnedFd};
+use crate::fs::AtFlags;
+use crate::{backend, io, path};
+
+/// `fsopen(fs_name, flags)`—Creates a filesystem context.
+///
+/// If any of the operations on the context fail, the kernel may have logged
+/// details about the failure, which can be read with
+/// [`fs_context_read_log`].
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fsopen.2.html
+#[inline]
+pub fn fsopen<Fs: path::Arg>(fs_name: Fs, flags: FsOpenFlags) -> io::Result<OwnedFd> {
+    fs_name.into_with_c_str(|fs_name| backend::fs::syscalls::fsopen(fs_name, flags))
+}
+
+/// `fspick(dfd, path, flags)`—Creates a filesystem context for an existing
+/// superblock, for reconfiguring it.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fspick.2.html
+#[inline]
+pub fn fspick<Fd: AsFd, P: path::Arg>(dfd: Fd, path: P, flags: FsPickFlags) -> io::Result<OwnedFd> {
+    path.into_with_c_str(|path| backend::fs::syscalls::fspick(dfd.as_fd(), path, flags))
+}
+
+/// `fsmount(fs_fd, flags, attr_flags)`—Creates a detached mount from a
+/// filesystem context.
+///
+/// The context must have been created with [`fsconfig_create`] first.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fsmount.2.html
+#[inline]
+pub fn fsmount<Fd: AsFd>(
+    fs_fd: Fd,
+    flags: FsMountFlags,
+    attr_flags: MountAttrFlags,
+) -> io::Result<OwnedFd> {
+    backend::fs::syscalls::fsmount(fs_fd.as_fd(), flags, attr_flags)
+}
+
+/// `move_mount(from_dfd, from_pathname, to_dfd, to_pathname, flags)`—Moves
+/// a mount.
+///
+/// This is commonly used with [`MoveMountFlags::F_EMPTY_PATH`] to attach a
+/// mount created by [`fsmount`] or [`open_tree`] to the filesystem tree.
+///
+/// For the legacy `MS_MOVE` form of `mount`, see [`move_mount`].
+///
+/// [`move_mount`]: crate::fs::move_mount
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/move_mount.2.html
+#[inline]
+#[doc(alias = "move_mount")]
+pub fn move_mount_at<FromFd: AsFd, From: path::Arg, ToFd: AsFd, To: path::Arg>(
+    from_dfd: FromFd,
+    from_pathname: From,
+    to_dfd: ToFd,
+    to_pathname: To,
+    flags: MoveMountFlags,
+) -> io::Result<()> {
+    from_pathname.into_with_c_str(|from_pathname| {
+        to_pathname.into_with_c_str(|to_pathname| {
+            backend::fs::syscalls::move_mount(
+                from_dfd.as_fd(),
+                from_pathname,
+                to_dfd.as_fd(),
+                to_pathname,
+                flags,
+            )
+        })
+    })
+}
+
+/// `open_tree(dfd, filename, flags)`—Opens a mount, or with
+/// [`OpenTreeFlags::CLONE`], creates a detached copy of it.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/open_tree.2.html
+#[inline]
+pub fn open_tree<Fd: AsFd, P: path::Arg>(
+    dfd: Fd,
+    filename: P,
+    flags: OpenTreeFlags,
+) -> io::Result<OwnedFd> {
+    filename
+        .into_with_c_str(|filename| backend::fs::syscalls::open_tree(dfd.as_fd(), filename, flags))
+}
+
+/// `fsconfig(fs_fd, FSCONFIG_SET_FLAG, key, NULL, 0)`—Sets a flag
+/// parameter, such as `ro`, in a filesystem context.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fsconfig.2.html
+#[inline]
+#[doc(alias = "fsconfig")]
+pub fn fsconfig_set_flag<Fd: AsFd, Key: path::Arg>(fs_fd: Fd, key: Key) -> io::Result<()> {
+    key.into_with_c_str(|key| backend::fs::syscalls::fsconfig_set_flag(fs_fd.as_fd(), key))
+}
+
+/// `fsconfig(fs_fd, FSCONFIG_SET_STRING, key, value, 0)`—Sets a string
+/// parameter, such as `source`, in a filesystem context.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fsconfig.2.html
+#[inline]
+#[doc(alias = "fsconfig")]
+pub fn fsconfig_set_string<Fd: AsFd, Key: path::Arg, Value: path::Arg>(
+    fs_fd: Fd,
+    key: Key,
+    value: Value,
+) -> io::Result<()> {
+    key.into_with_c_str(|key| {
+        value.into_with_c_str(|value| {
+            backend::fs::syscalls::fsconfig_set_string(fs_fd.as_fd(), key, value)
+        })
+    })
+}
+
+/// `fsconfig(fs_fd, FSCONFIG_SET_BINARY, key, value, value.len())`—Sets a
+/// binary blob parameter in a filesystem context.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fsconfig.2.html
+#[inline]
+#[doc(alias = "fsconfig")]
+pub fn fsconfig_set_binary<Fd: AsFd, Key: path::Arg>(
+    fs_fd: Fd,
+    key: Key,
+    value: &[u8],
+) -> io::Result<()> {
+    key.into_with_c_str(|key| backend::fs::syscalls::fsconfig_set_binary(fs_fd.as_fd(), key, value))
+}
+
+/// `fsconfig(fs_fd, FSCONFIG_SET_PATH, key, path, fd)`—Sets a path
+/// parameter, such as a journal device, in a filesystem context.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fsconfig.2.html
+#[inline]
+#[doc(alias = "fsconfig")]
+pub fn fsconfig_set_path<Fd: AsFd, Key: path::Arg, P: path::Arg, DirFd: AsFd>(
+    fs_fd: Fd,
+    key: Key,
+    path: P,
+    fd: DirFd,
+) -> io::Result<()> {
+    key.into_with_c_str(|key| {
+        path.into_with_c_str(|path| {
+            backend::fs::syscalls::fsconfig_set_path(fs_fd.as_fd(), key, path, fd.as_fd())
+        })
+    })
+}
+
+/// `fsconfig(fs_fd, FSCONFIG_SET_PATH_EMPTY, key, "", fd)`—Sets a path
+/// parameter to the object referred to by `fd`.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fsconfig.2.html
+#[inline]
+#[doc(alias = "fsconfig")]
+pub fn fsconfig_set_path_empty<Fd: AsFd, Key: path::Arg, PathFd: AsFd>(
+    fs_fd: Fd,
+    key: Key,
+    fd: PathFd,
+) -> io::Result<()> {
+    key.into_with_c_str(|key| {
+        backend::fs::syscalls::fsconfig_set_path_empty(fs_fd.as_fd(), key, fd.as_fd())
+    })
+}
+
+/// `fsconfig(fs_fd, FSCONFIG_SET_FD, key, NULL, fd)`—Sets a file
+/// descriptor parameter in a filesystem context.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fsconfig.2.html
+#[inline]
+#[doc(alias = "fsconfig")]
+pub fn fsconfig_set_fd<Fd: AsFd, Key: path::Arg, ValueFd: AsFd>(
+    fs_fd: Fd,
+    key: Key,
+    fd: ValueFd,
+) -> io::Result<()> {
+    key.into_with_c_str(|key| {
+        backend::fs::syscalls::fsconfig_set_fd(fs_fd.as_fd(), key, fd.as_fd())
+    })
+}
+
+/// `fsconfig(fs_fd, FSCONFIG_CMD_CREATE, NULL, NULL, 0)`—Creates the
+/// superblock for a filesystem context, so that it can be mounted with
+/// [`fsmount`].
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fsconfig.2.html
+#[inline]
+#[doc(alias = "fsconfig")]
+pub fn fsconfig_create<Fd: AsFd>(fs_fd: Fd) -> io::Result<()> {
+    backend::fs::syscalls::fsconfig_create(fs_fd.as_fd())
+}
+
+/// `fsconfig(fs_fd, FSCONFIG_CMD_RECONFIGURE, NULL, NULL, 0)`—Applies the
+/// parameters of a filesystem context created by [`fspick`] to its
+/// superblock.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fsconfig.2.html
+#[inline]
+#[doc(alias = "fsconfig")]
+pub fn fsconfig_reconfigure<Fd: AsFd>(fs_fd: Fd) -> io::Result<()> {
+    backend::fs::syscalls::fsconfig_reconfigure(fs_fd.as_fd())
+}
+
+/// `struct mount_attr`—Mount attributes for use with [`mount_setattr`].
+#[derive(Debug, Copy, Clone)]
+pub struct MountAttr<'a> {
+    /// Attributes to set.
+    pub attr_set: MountAttrFlags,
+
+    /// Attributes to clear.
+    ///
+    /// To change the access time behavior, include
+    /// [`MountAttrFlags::_ATIME`] here and the new behavior in `attr_set`.
+    pub attr_clr: MountAttrFlags,
+
+    /// The new propagation type, which is one of
+    /// [`MountPropagationFlags::SHARED`], [`MountPropagationFlags::PRIVATE`],
+    /// [`MountPropagationFlags::SLAVE`], or
+    /// [`MountPropagationFlags::UNBINDABLE`], or empty to leave it unchanged.
+    pub propagation: MountPropagationFlags,
+
+    /// The user namespace to use with [`MountAttrFlags::IDMAP`], such as a
+    /// file descriptor for `/proc/<pid>/ns/user`.
+    pub userns_fd: Option<BorrowedFd<'a>>,
+}
+
+impl<'a> Default for MountAttr<'a> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            attr_set: MountAttrFlags::empty(),
+            attr_clr: MountAttrFlags::empty(),
+            propagation: MountPropagationFlags::empty(),
+            userns_fd: None,
+        }
+    }
+}
+
+/// `mount_setattr(dirfd, path, flags, attr, sizeof(*attr))`—Changes the
+/// properties of a mount or a mount tree.
+///
+/// `flags` may contain [`AtFlags::EMPTY_PATH`], [`AtFlags::RECURSIVE`],
+/// [`AtFlags::SYMLINK_NOFOLLOW`], and [`AtFlags::NO_AUTOMOUNT`].
+///
+/// To create an idmapped mount, set [`MountAttrFlags::IDMAP`] and
+/// `userns_fd` on a detached mount created with [`open_tree`] and
+/// [`OpenTreeFlags::CLONE`].
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/mount_setattr.2.html
+#[inline]
+pub fn mount_setattr<Fd: AsFd, P: path::Arg>(
+    dirfd: Fd,
+    path: P,
+    flags: AtFlags,
+    attr: &MountAttr<'_>,
+) -> io::Result<()> {
+    path.into_with_c_str(|path| {
+        backend::fs::syscalls::mount_setattr(dirfd.as_fd(), path, flags, attr)
+    })
+}
+
+/// The severity of a message in a filesystem context's log.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum FsLogLevel {
+    /// An error, logged with an `e` prefix.
+    Error,
+    /// A warning, logged with a `w` prefix.
+    Warning,
+    /// Informational, logged with an `i` prefix.
+    Info,
+}
+
+/// A message from a filesystem context's log, as returned by
+/// [`fs_context_read_log`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct FsLogMessage<'buf> {
+    /// The severity of the message.
+    pub level: FsLogLevel,
+
+    /// The text of the message, without the severity prefix.
+    pub message: &'buf [u8],
+}
+
+/// `read(fs_fd, buf)`—Reads the next message from a filesystem context's
+/// log.
+///
+/// Filesystems log messages here explaining why [`fsconfig_set_string`],
+/// [`fsconfig_create`], and similar functions failed. Returns `Ok(None)` if
+/// there are no more messages.
+///
+/// Messages that don't fit in `buf` are discarded by the kernel, and this
+/// returns [`io::Errno::MSGSIZE`]. A buffer of a few hundred bytes is
+/// usually enough.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://docs.kernel.org/filesystems/mount_api.html
+#[doc(alias = "read")]
+pub fn fs_context_read_log<Fd: AsFd>(
+    fs_fd: Fd,
+    buf: &mut [u8],
+) -> io::Result<Option<FsLogMessage<'_>>> {
+    let len = match io::read(fs_fd, buf) {
+        Ok(len) => len,
+        Err(io::Errno::NODATA) => return Ok(None),
+        Err(err) => return Err(err),
+    };
+
+    let (level, message) = match &buf[..len] {
+        [b'e', b' ', message @ ..] => (FsLogLevel::Error, message),
+        [b'w', b' ', message @ ..] => (FsLogLevel::Warning, message),
+        [b'i', b' ', message @ ..] => (FsLogLevel::Info, message),
+        // Future kernels might add new prefixes; don't lose the message.
+        message => (FsLogLevel::Info, message),
+    };
+    Ok(Some(FsLogMessage { level, message }))
+}
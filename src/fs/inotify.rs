@@ -0,0 +1,187 @@
+//! inotify support for working with inotifies
+
+#![allow(unsafe_code)]
+
+pub use crate::backend::fs::inotify::{
+    inotify_add_watch, inotify_init, inotify_remove_watch, CreateFlags, ReadFlags, WatchFlags,
+};
+use crate::fd::AsFd;
+use crate::ffi::CStr;
+use crate::io;
+use core::mem::MaybeUninit;
+
+/// The size of the fixed-length part of `struct inotify_event`, which is
+/// `__s32 wd; __u32 mask; __u32 cookie; __u32 len;`.
+const EVENT_HEADER_LEN: usize = 16;
+
+/// A buffered reader of inotify events.
+///
+/// The kernel never splits an event across reads; if the buffer is too small
+/// to hold the next event, reading fails with [`io::Errno::INVAL`]. A buffer
+/// of at least `16 + NAME_MAX + 1` bytes is enough to hold any single event.
+///
+/// The buffer need not be aligned, so this works in `no_std` environments
+/// without an allocator.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::mem::MaybeUninit;
+/// # use rustix::fd::AsFd;
+/// # use rustix::fs::inotify::{self, CreateFlags, Reader, WatchFlags};
+/// let inotify = inotify::inotify_init(CreateFlags::CLOEXEC).unwrap();
+/// inotify::inotify_add_watch(inotify.as_fd(), "/tmp", WatchFlags::CREATE).unwrap();
+///
+/// let mut buf = [MaybeUninit::uninit(); 512];
+/// let mut reader = Reader::new(&inotify, &mut buf);
+/// loop {
+///     let event = reader.next().unwrap();
+///     println!("{:?} {:?}", event.events(), event.file_name());
+/// }
+/// ```
+pub struct Reader<'buf, Fd: AsFd> {
+    fd: Fd,
+    buf: &'buf mut [MaybeUninit<u8>],
+    initialized: usize,
+    offset: usize,
+}
+
+impl<'buf, Fd: AsFd> Reader<'buf, Fd> {
+    /// Create a new reader from the given inotify file descriptor and
+    /// buffer.
+    #[inline]
+    pub fn new(fd: Fd, buf: &'buf mut [MaybeUninit<u8>]) -> Self {
+        Self {
+            fd,
+            buf,
+            initialized: 0,
+            offset: 0,
+        }
+    }
+
+    /// Read the next inotify event.
+    ///
+    /// If the buffer is empty, this reads a new batch of events from the
+    /// inotify file descriptor, which blocks unless the file descriptor is
+    /// in non-blocking mode.
+    ///
+    /// If the kernel's event queue overflowed, an event with a watch
+    /// descriptor of `-1` and [`ReadFlags::Q_OVERFLOW`] is reported, and
+    /// some events have been lost.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> io::Result<Event<'_>> {
+        if self.is_buffer_empty() {
+            match io::read_uninit(self.fd.as_fd(), self.buf).map(|(init, _uninit)| init.len()) {
+                Ok(0) => return Err(io::Errno::INVAL),
+                Ok(bytes_read) => {
+                    self.initialized = bytes_read;
+                    self.offset = 0;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        // SAFETY: The bytes up to `self.initialized` were written by `read`.
+        let bytes = unsafe {
+            let buf = &self.buf[self.offset..self.initialized];
+            core::slice::from_raw_parts(buf.as_ptr().cast::<u8>(), buf.len())
+        };
+
+        match parse_event(bytes) {
+            Some((event, len)) => {
+                self.offset += len;
+                Ok(event)
+            }
+            None => {
+                // The kernel doesn't produce partial events, so this means
+                // something other than inotify wrote to the buffer. Discard
+                // the rest of it rather than misinterpret it.
+                self.offset = self.initialized;
+                Err(io::Errno::INVAL)
+            }
+        }
+    }
+
+    /// Returns true if the internal buffer is empty and will be refilled when
+    /// calling [`next`].
+    ///
+    /// [`next`]: Self::next
+    #[inline]
+    pub fn is_buffer_empty(&self) -> bool {
+        self.offset >= self.initialized
+    }
+}
+
+/// An inotify event, as returned by [`Reader::next`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Event<'a> {
+    wd: i32,
+    events: ReadFlags,
+    cookie: u32,
+    file_name: Option<&'a CStr>,
+}
+
+impl<'a> Event<'a> {
+    /// Returns the watch descriptor this event is for, as returned by
+    /// [`inotify_add_watch`].
+    ///
+    /// This is `-1` for [`ReadFlags::Q_OVERFLOW`] events.
+    #[inline]
+    pub fn wd(&self) -> i32 {
+        self.wd
+    }
+
+    /// Returns a description of the events.
+    #[inline]
+    #[doc(alias = "mask")]
+    pub fn events(&self) -> ReadFlags {
+        self.events
+    }
+
+    /// Returns the unique cookie associating related events, such as the
+    /// [`ReadFlags::MOVED_FROM`] and [`ReadFlags::MOVED_TO`] events of a
+    /// rename, or `0` for unrelated events.
+    #[inline]
+    pub fn cookie(&self) -> u32 {
+        self.cookie
+    }
+
+    /// Returns the name of the file within a watched directory that this
+    /// event is about, or `None` for events about the watched object itself.
+    #[inline]
+    #[doc(alias = "name")]
+    pub fn file_name(&self) -> Option<&'a CStr> {
+        self.file_name
+    }
+}
+
+/// Parse one event from the start of `bytes`, returning it and its length.
+fn parse_event(bytes: &[u8]) -> Option<(Event<'_>, usize)> {
+    let header = bytes.get(..EVENT_HEADER_LEN)?;
+    let field = |at: usize| header[at..at + 4].try_into().unwrap();
+    let wd = i32::from_ne_bytes(field(0));
+    let mask = u32::from_ne_bytes(field(4));
+    let cookie = u32::from_ne_bytes(field(8));
+    let len = u32::from_ne_bytes(field(12)) as usize;
+
+    let event_len = EVENT_HEADER_LEN.checked_add(len)?;
+    let name = bytes.get(EVENT_HEADER_LEN..event_len)?;
+
+    // The name is NUL-terminated and then padded with further NULs.
+    let file_name = if name.is_empty() {
+        None
+    } else {
+        let nul = name.iter().position(|b| *b == b'\0')?;
+        Some(CStr::from_bytes_with_nul(&name[..=nul]).unwrap())
+    };
+
+    Some((
+        Event {
+            wd,
+            events: ReadFlags::from_bits_retain(mask),
+            cookie,
+            file_name,
+        },
+        event_len,
+    ))
+}
@@ -6,6 +6,9 @@ use {
     crate::{backend, io},
 };
 
+#[cfg(linux_kernel)]
+pub use backend::fs::types::{FiemapExtentFlags, FiemapFlags, FsXFlags, InodeFlags};
+
 /// `ioctl(fd, BLKSSZGET)`—Returns the logical block size of a block device.
 ///
 /// This is mentioned in the [Linux `openat` manual page].
@@ -48,3 +51,247 @@ pub fn ioctl_ficlone<Fd: AsFd, SrcFd: AsFd>(fd: Fd, src_fd: SrcFd) -> io::Result
 pub fn ext4_ioc_resize_fs<Fd: AsFd>(fd: Fd, blocks: u64) -> io::Result<()> {
     backend::fs::syscalls::ext4_ioc_resize_fs(fd.as_fd(), blocks)
 }
+
+/// `ioctl(fd, FS_IOC_GETFLAGS)`—Returns the inode flags of a file.
+///
+/// These are the flags shown by `lsattr`. Not all filesystems support all
+/// flags, and some don't support this at all, in which case this fails with
+/// [`io::Errno::NOTTY`] or [`io::Errno::OPNOTSUPP`].
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/ioctl_iflags.2.html
+#[cfg(linux_kernel)]
+#[inline]
+#[doc(alias = "FS_IOC_GETFLAGS")]
+pub fn ioctl_getflags<Fd: AsFd>(fd: Fd) -> io::Result<InodeFlags> {
+    backend::fs::syscalls::ioctl_getflags(fd.as_fd())
+}
+
+/// `ioctl(fd, FS_IOC_SETFLAGS, flags)`—Sets the inode flags of a file.
+///
+/// This replaces all of the flags, so to change one flag, like `chattr`
+/// does, call [`ioctl_getflags`] first and modify its result. Setting
+/// [`InodeFlags::IMMUTABLE`] or [`InodeFlags::APPEND`] requires
+/// `CAP_LINUX_IMMUTABLE`.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/ioctl_iflags.2.html
+#[cfg(linux_kernel)]
+#[inline]
+#[doc(alias = "FS_IOC_SETFLAGS")]
+pub fn ioctl_setflags<Fd: AsFd>(fd: Fd, flags: InodeFlags) -> io::Result<()> {
+    backend::fs::syscalls::ioctl_setflags(fd.as_fd(), flags)
+}
+
+/// `struct fsxattr` for use with [`ioctl_fsgetxattr`] and
+/// [`ioctl_fssetxattr`].
+#[cfg(linux_kernel)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct FsXattr {
+    /// `fsx_xflags`—The extended inode flags.
+    pub xflags: FsXFlags,
+    /// `fsx_extsize`—The extent size allocation hint, in bytes.
+    pub extsize: u32,
+    /// `fsx_nextents`—The number of data extents. This is ignored by
+    /// [`ioctl_fssetxattr`].
+    pub nextents: u32,
+    /// `fsx_projid`—The project ID.
+    pub projid: u32,
+    /// `fsx_cowextsize`—The copy-on-write extent size allocation hint, in
+    /// bytes.
+    pub cowextsize: u32,
+}
+
+/// `ioctl(fd, FS_IOC_FSGETXATTR)`—Returns the extended inode attributes of a
+/// file, such as its project ID and extent size hints.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/ioctl_xfs_fsgetxattr.2.html
+#[cfg(linux_kernel)]
+#[inline]
+#[doc(alias = "FS_IOC_FSGETXATTR")]
+pub fn ioctl_fsgetxattr<Fd: AsFd>(fd: Fd) -> io::Result<FsXattr> {
+    backend::fs::syscalls::ioctl_fsgetxattr(fd.as_fd())
+}
+
+/// `ioctl(fd, FS_IOC_FSSETXATTR, xattr)`—Sets the extended inode attributes of
+/// a file.
+///
+/// Like [`ioctl_setflags`], this replaces all of the attributes, so call
+/// [`ioctl_fsgetxattr`] first to change only some of them.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/ioctl_xfs_fsgetxattr.2.html
+#[cfg(linux_kernel)]
+#[inline]
+#[doc(alias = "FS_IOC_FSSETXATTR")]
+pub fn ioctl_fssetxattr<Fd: AsFd>(fd: Fd, xattr: &FsXattr) -> io::Result<()> {
+    backend::fs::syscalls::ioctl_fssetxattr(fd.as_fd(), xattr)
+}
+
+/// The number of extents we ask for in each `FS_IOC_FIEMAP` call.
+#[cfg(linux_kernel)]
+const FIEMAP_BATCH: usize = 32;
+
+/// `struct fiemap`, with room for [`FIEMAP_BATCH`] extents.
+#[cfg(linux_kernel)]
+#[repr(C)]
+pub(crate) struct RawFiemap {
+    fm_start: u64,
+    fm_length: u64,
+    fm_flags: u32,
+    fm_mapped_extents: u32,
+    fm_extent_count: u32,
+    fm_reserved: u32,
+    fm_extents: [RawFiemapExtent; FIEMAP_BATCH],
+}
+
+/// `struct fiemap_extent`.
+#[cfg(linux_kernel)]
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct RawFiemapExtent {
+    fe_logical: u64,
+    fe_physical: u64,
+    fe_length: u64,
+    fe_reserved64: [u64; 2],
+    fe_flags: u32,
+    fe_reserved: [u32; 3],
+}
+
+/// An extent returned by [`ioctl_fiemap`].
+#[cfg(linux_kernel)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct FiemapExtent {
+    /// `fe_logical`—The byte offset of the extent in the file.
+    pub logical: u64,
+    /// `fe_physical`—The byte offset of the extent on the disk.
+    pub physical: u64,
+    /// `fe_length`—The length of the extent in bytes.
+    pub length: u64,
+    /// `fe_flags`—Flags describing the extent.
+    pub flags: FiemapExtentFlags,
+}
+
+/// `ioctl(fd, FS_IOC_FIEMAP, fiemap)`—Returns an iterator over the physical
+/// extents of a file.
+///
+/// This yields the extents which overlap the byte range starting at `start`
+/// and extending for `len` bytes; pass `u64::MAX` for `len` to map the rest
+/// of the file. Holes in the file don't have extents. The extents are
+/// fetched from the kernel in batches as the iterator is advanced.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://docs.kernel.org/filesystems/fiemap.html
+#[cfg(linux_kernel)]
+#[inline]
+#[doc(alias = "FS_IOC_FIEMAP")]
+pub fn ioctl_fiemap<Fd: AsFd>(fd: Fd, start: u64, len: u64, flags: FiemapFlags) -> Fiemap<Fd> {
+    Fiemap {
+        fd,
+        raw: RawFiemap {
+            fm_start: 0,
+            fm_length: 0,
+            fm_flags: 0,
+            fm_mapped_extents: 0,
+            fm_extent_count: 0,
+            fm_reserved: 0,
+            fm_extents: [RawFiemapExtent {
+                fe_logical: 0,
+                fe_physical: 0,
+                fe_length: 0,
+                fe_reserved64: [0; 2],
+                fe_flags: 0,
+                fe_reserved: [0; 3],
+            }; FIEMAP_BATCH],
+        },
+        index: 0,
+        pos: start,
+        end: start.saturating_add(len),
+        flags,
+        done: false,
+    }
+}
+
+/// An iterator over the extents of a file, returned by [`ioctl_fiemap`].
+#[cfg(linux_kernel)]
+pub struct Fiemap<Fd: AsFd> {
+    fd: Fd,
+    raw: RawFiemap,
+    index: usize,
+    pos: u64,
+    end: u64,
+    flags: FiemapFlags,
+    done: bool,
+}
+
+#[cfg(linux_kernel)]
+impl<Fd: AsFd> Iterator for Fiemap<Fd> {
+    type Item = io::Result<FiemapExtent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.raw.fm_mapped_extents as usize {
+            if self.done || self.pos >= self.end {
+                return None;
+            }
+            if let Err(err) = self.fetch() {
+                self.done = true;
+                return Some(Err(err));
+            }
+            if self.raw.fm_mapped_extents == 0 {
+                self.done = true;
+                return None;
+            }
+        }
+
+        let raw = &self.raw.fm_extents[self.index];
+        self.index += 1;
+        let extent = FiemapExtent {
+            logical: raw.fe_logical,
+            physical: raw.fe_physical,
+            length: raw.fe_length,
+            flags: FiemapExtentFlags::from_bits_retain(raw.fe_flags),
+        };
+        self.pos = extent.logical.saturating_add(extent.length);
+        if extent.flags.contains(FiemapExtentFlags::LAST) {
+            self.done = true;
+        }
+        Some(Ok(extent))
+    }
+}
+
+#[cfg(linux_kernel)]
+impl<Fd: AsFd> Fiemap<Fd> {
+    /// Ask the kernel for the next batch of extents, starting at `pos`.
+    fn fetch(&mut self) -> io::Result<()> {
+        self.index = 0;
+        self.raw.fm_start = self.pos;
+        self.raw.fm_length = self.end - self.pos;
+        self.raw.fm_flags = self.flags.bits();
+        self.raw.fm_mapped_extents = 0;
+        self.raw.fm_extent_count = FIEMAP_BATCH as u32;
+        backend::fs::syscalls::ioctl_fiemap(self.fd.as_fd(), &mut self.raw)
+    }
+}
+
+#[cfg(linux_kernel)]
+impl<Fd: AsFd> core::fmt::Debug for Fiemap<Fd> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Fiemap")
+            .field("fd", &self.fd.as_fd())
+            .field("pos", &self.pos)
+            .field("end", &self.end)
+            .field("flags", &self.flags)
+            .finish()
+    }
+}
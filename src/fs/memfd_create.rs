@@ -17,3 +17,46 @@ pub use backend::fs::types::MemfdFlags;
 pub fn memfd_create<P: path::Arg>(path: P, flags: MemfdFlags) -> io::Result<OwnedFd> {
     path.into_with_c_str(|path| backend::fs::syscalls::memfd_create(path, flags))
 }
+
+#[cfg(linux_kernel)]
+impl MemfdFlags {
+    /// Return `MFD_HUGETLB` combined with the `MFD_HUGE_*` encoding of
+    /// `page_size`, which selects a huge page size other than the system
+    /// default.
+    ///
+    /// This returns `None` if `page_size` isn't a power of two greater than
+    /// one. Whether the kernel supports the size depends on the architecture
+    /// and configuration; if it doesn't, [`memfd_create`] fails with
+    /// [`io::Errno::INVAL`].
+    ///
+    /// # References
+    ///  - [Linux]
+    ///
+    /// [Linux]: https://man7.org/linux/man-pages/man2/memfd_create.2.html
+    #[inline]
+    pub const fn hugetlb_size(page_size: u64) -> Option<Self> {
+        if !page_size.is_power_of_two() || page_size == 1 {
+            return None;
+        }
+        let log2 = page_size.trailing_zeros();
+        Some(Self::from_bits_retain(
+            Self::HUGETLB.bits() | (log2 << MFD_HUGE_SHIFT),
+        ))
+    }
+
+    /// Return the huge page size selected by the `MFD_HUGE_*` bits, or `None`
+    /// if `MFD_HUGETLB` isn't set or the system default size is selected.
+    #[inline]
+    pub const fn huge_page_size(self) -> Option<u64> {
+        let log2 = (self.bits() >> MFD_HUGE_SHIFT) & MFD_HUGE_MASK;
+        if !self.contains(Self::HUGETLB) || log2 == 0 {
+            return None;
+        }
+        Some(1 << log2)
+    }
+}
+
+#[cfg(linux_kernel)]
+const MFD_HUGE_SHIFT: u32 = 26;
+#[cfg(linux_kernel)]
+const MFD_HUGE_MASK: u32 = 0x3f;
@@ -0,0 +1,26 @@
+use crate::fd::OwnedFd;
+use crate::{backend, io};
+
+pub use backend::fs::types::SecretFlags;
+
+/// `memfd_secret(flags)`—Create an anonymous file whose memory is hidden
+/// from the kernel.
+///
+/// The returned file has a size of zero; set its size with [`ftruncate`] and
+/// map it with [`mmap`]. The pages of the mapping are removed from the
+/// kernel's direct map, and aren't accessible to other processes, even with
+/// `ptrace`, which makes it suitable for holding key material.
+///
+/// On Linux 5.14 through 6.4, this is only available if the kernel is booted
+/// with `secretmem.enable=1`, and otherwise fails with [`io::Errno::NOSYS`].
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/memfd_secret.2.html
+/// [`ftruncate`]: crate::fs::ftruncate
+/// [`mmap`]: https://docs.rs/rustix/*/rustix/mm/fn.mmap.html
+#[inline]
+pub fn memfd_secret(flags: SecretFlags) -> io::Result<OwnedFd> {
+    backend::fs::syscalls::memfd_secret(flags)
+}
@@ -1,14 +1,22 @@
 //! Filesystem operations.
 
 mod abs;
+#[cfg(all(feature = "alloc", linux_kernel))]
+mod acl;
 #[cfg(not(target_os = "redox"))]
 mod at;
+#[cfg(all(feature = "alloc", linux_kernel))]
+mod atomic_write;
+#[cfg(target_os = "linux")]
+mod cachestat;
 mod constants;
+#[cfg(all(feature = "alloc", target_os = "linux"))]
+mod copy_fd;
 #[cfg(linux_kernel)]
 mod copy_file_range;
 #[cfg(not(target_os = "redox"))]
 mod cwd;
-#[cfg(not(target_os = "redox"))]
+#[cfg(all(feature = "alloc", not(target_os = "redox")))]
 mod dir;
 #[cfg(not(any(
     apple,
@@ -19,50 +27,88 @@ mod dir;
     target_os = "redox",
 )))]
 mod fadvise;
+#[cfg(target_os = "linux")]
+pub mod fanotify;
 pub(crate) mod fcntl;
 #[cfg(apple)]
 mod fcntl_apple;
 #[cfg(apple)]
 mod fcopyfile;
 pub(crate) mod fd;
+#[cfg(linux_kernel)]
+mod file_handle;
 mod file_type;
+#[cfg(linux_kernel)]
+mod fsopen;
 #[cfg(apple)]
 mod getpath;
 #[cfg(not(target_os = "wasi"))] // WASI doesn't have get[gpu]id.
 mod id;
+#[cfg(linux_kernel)]
+pub mod inotify;
 #[cfg(not(target_os = "wasi"))]
 mod ioctl;
 #[cfg(not(any(target_os = "haiku", target_os = "redox", target_os = "wasi")))]
 mod makedev;
 #[cfg(any(linux_kernel, target_os = "freebsd"))]
 mod memfd_create;
+#[cfg(all(
+    linux_kernel,
+    any(
+        target_arch = "aarch64",
+        target_arch = "riscv32",
+        target_arch = "riscv64",
+        target_arch = "x86",
+        target_arch = "x86_64",
+    )
+))]
+mod memfd_secret;
 #[cfg(linux_kernel)]
 mod mount;
 #[cfg(linux_kernel)]
 mod openat2;
+#[cfg(all(feature = "alloc", linux_kernel))]
+mod random_name;
 #[cfg(linux_kernel)]
 mod raw_dir;
 mod seek_from;
 #[cfg(target_os = "linux")]
 mod sendfile;
+#[cfg(all(feature = "alloc", linux_kernel))]
+mod statmount;
 #[cfg(linux_kernel)]
 mod statx;
 #[cfg(not(any(target_os = "redox", target_os = "wasi")))]
 mod sync;
+#[cfg(all(feature = "alloc", linux_kernel))]
+mod temp;
+#[cfg(all(feature = "alloc", linux_kernel))]
+mod walk;
 #[cfg(any(apple, linux_kernel))]
 mod xattr;
 
-#[cfg(linux_kernel)]
-pub use crate::backend::fs::inotify;
 pub use abs::*;
+#[cfg(all(feature = "alloc", linux_kernel))]
+pub use acl::{
+    fget_acl, fset_acl, get_acl, lget_acl, lset_acl, set_acl, Acl, AclEntry, AclPerms, AclTag,
+    AclType,
+};
 #[cfg(not(target_os = "redox"))]
 pub use at::*;
+#[cfg(all(feature = "alloc", linux_kernel))]
+pub use atomic_write::{
+    atomic_exchange_at, atomic_write_at, atomic_write_at_with, AtomicWriteOptions,
+};
+#[cfg(target_os = "linux")]
+pub use cachestat::*;
 pub use constants::*;
+#[cfg(all(feature = "alloc", target_os = "linux"))]
+pub use copy_fd::{copy_fd, CopyFdOptions, CopyMethod};
 #[cfg(linux_kernel)]
 pub use copy_file_range::copy_file_range;
 #[cfg(not(target_os = "redox"))]
 pub use cwd::*;
-#[cfg(not(target_os = "redox"))]
+#[cfg(all(feature = "alloc", not(target_os = "redox")))]
 pub use dir::{Dir, DirEntry};
 #[cfg(not(any(
     apple,
@@ -79,7 +125,11 @@ pub use fcntl_apple::{fcntl_fullfsync, fcntl_rdadvise};
 #[cfg(apple)]
 pub use fcopyfile::*;
 pub use fd::*;
+#[cfg(linux_kernel)]
+pub use file_handle::{name_to_handle_at, open_by_handle_at, FileHandle};
 pub use file_type::FileType;
+#[cfg(linux_kernel)]
+pub use fsopen::*;
 #[cfg(apple)]
 pub use getpath::getpath;
 #[cfg(not(target_os = "wasi"))]
@@ -90,19 +140,44 @@ pub use ioctl::*;
 pub use makedev::*;
 #[cfg(any(linux_kernel, target_os = "freebsd"))]
 pub use memfd_create::{memfd_create, MemfdFlags};
+#[cfg(all(
+    linux_kernel,
+    any(
+        target_arch = "aarch64",
+        target_arch = "riscv32",
+        target_arch = "riscv64",
+        target_arch = "x86",
+        target_arch = "x86_64",
+    )
+))]
+pub use memfd_secret::{memfd_secret, SecretFlags};
 #[cfg(linux_kernel)]
 pub use mount::*;
 #[cfg(linux_kernel)]
 pub use openat2::openat2;
+#[cfg(all(feature = "alloc", linux_kernel))]
+pub use openat2::openat_resolve;
+#[cfg(all(feature = "alloc", linux_kernel))]
+pub use raw_dir::{OwnedRawDir, OwnedRawDirEntry};
 #[cfg(linux_kernel)]
 pub use raw_dir::{RawDir, RawDirEntry};
 pub use seek_from::SeekFrom;
 #[cfg(target_os = "linux")]
 pub use sendfile::sendfile;
+#[cfg(all(feature = "alloc", linux_kernel))]
+pub(crate) use statmount::MntIdReq;
+#[cfg(all(feature = "alloc", linux_kernel))]
+pub use statmount::{
+    listmount, statmount, ListmountFlags, Statmount, StatmountFlags, StatmountOpts, LSMT_ROOT,
+};
 #[cfg(linux_kernel)]
-pub use statx::{statx, Statx, StatxFlags, StatxTimestamp};
+pub use statx::{statx, statx_or_statat, Statx, StatxAttributes, StatxFlags, StatxTimestamp};
 #[cfg(not(any(target_os = "redox", target_os = "wasi")))]
 pub use sync::sync;
+#[cfg(all(feature = "alloc", linux_kernel))]
+pub use temp::{mkdtempat, mkstempat, TempDir, TempFile};
+#[cfg(all(feature = "alloc", linux_kernel))]
+pub use walk::{remove_dir_all_at, walk, SymlinkPolicy, Walk, WalkEntry, WalkOptions};
 #[cfg(any(apple, linux_kernel))]
 pub use xattr::*;
 
@@ -131,6 +131,11 @@ pub fn change_mount<Target: path::Arg>(
 
 /// `mount(source, target, NULL, MS_MOVE, NULL)`
 ///
+/// This is the legacy way to move a mount. To use the `move_mount` syscall,
+/// see [`move_mount_at`].
+///
+/// [`move_mount_at`]: crate::fs::move_mount_at
+///
 /// # References
 ///  - [Linux]
 ///
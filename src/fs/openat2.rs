@@ -1,7 +1,19 @@
+#[cfg(feature = "alloc")]
+use crate::fd::BorrowedFd;
 use crate::fd::OwnedFd;
+#[cfg(feature = "alloc")]
+use crate::ffi::CStr;
+#[cfg(feature = "alloc")]
+use crate::fs::{fstat, fstatfs, openat, readlinkat, FileType, Stat, CWD, PROC_SUPER_MAGIC};
 use crate::{backend, io, path};
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 use backend::fd::AsFd;
 use backend::fs::types::{Mode, OFlags, ResolveFlags};
+#[cfg(feature = "alloc")]
+use core::sync::atomic::{AtomicBool, Ordering};
 
 /// `openat2(dirfd, path, OpenHow { oflags, mode, resolve }, sizeof(OpenHow))`
 ///
@@ -21,3 +33,557 @@ pub fn openat2<Fd: AsFd, P: path::Arg>(
         backend::fs::syscalls::openat2(dirfd.as_fd(), path, oflags, mode, resolve)
     })
 }
+
+/// `openat2(dirfd, path, OpenHow { oflags, mode, resolve }, sizeof(OpenHow))`,
+/// emulated in userspace if `openat2` isn't available.
+///
+/// This calls [`openat2`] if it's available. If it isn't, such as on Linux
+/// before 5.6 or under a seccomp filter which rejects it with `ENOSYS` or
+/// `EPERM`, this resolves `path` one component at a time, opening each
+/// directory with `O_PATH | O_NOFOLLOW` and reading symlinks with
+/// `readlinkat`, so that the following `resolve` flags have the same effect:
+///
+///  - [`ResolveFlags::BENEATH`]: Absolute paths, absolute symlinks, and `..`
+///    components which would leave `dirfd` fail with [`io::Errno::XDEV`].
+///  - [`ResolveFlags::IN_ROOT`]: Absolute paths, absolute symlinks, and `..`
+///    components are resolved as if `dirfd` were the root directory.
+///  - [`ResolveFlags::NO_SYMLINKS`]: Any symlink fails with
+///    [`io::Errno::LOOP`], except for a final component opened with
+///    `O_PATH | O_NOFOLLOW`.
+///  - [`ResolveFlags::NO_XDEV`]: Any directory or file whose `st_dev` differs
+///    from that of `dirfd` fails with [`io::Errno::XDEV`], before the final
+///    component is opened with `oflags`. Unlike the kernel, this doesn't
+///    detect bind mounts within the same filesystem.
+///  - [`ResolveFlags::NO_MAGICLINKS`]: Symlinks in procfs below the top
+///    level, such as `/proc/self/fd/*` and `/proc/self/exe`, fail with
+///    [`io::Errno::LOOP`].
+///  - [`ResolveFlags::CACHED`]: The emulation can't tell whether a lookup
+///    would block, so this fails with [`io::Errno::AGAIN`], as `openat2`
+///    does when the lookup would block.
+///
+/// Following more than 40 symlinks fails with [`io::Errno::LOOP`], as it
+/// does in the kernel.
+///
+/// The emulation follows magic links by their contents, as if they were
+/// ordinary symlinks, so without `NO_MAGICLINKS`, links to files which
+/// aren't reachable by name, such as pipes and deleted files, fail to
+/// resolve.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/openat2.2.html
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn openat_resolve<Fd: AsFd, P: path::Arg>(
+    dirfd: Fd,
+    path: P,
+    oflags: OFlags,
+    mode: Mode,
+    resolve: ResolveFlags,
+) -> io::Result<OwnedFd> {
+    path.into_with_c_str(|path| _openat_resolve(dirfd.as_fd(), path, oflags, mode, resolve))
+}
+
+// Whether a previous `openat2` call failed with `NOSYS`. We store this in a
+// global to avoid unnecessary syscalls.
+#[cfg(feature = "alloc")]
+static OPENAT2_UNAVAILABLE: AtomicBool = AtomicBool::new(false);
+
+#[cfg(feature = "alloc")]
+fn _openat_resolve(
+    dirfd: BorrowedFd<'_>,
+    path: &CStr,
+    oflags: OFlags,
+    mode: Mode,
+    resolve: ResolveFlags,
+) -> io::Result<OwnedFd> {
+    if !OPENAT2_UNAVAILABLE.load(Ordering::Relaxed) {
+        match backend::fs::syscalls::openat2(dirfd, path, oflags, mode, resolve) {
+            Err(io::Errno::NOSYS) => OPENAT2_UNAVAILABLE.store(true, Ordering::Relaxed),
+            // Some seccomp filters reject syscalls they don't recognize with
+            // `PERM`. If the failure is genuine, the emulation will fail the
+            // same way.
+            Err(io::Errno::PERM) => (),
+            result => return result,
+        }
+    }
+    openat2_emulated(dirfd, path, oflags, mode, resolve)
+}
+
+/// Linux's `MAXSYMLINKS`.
+#[cfg(feature = "alloc")]
+const MAX_SYMLINKS: usize = 40;
+
+/// The inode number of the root of procfs.
+#[cfg(feature = "alloc")]
+const PROC_ROOT_INO: u64 = 1;
+
+/// The flags we use to open the directories along the path.
+#[cfg(feature = "alloc")]
+const DIR_FLAGS: OFlags = OFlags::PATH
+    .union(OFlags::DIRECTORY)
+    .union(OFlags::NOFOLLOW)
+    .union(OFlags::CLOEXEC);
+
+#[cfg(feature = "alloc")]
+fn openat2_emulated(
+    dirfd: BorrowedFd<'_>,
+    path: &CStr,
+    oflags: OFlags,
+    mode: Mode,
+    resolve: ResolveFlags,
+) -> io::Result<OwnedFd> {
+    if resolve.bits() & !ResolveFlags::all().bits() != 0
+        || resolve.contains(ResolveFlags::BENEATH | ResolveFlags::IN_ROOT)
+    {
+        return Err(io::Errno::INVAL);
+    }
+    // `openat2` is stricter than `openat` about the mode.
+    if !mode.is_empty() && !oflags.intersects(OFlags::CREATE) && !oflags.contains(OFlags::TMPFILE) {
+        return Err(io::Errno::INVAL);
+    }
+    if resolve.contains(ResolveFlags::CACHED) {
+        return Err(io::Errno::AGAIN);
+    }
+    if path.to_bytes().is_empty() {
+        return Err(io::Errno::NOENT);
+    }
+
+    let root = openat(dirfd, cstr!("."), DIR_FLAGS, Mode::empty())?;
+    let resolver = Resolver {
+        root: fstat(&root)?,
+        resolve,
+    };
+
+    // The directories we've descended through, starting with the root. We
+    // keep them open so that `..` can't be redirected by concurrent renames.
+    let mut stack = vec![root];
+    let mut remaining = path.to_bytes().to_vec();
+    let mut pos = 0;
+    let mut symlinks = 0;
+    let mut at_start = true;
+
+    loop {
+        if at_start && remaining.first() == Some(&b'/') {
+            if resolve.contains(ResolveFlags::BENEATH) {
+                return Err(io::Errno::XDEV);
+            } else if resolve.contains(ResolveFlags::IN_ROOT) {
+                stack.truncate(1);
+            } else {
+                let fs_root = openat(CWD, cstr!("/"), DIR_FLAGS, Mode::empty())?;
+                resolver.check_dev(&fs_root)?;
+                stack = vec![fs_root];
+            }
+        }
+        at_start = false;
+
+        while remaining.get(pos) == Some(&b'/') {
+            pos += 1;
+        }
+        let cur = stack.last().unwrap();
+        if pos == remaining.len() {
+            // The path ends with a `/`, so it names the current directory.
+            return resolver.open_final(cur, cstr!("."), oflags, mode);
+        }
+        let end = remaining[pos..]
+            .iter()
+            .position(|b| *b == b'/')
+            .map_or(remaining.len(), |len| pos + len);
+        let name = &remaining[pos..end];
+        let last = end == remaining.len();
+
+        let link = match name {
+            b"." => {
+                if last {
+                    return resolver.open_final(cur, cstr!("."), oflags, mode);
+                }
+                None
+            }
+            b".." => {
+                if stack.len() > 1 {
+                    stack.pop();
+                } else if resolve.contains(ResolveFlags::BENEATH) {
+                    return Err(io::Errno::XDEV);
+                } else if !resolve.contains(ResolveFlags::IN_ROOT) {
+                    let parent = openat(&stack[0], cstr!(".."), DIR_FLAGS, Mode::empty())?;
+                    resolver.check_dev(&parent)?;
+                    stack[0] = parent;
+                }
+                if last {
+                    let cur = stack.last().unwrap();
+                    return resolver.open_final(cur, cstr!("."), oflags, mode);
+                }
+                None
+            }
+            _ if last => {
+                // Open the final component with `O_PATH` first, so that we
+                // can check it before opening it with `oflags`, which may
+                // create or truncate it.
+                let path_fd = match openat(
+                    cur,
+                    name,
+                    OFlags::PATH | OFlags::NOFOLLOW | OFlags::CLOEXEC,
+                    Mode::empty(),
+                ) {
+                    Ok(fd) => fd,
+                    // It will be created in `cur`, which we've checked.
+                    Err(io::Errno::NOENT) if oflags.contains(OFlags::CREATE) => {
+                        return resolver.open_final(cur, name, oflags | OFlags::NOFOLLOW, mode);
+                    }
+                    Err(err) => return Err(err),
+                };
+                let stat = fstat(&path_fd)?;
+
+                // `O_CREAT | O_EXCL` doesn't follow symlinks, and fails with
+                // `EEXIST` on them.
+                if FileType::from_raw_mode(stat.st_mode) == FileType::Symlink
+                    && !oflags.contains(OFlags::NOFOLLOW)
+                    && !oflags.contains(OFlags::CREATE | OFlags::EXCL)
+                {
+                    resolver.check_symlink(cur, &mut symlinks)?;
+                    Some(readlinkat(&path_fd, cstr!(""), Vec::new())?)
+                } else {
+                    resolver.check_dev(&path_fd)?;
+                    let fd = openat(cur, name, oflags | OFlags::NOFOLLOW, mode)?;
+
+                    // If the name was replaced after we checked it, fail as
+                    // `openat2` does when it detects a race.
+                    let new_stat = fstat(&fd)?;
+                    if (new_stat.st_dev, new_stat.st_ino) != (stat.st_dev, stat.st_ino) {
+                        return Err(io::Errno::AGAIN);
+                    }
+                    return Ok(fd);
+                }
+            }
+            _ => {
+                let fd = openat(
+                    cur,
+                    name,
+                    OFlags::PATH | OFlags::NOFOLLOW | OFlags::CLOEXEC,
+                    Mode::empty(),
+                )?;
+                let stat = fstat(&fd)?;
+                match FileType::from_raw_mode(stat.st_mode) {
+                    FileType::Directory => {
+                        if stat.st_dev != resolver.root.st_dev
+                            && resolve.contains(ResolveFlags::NO_XDEV)
+                        {
+                            return Err(io::Errno::XDEV);
+                        }
+                        stack.push(fd);
+                        None
+                    }
+                    FileType::Symlink => {
+                        resolver.check_symlink(cur, &mut symlinks)?;
+                        Some(readlinkat(&fd, cstr!(""), Vec::new())?)
+                    }
+                    _ => return Err(io::Errno::NOTDIR),
+                }
+            }
+        };
+
+        pos = end;
+        if let Some(target) = link {
+            // Splice the symlink's target in place of the component.
+            if target.as_bytes().is_empty() {
+                return Err(io::Errno::NOENT);
+            }
+            let mut spliced = target.into_bytes();
+            spliced.extend_from_slice(&remaining[pos..]);
+            remaining = spliced;
+            pos = 0;
+            at_start = true;
+        }
+    }
+}
+
+/// The state of an emulated `openat2` which doesn't change as the path is
+/// resolved.
+#[cfg(feature = "alloc")]
+struct Resolver {
+    root: Stat,
+    resolve: ResolveFlags,
+}
+
+#[cfg(feature = "alloc")]
+impl Resolver {
+    /// Check that `fd` is on the same device as the root, if we need to.
+    fn check_dev(&self, fd: &OwnedFd) -> io::Result<()> {
+        if self.resolve.contains(ResolveFlags::NO_XDEV) && fstat(fd)?.st_dev != self.root.st_dev {
+            return Err(io::Errno::XDEV);
+        }
+        Ok(())
+    }
+
+    /// Check that we can follow a symlink in `dir`, and count it.
+    fn check_symlink(&self, dir: &OwnedFd, symlinks: &mut usize) -> io::Result<()> {
+        if self.resolve.contains(ResolveFlags::NO_SYMLINKS) || *symlinks == MAX_SYMLINKS {
+            return Err(io::Errno::LOOP);
+        }
+        // Magic links only live in procfs, in the per-process directories.
+        if self.resolve.contains(ResolveFlags::NO_MAGICLINKS)
+            && fstatfs(dir)?.f_type == PROC_SUPER_MAGIC
+            && fstat(dir)?.st_ino != PROC_ROOT_INO
+        {
+            return Err(io::Errno::LOOP);
+        }
+        *symlinks += 1;
+        Ok(())
+    }
+
+    /// Open a final component which isn't a symlink.
+    fn open_final<P: path::Arg>(
+        &self,
+        dir: &OwnedFd,
+        name: P,
+        oflags: OFlags,
+        mode: Mode,
+    ) -> io::Result<OwnedFd> {
+        let fd = openat(dir, name, oflags, mode)?;
+        self.check_dev(&fd)?;
+        Ok(fd)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::fs::{mkdirat, statat, symlinkat, AtFlags};
+    use crate::path::Arg;
+
+    /// Open `path` with the emulation, and check that `openat2` agrees, if
+    /// it's available.
+    fn open(
+        dirfd: &OwnedFd,
+        path: &str,
+        oflags: OFlags,
+        resolve: ResolveFlags,
+    ) -> io::Result<Stat> {
+        let mode = if oflags.contains(OFlags::CREATE) {
+            Mode::RUSR | Mode::WUSR
+        } else {
+            Mode::empty()
+        };
+        let emulated = path.into_with_c_str(|path| {
+            openat2_emulated(dirfd.as_fd(), path, oflags | OFlags::CLOEXEC, mode, resolve)
+        });
+        let emulated = emulated.and_then(|fd| fstat(&fd));
+        match openat2(dirfd, path, oflags | OFlags::CLOEXEC, mode, resolve) {
+            Err(io::Errno::NOSYS) => {}
+            Err(err) => assert_eq!(emulated.as_ref().map(|_| ()), Err(&err), "{}", path),
+            Ok(fd) => {
+                let stat = fstat(&fd).unwrap();
+                let emulated = emulated.as_ref().unwrap();
+                assert_eq!(
+                    (stat.st_dev, stat.st_ino),
+                    (emulated.st_dev, emulated.st_ino)
+                );
+            }
+        }
+        emulated
+    }
+
+    fn same_file(a: &Stat, b: &Stat) -> bool {
+        (a.st_dev, a.st_ino) == (b.st_dev, b.st_ino)
+    }
+
+    /// Create `top/root/file`, `top/outside`, and symlinks to them in `root`,
+    /// and return `top` and `root`.
+    fn setup(tmp: &std::path::Path) -> (OwnedFd, OwnedFd) {
+        let top = openat(CWD, tmp, OFlags::RDONLY | OFlags::CLOEXEC, Mode::empty()).unwrap();
+        mkdirat(&top, "root", Mode::RWXU).unwrap();
+        let root = openat(
+            &top,
+            "root",
+            OFlags::RDONLY | OFlags::CLOEXEC,
+            Mode::empty(),
+        )
+        .unwrap();
+        mkdirat(&root, "dir", Mode::RWXU).unwrap();
+        drop(
+            openat(
+                &root,
+                "file",
+                OFlags::CREATE | OFlags::WRONLY | OFlags::CLOEXEC,
+                Mode::RUSR,
+            )
+            .unwrap(),
+        );
+        drop(
+            openat(
+                &top,
+                "outside",
+                OFlags::CREATE | OFlags::WRONLY | OFlags::CLOEXEC,
+                Mode::RUSR,
+            )
+            .unwrap(),
+        );
+        symlinkat("file", &root, "relative").unwrap();
+        symlinkat("../outside", &root, "escape").unwrap();
+        symlinkat("/file", &root, "absolute").unwrap();
+        symlinkat("dir/../file", &root, "dotdot").unwrap();
+        symlinkat("loop", &root, "loop").unwrap();
+        symlinkat("new", &root, "dangling").unwrap();
+        (top, root)
+    }
+
+    #[test]
+    fn test_openat2_emulated_beneath() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (_top, root) = setup(tmp.path());
+        let file = statat(&root, "file", AtFlags::empty()).unwrap();
+        let beneath = ResolveFlags::BENEATH;
+
+        for path in [
+            "file",
+            "dir/../file",
+            "./dir/./../file",
+            "relative",
+            "dotdot",
+        ] {
+            assert!(same_file(
+                &open(&root, path, OFlags::RDONLY, beneath).unwrap(),
+                &file
+            ));
+        }
+        for path in [
+            "..",
+            "../outside",
+            "dir/../../outside",
+            "/file",
+            "escape",
+            "absolute",
+        ] {
+            assert_eq!(
+                open(&root, path, OFlags::RDONLY, beneath).unwrap_err(),
+                io::Errno::XDEV
+            );
+        }
+    }
+
+    #[test]
+    fn test_openat2_emulated_in_root() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (_top, root) = setup(tmp.path());
+        let file = statat(&root, "file", AtFlags::empty()).unwrap();
+        let dir = fstat(&root).unwrap();
+        let in_root = ResolveFlags::IN_ROOT;
+
+        for path in [
+            "../file",
+            "../../dir/../../file",
+            "/file",
+            "absolute",
+            "dotdot",
+        ] {
+            assert!(same_file(
+                &open(&root, path, OFlags::RDONLY, in_root).unwrap(),
+                &file
+            ));
+        }
+        assert!(same_file(
+            &open(&root, "..", OFlags::RDONLY, in_root).unwrap(),
+            &dir
+        ));
+        assert!(same_file(
+            &open(&root, "/", OFlags::RDONLY, in_root).unwrap(),
+            &dir
+        ));
+        // `escape` resolves to `outside` within the root, which doesn't
+        // exist.
+        assert_eq!(
+            open(&root, "escape", OFlags::RDONLY, in_root).unwrap_err(),
+            io::Errno::NOENT
+        );
+    }
+
+    #[test]
+    fn test_openat2_emulated_symlinks() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (_top, root) = setup(tmp.path());
+        let file = statat(&root, "file", AtFlags::empty()).unwrap();
+        let none = ResolveFlags::empty();
+
+        assert!(same_file(
+            &open(&root, "relative", OFlags::RDONLY, none).unwrap(),
+            &file
+        ));
+        assert_eq!(
+            open(&root, "loop", OFlags::RDONLY, none).unwrap_err(),
+            io::Errno::LOOP
+        );
+        assert_eq!(
+            open(&root, "relative", OFlags::RDONLY | OFlags::NOFOLLOW, none).unwrap_err(),
+            io::Errno::LOOP
+        );
+        assert_eq!(
+            open(&root, "relative", OFlags::RDONLY, ResolveFlags::NO_SYMLINKS).unwrap_err(),
+            io::Errno::LOOP
+        );
+        assert_eq!(
+            open(&root, "relative", OFlags::RDONLY | OFlags::DIRECTORY, none).unwrap_err(),
+            io::Errno::NOTDIR
+        );
+
+        // `O_PATH | O_NOFOLLOW` opens the symlink itself, even with
+        // `RESOLVE_NO_SYMLINKS`.
+        let link = statat(&root, "relative", AtFlags::SYMLINK_NOFOLLOW).unwrap();
+        let oflags = OFlags::PATH | OFlags::NOFOLLOW;
+        assert!(same_file(
+            &open(&root, "relative", oflags, ResolveFlags::NO_SYMLINKS).unwrap(),
+            &link
+        ));
+
+        // `O_CREAT` follows a dangling symlink and creates its target, but
+        // `O_CREAT | O_EXCL` doesn't follow it.
+        let oflags = OFlags::CREATE | OFlags::WRONLY;
+        assert_eq!(
+            open(&root, "dangling", oflags | OFlags::EXCL, none).unwrap_err(),
+            io::Errno::EXIST
+        );
+        let new = open(&root, "dangling", oflags, none).unwrap();
+        assert!(same_file(
+            &new,
+            &statat(&root, "new", AtFlags::empty()).unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_openat2_emulated_no_xdev() {
+        let fs_root = openat(CWD, "/", OFlags::RDONLY | OFlags::CLOEXEC, Mode::empty()).unwrap();
+        let proc = match statat(&fs_root, "proc", AtFlags::empty()) {
+            Ok(proc) => proc,
+            Err(io::Errno::NOENT) => return,
+            Err(err) => panic!("{:?}", err),
+        };
+        if proc.st_dev == fstat(&fs_root).unwrap().st_dev {
+            return;
+        }
+        let no_xdev = ResolveFlags::NO_XDEV;
+
+        assert_eq!(
+            open(&fs_root, "proc/self", OFlags::RDONLY, no_xdev).unwrap_err(),
+            io::Errno::XDEV
+        );
+        assert_eq!(
+            open(&fs_root, "proc", OFlags::RDONLY, no_xdev).unwrap_err(),
+            io::Errno::XDEV
+        );
+
+        // The mount point is checked before it's opened with the caller's
+        // flags, so this fails with `EXDEV` rather than `EISDIR`.
+        assert_eq!(
+            open(&fs_root, "proc", OFlags::CREATE | OFlags::WRONLY, no_xdev).unwrap_err(),
+            io::Errno::XDEV
+        );
+
+        let tmp = tempfile::tempdir().unwrap();
+        let (_top, root) = setup(tmp.path());
+        let file = statat(&root, "file", AtFlags::empty()).unwrap();
+        assert!(same_file(
+            &open(&root, "dir/../file", OFlags::RDONLY, no_xdev).unwrap(),
+            &file
+        ));
+    }
+}
@@ -0,0 +1,57 @@
+//! Random names for the temporary files and directories created by
+//! `atomic_write_at` and `mkstempat`.
+
+use crate::backend::rand::types::GetRandomFlags;
+use crate::io::{self, retry_on_intr};
+
+/// The characters that random names are made of.
+const ALPHABET: &[u8; 62] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+/// The largest multiple of `ALPHABET.len()` that a byte can hold. Random
+/// bytes at or above this are discarded, so that every character is equally
+/// likely.
+const LIMIT: u8 = (256 / ALPHABET.len() * ALPHABET.len()) as u8;
+
+/// Replace each byte of `name` with a random letter or digit, using random
+/// bytes from `getrandom`.
+pub(super) fn fill_random_name(name: &mut [u8]) -> io::Result<()> {
+    let mut random = [0_u8; 64];
+    let mut filled = 0;
+    while filled < name.len() {
+        let n = retry_on_intr(|| getrandom(&mut random))?;
+        for &r in random[..n].iter().filter(|r| **r < LIMIT) {
+            if filled == name.len() {
+                break;
+            }
+            name[filled] = ALPHABET[usize::from(r) % ALPHABET.len()];
+            filled += 1;
+        }
+    }
+    Ok(())
+}
+
+#[allow(unsafe_code)]
+fn getrandom(buf: &mut [u8]) -> io::Result<usize> {
+    // SAFETY: `buf` is valid for writes of `buf.len()` bytes.
+    unsafe {
+        crate::backend::rand::syscalls::getrandom(
+            buf.as_mut_ptr(),
+            buf.len(),
+            GetRandomFlags::empty(),
+        )
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_fill_random_name() {
+    let mut name = [0_u8; 2000];
+    fill_random_name(&mut name).unwrap();
+    assert!(name.iter().all(u8::is_ascii_alphanumeric));
+
+    // With 2000 characters, each one is overwhelmingly likely to appear.
+    for c in ALPHABET {
+        assert!(name.contains(c), "{} is missing", *c as char);
+    }
+    fill_random_name(&mut []).unwrap();
+}
@@ -1,5 +1,7 @@
 //! `RawDir` and `RawDirEntry`.
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 use core::fmt;
 use core::mem::{align_of, MaybeUninit};
 use linux_raw_sys::general::linux_dirent64;
@@ -7,7 +9,11 @@ use linux_raw_sys::general::linux_dirent64;
 use crate::backend::fs::syscalls::getdents_uninit;
 use crate::fd::AsFd;
 use crate::ffi::CStr;
+#[cfg(feature = "alloc")]
+use crate::ffi::CString;
 use crate::fs::FileType;
+#[cfg(feature = "alloc")]
+use crate::fs::{seek, SeekFrom};
 use crate::io;
 
 /// A directory iterator implemented with getdents.
@@ -16,7 +22,8 @@ use crate::io;
 /// functionality is necessary, you'll need to drop the current iterator,
 /// resize the buffer, and then re-create the iterator. The iterator is
 /// guaranteed to continue where it left off provided the file descriptor isn't
-/// changed. See the example in [`RawDir::new`].
+/// changed. See the example in [`RawDir::new`], or use [`OwnedRawDir`], which
+/// manages its own buffer.
 pub struct RawDir<'buf, Fd: AsFd> {
     fd: Fd,
     buf: &'buf mut [MaybeUninit<u8>],
@@ -205,23 +212,9 @@ impl<'buf, Fd: AsFd> RawDir<'buf, Fd> {
             }
         }
 
-        let dirent_ptr = self.buf[self.offset..].as_ptr();
-        // SAFETY:
-        // - This data is initialized by the check above.
-        //   - Assumption: the kernel will not give us partial structs.
-        // - Assumption: the kernel uses proper alignment between structs.
-        // - The starting pointer is aligned (performed in RawDir::new)
-        let dirent = unsafe { &*dirent_ptr.cast::<linux_dirent64>() };
-
-        self.offset += usize::from(dirent.d_reclen);
-
-        Some(Ok(RawDirEntry {
-            file_type: dirent.d_type,
-            inode_number: dirent.d_ino.into(),
-            next_entry_cookie: dirent.d_off.into(),
-            // SAFETY: The kernel guarantees a NUL-terminated string.
-            file_name: unsafe { CStr::from_ptr(dirent.d_name.as_ptr().cast()) },
-        }))
+        // SAFETY: The buffer is aligned (performed in `RawDir::new`), and
+        // `initialized` and `offset` come from `getdents_uninit`.
+        Some(Ok(unsafe { parse_entry(self.buf, &mut self.offset) }))
     }
 
     /// Returns true if the internal buffer is empty and will be refilled when
@@ -232,3 +225,262 @@ impl<'buf, Fd: AsFd> RawDir<'buf, Fd> {
         self.offset >= self.initialized
     }
 }
+
+/// Parse the directory entry at `*offset` in `buf`, and advance `*offset` to
+/// the next entry.
+///
+/// # Safety
+///
+/// `buf` must be aligned for `linux_dirent64`, and `*offset` must be the
+/// offset of an entry within the part of `buf` initialized by
+/// `getdents_uninit`.
+#[allow(unsafe_code)]
+unsafe fn parse_entry<'a>(buf: &'a [MaybeUninit<u8>], offset: &mut usize) -> RawDirEntry<'a> {
+    let dirent_ptr = buf[*offset..].as_ptr();
+    // SAFETY:
+    // - This data is initialized, per our caller.
+    //   - Assumption: the kernel will not give us partial structs.
+    // - Assumption: the kernel uses proper alignment between structs.
+    // - The starting pointer is aligned, per our caller.
+    let dirent = &*dirent_ptr.cast::<linux_dirent64>();
+
+    *offset += usize::from(dirent.d_reclen);
+
+    RawDirEntry {
+        file_type: dirent.d_type,
+        inode_number: dirent.d_ino.into(),
+        next_entry_cookie: dirent.d_off.into(),
+        // SAFETY: The kernel guarantees a NUL-terminated string.
+        file_name: CStr::from_ptr(dirent.d_name.as_ptr().cast()),
+    }
+}
+
+/// A directory iterator implemented with getdents, which owns its buffer and
+/// grows it as needed, up to 4 MiB. If getdents still fails with
+/// [`io::Errno::INVAL`] at that size, the error is returned.
+///
+/// Unlike [`RawDir`], this implements [`Iterator`], yielding
+/// [`OwnedRawDirEntry`]s. To avoid copying each file name, use
+/// [`OwnedRawDir::read`], which returns entries which borrow from the
+/// buffer.
+///
+/// This only depends on `alloc`, so it's usable in `no_std` environments.
+#[cfg(feature = "alloc")]
+pub struct OwnedRawDir<Fd: AsFd> {
+    fd: Fd,
+    buf: Vec<MaybeUninit<u8>>,
+    /// The offset of the first `linux_dirent64`-aligned byte in `buf`.
+    start: usize,
+    initialized: usize,
+    offset: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<Fd: AsFd> OwnedRawDir<Fd> {
+    /// The initial size of the buffer, which is doubled whenever an entry
+    /// doesn't fit, up to `MAX_CAPACITY`.
+    const INITIAL_CAPACITY: usize = 8192;
+
+    /// The size beyond which the buffer isn't grown. Entries are at most a
+    /// few hundred bytes, so an `EINVAL` with a buffer this big isn't caused
+    /// by an entry not fitting.
+    const MAX_CAPACITY: usize = 4 * 1024 * 1024;
+
+    /// Create a new iterator from the given file descriptor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rustix::fs::{CWD, Mode, OFlags, openat, OwnedRawDir};
+    ///
+    /// let fd = openat(
+    ///     CWD,
+    ///     ".",
+    ///     OFlags::RDONLY | OFlags::DIRECTORY | OFlags::CLOEXEC,
+    ///     Mode::empty(),
+    /// )
+    /// .unwrap();
+    ///
+    /// for entry in OwnedRawDir::new(fd) {
+    ///     let entry = entry.unwrap();
+    ///     dbg!(&entry);
+    /// }
+    /// ```
+    #[inline]
+    pub fn new(fd: Fd) -> Self {
+        Self::with_capacity(fd, Self::INITIAL_CAPACITY)
+    }
+
+    /// Create a new iterator from the given file descriptor, with an initial
+    /// buffer of `capacity` bytes.
+    pub fn with_capacity(fd: Fd, capacity: usize) -> Self {
+        let mut dir = Self {
+            fd,
+            buf: Vec::new(),
+            start: 0,
+            initialized: 0,
+            offset: 0,
+        };
+        dir.resize(capacity);
+        dir
+    }
+
+    /// Identical to [`Iterator::next`] except that the entry borrows from
+    /// self, so it doesn't need to copy the file name.
+    #[allow(unsafe_code)]
+    pub fn read(&mut self) -> Option<io::Result<RawDirEntry<'_>>> {
+        if self.is_buffer_empty() {
+            loop {
+                match getdents_uninit(self.fd.as_fd(), &mut self.buf[self.start..]) {
+                    Ok(0) => return None,
+                    Ok(bytes_read) => {
+                        self.initialized = bytes_read;
+                        self.offset = 0;
+                        break;
+                    }
+                    // The next entry doesn't fit in the buffer.
+                    Err(io::Errno::INVAL) if self.capacity() < Self::MAX_CAPACITY => {
+                        self.resize(self.capacity() * 2)
+                    }
+                    Err(e) => return Some(Err(e)),
+                }
+            }
+        }
+
+        // SAFETY: The buffer is aligned at `start`, and `initialized` and
+        // `offset` come from `getdents_uninit`.
+        Some(Ok(unsafe {
+            parse_entry(&self.buf[self.start..], &mut self.offset)
+        }))
+    }
+
+    /// Continue reading from the entry identified by `cookie`, as returned
+    /// by [`RawDirEntry::next_entry_cookie`] or
+    /// [`OwnedRawDirEntry::next_entry_cookie`].
+    #[doc(alias = "seekdir")]
+    pub fn seek(&mut self, cookie: u64) -> io::Result<()> {
+        self.initialized = 0;
+        self.offset = 0;
+        seek(&self.fd, SeekFrom::Start(cookie)).map(|_| ())
+    }
+
+    /// Continue reading from the beginning of the directory.
+    #[doc(alias = "rewinddir")]
+    #[inline]
+    pub fn rewind(&mut self) -> io::Result<()> {
+        self.seek(0)
+    }
+
+    /// Returns true if the internal buffer is empty and will be refilled when
+    /// calling [`read`] or [`next`].
+    ///
+    /// [`read`]: Self::read
+    /// [`next`]: Iterator::next
+    #[inline]
+    pub fn is_buffer_empty(&self) -> bool {
+        self.offset >= self.initialized
+    }
+
+    /// Returns the file descriptor this iterator reads from.
+    #[inline]
+    pub fn fd(&self) -> &Fd {
+        &self.fd
+    }
+
+    /// Consumes this iterator, returning the file descriptor it reads from.
+    #[inline]
+    pub fn into_fd(self) -> Fd {
+        self.fd
+    }
+
+    /// The number of usable bytes in the buffer.
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.buf.len() - self.start
+    }
+
+    /// Replace the buffer with an empty one of at least `capacity` usable
+    /// bytes. This must only be called when the buffer is empty.
+    fn resize(&mut self, capacity: usize) {
+        debug_assert!(self.is_buffer_empty());
+        let capacity = capacity.max(core::mem::size_of::<linux_dirent64>());
+        self.buf = Vec::with_capacity(capacity + align_of::<linux_dirent64>());
+        self.buf.resize(
+            capacity + align_of::<linux_dirent64>(),
+            MaybeUninit::uninit(),
+        );
+        self.start = self.buf.as_ptr().align_offset(align_of::<linux_dirent64>());
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Fd: AsFd> Iterator for OwnedRawDir<Fd> {
+    type Item = io::Result<OwnedRawDirEntry>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read()
+            .map(|entry| entry.map(|entry| entry.to_owned_entry()))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Fd: AsFd + fmt::Debug> fmt::Debug for OwnedRawDir<Fd> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedRawDir").field("fd", &self.fd).finish()
+    }
+}
+
+/// An owned raw directory entry, as yielded by [`OwnedRawDir`].
+///
+/// Unlike the std version, this may represent the `.` or `..` entries.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct OwnedRawDirEntry {
+    file_name: CString,
+    file_type: u8,
+    inode_number: u64,
+    next_entry_cookie: i64,
+}
+
+#[cfg(feature = "alloc")]
+impl OwnedRawDirEntry {
+    /// Returns the file name of this directory entry.
+    #[inline]
+    pub fn file_name(&self) -> &CStr {
+        &self.file_name
+    }
+
+    /// Returns the type of this directory entry.
+    #[inline]
+    pub fn file_type(&self) -> FileType {
+        FileType::from_dirent_d_type(self.file_type)
+    }
+
+    /// Returns the inode number of this directory entry.
+    #[inline]
+    #[doc(alias = "inode_number")]
+    pub fn ino(&self) -> u64 {
+        self.inode_number
+    }
+
+    /// Returns the seek cookie to the next directory entry.
+    #[inline]
+    #[doc(alias = "off")]
+    pub fn next_entry_cookie(&self) -> u64 {
+        self.next_entry_cookie as u64
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> RawDirEntry<'a> {
+    /// Copies this entry into an [`OwnedRawDirEntry`].
+    pub fn to_owned_entry(&self) -> OwnedRawDirEntry {
+        OwnedRawDirEntry {
+            file_name: self.file_name.into(),
+            file_type: self.file_type,
+            inode_number: self.inode_number,
+            next_entry_cookie: self.next_entry_cookie,
+        }
+    }
+}
@@ -0,0 +1,386 @@
+//! `statmount` and `listmount`, which describe the mounts in a mount
+//! namespace directly, without parsing `/proc/self/mountinfo`.
+
+use crate::ffi::CStr;
+use crate::fs::{MountAttrFlags, MountPropagationFlags};
+use crate::{backend, io};
+use alloc::vec;
+use alloc::vec::Vec;
+use bitflags::bitflags;
+use core::fmt;
+
+/// `LSMT_ROOT`—The mount ID to pass to [`listmount`] to list the mounts
+/// under the root of the mount namespace.
+pub const LSMT_ROOT: u64 = u64::MAX;
+
+bitflags! {
+    /// `STATMOUNT_*` constants for use with [`statmount`].
+    #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+    pub struct StatmountFlags: u64 {
+        /// `STATMOUNT_SB_BASIC`
+        const SB_BASIC = 0x0000_0001;
+        /// `STATMOUNT_MNT_BASIC`
+        const MNT_BASIC = 0x0000_0002;
+        /// `STATMOUNT_PROPAGATE_FROM`
+        const PROPAGATE_FROM = 0x0000_0004;
+        /// `STATMOUNT_MNT_ROOT`
+        const MNT_ROOT = 0x0000_0008;
+        /// `STATMOUNT_MNT_POINT`
+        const MNT_POINT = 0x0000_0010;
+        /// `STATMOUNT_FS_TYPE`
+        const FS_TYPE = 0x0000_0020;
+        /// `STATMOUNT_MNT_NS_ID` (since Linux 6.11)
+        const MNT_NS_ID = 0x0000_0040;
+        /// `STATMOUNT_MNT_OPTS` (since Linux 6.11)
+        const MNT_OPTS = 0x0000_0080;
+        /// `STATMOUNT_FS_SUBTYPE` (since Linux 6.13)
+        const FS_SUBTYPE = 0x0000_0100;
+        /// `STATMOUNT_SB_SOURCE` (since Linux 6.13)
+        const SB_SOURCE = 0x0000_0200;
+        /// `STATMOUNT_OPT_ARRAY` (since Linux 6.13)
+        const OPT_ARRAY = 0x0000_0400;
+        /// `STATMOUNT_OPT_SEC_ARRAY` (since Linux 6.13)
+        const OPT_SEC_ARRAY = 0x0000_0800;
+        /// `STATMOUNT_SUPPORTED_MASK` (since Linux 6.15)
+        const SUPPORTED_MASK = 0x0000_1000;
+    }
+}
+
+bitflags! {
+    /// `LISTMOUNT_*` constants for use with [`listmount`].
+    #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+    pub struct ListmountFlags: u32 {
+        /// `LISTMOUNT_REVERSE` (since Linux 6.14)
+        const REVERSE = 0x0000_0001;
+    }
+}
+
+/// `struct mnt_id_req`, in its original 24-byte form, which all kernels with
+/// `statmount` and `listmount` accept.
+#[repr(C)]
+pub(crate) struct MntIdReq {
+    size: u32,
+    spare: u32,
+    mnt_id: u64,
+    param: u64,
+}
+
+impl MntIdReq {
+    fn new(mnt_id: u64, param: u64) -> Self {
+        Self {
+            size: core::mem::size_of::<Self>() as u32,
+            spare: 0,
+            mnt_id,
+            param,
+        }
+    }
+}
+
+/// The size of the fixed part of `struct statmount`, which the string offsets
+/// are relative to the end of.
+const STATMOUNT_SIZE: usize = 512;
+
+/// `listmount(req, mnt_ids, flags)`—List the IDs of the mounts under the
+/// mount `mnt_id`.
+///
+/// This returns the number of IDs written to `mnt_ids`. The IDs are the
+/// 64-bit unique mount IDs, in ascending order, or descending order with
+/// [`ListmountFlags::REVERSE`], starting after `last_mnt_id`. To list all of
+/// the mounts, pass [`LSMT_ROOT`] as `mnt_id` and 0 as `last_mnt_id`, and
+/// then call this again with the last ID returned until it returns 0.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/listmount.2.html
+#[inline]
+pub fn listmount(
+    mnt_id: u64,
+    last_mnt_id: u64,
+    mnt_ids: &mut [u64],
+    flags: ListmountFlags,
+) -> io::Result<usize> {
+    let req = MntIdReq::new(mnt_id, last_mnt_id);
+    backend::fs::syscalls::listmount(&req, mnt_ids, flags.bits())
+}
+
+/// `statmount(req, buf, bufsize, 0)`—Describe the mount `mnt_id`.
+///
+/// `mnt_id` is a 64-bit unique mount ID, as returned by [`listmount`].
+/// `mask` selects which information to return; [`Statmount::mask`] reports
+/// which of it the kernel was able to provide. The buffer is grown until the
+/// strings fit.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/statmount.2.html
+pub fn statmount(mnt_id: u64, mask: StatmountFlags) -> io::Result<Statmount> {
+    let req = MntIdReq::new(mnt_id, mask.bits());
+    let mut buf = vec![0_u8; STATMOUNT_SIZE * 2];
+    loop {
+        match backend::fs::syscalls::statmount(&req, &mut buf) {
+            Ok(()) => break,
+            Err(io::Errno::OVERFLOW) => {
+                let len = buf.len() * 2;
+                buf.resize(len, 0);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    let size = u32_at(&buf, 0) as usize;
+    buf.truncate(size.clamp(STATMOUNT_SIZE, buf.len()));
+    Ok(Statmount { buf })
+}
+
+/// The description of a mount returned by [`statmount`].
+///
+/// The accessors for information that wasn't requested, or that the kernel
+/// didn't provide, return 0 or `None`.
+#[derive(Clone)]
+pub struct Statmount {
+    buf: Vec<u8>,
+}
+
+impl Statmount {
+    /// Return which information the kernel provided.
+    #[inline]
+    pub fn mask(&self) -> StatmountFlags {
+        StatmountFlags::from_bits_retain(self.u64_at(8))
+    }
+
+    /// Return the major number of the filesystem's device.
+    #[inline]
+    pub fn sb_dev_major(&self) -> u32 {
+        self.u32_at(16)
+    }
+
+    /// Return the minor number of the filesystem's device.
+    #[inline]
+    pub fn sb_dev_minor(&self) -> u32 {
+        self.u32_at(20)
+    }
+
+    /// Return the filesystem's magic number, such as
+    /// [`PROC_SUPER_MAGIC`](crate::fs::PROC_SUPER_MAGIC).
+    #[inline]
+    pub fn sb_magic(&self) -> u64 {
+        self.u64_at(24)
+    }
+
+    /// Return the filesystem's `SB_RDONLY`, `SB_SYNCHRONOUS`, `SB_DIRSYNC`,
+    /// and `SB_LAZYTIME` flags.
+    #[inline]
+    pub fn sb_flags(&self) -> u32 {
+        self.u32_at(32)
+    }
+
+    /// Return the name of the filesystem type, such as `ext4`.
+    #[inline]
+    pub fn fs_type(&self) -> Option<&CStr> {
+        self.str_at(StatmountFlags::FS_TYPE, 36)
+    }
+
+    /// Return the unique ID of the mount.
+    #[inline]
+    pub fn mnt_id(&self) -> u64 {
+        self.u64_at(40)
+    }
+
+    /// Return the unique ID of the mount's parent, or of the mount itself if
+    /// it's the root of the namespace.
+    #[inline]
+    pub fn mnt_parent_id(&self) -> u64 {
+        self.u64_at(48)
+    }
+
+    /// Return the ID of the mount used in `/proc/self/mountinfo`, which may
+    /// be reused after the mount is unmounted.
+    #[inline]
+    pub fn mnt_id_old(&self) -> u32 {
+        self.u32_at(56)
+    }
+
+    /// Return the ID of the mount's parent used in `/proc/self/mountinfo`.
+    #[inline]
+    pub fn mnt_parent_id_old(&self) -> u32 {
+        self.u32_at(60)
+    }
+
+    /// Return the mount's attributes.
+    #[inline]
+    pub fn mnt_attr(&self) -> MountAttrFlags {
+        MountAttrFlags::from_bits_retain(self.u64_at(64) as _)
+    }
+
+    /// Return the mount's propagation type.
+    #[inline]
+    pub fn mnt_propagation(&self) -> MountPropagationFlags {
+        MountPropagationFlags::from_bits_retain(self.u64_at(72) as _)
+    }
+
+    /// Return the ID of the mount's shared peer group.
+    #[inline]
+    pub fn mnt_peer_group(&self) -> u64 {
+        self.u64_at(80)
+    }
+
+    /// Return the ID of the peer group the mount receives propagation from.
+    #[inline]
+    pub fn mnt_master(&self) -> u64 {
+        self.u64_at(88)
+    }
+
+    /// Return the ID of the closest dominant peer group the mount receives
+    /// propagation from in the current namespace.
+    #[inline]
+    pub fn propagate_from(&self) -> u64 {
+        self.u64_at(96)
+    }
+
+    /// Return the path of the mount's root within its filesystem.
+    #[inline]
+    pub fn mnt_root(&self) -> Option<&CStr> {
+        self.str_at(StatmountFlags::MNT_ROOT, 104)
+    }
+
+    /// Return the path of the mount point, relative to the process's root.
+    #[inline]
+    pub fn mnt_point(&self) -> Option<&CStr> {
+        self.str_at(StatmountFlags::MNT_POINT, 108)
+    }
+
+    /// Return the ID of the mount namespace the mount is in.
+    #[inline]
+    pub fn mnt_ns_id(&self) -> u64 {
+        self.u64_at(112)
+    }
+
+    /// Return the filesystem's own mount options, separated by commas.
+    #[inline]
+    pub fn mnt_opts(&self) -> Option<&CStr> {
+        self.str_at(StatmountFlags::MNT_OPTS, 4)
+    }
+
+    /// Return the subtype of the filesystem type, such as `sshfs` for a
+    /// `fuse` filesystem.
+    #[inline]
+    pub fn fs_subtype(&self) -> Option<&CStr> {
+        self.str_at(StatmountFlags::FS_SUBTYPE, 120)
+    }
+
+    /// Return the source of the mount, such as a device path.
+    #[inline]
+    pub fn sb_source(&self) -> Option<&CStr> {
+        self.str_at(StatmountFlags::SB_SOURCE, 124)
+    }
+
+    /// Return an iterator over the filesystem's own mount options.
+    #[inline]
+    pub fn opt_array(&self) -> StatmountOpts<'_> {
+        self.array_at(StatmountFlags::OPT_ARRAY, 128)
+    }
+
+    /// Return an iterator over the filesystem's security mount options.
+    #[inline]
+    pub fn opt_sec_array(&self) -> StatmountOpts<'_> {
+        self.array_at(StatmountFlags::OPT_SEC_ARRAY, 136)
+    }
+
+    /// Return which information the kernel supports, if
+    /// [`StatmountFlags::SUPPORTED_MASK`] was requested.
+    #[inline]
+    pub fn supported_mask(&self) -> StatmountFlags {
+        StatmountFlags::from_bits_retain(self.u64_at(144))
+    }
+
+    fn u32_at(&self, offset: usize) -> u32 {
+        u32_at(&self.buf, offset)
+    }
+
+    fn u64_at(&self, offset: usize) -> u64 {
+        let mut bytes = [0_u8; 8];
+        bytes.copy_from_slice(&self.buf[offset..offset + 8]);
+        u64::from_ne_bytes(bytes)
+    }
+
+    /// Return the string whose offset is the `u32` at `offset`, if `flag` is
+    /// in the mask.
+    fn str_at(&self, flag: StatmountFlags, offset: usize) -> Option<&CStr> {
+        if !self.mask().contains(flag) {
+            return None;
+        }
+        let strings = &self.buf[STATMOUNT_SIZE..];
+        let start = (self.u32_at(offset) as usize).min(strings.len());
+        nul_terminated(&strings[start..])
+    }
+
+    /// Return the array of strings whose count and offset are the `u32`s at
+    /// `offset`, if `flag` is in the mask.
+    fn array_at(&self, flag: StatmountFlags, offset: usize) -> StatmountOpts<'_> {
+        if !self.mask().contains(flag) {
+            return StatmountOpts {
+                strings: &[],
+                remaining: 0,
+            };
+        }
+        let strings = &self.buf[STATMOUNT_SIZE..];
+        let start = (self.u32_at(offset + 4) as usize).min(strings.len());
+        StatmountOpts {
+            strings: &strings[start..],
+            remaining: self.u32_at(offset),
+        }
+    }
+}
+
+impl fmt::Debug for Statmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Statmount")
+            .field("mask", &self.mask())
+            .field("mnt_id", &self.mnt_id())
+            .field("mnt_parent_id", &self.mnt_parent_id())
+            .field("fs_type", &self.fs_type())
+            .field("mnt_root", &self.mnt_root())
+            .field("mnt_point", &self.mnt_point())
+            .finish_non_exhaustive()
+    }
+}
+
+/// An iterator over an array of options in a [`Statmount`].
+#[derive(Clone, Debug)]
+pub struct StatmountOpts<'a> {
+    strings: &'a [u8],
+    remaining: u32,
+}
+
+impl<'a> Iterator for StatmountOpts<'a> {
+    type Item = &'a CStr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let opt = match nul_terminated(self.strings) {
+            Some(opt) => opt,
+            None => {
+                self.remaining = 0;
+                return None;
+            }
+        };
+        self.remaining -= 1;
+        self.strings = &self.strings[opt.to_bytes_with_nul().len()..];
+        Some(opt)
+    }
+}
+
+fn u32_at(buf: &[u8], offset: usize) -> u32 {
+    let mut bytes = [0_u8; 4];
+    bytes.copy_from_slice(&buf[offset..offset + 4]);
+    u32::from_ne_bytes(bytes)
+}
+
+/// Return the NUL-terminated string at the start of `bytes`, if there is one.
+fn nul_terminated(bytes: &[u8]) -> Option<&CStr> {
+    let nul = bytes.iter().position(|b| *b == b'\0')?;
+    Some(CStr::from_bytes_with_nul(&bytes[..=nul]).unwrap())
+}
@@ -1,10 +1,286 @@
 //! Linux `statx`.
 
-use crate::fd::AsFd;
-use crate::fs::AtFlags;
+use crate::fd::{AsFd, BorrowedFd};
+use crate::ffi::CStr;
+use crate::fs::{makedev, AtFlags, Dev, FileType, Gid, Mode, Stat, Uid};
 use crate::{backend, io, path};
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::SystemTime;
 
-pub use backend::fs::types::{Statx, StatxFlags, StatxTimestamp};
+pub use backend::fs::types::{StatxAttributes, StatxFlags};
+
+/// `struct statx` for use with [`statx`].
+///
+/// The fields are filled in according to `stx_mask`; the accessor methods
+/// check the mask and return `None` for fields the kernel didn't fill in.
+///
+/// This has the same fields, including the padding fields, and layout as
+/// `linux_raw_sys::general::statx`, which it was previously an alias of.
+// Neither glibc nor the other libcs declare a `struct statx` with the newer
+// fields, so we declare it ourselves, for both backends.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+#[allow(missing_docs)]
+pub struct Statx {
+    pub stx_mask: u32,
+    pub stx_blksize: u32,
+    pub stx_attributes: u64,
+    pub stx_nlink: u32,
+    pub stx_uid: u32,
+    pub stx_gid: u32,
+    pub stx_mode: u16,
+    #[doc(hidden)]
+    pub __spare0: [u16; 1],
+    pub stx_ino: u64,
+    pub stx_size: u64,
+    pub stx_blocks: u64,
+    pub stx_attributes_mask: u64,
+    pub stx_atime: StatxTimestamp,
+    pub stx_btime: StatxTimestamp,
+    pub stx_ctime: StatxTimestamp,
+    pub stx_mtime: StatxTimestamp,
+    pub stx_rdev_major: u32,
+    pub stx_rdev_minor: u32,
+    pub stx_dev_major: u32,
+    pub stx_dev_minor: u32,
+    pub stx_mnt_id: u64,
+    pub stx_dio_mem_align: u32,
+    pub stx_dio_offset_align: u32,
+    #[doc(hidden)]
+    pub __spare3: [u64; 12],
+}
+
+/// `struct statx_timestamp` for use with [`Statx`].
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[allow(missing_docs)]
+pub struct StatxTimestamp {
+    pub tv_sec: i64,
+    pub tv_nsec: u32,
+    #[doc(hidden)]
+    pub __reserved: i32,
+}
+
+impl StatxTimestamp {
+    /// Construct a new `StatxTimestamp`.
+    #[inline]
+    pub const fn new(tv_sec: i64, tv_nsec: u32) -> Self {
+        Self {
+            tv_sec,
+            tv_nsec,
+            __reserved: 0,
+        }
+    }
+}
+
+/// Convert a timestamp into a `Duration` since the Unix epoch.
+///
+/// This fails with [`io::Errno::OVERFLOW`] if the timestamp is before the
+/// epoch.
+impl TryFrom<StatxTimestamp> for Duration {
+    type Error = io::Errno;
+
+    #[inline]
+    fn try_from(timestamp: StatxTimestamp) -> Result<Self, Self::Error> {
+        let secs = timestamp
+            .tv_sec
+            .try_into()
+            .map_err(|_| io::Errno::OVERFLOW)?;
+        Ok(Duration::new(secs, timestamp.tv_nsec))
+    }
+}
+
+/// Convert a timestamp into a `SystemTime`.
+///
+/// This fails with [`io::Errno::OVERFLOW`] if the timestamp is outside the
+/// range of `SystemTime`.
+#[cfg(feature = "std")]
+impl TryFrom<StatxTimestamp> for SystemTime {
+    type Error = io::Errno;
+
+    #[inline]
+    fn try_from(timestamp: StatxTimestamp) -> Result<Self, Self::Error> {
+        let secs = Duration::from_secs(timestamp.tv_sec.unsigned_abs());
+        let nsecs = Duration::from_nanos(timestamp.tv_nsec.into());
+        let time = if timestamp.tv_sec >= 0 {
+            SystemTime::UNIX_EPOCH.checked_add(secs)
+        } else {
+            SystemTime::UNIX_EPOCH.checked_sub(secs)
+        };
+        time.and_then(|time| time.checked_add(nsecs))
+            .ok_or(io::Errno::OVERFLOW)
+    }
+}
+
+impl Statx {
+    /// Return the set of fields that were filled in.
+    #[inline]
+    pub const fn mask(&self) -> StatxFlags {
+        StatxFlags::from_bits_retain(self.stx_mask)
+    }
+
+    /// Test whether all of the fields in `mask` were filled in.
+    #[inline]
+    pub const fn has(&self, mask: StatxFlags) -> bool {
+        self.stx_mask & mask.bits() == mask.bits()
+    }
+
+    /// `stx_blksize`—The preferred block size for I/O.
+    #[inline]
+    pub const fn blksize(&self) -> u32 {
+        self.stx_blksize
+    }
+
+    /// `stx_attributes & stx_attributes_mask`—The file attributes which the
+    /// filesystem supports and which are set.
+    #[inline]
+    pub const fn attributes(&self) -> StatxAttributes {
+        StatxAttributes::from_bits_retain(self.stx_attributes & self.stx_attributes_mask)
+    }
+
+    /// `stx_attributes_mask`—The file attributes which the filesystem
+    /// supports.
+    #[inline]
+    pub const fn attributes_mask(&self) -> StatxAttributes {
+        StatxAttributes::from_bits_retain(self.stx_attributes_mask)
+    }
+
+    /// Test whether all of the attributes in `attributes` are set, or `None`
+    /// if the filesystem doesn't support reporting all of them.
+    ///
+    /// For example, `statx.attribute(StatxAttributes::IMMUTABLE)`.
+    #[inline]
+    pub const fn attribute(&self, attributes: StatxAttributes) -> Option<bool> {
+        let bits = attributes.bits();
+        if self.stx_attributes_mask & bits != bits {
+            return None;
+        }
+        Some(self.stx_attributes & bits == bits)
+    }
+
+    /// `stx_mode & S_IFMT`—The type of the file, if `STATX_TYPE` is set.
+    #[inline]
+    pub fn file_type(&self) -> Option<FileType> {
+        self.has(StatxFlags::TYPE)
+            .then(|| FileType::from_raw_mode(self.stx_mode.into()))
+    }
+
+    /// `stx_mode & !S_IFMT`—The permission bits of the file, if `STATX_MODE`
+    /// is set.
+    #[inline]
+    pub fn mode(&self) -> Option<Mode> {
+        self.has(StatxFlags::MODE)
+            .then(|| Mode::from_raw_mode(self.stx_mode.into()))
+    }
+
+    /// `stx_nlink`, if `STATX_NLINK` is set.
+    #[inline]
+    pub fn nlink(&self) -> Option<u32> {
+        self.has(StatxFlags::NLINK).then_some(self.stx_nlink)
+    }
+
+    /// `stx_uid`, if `STATX_UID` is set.
+    #[inline]
+    #[allow(unsafe_code)]
+    pub fn uid(&self) -> Option<Uid> {
+        // SAFETY: The kernel gave us this uid.
+        self.has(StatxFlags::UID)
+            .then(|| unsafe { Uid::from_raw(self.stx_uid) })
+    }
+
+    /// `stx_gid`, if `STATX_GID` is set.
+    #[inline]
+    #[allow(unsafe_code)]
+    pub fn gid(&self) -> Option<Gid> {
+        // SAFETY: The kernel gave us this gid.
+        self.has(StatxFlags::GID)
+            .then(|| unsafe { Gid::from_raw(self.stx_gid) })
+    }
+
+    /// `stx_atime`, if `STATX_ATIME` is set.
+    #[inline]
+    pub fn atime(&self) -> Option<StatxTimestamp> {
+        self.has(StatxFlags::ATIME).then_some(self.stx_atime)
+    }
+
+    /// `stx_btime`—The creation time, if `STATX_BTIME` is set.
+    #[inline]
+    pub fn btime(&self) -> Option<StatxTimestamp> {
+        self.has(StatxFlags::BTIME).then_some(self.stx_btime)
+    }
+
+    /// `stx_ctime`, if `STATX_CTIME` is set.
+    #[inline]
+    pub fn ctime(&self) -> Option<StatxTimestamp> {
+        self.has(StatxFlags::CTIME).then_some(self.stx_ctime)
+    }
+
+    /// `stx_mtime`, if `STATX_MTIME` is set.
+    #[inline]
+    pub fn mtime(&self) -> Option<StatxTimestamp> {
+        self.has(StatxFlags::MTIME).then_some(self.stx_mtime)
+    }
+
+    /// `stx_ino`, if `STATX_INO` is set.
+    #[inline]
+    pub fn ino(&self) -> Option<u64> {
+        self.has(StatxFlags::INO).then_some(self.stx_ino)
+    }
+
+    /// `stx_size`, if `STATX_SIZE` is set.
+    #[inline]
+    pub fn size(&self) -> Option<u64> {
+        self.has(StatxFlags::SIZE).then_some(self.stx_size)
+    }
+
+    /// `stx_blocks`—The number of 512-byte blocks allocated, if
+    /// `STATX_BLOCKS` is set.
+    #[inline]
+    pub fn blocks(&self) -> Option<u64> {
+        self.has(StatxFlags::BLOCKS).then_some(self.stx_blocks)
+    }
+
+    /// `makedev(stx_dev_major, stx_dev_minor)`—The device containing the
+    /// file.
+    #[inline]
+    pub fn dev(&self) -> Dev {
+        makedev(self.stx_dev_major, self.stx_dev_minor)
+    }
+
+    /// `makedev(stx_rdev_major, stx_rdev_minor)`—The device this file
+    /// represents, if it's a device file.
+    #[inline]
+    pub fn rdev(&self) -> Dev {
+        makedev(self.stx_rdev_major, self.stx_rdev_minor)
+    }
+
+    /// `stx_mnt_id`, if `STATX_MNT_ID` is set.
+    #[inline]
+    pub fn mnt_id(&self) -> Option<u64> {
+        self.has(StatxFlags::MNT_ID).then_some(self.stx_mnt_id)
+    }
+
+    /// `stx_dio_mem_align`—The alignment required for user memory buffers
+    /// for direct I/O, if `STATX_DIOALIGN` is set.
+    ///
+    /// This is 0 if direct I/O isn't supported on the file.
+    #[inline]
+    pub fn dio_mem_align(&self) -> Option<u32> {
+        self.has(StatxFlags::DIOALIGN)
+            .then_some(self.stx_dio_mem_align)
+    }
+
+    /// `stx_dio_offset_align`—The alignment required for file offsets and
+    /// I/O segment lengths for direct I/O, if `STATX_DIOALIGN` is set.
+    ///
+    /// This is 0 if direct I/O isn't supported on the file.
+    #[inline]
+    pub fn dio_offset_align(&self) -> Option<u32> {
+        self.has(StatxFlags::DIOALIGN)
+            .then_some(self.stx_dio_offset_align)
+    }
+}
 
 #[cfg(feature = "linux_4_11")]
 use backend::fs::syscalls::statx as _statx;
@@ -32,6 +308,94 @@ pub fn statx<P: path::Arg, Fd: AsFd>(
     path.into_with_c_str(|path| _statx(dirfd.as_fd(), path, flags, mask))
 }
 
+/// `statx(dirfd, path, flags, mask, statxbuf)`, falling back to
+/// `fstatat(dirfd, path, statbuf, flags)` if `statx` isn't available.
+///
+/// If `statx` isn't available, such as on Linux before 4.11, or if it's
+/// denied by a seccomp filter which fails it with `EPERM`, this calls
+/// [`statat`] and fills in the [`StatxFlags::BASIC_STATS`] fields from its
+/// result. `stx_mask` reports which fields were filled in, as it does for
+/// `statx`, so the accessor methods on [`Statx`] work the same either way.
+/// The `AT_STATX_*` sync flags are ignored by the fallback.
+///
+/// # References
+///  - [Linux `statx`]
+///  - [Linux `fstatat`]
+///
+/// [`statat`]: crate::fs::statat
+/// [Linux `statx`]: https://man7.org/linux/man-pages/man2/statx.2.html
+/// [Linux `fstatat`]: https://man7.org/linux/man-pages/man2/fstatat.2.html
+#[inline]
+pub fn statx_or_statat<P: path::Arg, Fd: AsFd>(
+    dirfd: Fd,
+    path: P,
+    flags: AtFlags,
+    mask: StatxFlags,
+) -> io::Result<Statx> {
+    path.into_with_c_str(|path| _statx_or_statat(dirfd.as_fd(), path, flags, mask))
+}
+
+fn _statx_or_statat(
+    dirfd: BorrowedFd<'_>,
+    path: &CStr,
+    flags: AtFlags,
+    mask: StatxFlags,
+) -> io::Result<Statx> {
+    match _statx(dirfd, path, flags, mask) {
+        Err(io::Errno::NOSYS) => (),
+        // Without the `compat` module, nothing has checked whether `PERM`
+        // means that a seccomp filter doesn't recognize `statx`.
+        #[cfg(feature = "linux_4_11")]
+        Err(io::Errno::PERM) if !backend::fs::syscalls::is_statx_available() => (),
+        result => return result,
+    }
+
+    statat_as_statx(dirfd, path, flags)
+}
+
+/// The fallback for [`statx_or_statat`], which calls `statat` and converts
+/// the result.
+fn statat_as_statx(dirfd: BorrowedFd<'_>, path: &CStr, flags: AtFlags) -> io::Result<Statx> {
+    let flags = flags - (AtFlags::STATX_FORCE_SYNC | AtFlags::STATX_DONT_SYNC);
+    let stat = backend::fs::syscalls::statat(dirfd, path, flags)?;
+    Ok(stat_to_statx(stat))
+}
+
+/// Convert from a `Stat` to a `Statx` with the `STATX_BASIC_STATS` fields
+/// filled in.
+#[allow(clippy::useless_conversion, clippy::unnecessary_cast)]
+fn stat_to_statx(stat: Stat) -> Statx {
+    let timestamp = |sec, nsec| StatxTimestamp::new(sec as i64, nsec as u32);
+    let dev = stat.st_dev as Dev;
+    let rdev = stat.st_rdev as Dev;
+    Statx {
+        stx_mask: StatxFlags::BASIC_STATS.bits(),
+        stx_blksize: stat.st_blksize as u32,
+        stx_attributes: 0,
+        stx_nlink: stat.st_nlink as u32,
+        stx_uid: stat.st_uid,
+        stx_gid: stat.st_gid,
+        stx_mode: stat.st_mode as u16,
+        __spare0: [0],
+        stx_ino: stat.st_ino as u64,
+        stx_size: stat.st_size as u64,
+        stx_blocks: stat.st_blocks as u64,
+        stx_attributes_mask: 0,
+        stx_atime: timestamp(stat.st_atime, stat.st_atime_nsec),
+        stx_btime: StatxTimestamp::new(0, 0),
+        stx_ctime: timestamp(stat.st_ctime, stat.st_ctime_nsec),
+        stx_mtime: timestamp(stat.st_mtime, stat.st_mtime_nsec),
+        stx_rdev_major: crate::fs::major(rdev),
+        stx_rdev_minor: crate::fs::minor(rdev),
+        stx_dev_major: crate::fs::major(dev),
+        stx_dev_minor: crate::fs::minor(dev),
+        stx_mnt_id: 0,
+        stx_dio_mem_align: 0,
+        stx_dio_offset_align: 0,
+        __spare3: [0; 12],
+    }
+}
+
 #[cfg(not(feature = "linux_4_11"))]
 mod compat {
     use crate::fd::BorrowedFd;
@@ -40,7 +404,7 @@ mod compat {
     use crate::{backend, io};
     use core::sync::atomic::{AtomicU8, Ordering};
 
-    use backend::fs::types::{Statx, StatxFlags};
+    use super::{Statx, StatxFlags};
 
     // Linux kernel prior to 4.11 old versions of Docker don't support `statx`.
     // We store the availability in a global to avoid unnecessary syscalls.
@@ -103,3 +467,39 @@ mod compat {
         }
     }
 }
+
+#[cfg(test)]
+#[test]
+fn test_statat_as_statx() {
+    let path = cstr!("Cargo.toml");
+    let expected = _statx(
+        crate::fs::CWD,
+        path,
+        AtFlags::empty(),
+        StatxFlags::BASIC_STATS,
+    )
+    .unwrap();
+    let statx = statat_as_statx(crate::fs::CWD, path, AtFlags::STATX_DONT_SYNC).unwrap();
+
+    assert_eq!(statx.mask(), StatxFlags::BASIC_STATS);
+    assert_eq!(statx.file_type(), expected.file_type());
+    assert_eq!(statx.mode(), expected.mode());
+    assert_eq!(statx.nlink(), expected.nlink());
+    assert_eq!(statx.uid(), expected.uid());
+    assert_eq!(statx.gid(), expected.gid());
+    assert_eq!(statx.ino(), expected.ino());
+    assert_eq!(statx.size(), expected.size());
+    assert_eq!(statx.blocks(), expected.blocks());
+    assert_eq!(statx.blksize(), expected.blksize());
+    assert_eq!(statx.dev(), expected.dev());
+    assert_eq!(statx.rdev(), expected.rdev());
+    assert_eq!(statx.atime(), expected.atime());
+    assert_eq!(statx.mtime(), expected.mtime());
+    assert_eq!(statx.ctime(), expected.ctime());
+    assert_eq!(statx.btime(), None);
+
+    assert_eq!(
+        statat_as_statx(crate::fs::CWD, cstr!("no-such-file"), AtFlags::empty()).unwrap_err(),
+        io::Errno::NOENT
+    );
+}
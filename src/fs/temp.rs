@@ -0,0 +1,202 @@
+//! `mkstempat` and `mkdtempat`, which create uniquely named temporary files
+//! and directories.
+
+use crate::fd::{AsFd, BorrowedFd, OwnedFd};
+use crate::ffi::{CStr, CString};
+use crate::fs::random_name::fill_random_name;
+use crate::fs::{mkdirat, openat, remove_dir_all_at, unlinkat, AtFlags, Mode, OFlags};
+use crate::{io, path};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// The number of names to try before giving up.
+const MAX_ATTEMPTS: usize = 100;
+
+/// The fewest `X`s a template may end with.
+const MIN_XS: usize = 6;
+
+/// `mkstemp(template)`, relative to `dirfd`—Create a uniquely named
+/// temporary file.
+///
+/// `template` is a path ending in at least six `X`s, which are replaced with
+/// random letters and digits obtained from `getrandom`. The file is opened
+/// with `O_RDWR | O_CREAT | O_EXCL | O_CLOEXEC` and mode `0o600`, and if a
+/// file with that name already exists, another name is tried.
+///
+/// The returned [`TempFile`] removes the file when it's dropped, unless it's
+/// [kept].
+///
+/// If `template` doesn't end in at least six `X`s, this fails with
+/// [`io::Errno::INVAL`].
+///
+/// [kept]: TempFile::keep
+///
+/// # References
+///  - [POSIX]
+///  - [Linux]
+///
+/// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/mkstemp.html
+/// [Linux]: https://man7.org/linux/man-pages/man3/mkstemp.3.html
+#[doc(alias = "mkstemp")]
+pub fn mkstempat<Fd: AsFd, P: path::Arg>(dirfd: Fd, template: P) -> io::Result<TempFile<Fd>> {
+    let (path, fd) = create_unique(template, |path| {
+        openat(
+            dirfd.as_fd(),
+            path,
+            OFlags::RDWR | OFlags::CREATE | OFlags::EXCL | OFlags::CLOEXEC,
+            Mode::RUSR | Mode::WUSR,
+        )
+    })?;
+    Ok(TempFile {
+        dirfd,
+        path: Some(path),
+        fd: Some(fd),
+    })
+}
+
+/// `mkdtemp(template)`, relative to `dirfd`—Create a uniquely named
+/// temporary directory.
+///
+/// `template` is a path ending in at least six `X`s, which are replaced with
+/// random letters and digits obtained from `getrandom`. The directory is
+/// created with `mkdirat` and mode `0o700`, and if something with that name
+/// already exists, another name is tried.
+///
+/// The returned [`TempDir`] removes the directory and its contents when it's
+/// dropped, unless it's [kept].
+///
+/// If `template` doesn't end in at least six `X`s, this fails with
+/// [`io::Errno::INVAL`].
+///
+/// [kept]: TempDir::keep
+///
+/// # References
+///  - [POSIX]
+///  - [Linux]
+///
+/// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/mkdtemp.html
+/// [Linux]: https://man7.org/linux/man-pages/man3/mkdtemp.3.html
+#[doc(alias = "mkdtemp")]
+pub fn mkdtempat<Fd: AsFd, P: path::Arg>(dirfd: Fd, template: P) -> io::Result<TempDir<Fd>> {
+    let (path, ()) = create_unique(template, |path| mkdirat(dirfd.as_fd(), path, Mode::RWXU))?;
+    Ok(TempDir {
+        dirfd,
+        path: Some(path),
+    })
+}
+
+/// A temporary file created by [`mkstempat`], which is removed with
+/// `unlinkat` when this is dropped.
+///
+/// This holds on to the `dirfd` that was passed to `mkstempat`, which may be
+/// owned or borrowed, so that it can remove the file from that directory.
+/// Errors from removing the file are ignored.
+pub struct TempFile<Fd: AsFd> {
+    dirfd: Fd,
+    path: Option<CString>,
+    fd: Option<OwnedFd>,
+}
+
+impl<Fd: AsFd> TempFile<Fd> {
+    /// Return the path of the file, relative to the directory it was created
+    /// in.
+    #[inline]
+    pub fn path(&self) -> &CStr {
+        self.path.as_deref().unwrap()
+    }
+
+    /// Keep the file, rather than removing it when this is dropped, and
+    /// return its file descriptor and path.
+    #[inline]
+    pub fn keep(mut self) -> (OwnedFd, CString) {
+        (self.fd.take().unwrap(), self.path.take().unwrap())
+    }
+}
+
+impl<Fd: AsFd> AsFd for TempFile<Fd> {
+    #[inline]
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.fd.as_ref().unwrap().as_fd()
+    }
+}
+
+impl<Fd: AsFd> fmt::Debug for TempFile<Fd> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TempFile")
+            .field("path", &self.path)
+            .field("fd", self.fd.as_ref().unwrap())
+            .finish()
+    }
+}
+
+impl<Fd: AsFd> Drop for TempFile<Fd> {
+    fn drop(&mut self) {
+        if let Some(path) = self.path.take() {
+            let _ = unlinkat(self.dirfd.as_fd(), path, AtFlags::empty());
+        }
+    }
+}
+
+/// A temporary directory created by [`mkdtempat`], which is removed, along
+/// with its contents, by [`remove_dir_all_at`] when this is dropped.
+///
+/// Like [`TempFile`], this holds on to the `dirfd` that was passed to
+/// `mkdtempat`. Errors from removing the directory are ignored.
+pub struct TempDir<Fd: AsFd> {
+    dirfd: Fd,
+    path: Option<CString>,
+}
+
+impl<Fd: AsFd> TempDir<Fd> {
+    /// Return the path of the directory, relative to the directory it was
+    /// created in.
+    #[inline]
+    pub fn path(&self) -> &CStr {
+        self.path.as_deref().unwrap()
+    }
+
+    /// Keep the directory, rather than removing it when this is dropped, and
+    /// return its path.
+    #[inline]
+    pub fn keep(mut self) -> CString {
+        self.path.take().unwrap()
+    }
+}
+
+impl<Fd: AsFd> fmt::Debug for TempDir<Fd> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TempDir").field("path", &self.path).finish()
+    }
+}
+
+impl<Fd: AsFd> Drop for TempDir<Fd> {
+    fn drop(&mut self) {
+        if let Some(path) = self.path.take() {
+            let _ = remove_dir_all_at(self.dirfd.as_fd(), path);
+        }
+    }
+}
+
+/// Fill in the `X`s at the end of `template` with random characters, and
+/// call `create` with the result until it doesn't fail with `EEXIST`.
+fn create_unique<P: path::Arg, T>(
+    template: P,
+    mut create: impl FnMut(&CStr) -> io::Result<T>,
+) -> io::Result<(CString, T)> {
+    let mut path: Vec<u8> = template.into_c_str()?.to_bytes_with_nul().to_vec();
+    let len = path.len() - 1;
+    let xs = path[..len].iter().rev().take_while(|b| **b == b'X').count();
+    if xs < MIN_XS {
+        return Err(io::Errno::INVAL);
+    }
+
+    for _ in 0..MAX_ATTEMPTS {
+        fill_random_name(&mut path[len - xs..len])?;
+        match create(CStr::from_bytes_with_nul(&path).unwrap()) {
+            Ok(value) => return Ok((CString::from_vec_with_nul(path).unwrap(), value)),
+            Err(io::Errno::EXIST) => continue,
+            Err(err) => return Err(err),
+        }
+    }
+    Err(io::Errno::EXIST)
+}
@@ -0,0 +1,557 @@
+//! A recursive directory walker, and `remove_dir_all_at`.
+//!
+//! The walker only ever opens directories relative to a file descriptor for
+//! their parent, with `O_NOFOLLOW` unless symlinks are being followed, so
+//! renaming or replacing a directory with a symlink while a walk is in
+//! progress can't redirect it outside the tree it started in.
+
+use crate::fd::{AsFd, BorrowedFd, OwnedFd};
+use crate::ffi::CStr;
+use crate::fs::{
+    fstat, openat, statat, unlinkat, AtFlags, FileType, Mode, OFlags, OwnedRawDir, Stat,
+};
+use crate::{io, path};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// How [`walk`] handles symbolic links.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum SymlinkPolicy {
+    /// Yield symlinks as entries, without following them.
+    NoFollow,
+    /// Don't yield symlinks.
+    Skip,
+    /// Follow symlinks, yielding entries for their targets, and descending
+    /// into them if they're directories.
+    ///
+    /// A symlink to one of its own ancestors yields an [`io::Errno::LOOP`]
+    /// error. Dangling symlinks are yielded as symlinks.
+    Follow,
+}
+
+/// Options for [`walk`].
+#[derive(Clone, Debug)]
+pub struct WalkOptions {
+    /// How to handle symlinks. The default is [`SymlinkPolicy::NoFollow`].
+    pub symlinks: SymlinkPolicy,
+
+    /// If set, only yield entries with these types. Directories are still
+    /// descended into when they aren't yielded. The default is `None`.
+    pub file_types: Option<Vec<FileType>>,
+
+    /// Yield directories after their contents, rather than before. The
+    /// default is `false`.
+    pub contents_first: bool,
+
+    /// Don't descend into directories at this depth. Entries in the
+    /// directory the walk starts in have a depth of 1. The default is
+    /// `usize::MAX`.
+    pub max_depth: usize,
+
+    /// The maximum number of directory file descriptors to hold open at
+    /// once. The default is 32, and values less than 3 are treated as 3.
+    ///
+    /// When a walk is deeper than this, directories are closed, starting
+    /// with the ones closest to the top of the walk. When the walk returns to
+    /// them, they're reopened by name, starting from the top, and checked to
+    /// be the same directories as before. If one has been moved or replaced,
+    /// the walk yields an [`io::Errno::STALE`] error, and skips the rest of
+    /// it.
+    pub max_open_fds: usize,
+}
+
+impl Default for WalkOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            symlinks: SymlinkPolicy::NoFollow,
+            file_types: None,
+            contents_first: false,
+            max_depth: usize::MAX,
+            max_open_fds: 32,
+        }
+    }
+}
+
+/// `walk(dirfd, path, options)`—Walks a directory tree depth-first.
+///
+/// `path` is opened relative to `dirfd`, with `O_NOFOLLOW` unless
+/// `options.symlinks` is [`SymlinkPolicy::Follow`]. The directory itself is
+/// not yielded.
+///
+/// # Examples
+///
+/// ```
+/// # use rustix::fs::{walk, FileType, WalkOptions, CWD};
+/// let options = WalkOptions {
+///     file_types: Some(vec![FileType::RegularFile]),
+///     ..WalkOptions::default()
+/// };
+/// let mut walk = walk(CWD, "src", options).unwrap();
+/// while let Some(entry) = walk.next() {
+///     let entry = entry.unwrap();
+///     dbg!(entry.path());
+/// }
+/// ```
+pub fn walk<Fd: AsFd, P: path::Arg>(dirfd: Fd, path: P, options: WalkOptions) -> io::Result<Walk> {
+    let follow = options.symlinks == SymlinkPolicy::Follow;
+    let root = openat(dirfd, path, dir_oflags(follow), Mode::empty())?;
+    let stat = fstat(&root)?;
+
+    Ok(Walk {
+        options,
+        stack: vec![Level {
+            dir: Some(OwnedRawDir::new(root)),
+            stat,
+            cookie: 0,
+            name_start: 0,
+            name_end: 0,
+            follow,
+        }],
+        path: Vec::new(),
+        open: 1,
+    })
+}
+
+/// A directory walk in progress, created by [`walk`].
+pub struct Walk {
+    options: WalkOptions,
+
+    /// The directories we're in, starting with the root. The root and the
+    /// deepest directory are always open.
+    stack: Vec<Level>,
+
+    /// The path of the current entry, relative to the root, followed by a
+    /// NUL. Each level's name is stored in here, between `name_start` and
+    /// `name_end`.
+    path: Vec<u8>,
+
+    /// The number of levels in `stack` which are open.
+    open: usize,
+}
+
+/// A directory in a [`Walk`]'s stack.
+struct Level {
+    /// The directory, or `None` if it's been closed to stay under
+    /// `max_open_fds`.
+    dir: Option<OwnedRawDir<OwnedFd>>,
+
+    /// The `Stat` of the directory, for checking that we reopen the same one.
+    stat: Stat,
+
+    /// The position to resume reading at if we need to reopen it.
+    cookie: u64,
+
+    name_start: usize,
+    name_end: usize,
+
+    /// Whether we got here by following a symlink.
+    follow: bool,
+}
+
+/// The location of an entry that's about to be yielded.
+struct Found {
+    parent: usize,
+    name_start: usize,
+    name_end: usize,
+    file_type: FileType,
+    /// Whether the entry is a symlink we're following.
+    follow: bool,
+}
+
+impl Walk {
+    /// Returns the next entry in the walk.
+    ///
+    /// This is like [`Iterator::next`], except that the entry borrows from
+    /// self.
+    ///
+    /// Errors from reading a directory end the walk of that directory.
+    /// Errors from opening or inspecting a directory entry just skip that
+    /// entry, so the walk can be continued.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<io::Result<WalkEntry<'_>>> {
+        match self.advance() {
+            Ok(Some(found)) => Some(Ok(self.entry(&found))),
+            Ok(None) => None,
+            Err(err) => Some(Err(err)),
+        }
+    }
+
+    fn advance(&mut self) -> io::Result<Option<Found>> {
+        loop {
+            let depth = self.stack.len();
+            let top = match self.stack.last_mut() {
+                Some(top) => top,
+                None => return Ok(None),
+            };
+
+            let entry = match top.dir.as_mut().unwrap().read() {
+                Some(entry) => entry,
+                None => match self.pop()? {
+                    Some(found) => return Ok(Some(found)),
+                    None => continue,
+                },
+            };
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(err) => {
+                    // Stop reading this directory, so that the walk can be
+                    // continued after the error.
+                    self.pop()?;
+                    return Err(err);
+                }
+            };
+            let name = entry.file_name().to_bytes();
+            if name == b"." || name == b".." {
+                continue;
+            }
+            top.cookie = entry.next_entry_cookie();
+            let d_type = entry.file_type();
+
+            // Append the name to the path of the directory it's in.
+            let parent = depth - 1;
+            self.path.truncate(top.name_end);
+            if !self.path.is_empty() {
+                self.path.push(b'/');
+            }
+            let name_start = self.path.len();
+            self.path.extend_from_slice(name);
+            let name_end = self.path.len();
+            self.path.push(b'\0');
+
+            let found = match self.inspect(parent, name_start, name_end, d_type) {
+                Ok(Some(found)) => found,
+                Ok(None) => continue,
+                Err(err) => return Err(err),
+            };
+
+            // Descend into directories whether or not they're yielded.
+            if found.file_type == FileType::Directory && depth < self.options.max_depth {
+                match self.descend(parent, name_start, name_end, found.follow) {
+                    Ok(true) => (),
+                    // The directory disappeared or was replaced before we
+                    // could open it.
+                    Ok(false) => continue,
+                    Err(err) => return Err(err),
+                }
+                if self.options.contents_first {
+                    continue;
+                }
+            }
+
+            if self.wants(found.file_type) {
+                return Ok(Some(found));
+            }
+        }
+    }
+
+    /// Determine the type of the entry we've just read, applying the symlink
+    /// policy. Returns `None` if the entry should be skipped.
+    fn inspect(
+        &self,
+        parent: usize,
+        name_start: usize,
+        name_end: usize,
+        d_type: FileType,
+    ) -> io::Result<Option<Found>> {
+        let dir = self.dir_fd(parent);
+        let name = &self.path[name_start..name_end];
+
+        let mut file_type = d_type;
+        let mut follow = false;
+        if file_type == FileType::Unknown {
+            file_type = match statat(dir, name, AtFlags::SYMLINK_NOFOLLOW) {
+                Ok(stat) => FileType::from_raw_mode(stat.st_mode),
+                Err(io::Errno::NOENT) => return Ok(None),
+                Err(err) => return Err(err),
+            };
+        }
+
+        if file_type == FileType::Symlink {
+            match self.options.symlinks {
+                SymlinkPolicy::NoFollow => (),
+                SymlinkPolicy::Skip => return Ok(None),
+                SymlinkPolicy::Follow => match statat(dir, name, AtFlags::empty()) {
+                    Ok(stat) => {
+                        file_type = FileType::from_raw_mode(stat.st_mode);
+                        follow = true;
+                    }
+                    // Yield dangling symlinks as symlinks.
+                    Err(io::Errno::NOENT) => (),
+                    Err(err) => return Err(err),
+                },
+            }
+        }
+
+        Ok(Some(Found {
+            parent,
+            name_start,
+            name_end,
+            file_type,
+            follow,
+        }))
+    }
+
+    /// Open a directory and push it onto the stack. Returns `false` if it
+    /// disappeared or stopped being a directory since we read its entry.
+    fn descend(
+        &mut self,
+        parent: usize,
+        name_start: usize,
+        name_end: usize,
+        follow: bool,
+    ) -> io::Result<bool> {
+        let name = &self.path[name_start..name_end];
+        let fd = match openat(self.dir_fd(parent), name, dir_oflags(follow), Mode::empty()) {
+            Ok(fd) => fd,
+            Err(io::Errno::NOENT) | Err(io::Errno::NOTDIR) | Err(io::Errno::LOOP) if !follow => {
+                return Ok(false)
+            }
+            Err(err) => return Err(err),
+        };
+        let stat = fstat(&fd)?;
+        if follow && self.stack.iter().any(|level| same_file(&level.stat, &stat)) {
+            return Err(io::Errno::LOOP);
+        }
+
+        self.stack.push(Level {
+            dir: Some(OwnedRawDir::new(fd)),
+            stat,
+            cookie: 0,
+            name_start,
+            name_end,
+            follow,
+        });
+        self.open += 1;
+
+        // If we're over the limit, close the open directory closest to the
+        // root, other than the root itself, which we keep open so that we
+        // can reopen the others from it.
+        if self.open > self.options.max_open_fds.max(3) {
+            let level = self.stack[1..]
+                .iter_mut()
+                .find(|level| level.dir.is_some())
+                .unwrap();
+            level.dir = None;
+            self.open -= 1;
+        }
+
+        Ok(true)
+    }
+
+    /// Pop a directory we've finished reading off the stack. Returns the
+    /// directory's entry, if it should be yielded now.
+    fn pop(&mut self) -> io::Result<Option<Found>> {
+        let level = self.stack.pop().unwrap();
+        drop(level.dir);
+        self.open -= 1;
+
+        let parent = match self.stack.len() {
+            0 => return Ok(None),
+            len => len - 1,
+        };
+        if self.stack[parent].dir.is_none() {
+            self.reopen(parent)?;
+        }
+
+        if self.options.contents_first && self.wants(FileType::Directory) {
+            self.path.truncate(level.name_end);
+            self.path.push(b'\0');
+            return Ok(Some(Found {
+                parent,
+                name_start: level.name_start,
+                name_end: level.name_end,
+                file_type: FileType::Directory,
+                follow: level.follow,
+            }));
+        }
+
+        Ok(None)
+    }
+
+    /// Reopen a directory we closed to stay under `max_open_fds`, by name
+    /// starting from its nearest open ancestor, checking that each directory
+    /// along the way is the same one we saw before.
+    fn reopen(&mut self, index: usize) -> io::Result<()> {
+        let start = (0..index)
+            .rev()
+            .find(|i| self.stack[*i].dir.is_some())
+            .unwrap();
+
+        match self.reopen_from(start, index) {
+            Ok(dir) => {
+                self.stack[index].dir = Some(dir);
+                self.open += 1;
+                Ok(())
+            }
+            Err(err) => {
+                // We can't get back to these directories, so abandon them,
+                // and continue from the nearest one which is still open.
+                self.stack.truncate(start + 1);
+                Err(err)
+            }
+        }
+    }
+
+    fn reopen_from(&self, start: usize, index: usize) -> io::Result<OwnedRawDir<OwnedFd>> {
+        let mut fd: Option<OwnedFd> = None;
+        for level in &self.stack[start + 1..=index] {
+            let dir = match &fd {
+                Some(fd) => fd.as_fd(),
+                None => self.dir_fd(start),
+            };
+            let name = &self.path[level.name_start..level.name_end];
+            let next = openat(dir, name, dir_oflags(level.follow), Mode::empty())?;
+            if !same_file(&fstat(&next)?, &level.stat) {
+                // The directory was moved or replaced while we weren't
+                // looking.
+                return Err(io::Errno::STALE);
+            }
+            fd = Some(next);
+        }
+
+        let mut dir = OwnedRawDir::new(fd.unwrap());
+        dir.seek(self.stack[index].cookie)?;
+        Ok(dir)
+    }
+
+    fn entry(&self, found: &Found) -> WalkEntry<'_> {
+        let path = CStr::from_bytes_with_nul(&self.path[..=found.name_end]).unwrap();
+        WalkEntry {
+            dir: self.dir_fd(found.parent),
+            path,
+            file_name: CStr::from_bytes_with_nul(&path.to_bytes_with_nul()[found.name_start..])
+                .unwrap(),
+            file_type: found.file_type,
+            depth: found.parent + 1,
+        }
+    }
+
+    fn dir_fd(&self, index: usize) -> BorrowedFd<'_> {
+        self.stack[index].dir.as_ref().unwrap().fd().as_fd()
+    }
+
+    fn wants(&self, file_type: FileType) -> bool {
+        match &self.options.file_types {
+            Some(file_types) => file_types.contains(&file_type),
+            None => true,
+        }
+    }
+}
+
+impl fmt::Debug for Walk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Walk")
+            .field("options", &self.options)
+            .field("depth", &self.stack.len())
+            .finish()
+    }
+}
+
+/// An entry yielded by [`Walk::next`].
+#[derive(Debug)]
+pub struct WalkEntry<'a> {
+    dir: BorrowedFd<'a>,
+    path: &'a CStr,
+    file_name: &'a CStr,
+    file_type: FileType,
+    depth: usize,
+}
+
+impl<'a> WalkEntry<'a> {
+    /// Returns a file descriptor for the directory containing this entry.
+    ///
+    /// Operating on the entry with `*at` functions relative to this, using
+    /// [`WalkEntry::file_name`], can't be redirected by changes to the
+    /// directories above it.
+    #[inline]
+    pub fn dir(&self) -> BorrowedFd<'a> {
+        self.dir
+    }
+
+    /// Returns the path of this entry, relative to the directory the walk
+    /// started in.
+    #[inline]
+    pub fn path(&self) -> &'a CStr {
+        self.path
+    }
+
+    /// Returns the file name of this entry.
+    #[inline]
+    pub fn file_name(&self) -> &'a CStr {
+        self.file_name
+    }
+
+    /// Returns the type of this entry. If symlinks are being followed, this
+    /// is the type of the symlink's target.
+    #[inline]
+    pub fn file_type(&self) -> FileType {
+        self.file_type
+    }
+
+    /// Returns the depth of this entry. Entries in the directory the walk
+    /// started in have a depth of 1.
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+}
+
+/// `remove_dir_all_at(dirfd, path)`—Removes a directory and all of its
+/// contents.
+///
+/// Unlike a remove implemented with paths, this can't be redirected outside
+/// of the tree by replacing one of its directories with a symlink while it's
+/// running; symlinks are removed, and never followed. If `path` itself is a
+/// symlink, the symlink is removed.
+///
+/// Entries which are removed concurrently are ignored.
+pub fn remove_dir_all_at<Fd: AsFd, P: path::Arg>(dirfd: Fd, path: P) -> io::Result<()> {
+    path.into_with_c_str(|path| _remove_dir_all_at(dirfd.as_fd(), path))
+}
+
+fn _remove_dir_all_at(dirfd: BorrowedFd<'_>, path: &CStr) -> io::Result<()> {
+    let stat = statat(dirfd, path, AtFlags::SYMLINK_NOFOLLOW)?;
+    match FileType::from_raw_mode(stat.st_mode) {
+        FileType::Directory => (),
+        FileType::Symlink => return unlinkat(dirfd, path, AtFlags::empty()),
+        _ => return Err(io::Errno::NOTDIR),
+    }
+
+    let options = WalkOptions {
+        contents_first: true,
+        ..WalkOptions::default()
+    };
+    let mut walk = walk(dirfd, path, options)?;
+    while let Some(entry) = walk.next() {
+        let entry = entry?;
+        let flags = if entry.file_type() == FileType::Directory {
+            AtFlags::REMOVEDIR
+        } else {
+            AtFlags::empty()
+        };
+        match unlinkat(entry.dir(), entry.file_name(), flags) {
+            Ok(()) | Err(io::Errno::NOENT) => (),
+            Err(err) => return Err(err),
+        }
+    }
+    drop(walk);
+
+    unlinkat(dirfd, path, AtFlags::REMOVEDIR)
+}
+
+/// The `OFlags` for opening a directory in a walk.
+fn dir_oflags(follow: bool) -> OFlags {
+    let oflags = OFlags::RDONLY | OFlags::DIRECTORY | OFlags::CLOEXEC | OFlags::NOCTTY;
+    if follow {
+        oflags
+    } else {
+        oflags | OFlags::NOFOLLOW
+    }
+}
+
+/// Test whether two `Stat`s are for the same file.
+fn same_file(a: &Stat, b: &Stat) -> bool {
+    a.st_dev == b.st_dev && a.st_ino == b.st_ino
+}
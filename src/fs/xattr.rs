@@ -1,7 +1,14 @@
+use crate::ffi::CStr;
 use crate::{backend, io, path};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 use backend::c;
 use backend::fd::AsFd;
 use bitflags::bitflags;
+#[cfg(feature = "alloc")]
+use core::fmt;
+#[cfg(feature = "alloc")]
+use core::iter::FusedIterator;
 
 bitflags! {
     /// `XATTR_*` constants for use with [`setxattr`], and other `*setxattr`
@@ -69,6 +76,62 @@ pub fn fgetxattr<Fd: AsFd, Name: path::Arg>(
     name.into_with_c_str(|name| backend::fs::syscalls::fgetxattr(fd.as_fd(), name, value))
 }
 
+/// `getxattr(path, name, value.as_ptr(), value.len())`—Get extended
+/// filesystem attributes, into a newly allocated `Vec`.
+///
+/// This asks for the size of the value first, and retries if the value grows
+/// before it can be read.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/getxattr.2.html
+#[cfg(feature = "alloc")]
+pub fn getxattr_vec<P: path::Arg, Name: path::Arg>(path: P, name: Name) -> io::Result<Vec<u8>> {
+    path.into_with_c_str(|path| {
+        name.into_with_c_str(|name| {
+            read_to_vec(|value| backend::fs::syscalls::getxattr(path, name, value))
+        })
+    })
+}
+
+/// `lgetxattr(path, name, value.as_ptr(), value.len())`—Get extended
+/// filesystem attributes, without following symlinks in the last path
+/// component, into a newly allocated `Vec`.
+///
+/// See [`getxattr_vec`] for details.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/lgetxattr.2.html
+#[cfg(feature = "alloc")]
+pub fn lgetxattr_vec<P: path::Arg, Name: path::Arg>(path: P, name: Name) -> io::Result<Vec<u8>> {
+    path.into_with_c_str(|path| {
+        name.into_with_c_str(|name| {
+            read_to_vec(|value| backend::fs::syscalls::lgetxattr(path, name, value))
+        })
+    })
+}
+
+/// `fgetxattr(fd, name, value.as_ptr(), value.len())`—Get extended
+/// filesystem attributes on an open file descriptor, into a newly allocated
+/// `Vec`.
+///
+/// See [`getxattr_vec`] for details.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/fgetxattr.2.html
+#[cfg(feature = "alloc")]
+pub fn fgetxattr_vec<Fd: AsFd, Name: path::Arg>(fd: Fd, name: Name) -> io::Result<Vec<u8>> {
+    let fd = fd.as_fd();
+    name.into_with_c_str(|name| {
+        read_to_vec(|value| backend::fs::syscalls::fgetxattr(fd, name, value))
+    })
+}
+
 /// `setxattr(path, name, value.as_ptr(), value.len(), flags)`—Set extended
 /// filesystem attributes.
 ///
@@ -161,6 +224,249 @@ pub fn flistxattr<Fd: AsFd>(fd: Fd, list: &mut [c::c_char]) -> io::Result<usize>
     backend::fs::syscalls::flistxattr(fd.as_fd(), list)
 }
 
+/// `listxattr(path, list.as_ptr(), list.len())`—List the names of extended
+/// filesystem attributes.
+///
+/// This asks for the size of the list first, and retries if the list grows
+/// before it can be read.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/listxattr.2.html
+#[cfg(feature = "alloc")]
+pub fn listxattr_names<P: path::Arg>(path: P) -> io::Result<XattrList> {
+    path.into_with_c_str(|path| {
+        read_to_vec(|list| backend::fs::syscalls::listxattr(path, as_c_chars(list))).map(XattrList)
+    })
+}
+
+/// `llistxattr(path, list.as_ptr(), list.len())`—List the names of extended
+/// filesystem attributes, without following symlinks in the last path
+/// component.
+///
+/// See [`listxattr_names`] for details.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/llistxattr.2.html
+#[cfg(feature = "alloc")]
+pub fn llistxattr_names<P: path::Arg>(path: P) -> io::Result<XattrList> {
+    path.into_with_c_str(|path| {
+        read_to_vec(|list| backend::fs::syscalls::llistxattr(path, as_c_chars(list))).map(XattrList)
+    })
+}
+
+/// `flistxattr(fd, list.as_ptr(), list.len())`—List the names of extended
+/// filesystem attributes on an open file descriptor.
+///
+/// See [`listxattr_names`] for details.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/flistxattr.2.html
+#[cfg(feature = "alloc")]
+pub fn flistxattr_names<Fd: AsFd>(fd: Fd) -> io::Result<XattrList> {
+    let fd = fd.as_fd();
+    read_to_vec(|list| backend::fs::syscalls::flistxattr(fd, as_c_chars(list))).map(XattrList)
+}
+
+/// An extended attribute namespace.
+///
+/// On Linux, the part of an attribute name before the first `.` determines
+/// which namespace it's in, and which permissions are needed to access it.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum XattrNamespace {
+    /// `user.`
+    User,
+
+    /// `trusted.`
+    Trusted,
+
+    /// `security.`
+    Security,
+
+    /// `system.`
+    System,
+}
+
+impl XattrNamespace {
+    /// Return the prefix of names in this namespace, including the trailing
+    /// `.`.
+    #[inline]
+    pub const fn prefix(self) -> &'static str {
+        match self {
+            Self::User => "user.",
+            Self::Trusted => "trusted.",
+            Self::Security => "security.",
+            Self::System => "system.",
+        }
+    }
+
+    /// Return the namespace of the attribute `name`, if it's one of the
+    /// known ones.
+    pub fn of(name: &CStr) -> Option<Self> {
+        let name = name.to_bytes();
+        [Self::User, Self::Trusted, Self::Security, Self::System]
+            .into_iter()
+            .find(|namespace| name.starts_with(namespace.prefix().as_bytes()))
+    }
+}
+
+/// A list of extended attribute names, as returned by [`listxattr_names`].
+#[cfg(feature = "alloc")]
+#[derive(Clone, Default, Eq, PartialEq)]
+pub struct XattrList(Vec<u8>);
+
+#[cfg(feature = "alloc")]
+impl XattrList {
+    /// Return an iterator over the names in the list.
+    #[inline]
+    pub fn iter(&self) -> XattrNames<'_> {
+        XattrNames {
+            list: &self.0,
+            namespace: None,
+        }
+    }
+
+    /// Return an iterator over the names in the list that are in
+    /// `namespace`.
+    #[inline]
+    pub fn in_namespace(&self, namespace: XattrNamespace) -> XattrNames<'_> {
+        XattrNames {
+            list: &self.0,
+            namespace: Some(namespace),
+        }
+    }
+
+    /// Test whether the list contains no names.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Return the raw list, which is a sequence of NUL-terminated names.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> IntoIterator for &'a XattrList {
+    type Item = &'a CStr;
+    type IntoIter = XattrNames<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Debug for XattrList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// An iterator over the names in an [`XattrList`].
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct XattrNames<'a> {
+    list: &'a [u8],
+    namespace: Option<XattrNamespace>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Iterator for XattrNames<'a> {
+    type Item = &'a CStr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.list.is_empty() {
+            // The kernel NUL-terminates every name, but don't trust that the
+            // last one is.
+            let len = match self.list.iter().position(|b| *b == b'\0') {
+                Some(nul) => nul + 1,
+                None => {
+                    self.list = &[];
+                    return None;
+                }
+            };
+            let (name, rest) = self.list.split_at(len);
+            self.list = rest;
+            let name = CStr::from_bytes_with_nul(name).unwrap();
+            match self.namespace {
+                Some(namespace) if !name.to_bytes().starts_with(namespace.prefix().as_bytes()) => {
+                    continue
+                }
+                _ => return Some(name),
+            }
+        }
+        None
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FusedIterator for XattrNames<'_> {}
+
+/// Read a value with `get`, growing the buffer until it fits.
+#[cfg(feature = "alloc")]
+fn read_to_vec(mut get: impl FnMut(&mut [u8]) -> io::Result<usize>) -> io::Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    loop {
+        // Ask for the size, and then try to read that much. If the value
+        // grew in between, we'll get `ERANGE`, or, if the size was zero, the
+        // second call is another size query and returns the new size. In
+        // either case, try again.
+        let len = get(&mut [])?;
+        buf.resize(len, 0);
+        match get(&mut buf) {
+            Ok(len) if len <= buf.len() => {
+                buf.truncate(len);
+                return Ok(buf);
+            }
+            Ok(_) | Err(io::Errno::RANGE) => continue,
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+#[test]
+fn test_read_to_vec_grows() {
+    // Simulate a value that's empty when its size is queried, and then grows
+    // before it's read. A read with an empty buffer is a size query.
+    let mut value: &[u8] = b"";
+    let mut calls = 0;
+    let read = read_to_vec(|buf| {
+        calls += 1;
+        let len = value.len();
+        if calls == 1 {
+            value = b"grown";
+        }
+        if buf.is_empty() {
+            Ok(len)
+        } else if buf.len() < len {
+            Err(io::Errno::RANGE)
+        } else {
+            buf[..len].copy_from_slice(value);
+            Ok(len)
+        }
+    });
+    assert_eq!(read.unwrap(), b"grown");
+}
+
+#[cfg(feature = "alloc")]
+#[allow(unsafe_code)]
+fn as_c_chars(buf: &mut [u8]) -> &mut [c::c_char] {
+    // SAFETY: `c_char` is either `i8` or `u8`, which have the same layout as
+    // `u8`.
+    unsafe { core::slice::from_raw_parts_mut(buf.as_mut_ptr().cast(), buf.len()) }
+}
+
 /// `removexattr(path, name)`—Remove an extended filesystem attribute.
 ///
 /// # References
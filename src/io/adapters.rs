@@ -0,0 +1,493 @@
+//! `FdReader`, `FdWriter`, and `BufFdWriter`—`read_exact` and `write_all`
+//! for file descriptors, without depending on `std`.
+//!
+//! `read` and `write` may transfer fewer bytes than requested, and may fail
+//! with [`Errno::INTR`] if a signal arrives, so callers that want to transfer
+//! a whole buffer need a loop. These types provide that loop.
+//!
+//! [`Errno::INTR`]: io::Errno::INTR
+
+#![allow(unsafe_code)]
+
+use crate::fd::AsFd;
+use crate::io::{self, retry_on_intr, IoSlice};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::mem::ManuallyDrop;
+#[cfg(feature = "alloc")]
+use core::ptr;
+use core::{fmt, slice};
+
+/// The default capacity of a [`BufFdWriter`].
+#[cfg(feature = "alloc")]
+const DEFAULT_BUF_SIZE: usize = 8 * 1024;
+
+/// A reader over a file descriptor, with `read_exact`.
+///
+/// [`Errno::INTR`] failures are retried automatically. If polling is enabled
+/// with [`FdReader::poll_on_again`], [`Errno::AGAIN`] failures on a
+/// non-blocking file descriptor wait for it to become readable and retry.
+///
+/// With the `std` feature, this implements [`std::io::Read`].
+///
+/// [`Errno::INTR`]: io::Errno::INTR
+/// [`Errno::AGAIN`]: io::Errno::AGAIN
+#[derive(Debug)]
+pub struct FdReader<Fd: AsFd> {
+    fd: Fd,
+    #[cfg(feature = "event")]
+    poll: bool,
+}
+
+impl<Fd: AsFd> FdReader<Fd> {
+    /// Create a new `FdReader` reading from `fd`.
+    #[inline]
+    pub const fn new(fd: Fd) -> Self {
+        Self {
+            fd,
+            #[cfg(feature = "event")]
+            poll: false,
+        }
+    }
+
+    /// Set whether to wait with `poll` and retry when a read fails with
+    /// [`Errno::AGAIN`].
+    ///
+    /// [`Errno::AGAIN`]: io::Errno::AGAIN
+    #[cfg(feature = "event")]
+    #[inline]
+    pub fn poll_on_again(mut self, poll: bool) -> Self {
+        self.poll = poll;
+        self
+    }
+
+    /// Return a reference to the underlying file descriptor.
+    #[inline]
+    pub fn get_ref(&self) -> &Fd {
+        &self.fd
+    }
+
+    /// Return a mutable reference to the underlying file descriptor.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut Fd {
+        &mut self.fd
+    }
+
+    /// Consume this `FdReader` and return the underlying file descriptor.
+    #[inline]
+    pub fn into_inner(self) -> Fd {
+        self.fd
+    }
+
+    /// Read into `buf`, returning the number of bytes read, which is zero at
+    /// end of file.
+    // Without `event`, the loop body always returns.
+    #[cfg_attr(not(feature = "event"), allow(clippy::never_loop))]
+    pub fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            match retry_on_intr(|| io::read(self.fd.as_fd(), &mut *buf)) {
+                #[cfg(feature = "event")]
+                Err(io::Errno::AGAIN) if self.poll => {
+                    wait(self.fd.as_fd(), crate::event::PollFlags::IN)?
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Read until `buf` is full.
+    ///
+    /// If end of file is reached before `buf` is full, this fails with
+    /// [`Errno::IO`], as there is no errno value for an unexpected end of
+    /// file. The contents of `buf` are unspecified in that case.
+    ///
+    /// [`Errno::IO`]: io::Errno::IO
+    pub fn read_exact(&mut self, mut buf: &mut [u8]) -> io::Result<()> {
+        while !buf.is_empty() {
+            match self.read(buf)? {
+                0 => return Err(io::Errno::IO),
+                n => buf = &mut buf[n..],
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A writer over a file descriptor, with `write_all` and
+/// `write_all_vectored`.
+///
+/// [`Errno::INTR`] failures are retried automatically. If polling is enabled
+/// with [`FdWriter::poll_on_again`], [`Errno::AGAIN`] failures on a
+/// non-blocking file descriptor wait for it to become writable and retry.
+///
+/// With the `std` feature, this implements [`std::io::Write`].
+///
+/// [`Errno::INTR`]: io::Errno::INTR
+/// [`Errno::AGAIN`]: io::Errno::AGAIN
+#[derive(Debug)]
+pub struct FdWriter<Fd: AsFd> {
+    fd: Fd,
+    #[cfg(feature = "event")]
+    poll: bool,
+}
+
+impl<Fd: AsFd> FdWriter<Fd> {
+    /// Create a new `FdWriter` writing to `fd`.
+    #[inline]
+    pub const fn new(fd: Fd) -> Self {
+        Self {
+            fd,
+            #[cfg(feature = "event")]
+            poll: false,
+        }
+    }
+
+    /// Set whether to wait with `poll` and retry when a write fails with
+    /// [`Errno::AGAIN`].
+    ///
+    /// [`Errno::AGAIN`]: io::Errno::AGAIN
+    #[cfg(feature = "event")]
+    #[inline]
+    pub fn poll_on_again(mut self, poll: bool) -> Self {
+        self.poll = poll;
+        self
+    }
+
+    /// Return a reference to the underlying file descriptor.
+    #[inline]
+    pub fn get_ref(&self) -> &Fd {
+        &self.fd
+    }
+
+    /// Return a mutable reference to the underlying file descriptor.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut Fd {
+        &mut self.fd
+    }
+
+    /// Consume this `FdWriter` and return the underlying file descriptor.
+    #[inline]
+    pub fn into_inner(self) -> Fd {
+        self.fd
+    }
+
+    /// Write from `buf`, returning the number of bytes written.
+    // Without `event`, the loop body always returns.
+    #[cfg_attr(not(feature = "event"), allow(clippy::never_loop))]
+    pub fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        loop {
+            match retry_on_intr(|| io::write(self.fd.as_fd(), buf)) {
+                #[cfg(feature = "event")]
+                Err(io::Errno::AGAIN) if self.poll => {
+                    wait(self.fd.as_fd(), crate::event::PollFlags::OUT)?
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Write from `bufs`, returning the number of bytes written.
+    // Without `event`, the loop body always returns.
+    #[cfg_attr(not(feature = "event"), allow(clippy::never_loop))]
+    pub fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        loop {
+            match retry_on_intr(|| io::writev(self.fd.as_fd(), bufs)) {
+                #[cfg(feature = "event")]
+                Err(io::Errno::AGAIN) if self.poll => {
+                    wait(self.fd.as_fd(), crate::event::PollFlags::OUT)?
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Write all of `buf`.
+    ///
+    /// If a write makes no progress, this fails with [`Errno::IO`].
+    ///
+    /// [`Errno::IO`]: io::Errno::IO
+    pub fn write_all(&mut self, mut buf: &[u8]) -> io::Result<()> {
+        while !buf.is_empty() {
+            match self.write(buf)? {
+                0 => return Err(io::Errno::IO),
+                n => buf = &buf[n..],
+            }
+        }
+        Ok(())
+    }
+
+    /// Write all of `bufs`.
+    ///
+    /// Partial writes are handled by advancing past the written data in
+    /// `bufs`, so the contents of `bufs` are unspecified after this returns.
+    /// If a write makes no progress, this fails with [`Errno::IO`].
+    ///
+    /// [`Errno::IO`]: io::Errno::IO
+    pub fn write_all_vectored(&mut self, mut bufs: &mut [IoSlice<'_>]) -> io::Result<()> {
+        // Skip any leading empty slices, so that an all-empty `bufs` doesn't
+        // look like a write that made no progress.
+        advance_slices(&mut bufs, 0);
+        while !bufs.is_empty() {
+            match self.write_vectored(bufs)? {
+                0 => return Err(io::Errno::IO),
+                n => advance_slices(&mut bufs, n),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A buffering writer over a file descriptor.
+///
+/// Small writes are collected in a buffer and written with a single `write`
+/// once the buffer is full, or when [`BufFdWriter::flush`] is called. The
+/// buffer is flushed when the `BufFdWriter` is dropped, ignoring any errors;
+/// call `flush` or [`BufFdWriter::into_inner`] first to observe them.
+///
+/// With the `std` feature, this implements [`std::io::Write`].
+#[cfg(feature = "alloc")]
+pub struct BufFdWriter<Fd: AsFd> {
+    inner: FdWriter<Fd>,
+    buf: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl<Fd: AsFd> BufFdWriter<Fd> {
+    /// Create a new `BufFdWriter` with a default buffer capacity, currently
+    /// 8 KiB.
+    #[inline]
+    pub fn new(inner: FdWriter<Fd>) -> Self {
+        Self::with_capacity(DEFAULT_BUF_SIZE, inner)
+    }
+
+    /// Create a new `BufFdWriter` with a buffer of at least `capacity`
+    /// bytes.
+    #[inline]
+    pub fn with_capacity(capacity: usize, inner: FdWriter<Fd>) -> Self {
+        Self {
+            inner,
+            buf: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Return a reference to the underlying writer.
+    #[inline]
+    pub fn get_ref(&self) -> &FdWriter<Fd> {
+        &self.inner
+    }
+
+    /// Return the data that has been buffered but not yet written.
+    #[inline]
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Return the number of bytes the buffer can hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    /// Write from `buf`, returning the number of bytes accepted.
+    ///
+    /// If `buf` doesn't fit in the buffer, the buffer is flushed first. If
+    /// `buf` is at least as big as the buffer, it's written directly.
+    pub fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.buf.len() + buf.len() > self.buf.capacity() {
+            self.flush()?;
+        }
+        if buf.len() >= self.buf.capacity() {
+            self.inner.write(buf)
+        } else {
+            self.buf.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+    }
+
+    /// Write all of `buf`.
+    pub fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        if self.buf.len() + buf.len() > self.buf.capacity() {
+            self.flush()?;
+        }
+        if buf.len() >= self.buf.capacity() {
+            self.inner.write_all(buf)
+        } else {
+            self.buf.extend_from_slice(buf);
+            Ok(())
+        }
+    }
+
+    /// Write all buffered data to the file descriptor.
+    ///
+    /// If this fails, the data that wasn't written remains in the buffer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        let mut written = 0;
+        let result = loop {
+            if written == self.buf.len() {
+                break Ok(());
+            }
+            match self.inner.write(&self.buf[written..]) {
+                Ok(0) => break Err(io::Errno::IO),
+                Ok(n) => written += n,
+                Err(err) => break Err(err),
+            }
+        };
+        self.buf.drain(..written);
+        result
+    }
+
+    /// Flush the buffer, and return the underlying writer.
+    ///
+    /// If flushing fails, the error is returned along with this
+    /// `BufFdWriter`, which still holds the data that wasn't written, so that
+    /// the caller can retry, or recover the data and the file descriptor.
+    pub fn into_inner(mut self) -> Result<FdWriter<Fd>, IntoInnerError<Self>> {
+        if let Err(err) = self.flush() {
+            return Err(IntoInnerError(self, err));
+        }
+        Ok(self.into_parts().0)
+    }
+
+    /// Return the underlying writer and the data that has been buffered but
+    /// not yet written, without flushing.
+    pub fn into_parts(self) -> (FdWriter<Fd>, Vec<u8>) {
+        let this = ManuallyDrop::new(self);
+        // SAFETY: `this` is never dropped, so each field is read out exactly
+        // once.
+        unsafe { (ptr::read(&this.inner), ptr::read(&this.buf)) }
+    }
+}
+
+/// The error returned by [`BufFdWriter::into_inner`], which holds the writer
+/// that failed to flush, so that it isn't lost.
+#[derive(Debug)]
+pub struct IntoInnerError<W>(W, io::Errno);
+
+impl<W> IntoInnerError<W> {
+    /// Return the error that flushing failed with.
+    #[inline]
+    pub fn error(&self) -> io::Errno {
+        self.1
+    }
+
+    /// Return the writer that failed to flush.
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.0
+    }
+
+    /// Return the error that flushing failed with, and the writer.
+    #[inline]
+    pub fn into_parts(self) -> (io::Errno, W) {
+        (self.1, self.0)
+    }
+}
+
+impl<W> fmt::Display for IntoInnerError<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.1, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: fmt::Debug> std::error::Error for IntoInnerError<W> {}
+
+#[cfg(feature = "std")]
+impl<W> From<IntoInnerError<W>> for std::io::Error {
+    #[inline]
+    fn from(err: IntoInnerError<W>) -> Self {
+        err.1.into()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Fd: AsFd + fmt::Debug> fmt::Debug for BufFdWriter<Fd> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufFdWriter")
+            .field("inner", &self.inner)
+            .field("buffered", &self.buf.len())
+            .field("capacity", &self.buf.capacity())
+            .finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Fd: AsFd> Drop for BufFdWriter<Fd> {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+/// Advance `bufs` past `n` bytes, removing slices that are fully consumed.
+///
+/// # Panics
+///
+/// This panics if `n` is greater than the total length of `bufs`.
+fn advance_slices<'a>(bufs: &mut &mut [IoSlice<'a>], n: usize) {
+    let mut remove = 0;
+    let mut left = n;
+    for buf in bufs.iter() {
+        if left < buf.len() {
+            break;
+        }
+        left -= buf.len();
+        remove += 1;
+    }
+
+    *bufs = &mut core::mem::take(bufs)[remove..];
+    if bufs.is_empty() {
+        assert_eq!(left, 0, "advancing IoSlices beyond their length");
+    } else if left != 0 {
+        let first: &[u8] = &bufs[0];
+        // SAFETY: `first` refers to memory borrowed for `'a`.
+        let rest = unsafe { slice::from_raw_parts(first.as_ptr().add(left), first.len() - left) };
+        bufs[0] = IoSlice::new(rest);
+    }
+}
+
+/// Wait for `fd` to be ready for the I/O in `flags`.
+#[cfg(feature = "event")]
+fn wait(fd: crate::fd::BorrowedFd<'_>, flags: crate::event::PollFlags) -> io::Result<()> {
+    let mut fds = [crate::event::PollFd::from_borrowed_fd(fd, flags)];
+    retry_on_intr(|| crate::event::poll(&mut fds, -1)).map(|_| ())
+}
+
+#[cfg(feature = "std")]
+impl<Fd: AsFd> std::io::Read for FdReader<Fd> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        Ok(FdReader::read(self, buf)?)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Fd: AsFd> std::io::Write for FdWriter<Fd> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        Ok(FdWriter::write(self, buf)?)
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> std::io::Result<usize> {
+        Ok(FdWriter::write_vectored(self, bufs)?)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Fd: AsFd> std::io::Write for BufFdWriter<Fd> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        Ok(BufFdWriter::write(self, buf)?)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(BufFdWriter::flush(self)?)
+    }
+}
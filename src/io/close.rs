@@ -1,4 +1,4 @@
-//! The unsafe `close` for raw file descriptors.
+//! The unsafe `close` and `close_range` for raw file descriptors.
 //!
 //! # Safety
 //!
@@ -6,8 +6,13 @@
 #![allow(unsafe_code)]
 
 use crate::backend;
+#[cfg(linux_kernel)]
+use crate::io;
 use backend::fd::RawFd;
 
+#[cfg(linux_kernel)]
+pub use backend::io::types::CloseRangeFlags;
+
 /// `close(raw_fd)`—Closes a `RawFd` directly.
 ///
 /// Most users won't need to use this, as `OwnedFd` automatically closes its
@@ -53,3 +58,202 @@ use backend::fd::RawFd;
 pub unsafe fn close(raw_fd: RawFd) {
     backend::io::syscalls::close(raw_fd)
 }
+
+/// `close_range(first, last, flags)`—Closes all the file descriptors in a
+/// range.
+///
+/// This closes every open file descriptor from `first` to `last`, inclusive.
+/// With [`CloseRangeFlags::CLOEXEC`], it instead sets the close-on-exec flag
+/// on them, leaving them open.
+///
+/// On kernels which don't support `close_range`, or which don't support
+/// [`CloseRangeFlags::CLOEXEC`], and when the `procfs` feature is enabled,
+/// this falls back to walking `/proc/self/fd` using the checks in
+/// [`rustix::procfs`]. The fallback doesn't allocate, and it skips the file
+/// descriptors `procfs` holds open for its own use. It doesn't support
+/// [`CloseRangeFlags::UNSHARE`].
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/close_range.2.html
+/// [`rustix::procfs`]: https://docs.rs/rustix/*/rustix/procfs/index.html
+///
+/// # Safety
+///
+/// Unless `flags` contains [`CloseRangeFlags::CLOEXEC`], the file descriptors
+/// in the range, which may include file descriptors owned by other parts of
+/// the program, are not valid after the call.
+#[cfg(linux_kernel)]
+#[inline]
+pub unsafe fn close_range(first: u32, last: u32, flags: CloseRangeFlags) -> io::Result<()> {
+    match backend::io::syscalls::close_range(first, last, flags) {
+        #[cfg(feature = "procfs")]
+        Err(io::Errno::NOSYS) if !flags.contains(CloseRangeFlags::UNSHARE) => {
+            close_range_fallback(first, last, flags)
+        }
+        // Linux 5.9 and 5.10 have `close_range` but not `CLOSE_RANGE_CLOEXEC`.
+        #[cfg(feature = "procfs")]
+        Err(io::Errno::INVAL) if flags == CloseRangeFlags::CLOEXEC && first <= last => {
+            close_range_fallback(first, last, flags)
+        }
+        otherwise => otherwise,
+    }
+}
+
+/// Implement `close_range` by listing the open file descriptors in
+/// `/proc/self/fd`.
+#[cfg(all(linux_kernel, feature = "procfs"))]
+unsafe fn close_range_fallback(first: u32, last: u32, flags: CloseRangeFlags) -> io::Result<()> {
+    use crate::fd::{AsRawFd, BorrowedFd};
+    use crate::fs::RawDir;
+    use crate::io::FdFlags;
+    use core::mem::MaybeUninit;
+
+    // Match the kernel, which rejects empty ranges.
+    if first > last {
+        return Err(io::Errno::INVAL);
+    }
+
+    // Open a new handle to the directory, rather than using the one `procfs`
+    // caches, which in a child process after `fork` still lists the parent's
+    // file descriptors. This also gives us our own directory position. It's
+    // closed when `dir` is dropped, after the walk.
+    let dir = crate::procfs::open_proc_self_fd()?;
+
+    // Use a buffer on the stack, so that this can be used in a child process
+    // between `fork` and `exec`.
+    let mut buf = [MaybeUninit::uninit(); 1024];
+    let mut iter = RawDir::new(&dir, &mut buf);
+    while let Some(entry) = iter.next() {
+        let fd = match parse_fd(entry?.file_name().to_bytes()) {
+            Some(fd) if (first..=last).contains(&fd) => fd as RawFd,
+            // Skip ".", "..", and anything outside the range.
+            _ => continue,
+        };
+        if fd == dir.as_raw_fd() || crate::procfs::is_static_fd(fd) {
+            continue;
+        }
+
+        if flags.contains(CloseRangeFlags::CLOEXEC) {
+            // Another thread may have closed the file descriptor since we
+            // read the directory.
+            match backend::io::syscalls::fcntl_setfd(BorrowedFd::borrow_raw(fd), FdFlags::CLOEXEC) {
+                Ok(()) | Err(io::Errno::BADF) => (),
+                Err(err) => return Err(err),
+            }
+        } else {
+            backend::io::syscalls::close(fd);
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse a file descriptor number from a `/proc/self/fd` entry name.
+#[cfg(all(linux_kernel, feature = "procfs"))]
+fn parse_fd(name: &[u8]) -> Option<u32> {
+    if name.is_empty() {
+        return None;
+    }
+    name.iter().try_fold(0_u32, |fd, byte| match byte {
+        b'0'..=b'9' => fd.checked_mul(10)?.checked_add(u32::from(byte - b'0')),
+        _ => None,
+    })
+}
+
+#[cfg(all(test, linux_kernel, feature = "procfs"))]
+#[test]
+fn test_close_range_fallback() {
+    use crate::fd::{BorrowedFd, IntoRawFd};
+    use crate::fs::{openat, Mode, OFlags, CWD};
+    use crate::io::{fcntl_dupfd_cloexec, fcntl_getfd, fcntl_setfd, FdFlags};
+
+    // Use file descriptors far above where the integration tests' file
+    // descriptors will be.
+    let file = openat(
+        CWD,
+        "Cargo.toml",
+        OFlags::RDONLY | OFlags::CLOEXEC,
+        Mode::empty(),
+    )
+    .unwrap();
+    for expected in 3900..3904 {
+        let fd = fcntl_dupfd_cloexec(&file, expected).unwrap();
+        fcntl_setfd(&fd, FdFlags::empty()).unwrap();
+        assert_eq!(fd.into_raw_fd(), expected);
+    }
+    let getfd = |fd| fcntl_getfd(unsafe { BorrowedFd::borrow_raw(fd) });
+
+    unsafe {
+        close_range_fallback(3900, 3901, CloseRangeFlags::CLOEXEC).unwrap();
+        assert_eq!(getfd(3900), Ok(FdFlags::CLOEXEC));
+        assert_eq!(getfd(3901), Ok(FdFlags::CLOEXEC));
+        assert_eq!(getfd(3902), Ok(FdFlags::empty()));
+
+        close_range_fallback(3901, 3902, CloseRangeFlags::empty()).unwrap();
+        assert_eq!(getfd(3900), Ok(FdFlags::CLOEXEC));
+        assert_eq!(getfd(3901), Err(io::Errno::BADF));
+        assert_eq!(getfd(3902), Err(io::Errno::BADF));
+        assert_eq!(getfd(3903), Ok(FdFlags::empty()));
+
+        assert_eq!(
+            close_range_fallback(3910, 3900, CloseRangeFlags::empty()),
+            Err(io::Errno::INVAL)
+        );
+
+        close_range_fallback(3900, 3950, CloseRangeFlags::empty()).unwrap();
+        assert_eq!(getfd(3900), Err(io::Errno::BADF));
+        assert_eq!(getfd(3903), Err(io::Errno::BADF));
+    }
+}
+
+#[cfg(all(test, linux_kernel, feature = "procfs"))]
+#[test]
+fn test_close_range_fallback_after_fork() {
+    use crate::fd::{AsRawFd, BorrowedFd, IntoRawFd};
+    use crate::fs::{openat, Mode, OFlags, CWD};
+    use crate::io::{fcntl_dupfd_cloexec, fcntl_getfd, fcntl_setfd, FdFlags};
+
+    // Make sure `procfs` has cached its handle to the parent's
+    // `/proc/self/fd`, which the child inherits.
+    crate::procfs::proc_self_fd().unwrap();
+
+    let file = openat(
+        CWD,
+        "Cargo.toml",
+        OFlags::RDONLY | OFlags::CLOEXEC,
+        Mode::empty(),
+    )
+    .unwrap();
+
+    unsafe {
+        match libc::fork() {
+            -1 => panic!("fork failed"),
+            0 => {
+                // Open a file descriptor that only the child has, so that
+                // listing the parent's file descriptors would miss it. Don't
+                // panic in the child; report the result in the exit status.
+                let ok = match fcntl_dupfd_cloexec(&file, 3920) {
+                    Ok(fd) if fd.as_raw_fd() == 3920 => {
+                        let fd = fd.into_raw_fd();
+                        let getfd = || fcntl_getfd(BorrowedFd::borrow_raw(fd));
+                        fcntl_setfd(BorrowedFd::borrow_raw(fd), FdFlags::empty()).is_ok()
+                            && close_range_fallback(3920, 3920, CloseRangeFlags::CLOEXEC).is_ok()
+                            && getfd() == Ok(FdFlags::CLOEXEC)
+                            && close_range_fallback(3920, 3920, CloseRangeFlags::empty()).is_ok()
+                            && getfd() == Err(io::Errno::BADF)
+                    }
+                    _ => false,
+                };
+                libc::_exit(if ok { 0 } else { 1 });
+            }
+            pid => {
+                let mut status = 0;
+                assert_eq!(libc::waitpid(pid, &mut status, 0), pid);
+                assert!(libc::WIFEXITED(status));
+                assert_eq!(libc::WEXITSTATUS(status), 0);
+            }
+        }
+    }
+}
@@ -5,6 +5,7 @@
 //! and we don't want unrecognized values to create UB.
 
 use crate::backend;
+use backend::io::errno::ERRNO_NAMES;
 use core::{fmt, result};
 #[cfg(feature = "std")]
 use std::error;
@@ -40,14 +41,110 @@ pub type Result<T> = result::Result<T, Errno>;
 pub use backend::io::errno::Errno;
 
 impl Errno {
-    /// Shorthand for `std::io::Error::from(self).kind()`.
+    /// Return the name of this error code, such as `"ENOENT"`.
+    ///
+    /// This doesn't depend on libc, so it works in `no_std` builds with the
+    /// linux_raw backend. It returns `None` for error codes rustix doesn't
+    /// have a constant for.
+    ///
+    /// When several names have the same value, this returns the platform's
+    /// primary name. On Linux, that's `"EAGAIN"` rather than
+    /// `"EWOULDBLOCK"`, `"EDEADLK"` rather than `"EDEADLOCK"`, and
+    /// `"EOPNOTSUPP"` rather than `"ENOTSUP"`. [`Errno::from_name`] accepts
+    /// all of them.
+    pub fn name(self) -> Option<&'static str> {
+        ERRNO_NAMES
+            .iter()
+            .find(|(errno, _, _)| *errno == self)
+            .map(|(_, name, _)| *name)
+    }
+
+    /// Return a description of this error code, such as
+    /// `"No such file or directory"`.
+    ///
+    /// The descriptions are those used by glibc's `strerror`, and don't
+    /// depend on libc or the locale. It returns `None` for error codes rustix
+    /// doesn't have a constant for.
+    pub fn description(self) -> Option<&'static str> {
+        ERRNO_NAMES
+            .iter()
+            .find(|(errno, _, _)| *errno == self)
+            .map(|(_, _, description)| *description)
+    }
+
+    /// Return the error code with the given name, such as `"ENOENT"`.
+    ///
+    /// Any of an error code's names are accepted, such as either `"EAGAIN"`
+    /// or `"EWOULDBLOCK"`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        ERRNO_NAMES
+            .iter()
+            .find(|(_, errno_name, _)| *errno_name == name)
+            .map(|(errno, _, _)| *errno)
+    }
+
+    /// Return the [`std::io::ErrorKind`] for this error code.
+    ///
+    /// This extends the mapping used by `std::io::Error` with kinds for
+    /// error codes that it leaves uncategorized, such as [`Errno::OPNOTSUPP`].
+    /// Converting an `Errno` into a `std::io::Error` keeps the raw error
+    /// code, so the `std::io::Error` has the kind `std` assigns it.
     #[cfg(feature = "std")]
-    #[inline]
     pub fn kind(self) -> std::io::ErrorKind {
-        std::io::Error::from(self).kind()
+        ERROR_KINDS
+            .iter()
+            .find(|(errno, _)| *errno == self)
+            .map_or_else(|| std::io::Error::from(self).kind(), |(_, kind)| *kind)
     }
 }
 
+/// [`std::io::ErrorKind`]s for error codes that `std::io::Error` leaves
+/// uncategorized.
+#[cfg(feature = "std")]
+static ERROR_KINDS: &[(Errno, std::io::ErrorKind)] = {
+    use std::io::ErrorKind;
+
+    &[
+        #[cfg(not(any(windows, target_os = "haiku", target_os = "redox")))]
+        (Errno::NOTSUP, ErrorKind::Unsupported),
+        (Errno::OPNOTSUPP, ErrorKind::Unsupported),
+        (Errno::AFNOSUPPORT, ErrorKind::Unsupported),
+        (Errno::PROTONOSUPPORT, ErrorKind::Unsupported),
+        (Errno::NOPROTOOPT, ErrorKind::Unsupported),
+        #[cfg(not(target_os = "wasi"))]
+        (Errno::PFNOSUPPORT, ErrorKind::Unsupported),
+        #[cfg(not(any(target_os = "haiku", target_os = "wasi")))]
+        (Errno::SOCKTNOSUPPORT, ErrorKind::Unsupported),
+        (Errno::NOBUFS, ErrorKind::OutOfMemory),
+        #[cfg(not(windows))]
+        (Errno::NXIO, ErrorKind::NotFound),
+        #[cfg(not(windows))]
+        (Errno::NODEV, ErrorKind::NotFound),
+        #[cfg(not(windows))]
+        (Errno::SRCH, ErrorKind::NotFound),
+        (Errno::FAULT, ErrorKind::InvalidInput),
+        (Errno::NOTSOCK, ErrorKind::InvalidInput),
+        (Errno::DESTADDRREQ, ErrorKind::InvalidInput),
+        (Errno::MSGSIZE, ErrorKind::InvalidInput),
+        #[cfg(not(windows))]
+        (Errno::NOTTY, ErrorKind::InvalidInput),
+        #[cfg(not(windows))]
+        (Errno::DOM, ErrorKind::InvalidInput),
+        #[cfg(not(windows))]
+        (Errno::ILSEQ, ErrorKind::InvalidData),
+        #[cfg(not(windows))]
+        (Errno::BADMSG, ErrorKind::InvalidData),
+        #[cfg(not(windows))]
+        (Errno::OVERFLOW, ErrorKind::InvalidData),
+        #[cfg(not(windows))]
+        (Errno::PROTO, ErrorKind::InvalidData),
+        #[cfg(not(target_os = "wasi"))]
+        (Errno::SHUTDOWN, ErrorKind::BrokenPipe),
+        #[cfg(not(any(freebsdlike, windows, target_os = "openbsd", target_os = "wasi")))]
+        (Errno::TIME, ErrorKind::TimedOut),
+    ]
+};
+
 impl fmt::Display for Errno {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         #[cfg(feature = "std")]
@@ -56,7 +153,12 @@ impl fmt::Display for Errno {
         }
         #[cfg(not(feature = "std"))]
         {
-            write!(fmt, "os error {}", self.raw_os_error())
+            match self.description() {
+                Some(description) => {
+                    write!(fmt, "{} (os error {})", description, self.raw_os_error())
+                }
+                None => write!(fmt, "os error {}", self.raw_os_error()),
+            }
         }
     }
 }
@@ -69,7 +171,10 @@ impl fmt::Debug for Errno {
         }
         #[cfg(not(feature = "std"))]
         {
-            write!(fmt, "os error {}", self.raw_os_error())
+            match self.name() {
+                Some(name) => write!(fmt, "{} (os error {})", name, self.raw_os_error()),
+                None => write!(fmt, "os error {}", self.raw_os_error()),
+            }
         }
     }
 }
@@ -5,6 +5,8 @@
 //! [`SeekFrom`]: https://docs.rs/rustix/*/rustix/fs/enum.SeekFrom.html
 //! [`fs`]: https://docs.rs/rustix/*/rustix/fs/index.html
 
+#[cfg(not(windows))]
+mod adapters;
 mod close;
 #[cfg(not(windows))]
 mod dup;
@@ -18,7 +20,13 @@ mod is_read_write;
 #[cfg(not(windows))]
 mod read_write;
 
+#[cfg(all(feature = "alloc", not(windows)))]
+pub use adapters::BufFdWriter;
+#[cfg(not(windows))]
+pub use adapters::{FdReader, FdWriter, IntoInnerError};
 pub use close::close;
+#[cfg(linux_kernel)]
+pub use close::{close_range, CloseRangeFlags};
 #[cfg(not(windows))]
 pub use dup::*;
 pub use errno::{retry_on_intr, Errno, Result};
@@ -1,7 +1,9 @@
 //! `read` and `write`, optionally positioned, optionally vectored
 
+use crate::utils::split_init;
 use crate::{backend, io};
 use backend::fd::AsFd;
+use core::mem::MaybeUninit;
 
 // Declare `IoSlice` and `IoSliceMut`.
 #[cfg(not(windows))]
@@ -32,9 +34,30 @@ pub use backend::io::types::ReadWriteFlags;
 /// [DragonFly BSD]: https://man.dragonflybsd.org/?command=read&section=2
 /// [illumos]: https://illumos.org/man/2/read
 /// [glibc]: https://www.gnu.org/software/libc/manual/html_node/I_002fO-Primitives.html#index-reading-from-a-file-descriptor
+#[allow(unsafe_code)]
 #[inline]
 pub fn read<Fd: AsFd>(fd: Fd, buf: &mut [u8]) -> io::Result<usize> {
-    backend::io::syscalls::read(fd.as_fd(), buf)
+    unsafe { backend::io::syscalls::read(fd.as_fd(), buf.as_mut_ptr(), buf.len()) }
+}
+
+/// `read(fd, buf)`—Reads from a stream into an uninitialized buffer.
+///
+/// This is equivalent to [`read`], except that it can read into
+/// uninitialized memory. It returns the slice that was initialized by this
+/// function and the slice that remains uninitialized.
+#[allow(unsafe_code)]
+#[inline]
+pub fn read_uninit<Fd: AsFd>(
+    fd: Fd,
+    buf: &mut [MaybeUninit<u8>],
+) -> io::Result<(&mut [u8], &mut [MaybeUninit<u8>])> {
+    // Get the number of initialized bytes.
+    let length = unsafe {
+        backend::io::syscalls::read(fd.as_fd(), buf.as_mut_ptr().cast::<u8>(), buf.len())?
+    };
+
+    // Split into the initialized and uninitialized portions.
+    Ok(unsafe { split_init(buf, length) })
 }
 
 /// `write(fd, buf)`—Writes to a stream.
@@ -84,9 +107,29 @@ pub fn write<Fd: AsFd>(fd: Fd, buf: &[u8]) -> io::Result<usize> {
 /// [OpenBSD]: https://man.openbsd.org/pread.2
 /// [DragonFly BSD]: https://man.dragonflybsd.org/?command=pread&section=2
 /// [illumos]: https://illumos.org/man/2/pread
+#[allow(unsafe_code)]
 #[inline]
 pub fn pread<Fd: AsFd>(fd: Fd, buf: &mut [u8], offset: u64) -> io::Result<usize> {
-    backend::io::syscalls::pread(fd.as_fd(), buf, offset)
+    unsafe { backend::io::syscalls::pread(fd.as_fd(), buf.as_mut_ptr(), buf.len(), offset) }
+}
+
+/// `pread(fd, buf, offset)`—Reads from a file at a given position into an
+/// uninitialized buffer.
+///
+/// This is equivalent to [`pread`], except that it can read into
+/// uninitialized memory. It returns the slice that was initialized by this
+/// function and the slice that remains uninitialized.
+#[allow(unsafe_code)]
+#[inline]
+pub fn pread_uninit<Fd: AsFd>(
+    fd: Fd,
+    buf: &mut [MaybeUninit<u8>],
+    offset: u64,
+) -> io::Result<(&mut [u8], &mut [MaybeUninit<u8>])> {
+    let length = unsafe {
+        backend::io::syscalls::pread(fd.as_fd(), buf.as_mut_ptr().cast::<u8>(), buf.len(), offset)?
+    };
+    Ok(unsafe { split_init(buf, length) })
 }
 
 /// `pwrite(fd, bufs)`—Writes to a file at a given position.
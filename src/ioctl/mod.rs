@@ -0,0 +1,240 @@
+//! Unsafe `ioctl` API.
+//!
+//! Unix systems expose a number of `ioctl`s. `ioctl`s have been adopted as a
+//! general-purpose system call for making calls into the kernel. In addition
+//! to the wide variety of system calls that are included by default in the
+//! kernel, many drivers expose their own `ioctl`s for controlling their
+//! behavior, some of which are proprietary. Therefore it is impossible to
+//! make a safe interface for every `ioctl` in existence.
+//!
+//! With that in mind, this module provides a handful of helper types for
+//! describing `ioctl`s, and a single unsafe function, [`ioctl`], for making
+//! them. rustix uses this to implement its own `ioctl`s, and users can use it
+//! to implement `ioctl`s rustix doesn't know about, while still getting
+//! rustix's error handling.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(target_os = "linux")]
+//! # mod example {
+//! use rustix::fd::AsFd;
+//! use rustix::ioctl::{ioctl, opcode, Getter};
+//!
+//! /// `BLKSSZGET`—Get the logical block size of a block device.
+//! pub fn blksszget<Fd: AsFd>(fd: Fd) -> rustix::io::Result<u32> {
+//!     // SAFETY: `BLKSSZGET` writes a 32-bit `int` to its argument.
+//!     unsafe {
+//!         let ctl = Getter::<{ opcode::none(0x12, 104) }, u32>::new();
+//!         ioctl(fd, ctl)
+//!     }
+//! }
+//! # }
+//! ```
+#![allow(unsafe_code)]
+
+use crate::backend::c;
+use crate::fd::{AsFd, BorrowedFd};
+use crate::{backend, io};
+
+#[cfg(any(linux_kernel, bsd))]
+pub mod opcode;
+mod patterns;
+
+pub use patterns::{Getter, IntegerSetter, NoArg, Setter, Updater};
+
+/// Perform an `ioctl` call.
+///
+/// `ioctl` was originally intended to act as a way of modifying the behavior
+/// of files, but has since been adopted as a general purpose system call for
+/// making calls into the kernel. In addition to the default calls exposed by
+/// generic file descriptors, many drivers expose their own `ioctl` calls for
+/// controlling their behavior, some of which are proprietary.
+///
+/// This crate exposes many other `ioctl` interfaces with safe and idiomatic
+/// wrappers, like [`ioctl_fionbio`] and [`ioctl_fionread`]. It is
+/// recommended to use those instead of this function, as they are safer and
+/// more idiomatic. For other cases, implement the [`Ioctl`] trait and pass it
+/// to this function.
+///
+/// See documentation for [`Ioctl`] for more information.
+///
+/// [`ioctl_fionbio`]: crate::io::ioctl_fionbio
+/// [`ioctl_fionread`]: crate::io::ioctl_fionread
+///
+/// # Safety
+///
+/// While [`Ioctl`] takes much of the unsafety out of `ioctl` calls, callers
+/// must still ensure that the opcode value, operand type, and data type are
+/// valid for the underlying device. Callers must also ensure that the
+/// operation doesn't violate any invariants of the file descriptor, such as
+/// by closing it or changing the object it refers to.
+///
+/// # References
+///  - [Linux]
+///  - [FreeBSD]
+///  - [NetBSD]
+///  - [OpenBSD]
+///  - [Apple]
+///  - [Solaris]
+///  - [illumos]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/ioctl.2.html
+/// [FreeBSD]: https://man.freebsd.org/cgi/man.cgi?query=ioctl&sektion=2
+/// [NetBSD]: https://man.netbsd.org/ioctl.2
+/// [OpenBSD]: https://man.openbsd.org/ioctl.2
+/// [Apple]: https://developer.apple.com/library/archive/documentation/System/Conceptual/ManPages_iPhoneOS/man2/ioctl.2.html
+/// [Solaris]: https://docs.oracle.com/cd/E23824_01/html/821-1463/ioctl-2.html
+/// [illumos]: https://illumos.org/man/2/ioctl
+#[inline]
+pub unsafe fn ioctl<F: AsFd, I: Ioctl>(fd: F, mut ioctl: I) -> io::Result<I::Output> {
+    let fd = fd.as_fd();
+    let request = I::OPCODE;
+    let arg = ioctl.as_ptr();
+
+    // SAFETY: The variant of `Ioctl` asserts that this is a valid IOCTL call
+    // to make.
+    let output = if I::IS_MUTATING {
+        _ioctl(fd, request, arg)?
+    } else {
+        _ioctl_readonly(fd, request, arg)?
+    };
+
+    // SAFETY: The variant of `Ioctl` asserts that this is a valid pointer to
+    // the output data.
+    I::output_from_ptr(output, arg)
+}
+
+unsafe fn _ioctl(
+    fd: BorrowedFd<'_>,
+    request: Opcode,
+    arg: *mut c::c_void,
+) -> io::Result<IoctlOutput> {
+    backend::io::syscalls::ioctl(fd, request, arg)
+}
+
+unsafe fn _ioctl_readonly(
+    fd: BorrowedFd<'_>,
+    request: Opcode,
+    arg: *mut c::c_void,
+) -> io::Result<IoctlOutput> {
+    backend::io::syscalls::ioctl_readonly(fd, request, arg)
+}
+
+/// A trait defining the properties of an `ioctl` command.
+///
+/// Objects implementing this trait can be passed to [`ioctl`] to make an
+/// `ioctl` call. The contents of the object represent the inputs to the
+/// `ioctl` call. The inputs must be convertible to a pointer through the
+/// `as_ptr` method. In most cases, this involves either casting a number to a
+/// pointer, or creating a pointer to the actual data. The latter case is
+/// necessary for `ioctl` calls that modify userspace data.
+///
+/// For most `ioctl`s, one of the types in this module, such as [`Getter`] or
+/// [`Setter`], can be used instead of implementing this trait directly.
+///
+/// # Safety
+///
+/// This trait is unsafe to implement because it is impossible to guarantee
+/// that the `ioctl` call is safe. The `ioctl` call may be proprietary, or it
+/// may be unsafe to call in certain circumstances.
+///
+/// By implementing this trait, you guarantee that:
+///
+///  - The `ioctl` call expects the input provided by `as_ptr` and produces
+///    the output as indicated by `output`.
+///  - That `output_from_ptr` can safely take the pointer from `as_ptr` and
+///    cast it to the correct type, *only* after the `ioctl` call.
+///  - That the return value of `as_ptr` can be safely passed to `ioctl`.
+///  - That `IS_MUTATING` is `true` if the `ioctl` call may modify memory
+///    through the pointer.
+pub unsafe trait Ioctl {
+    /// The type of the output data.
+    ///
+    /// Given a pointer, one should be able to construct an instance of this
+    /// type.
+    type Output;
+
+    /// The opcode used by this `ioctl` command.
+    ///
+    /// There are different types of opcodes depending on the operation. See
+    /// the [`opcode`] module for ways to construct them.
+    const OPCODE: Opcode;
+
+    /// Does the `ioctl` mutate any data in the userspace?
+    ///
+    /// If the `ioctl` call does not mutate any data in the userspace, then
+    /// making this `false` enables optimizations that can make the call
+    /// faster. When in doubt, set this to `true`.
+    const IS_MUTATING: bool;
+
+    /// Get a pointer to the data to be passed to the `ioctl` command.
+    ///
+    /// See trait-level documentation for more information.
+    fn as_ptr(&mut self) -> *mut c::c_void;
+
+    /// Cast the output data to the correct type.
+    ///
+    /// # Safety
+    ///
+    /// The `extract_output` value must be the resulting value after a
+    /// successful `ioctl` call, and `out` is the direct return value of an
+    /// `ioctl` call that did not fail. In this case `extract_output` is the
+    /// pointer that was passed to the `ioctl` call.
+    unsafe fn output_from_ptr(
+        out: IoctlOutput,
+        extract_output: *mut c::c_void,
+    ) -> io::Result<Self::Output>;
+}
+
+/// The direction that an `ioctl` is going.
+///
+/// The direction is relative to userspace: `Read` means that the kernel
+/// writes data for userspace to read, and `Write` means that userspace writes
+/// data for the kernel to read.
+#[cfg(any(linux_kernel, bsd))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// None of the above.
+    None,
+
+    /// Read data from the kernel.
+    Read,
+
+    /// Write data to the kernel.
+    Write,
+
+    /// Read and write data to the kernel.
+    ReadWrite,
+}
+
+/// The type used by the `ioctl` to signify the output.
+pub type IoctlOutput = c::c_int;
+
+/// The type used by the `ioctl` to signify the command.
+#[cfg(linux_raw)]
+pub type Opcode = c::c_uint;
+
+/// The type used by the `ioctl` to signify the command.
+#[cfg(all(libc, linux_kernel))]
+pub type Opcode = c::Ioctl;
+
+/// The type used by the `ioctl` to signify the command.
+#[cfg(all(
+    libc,
+    any(bsd, target_os = "haiku", target_os = "hurd", target_os = "redox",)
+))]
+pub type Opcode = c::c_ulong;
+
+/// The type used by the `ioctl` to signify the command.
+#[cfg(all(
+    libc,
+    not(any(
+        linux_kernel,
+        bsd,
+        target_os = "haiku",
+        target_os = "hurd",
+        target_os = "redox",
+    ))
+))]
+pub type Opcode = c::c_int;
@@ -0,0 +1,177 @@
+//! Const functions for computing `ioctl` opcodes.
+//!
+//! Most `ioctl` opcodes encode the direction of the data transfer, a group
+//! (or "type") byte, a number within the group, and the size of the data in
+//! a single integer, as with the C `_IO`, `_IOR`, `_IOW`, and `_IOWR` macros.
+//! The layout of that integer differs between platforms, and between
+//! architectures on Linux, so these functions compute it the way the
+//! platform's headers do.
+//!
+//! Some older `ioctl`s, such as `FIONREAD` on Linux, don't follow this
+//! scheme; use their raw values as [`Opcode`]s directly.
+
+use super::{Direction, Opcode};
+use core::mem::size_of;
+
+#[cfg(bsd)]
+use bsd as platform;
+#[cfg(linux_kernel)]
+use linux as platform;
+
+/// Create a new opcode from a direction, group, number, and size.
+///
+/// This corresponds to the C macro `_IOC(direction, group, number, size)`.
+///
+/// # Panics
+///
+/// This panics, or fails to compile when used in a const context, if `size`
+/// is too big to be encoded in an opcode.
+#[doc(alias = "_IOC")]
+#[inline]
+pub const fn from_components(
+    direction: Direction,
+    group: u8,
+    number: u8,
+    data_size: usize,
+) -> Opcode {
+    assert!(
+        data_size <= platform::SIZE_MAX,
+        "data size is too big to encode in an ioctl opcode"
+    );
+
+    let direction = match direction {
+        Direction::None => platform::NONE,
+        Direction::Read => platform::READ,
+        Direction::Write => platform::WRITE,
+        Direction::ReadWrite => platform::READ | platform::WRITE,
+    };
+
+    let opcode = (direction << platform::DIR_SHIFT)
+        | ((data_size as u32) << platform::SIZE_SHIFT)
+        | ((group as u32) << platform::GROUP_SHIFT)
+        | ((number as u32) << platform::NUMBER_SHIFT);
+    opcode as Opcode
+}
+
+/// Create a new opcode for an `ioctl` with no data.
+///
+/// This corresponds to the C macro `_IO(group, number)`.
+#[doc(alias = "_IO")]
+#[inline]
+pub const fn none(group: u8, number: u8) -> Opcode {
+    from_components(Direction::None, group, number, 0)
+}
+
+/// Create a new opcode for an `ioctl` where the kernel writes a `T` for
+/// userspace to read.
+///
+/// This corresponds to the C macro `_IOR(group, number, T)`.
+///
+/// # Panics
+///
+/// This panics, or fails to compile when used in a const context, if `T` is
+/// too big to be encoded in an opcode.
+#[doc(alias = "_IOR")]
+#[inline]
+pub const fn read<T>(group: u8, number: u8) -> Opcode {
+    from_components(Direction::Read, group, number, size_of::<T>())
+}
+
+/// Create a new opcode for an `ioctl` where userspace writes a `T` for the
+/// kernel to read.
+///
+/// This corresponds to the C macro `_IOW(group, number, T)`.
+///
+/// # Panics
+///
+/// This panics, or fails to compile when used in a const context, if `T` is
+/// too big to be encoded in an opcode.
+#[doc(alias = "_IOW")]
+#[inline]
+pub const fn write<T>(group: u8, number: u8) -> Opcode {
+    from_components(Direction::Write, group, number, size_of::<T>())
+}
+
+/// Create a new opcode for an `ioctl` where a `T` is passed to the kernel,
+/// which may modify it.
+///
+/// This corresponds to the C macro `_IOWR(group, number, T)`.
+///
+/// # Panics
+///
+/// This panics, or fails to compile when used in a const context, if `T` is
+/// too big to be encoded in an opcode.
+#[doc(alias = "_IOWR")]
+#[inline]
+pub const fn read_write<T>(group: u8, number: u8) -> Opcode {
+    from_components(Direction::ReadWrite, group, number, size_of::<T>())
+}
+
+/// The layout of `_IOC` in Linux's `asm/ioctl.h` and
+/// `asm-generic/ioctl.h`.
+#[cfg(linux_kernel)]
+mod linux {
+    pub(super) const NUMBER_SHIFT: u32 = 0;
+    pub(super) const GROUP_SHIFT: u32 = 8;
+    pub(super) const SIZE_SHIFT: u32 = 16;
+
+    #[cfg(any(
+        target_arch = "mips",
+        target_arch = "mips32r6",
+        target_arch = "mips64",
+        target_arch = "mips64r6",
+        target_arch = "powerpc",
+        target_arch = "powerpc64",
+        target_arch = "sparc",
+        target_arch = "sparc64",
+    ))]
+    mod arch {
+        pub(in super::super) const NONE: u32 = 1;
+        pub(in super::super) const READ: u32 = 2;
+        pub(in super::super) const WRITE: u32 = 4;
+        pub(in super::super) const SIZE_BITS: u32 = 13;
+    }
+
+    #[cfg(not(any(
+        target_arch = "mips",
+        target_arch = "mips32r6",
+        target_arch = "mips64",
+        target_arch = "mips64r6",
+        target_arch = "powerpc",
+        target_arch = "powerpc64",
+        target_arch = "sparc",
+        target_arch = "sparc64",
+    )))]
+    mod arch {
+        pub(in super::super) const NONE: u32 = 0;
+        pub(in super::super) const READ: u32 = 2;
+        pub(in super::super) const WRITE: u32 = 1;
+        pub(in super::super) const SIZE_BITS: u32 = 14;
+    }
+
+    pub(super) use arch::{NONE, READ, WRITE};
+
+    pub(super) const DIR_SHIFT: u32 = SIZE_SHIFT + arch::SIZE_BITS;
+    pub(super) const SIZE_MAX: usize = (1 << arch::SIZE_BITS) - 1;
+}
+
+/// The layout of `_IOC` in the BSDs' `sys/ioccom.h`.
+///
+/// The BSDs encode the direction with separate bits rather than a bitfield,
+/// so `DIR_SHIFT` is zero.
+#[cfg(bsd)]
+mod bsd {
+    pub(super) const NUMBER_SHIFT: u32 = 0;
+    pub(super) const GROUP_SHIFT: u32 = 8;
+    pub(super) const SIZE_SHIFT: u32 = 16;
+    pub(super) const DIR_SHIFT: u32 = 0;
+
+    /// `IOC_VOID`
+    pub(super) const NONE: u32 = 0x2000_0000;
+    /// `IOC_OUT`
+    pub(super) const READ: u32 = 0x4000_0000;
+    /// `IOC_IN`
+    pub(super) const WRITE: u32 = 0x8000_0000;
+    /// `IOCPARM_MASK`
+    pub(super) const SIZE_MAX: usize = 0x1fff;
+}
@@ -59,7 +59,9 @@
 //!  - Multiplexed functions (eg. `fcntl`, `ioctl`, etc.) are de-multiplexed.
 //!  - Variadic functions (eg. `openat`, etc.) are presented as non-variadic.
 //!  - Functions that return strings automatically allocate sufficient memory
-//!    and retry the syscall as needed to determine the needed length.
+//!    and retry the syscall as needed to determine the needed length. With
+//!    the `alloc` feature disabled, `_into` variants which write into
+//!    caller-provided buffers are available instead.
 //!  - Functions and types which need `l` prefixes or `64` suffixes to enable
 //!    large-file support (LFS) are used automatically. File sizes and offsets
 //!    are always presented as `u64` and `i64`.
@@ -124,7 +126,7 @@
 // precisely conditionallizing all the `use`s for them.
 #![cfg_attr(any(target_os = "redox", target_os = "wasi"), allow(unused_imports))]
 
-#[cfg(not(feature = "rustc-dep-of-std"))]
+#[cfg(all(feature = "alloc", not(feature = "rustc-dep-of-std")))]
 extern crate alloc;
 
 // Internal utilities.
@@ -187,6 +189,8 @@ pub mod io;
 #[cfg_attr(doc_cfg, doc(cfg(feature = "io_uring")))]
 pub mod io_uring;
 #[cfg(not(any(windows, target_os = "wasi")))]
+pub mod ioctl;
+#[cfg(not(any(windows, target_os = "wasi")))]
 #[cfg(feature = "mm")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "mm")))]
 pub mod mm;
@@ -258,6 +262,7 @@ pub mod runtime;
 mod clockid;
 #[cfg(not(any(windows, target_os = "wasi")))]
 #[cfg(any(
+    feature = "fs",
     feature = "procfs",
     feature = "process",
     feature = "runtime",
@@ -9,6 +9,7 @@ use crate::backend::c;
 #[cfg(not(linux_raw))]
 use c::size_t as __kernel_size_t;
 use core::marker::PhantomData;
+use core::ops::Deref;
 use core::slice;
 #[cfg(linux_raw)]
 use linux_raw_sys::general::__kernel_size_t;
@@ -56,6 +57,15 @@ impl<'a> IoSlice<'a> {
     }
 }
 
+impl<'a> Deref for IoSlice<'a> {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
 /// <https://doc.rust-lang.org/stable/std/io/struct.IoSliceMut.html>
 #[repr(transparent)]
 pub struct IoSliceMut<'a> {
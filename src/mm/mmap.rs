@@ -313,3 +313,36 @@ pub unsafe fn mlock_with(ptr: *mut c_void, len: usize, flags: MlockFlags) -> io:
 pub unsafe fn munlock(ptr: *mut c_void, len: usize) -> io::Result<()> {
     backend::mm::syscalls::munlock(ptr, len)
 }
+
+/// `mincore(ptr, len, vec)`—Determine which pages of a mapping are resident in
+/// memory.
+///
+/// On success, the least significant bit of each byte of `vec` is set if the
+/// corresponding page is resident.
+///
+/// For a file mapping, this reports whether the file's pages are in the page
+/// cache, regardless of whether they've been touched through this mapping.
+///
+/// # Safety
+///
+/// `ptr` must be page-aligned.
+///
+/// # Errors
+///
+/// `vec` must have at least one byte for each page in the range, rounding
+/// `len` up to a whole number of pages. If it's too short, this fails with
+/// [`io::Errno::INVAL`].
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man2/mincore.2.html
+#[cfg(linux_kernel)]
+#[inline]
+pub unsafe fn mincore(ptr: *mut c_void, len: usize, vec: &mut [u8]) -> io::Result<()> {
+    let page_size = backend::param::auxv::page_size();
+    if vec.len() < len / page_size + usize::from(len % page_size != 0) {
+        return Err(io::Errno::INVAL);
+    }
+    backend::mm::syscalls::mincore(ptr, len, vec)
+}
@@ -3,8 +3,10 @@
 #[cfg(unix)]
 use crate::net::SocketAddrUnix;
 use crate::net::{SocketAddr, SocketAddrAny, SocketAddrV4, SocketAddrV6};
+use crate::utils::split_init;
 use crate::{backend, io};
 use backend::fd::{AsFd, BorrowedFd};
+use core::mem::MaybeUninit;
 
 pub use backend::net::send_recv::{RecvFlags, SendFlags};
 
@@ -40,9 +42,35 @@ pub use msg::*;
 /// [DragonFly BSD]: https://man.dragonflybsd.org/?command=recv&section=2
 /// [illumos]: https://illumos.org/man/3SOCKET/recv
 /// [glibc]: https://www.gnu.org/software/libc/manual/html_node/Receiving-Data.html
+#[allow(unsafe_code)]
 #[inline]
 pub fn recv<Fd: AsFd>(fd: Fd, buf: &mut [u8], flags: RecvFlags) -> io::Result<usize> {
-    backend::net::syscalls::recv(fd.as_fd(), buf, flags)
+    unsafe { backend::net::syscalls::recv(fd.as_fd(), buf.as_mut_ptr(), buf.len(), flags) }
+}
+
+/// `recv(fd, buf, flags)`—Reads data from a socket into an uninitialized
+/// buffer.
+///
+/// This is equivalent to [`recv`], except that it can read into
+/// uninitialized memory. It returns the slice that was initialized by this
+/// function, the slice that remains uninitialized, and the length that `recv`
+/// returned.
+///
+/// The length is usually the length of the initialized slice, but with
+/// [`RecvFlags::TRUNC`], it's the length of the whole datagram, which may be
+/// longer than `buf`.
+#[allow(unsafe_code, clippy::type_complexity)]
+#[inline]
+pub fn recv_uninit<Fd: AsFd>(
+    fd: Fd,
+    buf: &mut [MaybeUninit<u8>],
+    flags: RecvFlags,
+) -> io::Result<(&mut [u8], &mut [MaybeUninit<u8>], usize)> {
+    let length = unsafe {
+        backend::net::syscalls::recv(fd.as_fd(), buf.as_mut_ptr().cast::<u8>(), buf.len(), flags)?
+    };
+    let (init, uninit) = unsafe { split_init(buf, length.min(buf.len())) };
+    Ok((init, uninit, length))
 }
 
 /// `send(fd, buf, flags)`—Writes data to a socket.
@@ -103,13 +131,48 @@ pub fn send<Fd: AsFd>(fd: Fd, buf: &[u8], flags: SendFlags) -> io::Result<usize>
 /// [DragonFly BSD]: https://man.dragonflybsd.org/?command=recvfrom&section=2
 /// [illumos]: https://illumos.org/man/3SOCKET/recvfrom
 /// [glibc]: https://www.gnu.org/software/libc/manual/html_node/Receiving-Datagrams.html
+#[allow(unsafe_code)]
 #[inline]
 pub fn recvfrom<Fd: AsFd>(
     fd: Fd,
     buf: &mut [u8],
     flags: RecvFlags,
 ) -> io::Result<(usize, Option<SocketAddrAny>)> {
-    backend::net::syscalls::recvfrom(fd.as_fd(), buf, flags)
+    unsafe { backend::net::syscalls::recvfrom(fd.as_fd(), buf.as_mut_ptr(), buf.len(), flags) }
+}
+
+/// `recvfrom(fd, buf, flags, addr, len)`—Reads data from a socket and
+/// returns the sender address, into an uninitialized buffer.
+///
+/// This is equivalent to [`recvfrom`], except that it can read into
+/// uninitialized memory. It returns the slice that was initialized by this
+/// function, the slice that remains uninitialized, the length that
+/// `recvfrom` returned, and the sender address.
+///
+/// As with [`recv_uninit`], the length may be longer than `buf` when
+/// [`RecvFlags::TRUNC`] is used.
+#[allow(unsafe_code, clippy::type_complexity)]
+#[inline]
+pub fn recvfrom_uninit<Fd: AsFd>(
+    fd: Fd,
+    buf: &mut [MaybeUninit<u8>],
+    flags: RecvFlags,
+) -> io::Result<(
+    &mut [u8],
+    &mut [MaybeUninit<u8>],
+    usize,
+    Option<SocketAddrAny>,
+)> {
+    let (length, addr) = unsafe {
+        backend::net::syscalls::recvfrom(
+            fd.as_fd(),
+            buf.as_mut_ptr().cast::<u8>(),
+            buf.len(),
+            flags,
+        )?
+    };
+    let (init, uninit) = unsafe { split_init(buf, length.min(buf.len())) };
+    Ok((init, uninit, length, addr))
 }
 
 /// `sendto(fd, buf, flags, addr)`—Writes data to a socket to a specific IP
@@ -5,15 +5,20 @@
 //! to rustix APIs with string arguments, and it allows rustix to implement
 //! NUL-termination without the need for copying where possible.
 
-use crate::ffi::{CStr, CString};
+use crate::ffi::CStr;
+#[cfg(feature = "alloc")]
+use crate::ffi::CString;
 use crate::io;
 #[cfg(feature = "itoa")]
 use crate::path::DecInt;
 use crate::path::SMALL_PATH_BUFFER_SIZE;
+#[cfg(feature = "alloc")]
 use alloc::borrow::Cow;
-#[cfg(feature = "itoa")]
+#[cfg(all(feature = "alloc", feature = "itoa"))]
 use alloc::borrow::ToOwned;
+#[cfg(feature = "alloc")]
 use alloc::string::String;
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 use core::mem::MaybeUninit;
 use core::{ptr, slice, str};
@@ -67,13 +72,16 @@ pub trait Arg {
 
     /// Returns a potentially-lossy rendering of this string as a `Cow<'_,
     /// str>`.
+    #[cfg(feature = "alloc")]
     fn to_string_lossy(&self) -> Cow<'_, str>;
 
     /// Returns a view of this string as a maybe-owned [`CStr`].
+    #[cfg(feature = "alloc")]
     fn as_cow_c_str(&self) -> io::Result<Cow<'_, CStr>>;
 
     /// Consumes `self` and returns a view of this string as a maybe-owned
     /// [`CStr`].
+    #[cfg(feature = "alloc")]
     fn into_c_str<'b>(self) -> io::Result<Cow<'b, CStr>>
     where
         Self: 'b;
@@ -91,11 +99,13 @@ impl Arg for &str {
         Ok(self)
     }
 
+    #[cfg(feature = "alloc")]
     #[inline]
     fn to_string_lossy(&self) -> Cow<'_, str> {
         Cow::Borrowed(self)
     }
 
+    #[cfg(feature = "alloc")]
     #[inline]
     fn as_cow_c_str(&self) -> io::Result<Cow<'_, CStr>> {
         Ok(Cow::Owned(
@@ -103,6 +113,7 @@ impl Arg for &str {
         ))
     }
 
+    #[cfg(feature = "alloc")]
     #[inline]
     fn into_c_str<'b>(self) -> io::Result<Cow<'b, CStr>>
     where
@@ -123,6 +134,7 @@ impl Arg for &str {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Arg for &String {
     #[inline]
     fn as_str(&self) -> io::Result<&str> {
@@ -159,6 +171,7 @@ impl Arg for &String {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Arg for String {
     #[inline]
     fn as_str(&self) -> io::Result<&str> {
@@ -441,16 +454,19 @@ impl Arg for &CStr {
         self.to_str().map_err(|_utf8_err| io::Errno::INVAL)
     }
 
+    #[cfg(feature = "alloc")]
     #[inline]
     fn to_string_lossy(&self) -> Cow<'_, str> {
         CStr::to_string_lossy(self)
     }
 
+    #[cfg(feature = "alloc")]
     #[inline]
     fn as_cow_c_str(&self) -> io::Result<Cow<'_, CStr>> {
         Ok(Cow::Borrowed(self))
     }
 
+    #[cfg(feature = "alloc")]
     #[inline]
     fn into_c_str<'b>(self) -> io::Result<Cow<'b, CStr>>
     where
@@ -469,6 +485,7 @@ impl Arg for &CStr {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Arg for &CString {
     #[inline]
     fn as_str(&self) -> io::Result<&str> {
@@ -503,6 +520,7 @@ impl Arg for &CString {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Arg for CString {
     #[inline]
     fn as_str(&self) -> io::Result<&str> {
@@ -537,6 +555,7 @@ impl Arg for CString {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a> Arg for Cow<'a, str> {
     #[inline]
     fn as_str(&self) -> io::Result<&str> {
@@ -622,6 +641,7 @@ impl<'a> Arg for Cow<'a, OsStr> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a> Arg for Cow<'a, CStr> {
     #[inline]
     fn as_str(&self) -> io::Result<&str> {
@@ -784,11 +804,13 @@ impl Arg for &[u8] {
         str::from_utf8(self).map_err(|_utf8_err| io::Errno::INVAL)
     }
 
+    #[cfg(feature = "alloc")]
     #[inline]
     fn to_string_lossy(&self) -> Cow<'_, str> {
         String::from_utf8_lossy(self)
     }
 
+    #[cfg(feature = "alloc")]
     #[inline]
     fn as_cow_c_str(&self) -> io::Result<Cow<'_, CStr>> {
         Ok(Cow::Owned(
@@ -796,6 +818,7 @@ impl Arg for &[u8] {
         ))
     }
 
+    #[cfg(feature = "alloc")]
     #[inline]
     fn into_c_str<'b>(self) -> io::Result<Cow<'b, CStr>>
     where
@@ -816,6 +839,7 @@ impl Arg for &[u8] {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Arg for &Vec<u8> {
     #[inline]
     fn as_str(&self) -> io::Result<&str> {
@@ -854,6 +878,7 @@ impl Arg for &Vec<u8> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Arg for Vec<u8> {
     #[inline]
     fn as_str(&self) -> io::Result<&str> {
@@ -899,16 +924,19 @@ impl Arg for DecInt {
         Ok(self.as_str())
     }
 
+    #[cfg(feature = "alloc")]
     #[inline]
     fn to_string_lossy(&self) -> Cow<'_, str> {
         Cow::Borrowed(self.as_str())
     }
 
+    #[cfg(feature = "alloc")]
     #[inline]
     fn as_cow_c_str(&self) -> io::Result<Cow<'_, CStr>> {
         Ok(Cow::Borrowed(self.as_c_str()))
     }
 
+    #[cfg(feature = "alloc")]
     #[inline]
     fn into_c_str<'b>(self) -> io::Result<Cow<'b, CStr>>
     where
@@ -970,6 +998,7 @@ where
 
 /// The slow path which handles any length. In theory OS's only support up
 /// to `PATH_MAX`, but we let the OS enforce that.
+#[cfg(feature = "alloc")]
 #[cold]
 fn with_c_str_slow_path<T, F>(bytes: &[u8], f: F) -> io::Result<T>
 where
@@ -977,3 +1006,15 @@ where
 {
     f(&CString::new(bytes).map_err(|_cstr_err| io::Errno::INVAL)?)
 }
+
+/// The slow path which handles any length. Without `alloc`, there's nowhere
+/// to put a string which doesn't fit in the small buffer, so fail as if it
+/// were too long for the OS.
+#[cfg(not(feature = "alloc"))]
+#[cold]
+fn with_c_str_slow_path<T, F>(_bytes: &[u8], _f: F) -> io::Result<T>
+where
+    F: FnOnce(&CStr) -> io::Result<T>,
+{
+    Err(io::Errno::NAMETOOLONG)
+}
@@ -3,11 +3,9 @@ use crate::backend::fd::AsFd;
 #[cfg(any(feature = "fs", not(target_os = "fuchsia")))]
 use crate::{backend, io};
 #[cfg(feature = "fs")]
-use {
-    crate::ffi::{CStr, CString},
-    crate::path::{self, SMALL_PATH_BUFFER_SIZE},
-    alloc::vec::Vec,
-};
+use {crate::ffi::CStr, crate::path, core::mem::MaybeUninit};
+#[cfg(all(feature = "alloc", feature = "fs"))]
+use {crate::ffi::CString, crate::path::SMALL_PATH_BUFFER_SIZE, alloc::vec::Vec};
 
 /// `chdir(path)`—Change the current working directory.
 ///
@@ -48,15 +46,15 @@ pub fn fchdir<Fd: AsFd>(fd: Fd) -> io::Result<()> {
 ///
 /// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/getcwd.html
 /// [Linux]: https://man7.org/linux/man-pages/man3/getcwd.3.html
-#[cfg(feature = "fs")]
+#[cfg(all(feature = "alloc", feature = "fs"))]
 #[cfg(not(target_os = "wasi"))]
-#[cfg_attr(doc_cfg, doc(cfg(feature = "fs")))]
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "alloc", feature = "fs"))))]
 #[inline]
 pub fn getcwd<B: Into<Vec<u8>>>(reuse: B) -> io::Result<CString> {
     _getcwd(reuse.into())
 }
 
-#[cfg(feature = "fs")]
+#[cfg(all(feature = "alloc", feature = "fs"))]
 #[allow(unsafe_code)]
 fn _getcwd(mut buffer: Vec<u8>) -> io::Result<CString> {
     buffer.clear();
@@ -88,3 +86,28 @@ fn _getcwd(mut buffer: Vec<u8>) -> io::Result<CString> {
         }
     }
 }
+
+/// `getcwd`—Return the current working directory, without allocating.
+///
+/// The path is written to `buf`, including its NUL terminator, and returned
+/// as a `CStr` borrowed from `buf`. If `buf` is too small, this fails with
+/// [`io::Errno::RANGE`]; the call may be retried with a larger buffer.
+///
+/// # References
+///  - [POSIX]
+///  - [Linux]
+///
+/// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/getcwd.html
+/// [Linux]: https://man7.org/linux/man-pages/man3/getcwd.3.html
+#[cfg(feature = "fs")]
+#[cfg(not(target_os = "wasi"))]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "fs")))]
+#[allow(unsafe_code)]
+#[inline]
+pub fn getcwd_into(buf: &mut [MaybeUninit<u8>]) -> io::Result<&CStr> {
+    backend::process::syscalls::getcwd(buf)?;
+
+    // SAFETY: `getcwd` wrote a NUL-terminated string into `buf`, as in
+    // `_getcwd`.
+    unsafe { Ok(CStr::from_ptr(buf.as_ptr().cast())) }
+}
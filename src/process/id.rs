@@ -8,6 +8,7 @@
 #![allow(unsafe_code)]
 
 use crate::{backend, io};
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 #[cfg(linux_kernel)]
 use backend::process::types::RawCpuid;
@@ -211,6 +212,7 @@ pub fn setsid() -> io::Result<Pid> {
 ///
 /// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/getgroups.html
 /// [Linux]: https://man7.org/linux/man-pages/man2/getgroups.2.html
+#[cfg(feature = "alloc")]
 pub fn getgroups() -> io::Result<Vec<Gid>> {
     let mut buffer = Vec::new();
 
@@ -220,15 +222,41 @@ pub fn getgroups() -> io::Result<Vec<Gid>> {
     buffer.resize(buffer.capacity(), Gid::ROOT);
 
     loop {
-        let ngroups = backend::process::syscalls::getgroups(&mut buffer)?;
-
-        let ngroups = ngroups as usize;
-        assert!(ngroups <= buffer.len());
-        if ngroups < buffer.len() {
-            buffer.resize(ngroups, Gid::ROOT);
-            return Ok(buffer);
+        match getgroups_into(&mut buffer) {
+            Ok(groups) => {
+                let ngroups = groups.len();
+                buffer.truncate(ngroups);
+                return Ok(buffer);
+            }
+            Err(io::Errno::RANGE) => {
+                buffer.reserve(1); // use `Vec` reallocation strategy to grow capacity exponentially
+                buffer.resize(buffer.capacity(), Gid::ROOT);
+            }
+            Err(errno) => return Err(errno),
         }
-        buffer.reserve(1); // use `Vec` reallocation strategy to grow capacity exponentially
-        buffer.resize(buffer.capacity(), Gid::ROOT);
+    }
+}
+
+/// `getgroups()`—Return a list of the current user's groups, without
+/// allocating.
+///
+/// The groups are written to `buf`, and returned as a slice borrowed from
+/// `buf`. If `buf` is too small, this fails with [`io::Errno::RANGE`]; the
+/// call may be retried with a larger buffer.
+///
+/// # References
+///  - [POSIX]
+///  - [Linux]
+///
+/// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/getgroups.html
+/// [Linux]: https://man7.org/linux/man-pages/man2/getgroups.2.html
+pub fn getgroups_into(buf: &mut [Gid]) -> io::Result<&[Gid]> {
+    match backend::process::syscalls::getgroups(buf) {
+        // With an empty buffer, `getgroups` returns the number of groups
+        // without writing any.
+        Ok(ngroups) if ngroups <= buf.len() => Ok(&buf[..ngroups]),
+        // `getgroups` fails with `EINVAL` if the buffer is too small.
+        Ok(_) | Err(io::Errno::INVAL) => Err(io::Errno::RANGE),
+        Err(errno) => Err(errno),
     }
 }
@@ -17,19 +17,26 @@
 //! to succeed with bogus results.
 
 use crate::backend::pid::syscalls::getpid;
-use crate::fd::{AsFd, BorrowedFd, OwnedFd};
+use crate::fd::{AsFd, AsRawFd, BorrowedFd, OwnedFd, RawFd};
 use crate::ffi::CStr;
 use crate::fs::{
-    fstat, fstatfs, major, openat, renameat, Dir, FileType, Mode, OFlags, Stat, CWD,
+    fstat, fstatfs, major, openat, renameat, FileType, Mode, OFlags, RawDir, Stat, CWD,
     PROC_SUPER_MAGIC,
 };
 use crate::io;
 use crate::path::DecInt;
+use core::mem::MaybeUninit;
 #[cfg(feature = "rustc-dep-of-std")]
 use core::lazy::OnceCell;
 #[cfg(not(feature = "rustc-dep-of-std"))]
 use once_cell::sync::OnceCell;
 
+#[cfg(feature = "alloc")]
+mod mountinfo;
+
+#[cfg(feature = "alloc")]
+pub use mountinfo::{mountinfo, MountInfo, MountOptionalField};
+
 /// Linux's procfs always uses inode 1 for its root directory.
 const PROC_ROOT_INO: u64 = 1;
 
@@ -213,8 +220,6 @@ fn proc_opendirat<P: crate::path::Arg, Fd: AsFd>(dirfd: Fd, path: P) -> io::Resu
 ///
 /// [Linux]: https://man7.org/linux/man-pages/man5/proc.5.html
 fn proc() -> io::Result<(BorrowedFd<'static>, &'static Stat)> {
-    static PROC: StaticFd = StaticFd::new();
-
     // `OnceBox` is "racey" in that the initialization function may run
     // multiple times. We're ok with that, since the initialization function
     // has no side effects.
@@ -239,8 +244,6 @@ fn proc() -> io::Result<(BorrowedFd<'static>, &'static Stat)> {
 ///
 /// [Linux]: https://man7.org/linux/man-pages/man5/proc.5.html
 fn proc_self() -> io::Result<(BorrowedFd<'static>, &'static Stat)> {
-    static PROC_SELF: StaticFd = StaticFd::new();
-
     // The init function here may run multiple times; see above.
     PROC_SELF
         .get_or_try_init(|| {
@@ -270,8 +273,6 @@ fn proc_self() -> io::Result<(BorrowedFd<'static>, &'static Stat)> {
 /// [Linux]: https://man7.org/linux/man-pages/man5/proc.5.html
 #[cfg_attr(doc_cfg, doc(cfg(feature = "procfs")))]
 pub fn proc_self_fd() -> io::Result<BorrowedFd<'static>> {
-    static PROC_SELF_FD: StaticFd = StaticFd::new();
-
     // The init function here may run multiple times; see above.
     PROC_SELF_FD
         .get_or_try_init(|| {
@@ -292,6 +293,45 @@ pub fn proc_self_fd() -> io::Result<BorrowedFd<'static>> {
 
 type StaticFd = OnceCell<(OwnedFd, Stat)>;
 
+static PROC: StaticFd = StaticFd::new();
+static PROC_SELF: StaticFd = StaticFd::new();
+static PROC_SELF_FD: StaticFd = StaticFd::new();
+static PROC_SELF_FDINFO: StaticFd = StaticFd::new();
+
+/// Test whether `fd` is one of the file descriptors this module holds open
+/// for its own use.
+///
+/// Code which closes file descriptors in bulk uses this to avoid closing
+/// them out from under us.
+pub(crate) fn is_static_fd(fd: RawFd) -> bool {
+    [&PROC, &PROC_SELF, &PROC_SELF_FD, &PROC_SELF_FDINFO]
+        .iter()
+        .filter_map(|static_fd| static_fd.get())
+        .any(|(owned, _stat)| owned.as_raw_fd() == fd)
+}
+
+/// Open a new handle to Linux's `/proc/self/fd` directory, with the same
+/// checks as [`proc_self_fd`].
+///
+/// Unlike `proc_self_fd`, this doesn't cache the handle, so it opens the
+/// directory of the calling process even in a child process after `fork`,
+/// where the cached handle still refers to the parent's directory.
+pub(crate) fn open_proc_self_fd() -> io::Result<OwnedFd> {
+    let (proc, proc_stat) = proc()?;
+
+    // Use our pid to compute the name rather than literally using "self", as
+    // "self" is a symlink.
+    let proc_self = proc_opendirat(proc, DecInt::new(getpid().as_raw_nonzero().get()))?;
+    check_proc_entry(Kind::Pid, proc_self.as_fd(), Some(proc_stat))
+        .map_err(|_err| io::Errno::NOTSUP)?;
+
+    let proc_self_fd = proc_opendirat(&proc_self, cstr!("fd"))?;
+    check_proc_entry(Kind::Fd, proc_self_fd.as_fd(), Some(proc_stat))
+        .map_err(|_err| io::Errno::NOTSUP)?;
+
+    Ok(proc_self_fd)
+}
+
 #[inline]
 fn new_static_fd(fd: OwnedFd, stat: Stat) -> (OwnedFd, Stat) {
     (fd, stat)
@@ -308,8 +348,6 @@ fn new_static_fd(fd: OwnedFd, stat: Stat) -> (OwnedFd, Stat) {
 ///
 /// [Linux]: https://man7.org/linux/man-pages/man5/proc.5.html
 fn proc_self_fdinfo() -> io::Result<(BorrowedFd<'static>, &'static Stat)> {
-    static PROC_SELF_FDINFO: StaticFd = StaticFd::new();
-
     PROC_SELF_FDINFO
         .get_or_try_init(|| {
             let (_, proc_stat) = proc()?;
@@ -380,6 +418,22 @@ pub fn proc_self_maps() -> io::Result<OwnedFd> {
     proc_self_file(cstr!("maps"))
 }
 
+/// Returns a handle to a Linux `/proc/self/mountinfo` file.
+///
+/// This ensures that `/proc/self/mountinfo` is `procfs`, that nothing is
+/// mounted on top of it, and that it looks normal. To read and parse it, see
+/// [`mountinfo`].
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man5/proc_pid_mountinfo.5.html
+#[inline]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "procfs")))]
+pub fn proc_self_mountinfo() -> io::Result<OwnedFd> {
+    proc_self_file(cstr!("mountinfo"))
+}
+
 /// Returns a handle to a Linux `/proc/self/status` file.
 ///
 /// This ensures that `/proc/self/status` is `procfs`, that nothing is
@@ -422,23 +476,28 @@ fn open_and_check_file(dir: BorrowedFd, dir_stat: &Stat, name: &CStr) -> io::Res
     // mount on top of the file we want.
     //
     // As we scan, we also check for ".", to make sure it's the same directory
-    // as our original directory, to detect mount points, since
-    // `Dir::read_from` reopens ".".
+    // as our original directory, to detect mount points, since we reopen "."
+    // to get our own directory position.
     //
     // TODO: With Linux 5.8 we might be able to use `statx` and
     // `STATX_ATTR_MOUNT_ROOT` to detect mountpoints directly instead of doing
     // this scanning.
-    let dir = Dir::read_from(dir).map_err(|_err| io::Errno::NOTSUP)?;
+    let oflags = OFlags::RDONLY | OFlags::DIRECTORY | OFlags::CLOEXEC | OFlags::NOCTTY;
+    let dir = openat(dir, cstr!("."), oflags, Mode::empty()).map_err(|_err| io::Errno::NOTSUP)?;
 
     // Confirm that we got the same inode.
-    let dot_stat = dir.stat().map_err(|_err| io::Errno::NOTSUP)?;
+    let dot_stat = fstat(&dir).map_err(|_err| io::Errno::NOTSUP)?;
     if (dot_stat.st_dev, dot_stat.st_ino) != (dir_stat.st_dev, dir_stat.st_ino) {
         return Err(io::Errno::NOTSUP);
     }
 
+    // Read the entries into a buffer on the stack, so that this doesn't
+    // depend on `alloc`.
+    let mut buf = [MaybeUninit::uninit(); 2048];
+    let mut entries = RawDir::new(&dir, &mut buf);
     let mut found_file = false;
     let mut found_dot = false;
-    for entry in dir {
+    while let Some(entry) = entries.next() {
         let entry = entry.map_err(|_err| io::Errno::NOTSUP)?;
         if entry.ino() == file_stat.st_ino
             && entry.file_type() == FileType::RegularFile
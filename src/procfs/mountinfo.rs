@@ -0,0 +1,411 @@
+//! A typed view of the mount table, as described by `/proc/self/mountinfo`.
+
+use super::proc_self_mountinfo;
+use crate::fd::OwnedFd;
+use crate::ffi::CString;
+use crate::fs::{listmount, statmount, ListmountFlags, MountAttrFlags, MountPropagationFlags};
+use crate::fs::{Statmount, StatmountFlags, LSMT_ROOT};
+use crate::io;
+use alloc::borrow::ToOwned;
+use alloc::vec::Vec;
+
+/// One line of `/proc/self/mountinfo`, describing a mount.
+///
+/// The paths and the filesystem type and source have their octal escapes,
+/// such as `\040` for a space, decoded. The options are left as the kernel
+/// formats them.
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man5/proc_pid_mountinfo.5.html
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct MountInfo {
+    /// A unique ID for the mount, which may be reused after it's unmounted.
+    pub mount_id: u32,
+
+    /// The ID of the parent mount, or of the mount itself if it's the root
+    /// of the mount tree.
+    pub parent_id: u32,
+
+    /// The major number of the filesystem's device.
+    pub major: u32,
+
+    /// The minor number of the filesystem's device.
+    pub minor: u32,
+
+    /// The path of the directory in the filesystem which forms the root of
+    /// this mount.
+    pub root: CString,
+
+    /// The path of the mount point, relative to the process's root
+    /// directory.
+    pub mount_point: CString,
+
+    /// Per-mount options, such as `rw,nosuid,relatime`.
+    pub mount_options: CString,
+
+    /// Propagation information, such as `shared:1`.
+    pub optional_fields: Vec<MountOptionalField>,
+
+    /// The filesystem type, such as `ext4`, or `fuse.sshfs` for filesystems
+    /// with a subtype.
+    pub fs_type: CString,
+
+    /// Filesystem-specific information, such as a device path, or `none`.
+    pub source: CString,
+
+    /// Per-superblock options, such as `rw,errors=remount-ro`.
+    pub super_options: CString,
+}
+
+/// An optional field of a [`MountInfo`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum MountOptionalField {
+    /// `shared:X`—The mount is shared in peer group `X`.
+    Shared(u32),
+
+    /// `master:X`—The mount is a slave to shared peer group `X`.
+    Master(u32),
+
+    /// `propagate_from:X`—The mount is a slave and receives propagation from
+    /// shared peer group `X`, which is the closest dominant peer group under
+    /// the process's root.
+    PropagateFrom(u32),
+
+    /// `unbindable`—The mount is unbindable.
+    Unbindable,
+
+    /// A field this version of rustix doesn't recognize.
+    Other(CString),
+}
+
+impl MountInfo {
+    /// Parse a line of a `mountinfo` file, with or without its trailing
+    /// newline.
+    ///
+    /// This fails with [`io::Errno::INVAL`] if the line is malformed.
+    pub fn parse(line: &[u8]) -> io::Result<Self> {
+        let line = line.strip_suffix(b"\n").unwrap_or(line);
+        let mut fields = line.split(|b| *b == b' ');
+        let mut next = || fields.next().ok_or(io::Errno::INVAL);
+
+        let mount_id = parse_decimal(next()?)?;
+        let parent_id = parse_decimal(next()?)?;
+        let (major, minor) = {
+            let dev = next()?;
+            let colon = dev
+                .iter()
+                .position(|b| *b == b':')
+                .ok_or(io::Errno::INVAL)?;
+            (
+                parse_decimal(&dev[..colon])?,
+                parse_decimal(&dev[colon + 1..])?,
+            )
+        };
+        let root = unescape(next()?)?;
+        let mount_point = unescape(next()?)?;
+        let mount_options = to_c_string(next()?)?;
+
+        let mut optional_fields = Vec::new();
+        loop {
+            let field = next()?;
+            if field == b"-" {
+                break;
+            }
+            optional_fields.push(MountOptionalField::parse(field)?);
+        }
+
+        let fs_type = unescape(next()?)?;
+        let source = unescape(next()?)?;
+        let super_options = to_c_string(next()?)?;
+        if fields.next().is_some() {
+            return Err(io::Errno::INVAL);
+        }
+
+        Ok(Self {
+            mount_id,
+            parent_id,
+            major,
+            minor,
+            root,
+            mount_point,
+            mount_options,
+            optional_fields,
+            fs_type,
+            source,
+            super_options,
+        })
+    }
+
+    /// Convert the result of [`statmount`] into the form `mountinfo` would
+    /// show, or return `None` if it isn't under the process's root.
+    fn from_statmount(sm: &Statmount) -> io::Result<Option<Self>> {
+        let mount_point = match sm.mnt_point() {
+            Some(mount_point) => mount_point.to_owned(),
+            None => return Ok(None),
+        };
+        let root = sm.mnt_root().ok_or(io::Errno::NOTSUP)?.to_owned();
+
+        // This matches `show_mnt_opts` in the kernel.
+        let attr = sm.mnt_attr();
+        let mut mount_options = Vec::new();
+        mount_options.extend_from_slice(if attr.contains(MountAttrFlags::RDONLY) {
+            b"ro"
+        } else {
+            b"rw"
+        });
+        for (flag, name) in [
+            (MountAttrFlags::NOSUID, &b",nosuid"[..]),
+            (MountAttrFlags::NODEV, b",nodev"),
+            (MountAttrFlags::NOEXEC, b",noexec"),
+        ] {
+            if attr.contains(flag) {
+                mount_options.extend_from_slice(name);
+            }
+        }
+        match attr & MountAttrFlags::_ATIME {
+            atime if atime == MountAttrFlags::NOATIME => {
+                mount_options.extend_from_slice(b",noatime")
+            }
+            atime if atime == MountAttrFlags::RELATIME => {
+                mount_options.extend_from_slice(b",relatime")
+            }
+            _ => (),
+        }
+        for (flag, name) in [
+            (MountAttrFlags::NODIRATIME, &b",nodiratime"[..]),
+            (MountAttrFlags::NOSYMFOLLOW, b",nosymfollow"),
+            (MountAttrFlags::IDMAP, b",idmapped"),
+        ] {
+            if attr.contains(flag) {
+                mount_options.extend_from_slice(name);
+            }
+        }
+
+        // This matches `show_mountinfo` in the kernel.
+        let propagation = sm.mnt_propagation();
+        let mut optional_fields = Vec::new();
+        if propagation.contains(MountPropagationFlags::SHARED) {
+            optional_fields.push(MountOptionalField::Shared(sm.mnt_peer_group() as u32));
+        }
+        if propagation.contains(MountPropagationFlags::SLAVE) {
+            let master = sm.mnt_master();
+            optional_fields.push(MountOptionalField::Master(master as u32));
+            let propagate_from = sm.propagate_from();
+            if propagate_from != 0 && propagate_from != master {
+                optional_fields.push(MountOptionalField::PropagateFrom(propagate_from as u32));
+            }
+        }
+        if propagation.contains(MountPropagationFlags::UNBINDABLE) {
+            optional_fields.push(MountOptionalField::Unbindable);
+        }
+
+        let mut fs_type = sm.fs_type().ok_or(io::Errno::NOTSUP)?.to_bytes().to_vec();
+        if let Some(subtype) = sm.fs_subtype() {
+            fs_type.push(b'.');
+            fs_type.extend_from_slice(subtype.to_bytes());
+        }
+
+        let source = match sm.sb_source() {
+            Some(source) => source.to_owned(),
+            None => to_c_string(b"none")?,
+        };
+
+        // This matches `show_sb_opts` in the kernel, followed by the
+        // security and filesystem options.
+        let sb_flags = sm.sb_flags();
+        let mut super_options = Vec::new();
+        super_options.extend_from_slice(if sb_flags & SB_RDONLY != 0 {
+            b"ro"
+        } else {
+            b"rw"
+        });
+        for (flag, name) in [
+            (SB_SYNCHRONOUS, &b",sync"[..]),
+            (SB_DIRSYNC, b",dirsync"),
+            (SB_LAZYTIME, b",lazytime"),
+        ] {
+            if sb_flags & flag != 0 {
+                super_options.extend_from_slice(name);
+            }
+        }
+        if let Some(opts) = sm.mnt_opts() {
+            super_options.push(b',');
+            super_options.extend_from_slice(opts.to_bytes());
+        }
+
+        Ok(Some(Self {
+            mount_id: sm.mnt_id_old(),
+            parent_id: sm.mnt_parent_id_old(),
+            major: sm.sb_dev_major(),
+            minor: sm.sb_dev_minor(),
+            root,
+            mount_point,
+            mount_options: to_c_string(&mount_options)?,
+            optional_fields,
+            fs_type: to_c_string(&fs_type)?,
+            source,
+            super_options: to_c_string(&super_options)?,
+        }))
+    }
+}
+
+impl MountOptionalField {
+    fn parse(field: &[u8]) -> io::Result<Self> {
+        let colon = field.iter().position(|b| *b == b':');
+        Ok(match colon.map(|colon| field.split_at(colon)) {
+            Some((b"shared", id)) => Self::Shared(parse_decimal(&id[1..])?),
+            Some((b"master", id)) => Self::Master(parse_decimal(&id[1..])?),
+            Some((b"propagate_from", id)) => Self::PropagateFrom(parse_decimal(&id[1..])?),
+            None if field == b"unbindable" => Self::Unbindable,
+            _ => Self::Other(to_c_string(field)?),
+        })
+    }
+}
+
+// `SB_*` flags reported by `statmount`.
+const SB_RDONLY: u32 = 0x0000_0001;
+const SB_SYNCHRONOUS: u32 = 0x0000_0010;
+const SB_DIRSYNC: u32 = 0x0000_0080;
+const SB_LAZYTIME: u32 = 0x0200_0000;
+
+/// The `statmount` information needed to reproduce a line of `mountinfo`.
+const MOUNTINFO_MASK: StatmountFlags = StatmountFlags::SB_BASIC
+    .union(StatmountFlags::MNT_BASIC)
+    .union(StatmountFlags::PROPAGATE_FROM)
+    .union(StatmountFlags::MNT_ROOT)
+    .union(StatmountFlags::MNT_POINT)
+    .union(StatmountFlags::FS_TYPE)
+    .union(StatmountFlags::MNT_OPTS)
+    .union(StatmountFlags::FS_SUBTYPE)
+    .union(StatmountFlags::SB_SOURCE);
+
+/// Describe the mounts visible to the process, as `/proc/self/mountinfo`
+/// would.
+///
+/// On kernels where [`statmount`] can report everything `mountinfo` does,
+/// this uses [`listmount`] and `statmount`, which avoids depending on
+/// `/proc` and parsing text. Otherwise, this reads and parses
+/// `/proc/self/mountinfo`, opened with [`proc_self_mountinfo`].
+///
+/// # References
+///  - [Linux]
+///
+/// [Linux]: https://man7.org/linux/man-pages/man5/proc_pid_mountinfo.5.html
+pub fn mountinfo() -> io::Result<Vec<MountInfo>> {
+    match mountinfo_from_statmount() {
+        Ok(Some(mounts)) => return Ok(mounts),
+        Ok(None) => (),
+        // The syscalls are missing, or blocked by a seccomp filter.
+        Err(io::Errno::NOSYS) | Err(io::Errno::PERM) | Err(io::Errno::ACCESS) => (),
+        Err(err) => return Err(err),
+    }
+
+    let contents = read_to_end(proc_self_mountinfo()?)?;
+    contents
+        .split(|b| *b == b'\n')
+        .filter(|line| !line.is_empty())
+        .map(MountInfo::parse)
+        .collect()
+}
+
+/// Describe the mounts with `listmount` and `statmount`, or return `None` if
+/// `statmount` can't report everything that's needed.
+fn mountinfo_from_statmount() -> io::Result<Option<Vec<MountInfo>>> {
+    let mut ids = [0_u64; 64];
+    let mut mounts = Vec::new();
+    let mut last = 0;
+    loop {
+        let n = listmount(LSMT_ROOT, last, &mut ids, ListmountFlags::empty())?;
+        if n == 0 {
+            return Ok(Some(mounts));
+        }
+        for id in &ids[..n] {
+            let mask = if mounts.is_empty() {
+                MOUNTINFO_MASK | StatmountFlags::SUPPORTED_MASK
+            } else {
+                MOUNTINFO_MASK
+            };
+            let sm = match statmount(*id, mask) {
+                Ok(sm) => sm,
+                // The mount was unmounted since we listed it.
+                Err(io::Errno::NOENT) => continue,
+                Err(err) => return Err(err),
+            };
+
+            // Strings that are empty are left out of the mask, so check that
+            // the kernel supports them before trusting their absence.
+            if mounts.is_empty()
+                && !(sm.mask().contains(StatmountFlags::SUPPORTED_MASK)
+                    && sm.supported_mask().contains(MOUNTINFO_MASK))
+            {
+                return Ok(None);
+            }
+
+            if let Some(mount) = MountInfo::from_statmount(&sm)? {
+                mounts.push(mount);
+            }
+        }
+        last = ids[n - 1];
+    }
+}
+
+/// Read the whole contents of `fd`.
+fn read_to_end(fd: OwnedFd) -> io::Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    let mut len = 0;
+    loop {
+        if buf.len() == len {
+            buf.resize(len + 4096, 0);
+        }
+        match io::read(&fd, &mut buf[len..]) {
+            Ok(0) => break,
+            Ok(n) => len += n,
+            Err(io::Errno::INTR) => (),
+            Err(err) => return Err(err),
+        }
+    }
+    buf.truncate(len);
+    Ok(buf)
+}
+
+fn parse_decimal(bytes: &[u8]) -> io::Result<u32> {
+    if bytes.is_empty() {
+        return Err(io::Errno::INVAL);
+    }
+    bytes.iter().try_fold(0_u32, |n, b| match b {
+        b'0'..=b'9' => n
+            .checked_mul(10)
+            .and_then(|n| n.checked_add(u32::from(b - b'0')))
+            .ok_or(io::Errno::INVAL),
+        _ => Err(io::Errno::INVAL),
+    })
+}
+
+/// Decode the `\ooo` octal escapes the kernel uses for spaces, tabs,
+/// newlines, and backslashes.
+fn unescape(field: &[u8]) -> io::Result<CString> {
+    let mut out = Vec::with_capacity(field.len());
+    let mut i = 0;
+    while i < field.len() {
+        let b = field[i];
+        if b == b'\\' && i + 3 < field.len() && is_octal_escape(&field[i + 1..i + 4]) {
+            let digits = &field[i + 1..i + 4];
+            out.push((digits[0] - b'0') * 64 + (digits[1] - b'0') * 8 + (digits[2] - b'0'));
+            i += 4;
+        } else {
+            out.push(b);
+            i += 1;
+        }
+    }
+    to_c_string(&out)
+}
+
+fn is_octal_escape(digits: &[u8]) -> bool {
+    matches!(digits, [b'0'..=b'3', b'0'..=b'7', b'0'..=b'7'])
+}
+
+fn to_c_string(bytes: &[u8]) -> io::Result<CString> {
+    CString::new(bytes).map_err(|_err| io::Errno::INVAL)
+}
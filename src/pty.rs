@@ -9,8 +9,16 @@ use crate::backend::c;
 use crate::fd::{AsFd, OwnedFd};
 use crate::fs::OFlags;
 use crate::{backend, io};
+#[cfg(all(
+    feature = "alloc",
+    any(apple, linux_like, target_os = "freebsd", target_os = "fuchsia")
+))]
+use {
+    crate::fd::BorrowedFd, crate::ffi::CString, crate::path::SMALL_PATH_BUFFER_SIZE,
+    alloc::vec::Vec,
+};
 #[cfg(any(apple, linux_like, target_os = "freebsd", target_os = "fuchsia"))]
-use {crate::ffi::CString, alloc::vec::Vec};
+use {crate::ffi::CStr, core::mem::MaybeUninit, core::slice};
 
 bitflags::bitflags! {
     /// `O_*` flags for use with [`openpt`] and [`ioctl_tiocgptpeer`].
@@ -103,9 +111,71 @@ pub fn openpt(flags: OpenptFlags) -> io::Result<OwnedFd> {
 /// [glibc]: https://www.gnu.org/software/libc/manual/html_node/Allocation.html#index-ptsname
 #[inline]
 #[doc(alias = "ptsname_r")]
-#[cfg(any(apple, linux_like, target_os = "freebsd", target_os = "fuchsia"))]
+#[cfg(all(
+    feature = "alloc",
+    any(apple, linux_like, target_os = "freebsd", target_os = "fuchsia")
+))]
 pub fn ptsname<Fd: AsFd, B: Into<Vec<u8>>>(fd: Fd, reuse: B) -> io::Result<CString> {
-    backend::pty::syscalls::ptsname(fd.as_fd(), reuse.into())
+    _ptsname(fd.as_fd(), reuse.into())
+}
+
+#[cfg(all(
+    feature = "alloc",
+    any(apple, linux_like, target_os = "freebsd", target_os = "fuchsia")
+))]
+#[allow(unsafe_code)]
+fn _ptsname(fd: BorrowedFd<'_>, mut buffer: Vec<u8>) -> io::Result<CString> {
+    buffer.clear();
+    buffer.reserve(SMALL_PATH_BUFFER_SIZE);
+
+    loop {
+        match backend::pty::syscalls::ptsname(fd, buffer.spare_capacity_mut()) {
+            Err(io::Errno::RANGE) => {
+                buffer.reserve(buffer.capacity() + 1); // use `Vec` reallocation strategy to grow capacity exponentially
+            }
+            Ok(len) => {
+                // SAFETY: The backend returns the length of the string, not
+                // including the NUL terminator it wrote after it, and the
+                // name of a pseudoterminal contains no other NUL bytes.
+                unsafe {
+                    buffer.set_len(len + 1);
+                    return Ok(CString::from_vec_with_nul_unchecked(buffer));
+                }
+            }
+            Err(errno) => return Err(errno),
+        }
+    }
+}
+
+/// `ptsname(fd)`—Return the name of a pseudoterminal, without allocating.
+///
+/// The name is written to `buf`, including its NUL terminator, and returned
+/// as a `CStr` borrowed from `buf`. If `buf` is too small, this fails with
+/// [`io::Errno::RANGE`]; the call may be retried with a larger buffer.
+///
+/// # References
+///  - [POSIX]
+///  - [Linux]
+///  - [glibc]
+///
+/// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/ptsname.html
+/// [Linux]: https://man7.org/linux/man-pages/man3/ptsname.3.html
+/// [glibc]: https://www.gnu.org/software/libc/manual/html_node/Allocation.html#index-ptsname
+#[inline]
+#[doc(alias = "ptsname_r")]
+#[cfg(any(apple, linux_like, target_os = "freebsd", target_os = "fuchsia"))]
+#[allow(unsafe_code)]
+pub fn ptsname_into<Fd: AsFd>(fd: Fd, buf: &mut [MaybeUninit<u8>]) -> io::Result<&CStr> {
+    let len = backend::pty::syscalls::ptsname(fd.as_fd(), buf)?;
+
+    // SAFETY: The backend wrote a string of `len` bytes followed by a NUL
+    // terminator into `buf`.
+    unsafe {
+        Ok(CStr::from_bytes_with_nul_unchecked(slice::from_raw_parts(
+            buf.as_ptr().cast::<u8>(),
+            len + 1,
+        )))
+    }
 }
 
 /// `unlockpt(fd)`—Unlock a pseudoterminal.
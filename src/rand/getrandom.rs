@@ -1,4 +1,6 @@
+use crate::utils::split_init;
 use crate::{backend, io};
+use core::mem::MaybeUninit;
 
 /// `GRND_*` constants for use with `getrandom`.
 pub use backend::rand::types::GetRandomFlags;
@@ -15,7 +17,29 @@ pub use backend::rand::types::GetRandomFlags;
 ///  - [Linux]
 ///
 /// [Linux]: https://man7.org/linux/man-pages/man2/getrandom.2.html
+#[allow(unsafe_code)]
 #[inline]
 pub fn getrandom(buf: &mut [u8], flags: GetRandomFlags) -> io::Result<usize> {
-    backend::rand::syscalls::getrandom(buf, flags)
+    unsafe { backend::rand::syscalls::getrandom(buf.as_mut_ptr(), buf.len(), flags) }
+}
+
+/// `getrandom(buf, flags)`—Reads a sequence of random bytes into an
+/// uninitialized buffer.
+///
+/// This is equivalent to [`getrandom`], except that it can read into
+/// uninitialized memory. It returns the slice that was initialized by this
+/// function and the slice that remains uninitialized.
+#[allow(unsafe_code)]
+#[inline]
+pub fn getrandom_uninit(
+    buf: &mut [MaybeUninit<u8>],
+    flags: GetRandomFlags,
+) -> io::Result<(&mut [u8], &mut [MaybeUninit<u8>])> {
+    // Get the number of initialized bytes.
+    let length = unsafe {
+        backend::rand::syscalls::getrandom(buf.as_mut_ptr().cast::<u8>(), buf.len(), flags)?
+    };
+
+    // Split into the initialized and uninitialized portions.
+    Ok(unsafe { split_init(buf, length) })
 }
@@ -4,4 +4,4 @@
 mod getrandom;
 
 #[cfg(linux_kernel)]
-pub use getrandom::{getrandom, GetRandomFlags};
+pub use getrandom::{getrandom, getrandom_uninit, GetRandomFlags};
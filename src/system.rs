@@ -10,6 +10,7 @@ use crate::backend;
 use crate::ffi::CStr;
 #[cfg(not(target_os = "emscripten"))]
 use crate::io;
+#[cfg(feature = "alloc")]
 use core::fmt;
 
 #[cfg(linux_kernel)]
@@ -82,6 +83,7 @@ impl Uname {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl fmt::Debug for Uname {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         #[cfg(not(linux_kernel))]
@@ -4,8 +4,11 @@ use crate::backend;
 use backend::fd::AsFd;
 #[cfg(feature = "procfs")]
 #[cfg(not(any(target_os = "fuchsia", target_os = "wasi")))]
+use {crate::ffi::CStr, crate::io, core::mem::MaybeUninit, core::slice};
+#[cfg(all(feature = "alloc", feature = "procfs"))]
+#[cfg(not(any(target_os = "fuchsia", target_os = "wasi")))]
 use {
-    crate::ffi::CString, crate::io, crate::path::SMALL_PATH_BUFFER_SIZE, alloc::vec::Vec,
+    crate::ffi::CString, crate::path::SMALL_PATH_BUFFER_SIZE, alloc::vec::Vec,
     backend::fd::BorrowedFd,
 };
 
@@ -33,8 +36,8 @@ pub fn isatty<Fd: AsFd>(fd: Fd) -> bool {
 /// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/ttyname.html
 /// [Linux]: https://man7.org/linux/man-pages/man3/ttyname.3.html
 #[cfg(not(any(target_os = "fuchsia", target_os = "wasi")))]
-#[cfg(feature = "procfs")]
-#[cfg_attr(doc_cfg, doc(cfg(feature = "procfs")))]
+#[cfg(all(feature = "alloc", feature = "procfs"))]
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "alloc", feature = "procfs"))))]
 #[doc(alias = "ttyname_r")]
 #[inline]
 pub fn ttyname<Fd: AsFd, B: Into<Vec<u8>>>(dirfd: Fd, reuse: B) -> io::Result<CString> {
@@ -42,7 +45,7 @@ pub fn ttyname<Fd: AsFd, B: Into<Vec<u8>>>(dirfd: Fd, reuse: B) -> io::Result<CS
 }
 
 #[cfg(not(any(target_os = "fuchsia", target_os = "wasi")))]
-#[cfg(feature = "procfs")]
+#[cfg(all(feature = "alloc", feature = "procfs"))]
 #[allow(unsafe_code)]
 fn _ttyname(dirfd: BorrowedFd<'_>, mut buffer: Vec<u8>) -> io::Result<CString> {
     buffer.clear();
@@ -54,9 +57,10 @@ fn _ttyname(dirfd: BorrowedFd<'_>, mut buffer: Vec<u8>) -> io::Result<CString> {
                 buffer.reserve(buffer.capacity() + 1); // use `Vec` reallocation strategy to grow capacity exponentially
             }
             Ok(len) => {
-                // SAFETY: assume the backend returns the length of the string
+                // SAFETY: assume the backend returns the length of the string,
+                // not including the NUL terminator it wrote after it
                 unsafe {
-                    buffer.set_len(len);
+                    buffer.set_len(len + 1);
                 }
 
                 // SAFETY:
@@ -75,3 +79,34 @@ fn _ttyname(dirfd: BorrowedFd<'_>, mut buffer: Vec<u8>) -> io::Result<CString> {
         }
     }
 }
+
+/// `ttyname_r(fd)`, without allocating.
+///
+/// The name is written to `buf`, including its NUL terminator, and returned
+/// as a `CStr` borrowed from `buf`. If `buf` is too small, this fails with
+/// [`io::Errno::RANGE`]; the call may be retried with a larger buffer.
+///
+/// # References
+///  - [POSIX]
+///  - [Linux]
+///
+/// [POSIX]: https://pubs.opengroup.org/onlinepubs/9699919799/functions/ttyname.html
+/// [Linux]: https://man7.org/linux/man-pages/man3/ttyname.3.html
+#[cfg(not(any(target_os = "fuchsia", target_os = "wasi")))]
+#[cfg(feature = "procfs")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "procfs")))]
+#[doc(alias = "ttyname_r")]
+#[allow(unsafe_code)]
+#[inline]
+pub fn ttyname_into<Fd: AsFd>(dirfd: Fd, buf: &mut [MaybeUninit<u8>]) -> io::Result<&CStr> {
+    let len = backend::termios::syscalls::ttyname(dirfd.as_fd(), buf)?;
+
+    // SAFETY: The backend wrote a string of `len` bytes followed by a NUL
+    // terminator into `buf`.
+    unsafe {
+        Ok(CStr::from_bytes_with_nul_unchecked(slice::from_raw_parts(
+            buf.as_ptr().cast::<u8>(),
+            len + 1,
+        )))
+    }
+}
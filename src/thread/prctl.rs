@@ -19,7 +19,9 @@ use bitflags::bitflags;
 
 use crate::backend::c::{c_int, c_uint, c_void};
 use crate::backend::prctl::syscalls;
-use crate::ffi::{CStr, CString};
+use crate::ffi::CStr;
+#[cfg(feature = "alloc")]
+use crate::ffi::CString;
 use crate::io;
 use crate::pid::Pid;
 use crate::prctl::{
@@ -70,12 +72,30 @@ const PR_GET_NAME: c_int = 16;
 ///
 /// [`prctl(PR_GET_NAME,...)`]: https://man7.org/linux/man-pages/man2/prctl.2.html
 #[inline]
+#[cfg(feature = "alloc")]
 pub fn name() -> io::Result<CString> {
     let mut buffer = [0_u8; 16];
-    unsafe { prctl_2args(PR_GET_NAME, buffer.as_mut_ptr().cast())? };
+    name_into(&mut buffer).map(CString::from)
+}
 
-    let len = buffer.iter().position(|&x| x == 0_u8).unwrap_or(0);
-    CString::new(&buffer[..len]).map_err(|_r| io::Errno::ILSEQ)
+/// Get the name of the calling thread, without allocating.
+///
+/// The name, which is at most 15 bytes long, is written to `buf`, followed by
+/// a NUL terminator, and returned as a `CStr` borrowed from `buf`.
+///
+/// # References
+///  - [`prctl(PR_GET_NAME,...)`]
+///
+/// [`prctl(PR_GET_NAME,...)`]: https://man7.org/linux/man-pages/man2/prctl.2.html
+#[inline]
+pub fn name_into(buf: &mut [u8; 16]) -> io::Result<&CStr> {
+    unsafe { prctl_2args(PR_GET_NAME, buf.as_mut_ptr().cast())? };
+
+    let len = buf
+        .iter()
+        .position(|&x| x == 0_u8)
+        .ok_or(io::Errno::ILSEQ)?;
+    CStr::from_bytes_with_nul(&buf[..=len]).map_err(|_r| io::Errno::ILSEQ)
 }
 
 const PR_SET_NAME: c_int = 15;
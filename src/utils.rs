@@ -1,8 +1,9 @@
 #![allow(dead_code)]
 
 use core::ffi::c_void;
-use core::mem::{align_of, size_of};
+use core::mem::{align_of, size_of, MaybeUninit};
 use core::ptr::{null, null_mut, NonNull};
+use core::slice;
 
 /// Convert a `&T` into a `*const T` without using an `as`.
 #[inline]
@@ -46,3 +47,20 @@ pub(crate) fn check_raw_pointer<T>(value: *mut c_void) -> Option<NonNull<T>> {
 
     NonNull::new(value.cast())
 }
+
+/// Split an uninitialized byte slice into initialized and uninitialized
+/// parts.
+///
+/// # Safety
+///
+/// At least `init` bytes of `buf` must be initialized.
+#[allow(unsafe_code)]
+#[inline]
+pub(crate) unsafe fn split_init(
+    buf: &mut [MaybeUninit<u8>],
+    init: usize,
+) -> (&mut [u8], &mut [MaybeUninit<u8>]) {
+    let (init, uninit) = buf.split_at_mut(init);
+    let init = slice::from_raw_parts_mut(init.as_mut_ptr().cast::<u8>(), init.len());
+    (init, uninit)
+}
@@ -0,0 +1,197 @@
+use rustix::fs::{
+    fget_acl, fset_acl, get_acl, lget_acl, set_acl, Acl, AclEntry, AclPerms, AclTag, AclType, Gid,
+    Mode, Uid,
+};
+use rustix::io;
+
+fn uid(raw: u32) -> Uid {
+    unsafe { Uid::from_raw(raw) }
+}
+
+fn gid(raw: u32) -> Gid {
+    unsafe { Gid::from_raw(raw) }
+}
+
+/// An ACL with a named user and group, as `setfacl -m u:1000:rw,g:100:r`
+/// would produce on a file with mode 0o640.
+fn named_acl() -> Acl {
+    let mut acl = Acl::from_mode(Mode::from_bits_truncate(0o640));
+    acl.set(AclTag::User(uid(1000)), AclPerms::READ | AclPerms::WRITE);
+    acl.set(AclTag::Group(gid(100)), AclPerms::READ);
+    acl.calc_mask();
+    acl
+}
+
+#[test]
+fn test_acl_xattr_format() {
+    let acl = named_acl();
+    let bytes = acl.to_xattr();
+    #[rustfmt::skip]
+    assert_eq!(
+        bytes,
+        [
+            2, 0, 0, 0,
+            0x01, 0, 6, 0, 0xff, 0xff, 0xff, 0xff,
+            0x02, 0, 6, 0, 0xe8, 0x03, 0, 0,
+            0x04, 0, 4, 0, 0xff, 0xff, 0xff, 0xff,
+            0x08, 0, 4, 0, 100, 0, 0, 0,
+            0x10, 0, 6, 0, 0xff, 0xff, 0xff, 0xff,
+            0x20, 0, 0, 0, 0xff, 0xff, 0xff, 0xff,
+        ]
+    );
+    assert_eq!(Acl::from_xattr(&bytes).unwrap(), acl);
+
+    // Entries are sorted on decode, and malformed values are rejected.
+    let mut reversed = bytes[..4].to_vec();
+    for entry in bytes[4..].chunks(8).rev() {
+        reversed.extend_from_slice(entry);
+    }
+    assert_eq!(Acl::from_xattr(&reversed).unwrap(), acl);
+    assert_eq!(Acl::from_xattr(&bytes[..7]), Err(io::Errno::INVAL));
+    assert_eq!(Acl::from_xattr(&[1, 0, 0, 0]), Err(io::Errno::OPNOTSUPP));
+    let mut duplicate = bytes.clone();
+    duplicate.extend_from_slice(&bytes[4..12]);
+    assert_eq!(Acl::from_xattr(&duplicate), Err(io::Errno::INVAL));
+
+    // `User` and `Group` entries need a defined ID, and tags and
+    // permissions must be known.
+    let header = &bytes[..4];
+    for entry in [
+        [0x02, 0, 6, 0, 0xff, 0xff, 0xff, 0xff],
+        [0x08, 0, 6, 0, 0xff, 0xff, 0xff, 0xff],
+        [0x40, 0, 6, 0, 0xff, 0xff, 0xff, 0xff],
+        [0x01, 0, 8, 0, 0xff, 0xff, 0xff, 0xff],
+    ] {
+        let malformed = [header, &entry].concat();
+        assert_eq!(Acl::from_xattr(&malformed), Err(io::Errno::INVAL));
+    }
+}
+
+#[test]
+fn test_acl_text() {
+    let acl = named_acl();
+    let text = acl.to_string();
+    assert_eq!(
+        text,
+        "user::rw-\nuser:1000:rw-\ngroup::r--\ngroup:100:r--\nmask::rw-\nother::---\n"
+    );
+    assert_eq!(text.parse::<Acl>().unwrap(), acl);
+
+    // `getfacl`'s header and effective rights comments are ignored, and so
+    // are the short forms `setfacl` accepts.
+    let getfacl = "# file: project\n\
+                   # owner: root\n\
+                   # group: root\n\
+                   user::rw-\n\
+                   user:1000:rw-\n\
+                   group::r--\n\
+                   group:100:r--\t\t#effective:r--\n\
+                   mask::rw-\n\
+                   other::---\n\
+                   \n";
+    assert_eq!(getfacl.parse::<Acl>().unwrap(), acl);
+    assert_eq!(
+        "u::rw,u:1000:wr,g::r,g:100:r,m::rw,o:-"
+            .parse::<Acl>()
+            .unwrap(),
+        acl
+    );
+
+    assert_eq!("user:bob:rw-".parse::<Acl>(), Err(io::Errno::INVAL));
+    assert_eq!("user::rwz".parse::<Acl>(), Err(io::Errno::INVAL));
+    assert_eq!("user::rw-,user::r--".parse::<Acl>(), Err(io::Errno::INVAL));
+    assert_eq!("default:user::rw-".parse::<Acl>(), Err(io::Errno::INVAL));
+    assert_eq!("other:1:r--".parse::<AclEntry>(), Err(io::Errno::INVAL));
+    assert_eq!(
+        "user:4294967295:rw-".parse::<AclEntry>(),
+        Err(io::Errno::INVAL)
+    );
+}
+
+#[test]
+fn test_acl_edit() {
+    let mut acl = Acl::from_mode(Mode::from_bits_truncate(0o754));
+    assert!(acl.is_minimal());
+    assert_eq!(acl.to_mode(), Some(Mode::from_bits_truncate(0o754)));
+    acl.validate().unwrap();
+
+    // A named entry needs a mask.
+    acl.set(AclTag::Group(gid(100)), AclPerms::WRITE);
+    assert!(!acl.is_minimal());
+    assert_eq!(acl.validate(), Err(io::Errno::INVAL));
+    acl.calc_mask();
+    assert_eq!(
+        acl.get(AclTag::Mask),
+        Some(AclPerms::READ | AclPerms::WRITE | AclPerms::EXECUTE)
+    );
+    acl.validate().unwrap();
+
+    // The mask stands in for the group bits of the mode.
+    acl.set(AclTag::Mask, AclPerms::READ);
+    assert_eq!(acl.to_mode(), Some(Mode::from_bits_truncate(0o744)));
+
+    assert_eq!(acl.remove(AclTag::Group(gid(100))), Some(AclPerms::WRITE));
+    acl.calc_mask();
+    assert_eq!(acl.get(AclTag::Mask), None);
+    assert!(acl.is_minimal());
+
+    assert_eq!(Acl::new().validate(), Err(io::Errno::INVAL));
+}
+
+#[test]
+fn test_acl_get_set() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("file");
+    let file = std::fs::File::create(&path).unwrap();
+    std::fs::set_permissions(&path, PermissionsExt::from_mode(0o640)).unwrap();
+
+    // Without an ACL, the access ACL reflects the mode.
+    assert_eq!(
+        get_acl(&path, AclType::Access).unwrap(),
+        Acl::from_mode(Mode::from_bits_truncate(0o640))
+    );
+
+    let acl = named_acl();
+    match set_acl(&path, AclType::Access, &acl) {
+        Ok(()) => (),
+        // The filesystem may not support ACLs.
+        Err(io::Errno::OPNOTSUPP) => return,
+        Err(err) => panic!("{:?}", err),
+    }
+    assert_eq!(get_acl(&path, AclType::Access).unwrap(), acl);
+    assert_eq!(lget_acl(&path, AclType::Access).unwrap(), acl);
+    assert_eq!(fget_acl(&file, AclType::Access).unwrap(), acl);
+
+    // Setting the access ACL sets the mode, with the mask as the group bits.
+    let mut acl = acl;
+    acl.set(AclTag::Mask, AclPerms::READ);
+    fset_acl(&file, AclType::Access, &acl).unwrap();
+    let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o640);
+    assert_eq!(fget_acl(&file, AclType::Access).unwrap(), acl);
+
+    // Invalid ACLs are rejected before they get to the kernel.
+    acl.remove(AclTag::Mask);
+    assert_eq!(set_acl(&path, AclType::Access, &acl), Err(io::Errno::INVAL));
+
+    // Directories have default ACLs, which new files inherit.
+    let dir = tmp.path().join("dir");
+    std::fs::create_dir(&dir).unwrap();
+    assert_eq!(get_acl(&dir, AclType::Default).unwrap(), Acl::new());
+    let default = named_acl();
+    set_acl(&dir, AclType::Default, &default).unwrap();
+    assert_eq!(get_acl(&dir, AclType::Default).unwrap(), default);
+    std::fs::File::create(dir.join("inherited")).unwrap();
+    let inherited = get_acl(dir.join("inherited"), AclType::Access).unwrap();
+    assert_eq!(
+        inherited.get(AclTag::User(uid(1000))),
+        Some(AclPerms::READ | AclPerms::WRITE)
+    );
+
+    // Setting an empty default ACL removes it, even if there isn't one.
+    set_acl(&dir, AclType::Default, &Acl::new()).unwrap();
+    assert_eq!(get_acl(&dir, AclType::Default).unwrap(), Acl::new());
+    set_acl(&dir, AclType::Default, &Acl::new()).unwrap();
+}
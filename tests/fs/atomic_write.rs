@@ -0,0 +1,140 @@
+use rustix::fs::{
+    atomic_exchange_at, atomic_write_at, atomic_write_at_with, openat, AtomicWriteOptions, Mode,
+    OFlags, CWD,
+};
+use rustix::io;
+
+fn dir_names(dir: &std::path::Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_atomic_write_at() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = openat(CWD, tmp.path(), OFlags::RDONLY, Mode::empty()).unwrap();
+    let options = AtomicWriteOptions {
+        mode: Mode::from_raw_mode(0o600),
+        ..Default::default()
+    };
+
+    atomic_write_at(&dir, "config", b"first", &options).unwrap();
+    assert_eq!(std::fs::read(tmp.path().join("config")).unwrap(), b"first");
+    let mode = std::fs::metadata(tmp.path().join("config"))
+        .unwrap()
+        .permissions()
+        .mode();
+    assert_eq!(mode & 0o777, 0o600);
+
+    // Replacing the file leaves a different file in its place.
+    let old = std::fs::File::open(tmp.path().join("config")).unwrap();
+    atomic_write_at(&dir, "config", b"second", &options).unwrap();
+    assert_eq!(std::fs::read(tmp.path().join("config")).unwrap(), b"second");
+    let mut old_contents = Vec::new();
+    std::io::Read::read_to_end(&mut &old, &mut old_contents).unwrap();
+    assert_eq!(old_contents, b"first");
+
+    // Without `replace`, an existing file is left alone.
+    let no_replace = AtomicWriteOptions {
+        replace: false,
+        ..Default::default()
+    };
+    assert_eq!(
+        atomic_write_at(&dir, "config", b"third", &no_replace),
+        Err(io::Errno::EXIST)
+    );
+    assert_eq!(std::fs::read(tmp.path().join("config")).unwrap(), b"second");
+    atomic_write_at(&dir, "new", b"third", &no_replace).unwrap();
+    assert_eq!(std::fs::read(tmp.path().join("new")).unwrap(), b"third");
+
+    // Names may have parent directories.
+    std::fs::create_dir(tmp.path().join("sub")).unwrap();
+    atomic_write_at(CWD, tmp.path().join("sub/file"), b"nested", &options).unwrap();
+    assert_eq!(
+        std::fs::read(tmp.path().join("sub/file")).unwrap(),
+        b"nested"
+    );
+    assert_eq!(
+        atomic_write_at(&dir, "sub/", b"", &options),
+        Err(io::Errno::INVAL)
+    );
+    assert_eq!(
+        atomic_write_at(&dir, "sub/..", b"", &options),
+        Err(io::Errno::INVAL)
+    );
+
+    // No temporary files are left behind.
+    assert_eq!(dir_names(tmp.path()), ["config", "new", "sub"]);
+    assert_eq!(dir_names(&tmp.path().join("sub")), ["file"]);
+}
+
+#[test]
+fn test_atomic_write_at_with_error() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = openat(CWD, tmp.path(), OFlags::RDONLY, Mode::empty()).unwrap();
+    let options = AtomicWriteOptions::default();
+
+    atomic_write_at(&dir, "config", b"original", &options).unwrap();
+
+    // If writing fails, the original file is untouched.
+    let result = atomic_write_at_with(&dir, "config", &options, |fd| {
+        rustix::io::write(fd, b"partial")?;
+        Err(io::Errno::IO)
+    });
+    assert_eq!(result, Err(io::Errno::IO));
+    assert_eq!(
+        std::fs::read(tmp.path().join("config")).unwrap(),
+        b"original"
+    );
+    assert_eq!(dir_names(tmp.path()), ["config"]);
+
+    // The closure can write in pieces.
+    atomic_write_at_with(&dir, "config", &options, |fd| {
+        rustix::io::write(fd, b"new ")?;
+        rustix::io::write(fd, b"contents")?;
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(
+        std::fs::read(tmp.path().join("config")).unwrap(),
+        b"new contents"
+    );
+    assert_eq!(dir_names(tmp.path()), ["config"]);
+}
+
+#[test]
+fn test_atomic_exchange_at() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = openat(CWD, tmp.path(), OFlags::RDONLY, Mode::empty()).unwrap();
+
+    std::fs::create_dir(tmp.path().join("tree")).unwrap();
+    std::fs::write(tmp.path().join("tree/version"), b"1").unwrap();
+    std::fs::create_dir(tmp.path().join("tree.new")).unwrap();
+    std::fs::write(tmp.path().join("tree.new/version"), b"2").unwrap();
+
+    match atomic_exchange_at(&dir, "tree.new", "tree") {
+        Ok(()) => (),
+        // Some filesystems don't support `RENAME_EXCHANGE`.
+        Err(io::Errno::INVAL) | Err(io::Errno::NOSYS) => return,
+        Err(err) => panic!("{:?}", err),
+    }
+    assert_eq!(
+        std::fs::read(tmp.path().join("tree/version")).unwrap(),
+        b"2"
+    );
+    assert_eq!(
+        std::fs::read(tmp.path().join("tree.new/version")).unwrap(),
+        b"1"
+    );
+
+    assert_eq!(
+        atomic_exchange_at(&dir, "tree", "missing"),
+        Err(io::Errno::NOENT)
+    );
+}
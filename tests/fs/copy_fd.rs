@@ -0,0 +1,141 @@
+use rustix::fs::{copy_fd, fstat, CopyFdOptions, CopyMethod};
+use rustix::io;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::os::unix::fs::FileExt;
+
+/// Create a file with data at the start and in the middle, and holes between
+/// and after them.
+fn sparse_file() -> File {
+    let mut file = tempfile::tempfile().unwrap();
+    file.write_all(&[1; 4096]).unwrap();
+    file.write_all_at(&[2; 8192], 1 << 20).unwrap();
+    file.set_len(4 << 20).unwrap();
+    file
+}
+
+fn contents(mut file: &File) -> Vec<u8> {
+    let mut buf = Vec::new();
+    file.seek(SeekFrom::Start(0)).unwrap();
+    file.read_to_end(&mut buf).unwrap();
+    buf
+}
+
+#[test]
+fn test_copy_fd_sparse() {
+    let src = sparse_file();
+    let expected = contents(&src);
+
+    for reflink in [true, false] {
+        let dst = tempfile::tempfile().unwrap();
+        dst.write_all_at(b"old contents", 8 << 20).unwrap();
+
+        let options = CopyFdOptions {
+            reflink,
+            ..CopyFdOptions::default()
+        };
+        let (len, method) = copy_fd(&src, &dst, &options).unwrap();
+        assert_eq!(len, 4 << 20);
+        if !reflink {
+            assert_ne!(method, CopyMethod::Reflink);
+        }
+        assert_eq!(contents(&dst), expected);
+
+        // The holes are preserved, so far fewer blocks are used than the
+        // 4 MiB the file covers.
+        let blocks = fstat(&dst).unwrap().st_blocks as u64;
+        assert!(blocks * 512 < 1 << 20, "{} blocks", blocks);
+    }
+
+    // Without `sparse`, the holes are filled in.
+    let dst = tempfile::tempfile().unwrap();
+    let options = CopyFdOptions {
+        reflink: false,
+        sparse: false,
+    };
+    let (len, _method) = copy_fd(&src, &dst, &options).unwrap();
+    assert_eq!(len, 4 << 20);
+    assert_eq!(contents(&dst), expected);
+}
+
+#[test]
+fn test_copy_fd_offsets() {
+    // The whole source is copied, and its file offset is left alone.
+    let mut src = sparse_file();
+    src.seek(SeekFrom::Start(17)).unwrap();
+    let dst = tempfile::tempfile().unwrap();
+    let (len, _method) = copy_fd(&src, &dst, &CopyFdOptions::default()).unwrap();
+    assert_eq!(len, 4 << 20);
+    assert_eq!(src.stream_position().unwrap(), 17);
+}
+
+#[test]
+fn test_copy_fd_procfs() {
+    // Files in procfs report a size of 0, but still have contents.
+    let src = File::open("/proc/self/mountinfo").unwrap();
+    let dst = tempfile::tempfile().unwrap();
+    let (len, _method) = copy_fd(&src, &dst, &CopyFdOptions::default()).unwrap();
+    assert_ne!(len, 0);
+    assert_eq!(contents(&dst).len() as u64, len);
+}
+
+#[test]
+fn test_copy_fd_same_file() {
+    let src = sparse_file();
+    assert_eq!(
+        copy_fd(&src, &src, &CopyFdOptions::default()),
+        Err(io::Errno::INVAL)
+    );
+}
+
+#[cfg(feature = "pipe")]
+#[test]
+fn test_copy_fd_pipe() {
+    use rustix::pipe::pipe;
+
+    // From a regular file to a pipe.
+    let src = tempfile::tempfile().unwrap();
+    src.write_all_at(b"hello, world", 0).unwrap();
+    let (reader, writer) = pipe().unwrap();
+    let (len, method) = copy_fd(&src, &writer, &CopyFdOptions::default()).unwrap();
+    assert_eq!(len, 12);
+    assert_eq!(method, CopyMethod::Splice);
+    drop(writer);
+    let mut buf = String::new();
+    File::from(reader).read_to_string(&mut buf).unwrap();
+    assert_eq!(buf, "hello, world");
+
+    // From a pipe to a regular file.
+    let (reader, writer) = pipe().unwrap();
+    File::from(writer).write_all(b"goodbye").unwrap();
+    let dst = tempfile::tempfile().unwrap();
+    let (len, method) = copy_fd(&reader, &dst, &CopyFdOptions::default()).unwrap();
+    assert_eq!(len, 7);
+    assert_eq!(method, CopyMethod::Splice);
+    assert_eq!(contents(&dst), b"goodbye");
+
+    // From a pipe to a regular file with existing contents, which are
+    // replaced.
+    let (reader, writer) = pipe().unwrap();
+    let data: Vec<u8> = (0..200_000_u32).map(|i| i as u8).collect();
+    let writer = std::thread::spawn(move || File::from(writer).write_all(&data).map(|()| data));
+    let dst = tempfile::tempfile().unwrap();
+    dst.write_all_at(&[0xff; 300_000], 0).unwrap();
+    let (len, method) = copy_fd(&reader, &dst, &CopyFdOptions::default()).unwrap();
+    let data = writer.join().unwrap().unwrap();
+    assert_eq!(len, 200_000);
+    assert_eq!(method, CopyMethod::Splice);
+    assert_eq!(contents(&dst), data);
+
+    // From a pipe to a pipe.
+    let (src_reader, src_writer) = pipe().unwrap();
+    let (dst_reader, dst_writer) = pipe().unwrap();
+    File::from(src_writer).write_all(b"plumbing").unwrap();
+    let (len, method) = copy_fd(&src_reader, &dst_writer, &CopyFdOptions::default()).unwrap();
+    assert_eq!(len, 8);
+    assert_eq!(method, CopyMethod::Splice);
+    drop(dst_writer);
+    let mut buf = String::new();
+    File::from(dst_reader).read_to_string(&mut buf).unwrap();
+    assert_eq!(buf, "plumbing");
+}
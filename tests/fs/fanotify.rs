@@ -0,0 +1,139 @@
+use rustix::fs::fanotify::{
+    fanotify_init, fanotify_mark, EventFlags, FidKind, InfoRecord, InitFlags, MarkFlags, Reader,
+};
+use rustix::fs::{fstat, openat, Mode, OFlags, CWD};
+use rustix::io;
+use std::io::Write;
+use std::mem::MaybeUninit;
+
+#[test]
+fn test_fanotify_fd_events() {
+    let fan = match fanotify_init(
+        InitFlags::CLOEXEC | InitFlags::NONBLOCK | InitFlags::CLASS_NOTIF,
+        OFlags::RDONLY | OFlags::CLOEXEC,
+    ) {
+        Ok(fan) => fan,
+        // fanotify requires `CAP_SYS_ADMIN` for fd-reporting groups.
+        Err(io::Errno::PERM) | Err(io::Errno::NOSYS) => return,
+        Err(err) => panic!("{:?}", err),
+    };
+
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = openat(CWD, tmp.path(), OFlags::RDONLY, Mode::empty()).unwrap();
+    fanotify_mark(
+        &fan,
+        MarkFlags::ADD,
+        EventFlags::CLOSE_WRITE | EventFlags::EVENT_ON_CHILD,
+        &dir,
+        ".",
+    )
+    .unwrap();
+
+    let mut file = std::fs::File::create(tmp.path().join("file")).unwrap();
+    file.write_all(b"hello").unwrap();
+    let file_stat = fstat(&file).unwrap();
+    drop(file);
+
+    let mut buf = [MaybeUninit::uninit(); 4096];
+    let mut reader = unsafe { Reader::new(&fan, &mut buf) };
+    let event = reader.next().unwrap();
+    assert!(event.mask().contains(EventFlags::CLOSE_WRITE));
+    assert_eq!(event.pid(), std::process::id() as i32);
+    assert_eq!(event.info().count(), 0);
+
+    let event_fd = event.into_fd().expect("event without a file descriptor");
+    let event_stat = fstat(&event_fd).unwrap();
+    assert_eq!(event_stat.st_dev, file_stat.st_dev);
+    assert_eq!(event_stat.st_ino, file_stat.st_ino);
+
+    assert!(reader.is_buffer_empty());
+    assert_eq!(reader.next().unwrap_err(), io::Errno::AGAIN);
+}
+
+#[test]
+fn test_fanotify_dfid_name() {
+    let fan = match fanotify_init(
+        InitFlags::CLOEXEC | InitFlags::NONBLOCK | InitFlags::REPORT_DFID_NAME,
+        OFlags::RDONLY | OFlags::CLOEXEC,
+    ) {
+        Ok(fan) => fan,
+        Err(io::Errno::PERM) | Err(io::Errno::NOSYS) | Err(io::Errno::INVAL) => return,
+        Err(err) => panic!("{:?}", err),
+    };
+
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = openat(CWD, tmp.path(), OFlags::RDONLY, Mode::empty()).unwrap();
+    match fanotify_mark(
+        &fan,
+        MarkFlags::ADD | MarkFlags::ONLYDIR,
+        EventFlags::CREATE,
+        &dir,
+        ".",
+    ) {
+        Ok(()) => (),
+        // Some filesystems, such as overlayfs on older kernels, don't
+        // support reporting file handles.
+        Err(io::Errno::XDEV) | Err(io::Errno::NODEV) | Err(io::Errno::OPNOTSUPP) => return,
+        Err(err) => panic!("{:?}", err),
+    }
+
+    std::fs::File::create(tmp.path().join("created")).unwrap();
+
+    let mut buf = [MaybeUninit::uninit(); 4096];
+    let mut reader = unsafe { Reader::new(&fan, &mut buf) };
+    let event = reader.next().unwrap();
+    assert!(event.mask().contains(EventFlags::CREATE));
+    assert!(event.fd().is_none());
+
+    let mut infos = event.info();
+    match infos.next() {
+        Some(InfoRecord::Fid(fid)) => {
+            assert_eq!(fid.kind(), FidKind::DfidName);
+            assert!(!fid.handle().is_empty());
+            assert_eq!(fid.name().unwrap().to_bytes(), b"created");
+        }
+        other => panic!("unexpected info record: {:?}", other),
+    }
+    assert!(infos.next().is_none());
+}
+
+/// Check that a malformed event is skipped on its own, and that its file
+/// descriptor is closed, rather than discarding the events after it.
+#[cfg(feature = "pipe")]
+#[test]
+fn test_fanotify_reader_skips_malformed_event() {
+    use rustix::fd::IntoRawFd;
+    use rustix::pipe::pipe;
+
+    fn event(metadata_len: u16, mask: u64, fd: i32) -> Vec<u8> {
+        let mut event = Vec::new();
+        event.extend_from_slice(&24_u32.to_ne_bytes());
+        event.push(3);
+        event.push(0);
+        event.extend_from_slice(&metadata_len.to_ne_bytes());
+        event.extend_from_slice(&mask.to_ne_bytes());
+        event.extend_from_slice(&fd.to_ne_bytes());
+        event.extend_from_slice(&0_i32.to_ne_bytes());
+        event
+    }
+
+    // The malformed event carries the write end of a pipe, so that we can
+    // tell whether it was closed.
+    let (canary_reader, canary_writer) = pipe().unwrap();
+    let mut events = event(8, EventFlags::OPEN.bits(), canary_writer.into_raw_fd());
+    events.extend(event(24, EventFlags::CLOSE_WRITE.bits(), -1));
+
+    let mut file = tempfile::tempfile().unwrap();
+    file.write_all(&events).unwrap();
+    rustix::fs::seek(&file, rustix::fs::SeekFrom::Start(0)).unwrap();
+
+    let mut buf = [MaybeUninit::uninit(); 4096];
+    let mut reader = unsafe { Reader::new(&file, &mut buf) };
+    assert_eq!(reader.next().unwrap_err(), io::Errno::INVAL);
+    assert_eq!(rustix::io::read(&canary_reader, &mut [0_u8; 1]), Ok(0));
+
+    let event = reader.next().unwrap();
+    assert_eq!(event.mask(), EventFlags::CLOSE_WRITE);
+    assert!(event.fd().is_none());
+    assert!(reader.is_buffer_empty());
+}
@@ -15,3 +15,115 @@ fn test_fcntl_dupfd_cloexec() {
     let new = rustix::fs::fcntl_dupfd_cloexec(&file, 700).unwrap();
     assert_eq!(new.as_fd().as_raw_fd(), 700);
 }
+
+#[cfg(linux_kernel)]
+#[test]
+fn test_fcntl_setown_ex() {
+    use rustix::fs::{fcntl_getown_ex, fcntl_setown_ex, FdOwner};
+    use rustix::process::getpid;
+    use rustix::thread::gettid;
+
+    let f = tempfile::tempfile().unwrap();
+    assert_eq!(fcntl_getown_ex(&f).unwrap(), None);
+
+    fcntl_setown_ex(&f, Some(FdOwner::Process(getpid()))).unwrap();
+    assert_eq!(
+        fcntl_getown_ex(&f).unwrap(),
+        Some(FdOwner::Process(getpid()))
+    );
+
+    fcntl_setown_ex(&f, Some(FdOwner::Thread(gettid()))).unwrap();
+    assert_eq!(
+        fcntl_getown_ex(&f).unwrap(),
+        Some(FdOwner::Thread(gettid()))
+    );
+
+    fcntl_setown_ex(&f, None).unwrap();
+    assert_eq!(fcntl_getown_ex(&f).unwrap(), None);
+}
+
+#[cfg(linux_kernel)]
+#[test]
+fn test_fcntl_setsig() {
+    use rustix::fs::{fcntl_getsig, fcntl_setsig};
+
+    let f = tempfile::tempfile().unwrap();
+    assert_eq!(fcntl_getsig(&f).unwrap(), 0);
+
+    let sigrtmin = libc::SIGRTMIN();
+    fcntl_setsig(&f, sigrtmin).unwrap();
+    assert_eq!(fcntl_getsig(&f).unwrap(), sigrtmin);
+
+    fcntl_setsig(&f, 0).unwrap();
+    assert_eq!(fcntl_getsig(&f).unwrap(), 0);
+
+    assert_eq!(fcntl_setsig(&f, -1), Err(rustix::io::Errno::INVAL));
+}
+
+#[cfg(linux_kernel)]
+#[test]
+fn test_fcntl_setlease() {
+    use rustix::fs::{fcntl_getlease, fcntl_setlease, openat, LeaseType, Mode, OFlags, CWD};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("file");
+    let f = openat(
+        CWD,
+        &path,
+        OFlags::CREATE | OFlags::WRONLY | OFlags::CLOEXEC,
+        Mode::RUSR | Mode::WUSR,
+    )
+    .unwrap();
+    assert_eq!(fcntl_getlease(&f).unwrap(), LeaseType::Unlock);
+
+    // Leases may be disabled with `fs.leases-enable`.
+    match fcntl_setlease(&f, LeaseType::Write) {
+        Ok(()) => {}
+        Err(rustix::io::Errno::INVAL) => return,
+        Err(err) => panic!("{:?}", err),
+    }
+    assert_eq!(fcntl_getlease(&f).unwrap(), LeaseType::Write);
+
+    // A read lease requires a read-only file descriptor.
+    assert_eq!(
+        fcntl_setlease(&f, LeaseType::Read),
+        Err(rustix::io::Errno::AGAIN)
+    );
+
+    fcntl_setlease(&f, LeaseType::Unlock).unwrap();
+    assert_eq!(fcntl_getlease(&f).unwrap(), LeaseType::Unlock);
+    drop(f);
+
+    let f = openat(CWD, &path, OFlags::RDONLY | OFlags::CLOEXEC, Mode::empty()).unwrap();
+    fcntl_setlease(&f, LeaseType::Read).unwrap();
+    assert_eq!(fcntl_getlease(&f).unwrap(), LeaseType::Read);
+    fcntl_setlease(&f, LeaseType::Unlock).unwrap();
+}
+
+#[cfg(linux_kernel)]
+#[test]
+fn test_fcntl_notify() {
+    use rustix::fs::{fcntl_notify, openat, DnotifyFlags, Mode, OFlags, CWD};
+
+    let dir = tempfile::tempdir().unwrap();
+    let dir = openat(
+        CWD,
+        dir.path(),
+        OFlags::RDONLY | OFlags::DIRECTORY | OFlags::CLOEXEC,
+        Mode::empty(),
+    )
+    .unwrap();
+
+    fcntl_notify(
+        &dir,
+        DnotifyFlags::CREATE | DnotifyFlags::DELETE | DnotifyFlags::MULTISHOT,
+    )
+    .unwrap();
+    fcntl_notify(&dir, DnotifyFlags::empty()).unwrap();
+
+    let f = tempfile::tempfile().unwrap();
+    assert_eq!(
+        fcntl_notify(&f, DnotifyFlags::CREATE),
+        Err(rustix::io::Errno::NOTDIR)
+    );
+}
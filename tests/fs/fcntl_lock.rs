@@ -31,3 +31,125 @@ fn test_fcntl_lock() {
     drop(f);
     drop(g);
 }
+
+#[cfg(not(any(
+    target_os = "emscripten",
+    target_os = "fuchsia",
+    target_os = "redox",
+    target_os = "wasi"
+)))]
+#[test]
+fn test_fcntl_setlk() {
+    use rustix::fs::{fcntl_getlk, fcntl_setlk, fcntl_setlkw, Flock, FlockType};
+
+    let f = tempfile::tempfile().unwrap();
+    fcntl_setlk(&f, &Flock::new(FlockType::WriteLock, 0, 10)).unwrap();
+    fcntl_setlkw(&f, &Flock::new(FlockType::ReadLock, 20, 10)).unwrap();
+
+    // Our own locks never conflict with our own queries.
+    assert_eq!(
+        fcntl_getlk(&f, &Flock::new(FlockType::WriteLock, 0, 0)).unwrap(),
+        None
+    );
+
+    fcntl_setlk(&f, &Flock::new(FlockType::Unlocked, 0, 0)).unwrap();
+}
+
+#[cfg(linux_kernel)]
+#[test]
+fn test_fcntl_ofd_setlk() {
+    use rustix::fs::{
+        fcntl_getlk, fcntl_ofd_getlk, fcntl_ofd_setlk, fcntl_ofd_setlkw, fcntl_setlk, Flock,
+        FlockOffsetType, FlockType,
+    };
+    use rustix::io;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("file");
+    let open = || {
+        std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .unwrap()
+    };
+    let f = open();
+    let g = open();
+
+    // Open file description locks on separate opens of the same file
+    // conflict, even within one process.
+    fcntl_ofd_setlk(&f, &Flock::new(FlockType::WriteLock, 10, 5)).unwrap();
+    assert_eq!(
+        fcntl_ofd_setlk(&g, &Flock::new(FlockType::ReadLock, 12, 1)),
+        Err(io::Errno::AGAIN)
+    );
+    fcntl_ofd_setlkw(&g, &Flock::new(FlockType::WriteLock, 15, 5)).unwrap();
+
+    // The conflicting lock is reported with its range, and without a pid.
+    let conflict = fcntl_ofd_getlk(&g, &Flock::new(FlockType::ReadLock, 0, 12))
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        conflict,
+        Flock {
+            start: 10,
+            length: 5,
+            pid: None,
+            typ: FlockType::WriteLock,
+            offset_type: FlockOffsetType::Set,
+        }
+    );
+    assert_eq!(
+        fcntl_ofd_getlk(&g, &Flock::new(FlockType::WriteLock, 20, 0)).unwrap(),
+        None
+    );
+
+    // They conflict with process-associated locks too.
+    assert_eq!(
+        fcntl_getlk(&f, &Flock::new(FlockType::ReadLock, 16, 1))
+            .unwrap()
+            .map(|lock| (lock.start, lock.length, lock.typ)),
+        Some((15, 5, FlockType::WriteLock))
+    );
+    assert!(matches!(
+        fcntl_setlk(&f, &Flock::new(FlockType::WriteLock, 0, 0)),
+        Err(io::Errno::AGAIN) | Err(io::Errno::ACCESS)
+    ));
+
+    fcntl_ofd_setlk(&f, &Flock::new(FlockType::Unlocked, 0, 0)).unwrap();
+    assert_eq!(
+        fcntl_ofd_getlk(&g, &Flock::new(FlockType::WriteLock, 0, 15)).unwrap(),
+        None
+    );
+
+    // Open file description locks require `l_pid` to be zero, so the pid
+    // field is ignored when setting.
+    let mut lock = Flock::new(FlockType::ReadLock, 0, 1);
+    lock.pid = rustix::process::getpid().into();
+    fcntl_ofd_setlk(&f, &lock).unwrap();
+}
+
+/// Ranges beyond 4 GiB work, even on 32-bit platforms.
+#[cfg(linux_kernel)]
+#[test]
+fn test_fcntl_setlk_large_offset() {
+    use rustix::fs::{fcntl_getlk, fcntl_ofd_getlk, fcntl_ofd_setlk, Flock, FlockType};
+
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("file");
+    let f = std::fs::File::create(&path).unwrap();
+    let g = std::fs::File::create(&path).unwrap();
+
+    let start = (1 << 33) + 10;
+    fcntl_ofd_setlk(&f, &Flock::new(FlockType::WriteLock, start, 1 << 32)).unwrap();
+    let conflict = fcntl_ofd_getlk(&g, &Flock::new(FlockType::WriteLock, 0, 0))
+        .unwrap()
+        .unwrap();
+    assert_eq!((conflict.start, conflict.length), (start, 1 << 32));
+    let conflict = fcntl_getlk(&g, &Flock::new(FlockType::ReadLock, start + 5, 1))
+        .unwrap()
+        .unwrap();
+    assert_eq!((conflict.start, conflict.length), (start, 1 << 32));
+}
@@ -0,0 +1,68 @@
+use rustix::fs::{
+    fstat, name_to_handle_at, open_by_handle_at, openat, renameat, AtFlags, FileHandle, Mode,
+    OFlags, CWD,
+};
+use rustix::io;
+
+#[test]
+fn test_file_handle() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = openat(CWD, tmp.path(), OFlags::RDONLY, Mode::empty()).unwrap();
+    let file = openat(
+        &dir,
+        "file",
+        OFlags::CREATE | OFlags::WRONLY,
+        Mode::RUSR | Mode::WUSR,
+    )
+    .unwrap();
+
+    let (handle, _mount_id) = match name_to_handle_at(&dir, "file", AtFlags::empty()) {
+        Ok(handle) => handle,
+        // Some filesystems don't support file handles.
+        Err(io::Errno::OPNOTSUPP) | Err(io::Errno::NOSYS) => return,
+        Err(err) => panic!("{:?}", err),
+    };
+    assert!(!handle.as_bytes().is_empty());
+
+    // Getting a handle for the same file via `AT_EMPTY_PATH` gives the same
+    // handle.
+    let (same, _mount_id) = name_to_handle_at(&file, "", AtFlags::EMPTY_PATH).unwrap();
+    assert_eq!(handle, same);
+
+    // Handles can be round-tripped through their bytes.
+    let copy = FileHandle::new(handle.handle_type(), handle.as_bytes()).unwrap();
+    assert_eq!(handle, copy);
+    assert!(FileHandle::new(0, &[0; 129]).is_none());
+
+    // The handle survives renames.
+    renameat(&dir, "file", &dir, "renamed").unwrap();
+    let reopened = match open_by_handle_at(&dir, &copy, OFlags::RDONLY | OFlags::CLOEXEC) {
+        Ok(reopened) => reopened,
+        // Opening handles requires `CAP_DAC_READ_SEARCH`.
+        Err(io::Errno::PERM) => return,
+        Err(err) => panic!("{:?}", err),
+    };
+    let file_stat = fstat(&file).unwrap();
+    let reopened_stat = fstat(&reopened).unwrap();
+    assert_eq!(file_stat.st_dev, reopened_stat.st_dev);
+    assert_eq!(file_stat.st_ino, reopened_stat.st_ino);
+}
+
+#[test]
+fn test_file_handle_mount_id() {
+    use rustix::fs::{statx, StatxFlags};
+
+    let (_handle, mount_id) = match name_to_handle_at(CWD, "Cargo.toml", AtFlags::empty()) {
+        Ok(handle) => handle,
+        Err(io::Errno::OPNOTSUPP) | Err(io::Errno::NOSYS) => return,
+        Err(err) => panic!("{:?}", err),
+    };
+
+    // The mount ID agrees with `statx`'s, if it has one.
+    match statx(CWD, "Cargo.toml", AtFlags::empty(), StatxFlags::MNT_ID) {
+        Ok(statx) if statx.stx_mask & StatxFlags::MNT_ID.bits() != 0 => {
+            assert_eq!(statx.stx_mnt_id, mount_id as u64);
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,104 @@
+use rustix::fs::{
+    fs_context_read_log, fsconfig_create, fsconfig_set_flag, fsconfig_set_string, fsmount, fsopen,
+    mkdirat, mount_setattr, move_mount_at, open_tree, openat, statat, unmount, AtFlags, FsLogLevel,
+    FsMountFlags, FsOpenFlags, Mode, MountAttr, MountAttrFlags, MoveMountFlags, OFlags,
+    OpenTreeFlags, UnmountFlags, CWD,
+};
+use rustix::io;
+
+/// Create a filesystem context for a tmpfs, or return `None` if we aren't
+/// privileged or the kernel doesn't support the mount API.
+fn tmpfs_context() -> Option<rustix::fd::OwnedFd> {
+    match fsopen("tmpfs", FsOpenFlags::CLOEXEC) {
+        Ok(fs_fd) => Some(fs_fd),
+        Err(io::Errno::PERM) | Err(io::Errno::NOSYS) => None,
+        Err(err) => panic!("{:?}", err),
+    }
+}
+
+#[test]
+fn test_fsopen_fsmount() {
+    let fs_fd = match tmpfs_context() {
+        Some(fs_fd) => fs_fd,
+        None => return,
+    };
+    fsconfig_set_string(&fs_fd, "size", "1M").unwrap();
+    fsconfig_set_string(&fs_fd, "mode", "0700").unwrap();
+    fsconfig_create(&fs_fd).unwrap();
+    let mnt = fsmount(&fs_fd, FsMountFlags::CLOEXEC, MountAttrFlags::NODEV).unwrap();
+
+    // The detached mount can be used without attaching it anywhere.
+    mkdirat(&mnt, "dir", Mode::RWXU).unwrap();
+    assert!(statat(&mnt, "dir", AtFlags::empty()).is_ok());
+
+    // Attach it to a temporary directory, and check that it's visible there.
+    let tmp = tempfile::tempdir().unwrap();
+    move_mount_at(&mnt, "", CWD, tmp.path(), MoveMountFlags::F_EMPTY_PATH).unwrap();
+    assert!(tmp.path().join("dir").is_dir());
+    unmount(tmp.path(), UnmountFlags::DETACH).unwrap();
+    assert!(!tmp.path().join("dir").exists());
+}
+
+#[test]
+fn test_fsconfig_error_log() {
+    let fs_fd = match tmpfs_context() {
+        Some(fs_fd) => fs_fd,
+        None => return,
+    };
+
+    assert_eq!(
+        fsconfig_set_flag(&fs_fd, "no-such-parameter"),
+        Err(io::Errno::INVAL)
+    );
+
+    let mut buf = [0_u8; 512];
+    let message = fs_context_read_log(&fs_fd, &mut buf)
+        .unwrap()
+        .expect("no log message");
+    assert_eq!(message.level, FsLogLevel::Error);
+    let text = std::str::from_utf8(message.message).unwrap();
+    assert!(text.contains("no-such-parameter"), "{}", text);
+
+    assert_eq!(fs_context_read_log(&fs_fd, &mut buf).unwrap(), None);
+}
+
+#[test]
+fn test_open_tree_mount_setattr() {
+    let fs_fd = match tmpfs_context() {
+        Some(fs_fd) => fs_fd,
+        None => return,
+    };
+    fsconfig_create(&fs_fd).unwrap();
+    let mnt = fsmount(&fs_fd, FsMountFlags::CLOEXEC, MountAttrFlags::empty()).unwrap();
+
+    let clone = open_tree(
+        &mnt,
+        "",
+        OpenTreeFlags::CLONE | OpenTreeFlags::CLOEXEC | OpenTreeFlags::EMPTY_PATH,
+    )
+    .unwrap();
+
+    let attr = MountAttr {
+        attr_set: MountAttrFlags::RDONLY,
+        ..MountAttr::default()
+    };
+    match mount_setattr(&clone, "", AtFlags::EMPTY_PATH, &attr) {
+        Ok(()) => (),
+        Err(io::Errno::NOSYS) => return,
+        Err(err) => panic!("{:?}", err),
+    }
+
+    // The clone is read-only, while the original is still writable.
+    assert_eq!(
+        mkdirat(&clone, "dir", Mode::RWXU).unwrap_err(),
+        io::Errno::ROFS
+    );
+    mkdirat(&mnt, "dir", Mode::RWXU).unwrap();
+    openat(
+        &clone,
+        "dir",
+        OFlags::RDONLY | OFlags::DIRECTORY,
+        Mode::empty(),
+    )
+    .unwrap();
+}
@@ -0,0 +1,84 @@
+use rustix::fd::AsFd;
+use rustix::fs::inotify::{
+    inotify_add_watch, inotify_init, inotify_remove_watch, CreateFlags, ReadFlags, Reader,
+    WatchFlags,
+};
+use rustix::io;
+use std::mem::MaybeUninit;
+
+#[test]
+fn test_inotify_reader() {
+    let inotify = inotify_init(CreateFlags::CLOEXEC | CreateFlags::NONBLOCK).unwrap();
+    let tmp = tempfile::tempdir().unwrap();
+    let wd = inotify_add_watch(
+        inotify.as_fd(),
+        tmp.path(),
+        WatchFlags::CREATE | WatchFlags::MOVE | WatchFlags::DELETE,
+    )
+    .unwrap();
+
+    std::fs::File::create(tmp.path().join("a")).unwrap();
+    std::fs::create_dir(tmp.path().join("dir")).unwrap();
+    std::fs::rename(
+        tmp.path().join("a"),
+        tmp.path().join("a-rather-longer-name"),
+    )
+    .unwrap();
+    std::fs::remove_file(tmp.path().join("a-rather-longer-name")).unwrap();
+
+    // Use an unaligned buffer to check that the reader doesn't depend on
+    // alignment.
+    let mut buf = [MaybeUninit::uninit(); 1025];
+    let mut reader = Reader::new(&inotify, &mut buf[1..]);
+
+    let mut next = || {
+        let event = reader.next().unwrap();
+        assert_eq!(event.wd(), wd);
+        (
+            event.events(),
+            event.cookie(),
+            event.file_name().unwrap().to_str().unwrap().to_owned(),
+        )
+    };
+
+    assert_eq!(next(), (ReadFlags::CREATE, 0, "a".to_owned()));
+    assert_eq!(
+        next(),
+        (ReadFlags::CREATE | ReadFlags::ISDIR, 0, "dir".to_owned())
+    );
+    let (from, from_cookie, from_name) = next();
+    let (to, to_cookie, to_name) = next();
+    assert_eq!(from, ReadFlags::MOVED_FROM);
+    assert_eq!(from_name, "a");
+    assert_eq!(to, ReadFlags::MOVED_TO);
+    assert_eq!(to_name, "a-rather-longer-name");
+    assert_ne!(from_cookie, 0);
+    assert_eq!(from_cookie, to_cookie);
+    assert_eq!(
+        next(),
+        (ReadFlags::DELETE, 0, "a-rather-longer-name".to_owned())
+    );
+
+    assert!(reader.is_buffer_empty());
+    assert_eq!(reader.next().unwrap_err(), io::Errno::AGAIN);
+
+    // Removing the watch produces an `IN_IGNORED` event with no name.
+    inotify_remove_watch(inotify.as_fd(), wd).unwrap();
+    let event = reader.next().unwrap();
+    assert_eq!(event.wd(), wd);
+    assert_eq!(event.events(), ReadFlags::IGNORED);
+    assert_eq!(event.file_name(), None);
+}
+
+#[test]
+fn test_inotify_small_buffer() {
+    let inotify = inotify_init(CreateFlags::CLOEXEC | CreateFlags::NONBLOCK).unwrap();
+    let tmp = tempfile::tempdir().unwrap();
+    inotify_add_watch(inotify.as_fd(), tmp.path(), WatchFlags::CREATE).unwrap();
+    std::fs::File::create(tmp.path().join("file")).unwrap();
+
+    // A buffer too small to hold a whole event is rejected by the kernel.
+    let mut buf = [MaybeUninit::uninit(); 16];
+    let mut reader = Reader::new(&inotify, &mut buf);
+    assert_eq!(reader.next().unwrap_err(), io::Errno::INVAL);
+}
@@ -22,3 +22,123 @@ fn test_ioctl_ficlone() {
         Err(err) => Err(err).unwrap(),
     }
 }
+
+#[cfg(linux_kernel)]
+#[test]
+fn test_ioctl_inode_flags() {
+    use rustix::fs::{ioctl_fsgetxattr, ioctl_getflags, ioctl_setflags, FsXFlags, InodeFlags};
+    use rustix::io;
+
+    let file = tempfile::tempfile().unwrap();
+
+    let flags = match ioctl_getflags(&file) {
+        Ok(flags) => flags,
+        // Not all filesystems support inode flags.
+        Err(io::Errno::NOTTY) | Err(io::Errno::OPNOTSUPP) | Err(io::Errno::INVAL) => return,
+        Err(err) => panic!("{:?}", err),
+    };
+    assert!(!flags.contains(InodeFlags::IMMUTABLE));
+
+    // Setting `NOATIME` only requires owning the file.
+    ioctl_setflags(&file, flags | InodeFlags::NOATIME).unwrap();
+    assert!(ioctl_getflags(&file).unwrap().contains(InodeFlags::NOATIME));
+
+    // The extended attributes mirror the inode flags.
+    match ioctl_fsgetxattr(&file) {
+        Ok(xattr) => assert!(xattr.xflags.contains(FsXFlags::NOATIME)),
+        Err(io::Errno::NOTTY) | Err(io::Errno::OPNOTSUPP) => (),
+        Err(err) => panic!("{:?}", err),
+    }
+
+    ioctl_setflags(&file, flags).unwrap();
+    assert!(!ioctl_getflags(&file).unwrap().contains(InodeFlags::NOATIME));
+}
+
+#[cfg(linux_kernel)]
+#[test]
+fn test_ioctl_fssetxattr() {
+    use rustix::fs::{ioctl_fsgetxattr, ioctl_fssetxattr, FsXFlags};
+    use rustix::io;
+
+    let file = tempfile::tempfile().unwrap();
+    let mut xattr = match ioctl_fsgetxattr(&file) {
+        Ok(xattr) => xattr,
+        Err(io::Errno::NOTTY) | Err(io::Errno::OPNOTSUPP) | Err(io::Errno::INVAL) => return,
+        Err(err) => panic!("{:?}", err),
+    };
+
+    // Writing back what we read is always allowed.
+    ioctl_fssetxattr(&file, &xattr).unwrap();
+
+    xattr.xflags |= FsXFlags::NODUMP;
+    ioctl_fssetxattr(&file, &xattr).unwrap();
+    assert!(ioctl_fsgetxattr(&file)
+        .unwrap()
+        .xflags
+        .contains(FsXFlags::NODUMP));
+}
+
+#[cfg(linux_kernel)]
+#[test]
+fn test_ioctl_fiemap() {
+    use rustix::fs::{fsync, ioctl_fiemap, FiemapExtentFlags, FiemapFlags};
+    use rustix::io::{self, pwrite};
+
+    let file = tempfile::tempfile().unwrap();
+    let chunk = [1_u8; 4096];
+
+    // Write many separate 4 KiB chunks with holes between them, so that we
+    // need more than one batch of extents.
+    for i in 0..100 {
+        pwrite(&file, &chunk, i * 65536).unwrap();
+    }
+    fsync(&file).unwrap();
+
+    let mut extents = Vec::new();
+    for extent in ioctl_fiemap(&file, 0, u64::MAX, FiemapFlags::SYNC) {
+        match extent {
+            Ok(extent) => extents.push(extent),
+            Err(io::Errno::OPNOTSUPP) => return,
+            Err(err) => panic!("{:?}", err),
+        }
+    }
+    assert!(!extents.is_empty());
+    assert!(extents
+        .last()
+        .unwrap()
+        .flags
+        .contains(FiemapExtentFlags::LAST));
+
+    // The extents don't overlap, and they cover the chunks we wrote.
+    // Filesystems may allocate more than we wrote, so they may cover more.
+    check_extents(&extents, 0..100);
+
+    // A range in the middle only sees the extents overlapping it.
+    let middle: Vec<_> = ioctl_fiemap(&file, 65536 * 10, 65536 * 3, FiemapFlags::empty())
+        .map(Result::unwrap)
+        .collect();
+    check_extents(&middle, 10..13);
+    for extent in &middle {
+        assert!(extent.logical < 65536 * 13);
+        assert!(extent.logical + extent.length > 65536 * 10);
+    }
+}
+
+/// Check that `extents` are sorted, don't overlap, and cover the 4 KiB
+/// chunks at each multiple of 64 KiB in `chunks`.
+#[cfg(linux_kernel)]
+fn check_extents(extents: &[rustix::fs::FiemapExtent], chunks: std::ops::Range<u64>) {
+    for pair in extents.windows(2) {
+        assert!(pair[0].logical + pair[0].length <= pair[1].logical);
+    }
+    for i in chunks {
+        let (start, end) = (i * 65536, i * 65536 + 4096);
+        assert!(
+            extents
+                .iter()
+                .any(|extent| extent.logical <= start && extent.logical + extent.length >= end),
+            "chunk {} isn't covered",
+            i
+        );
+    }
+}
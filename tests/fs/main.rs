@@ -4,10 +4,18 @@
 #![cfg(not(windows))]
 #![cfg_attr(core_c_str, feature(core_c_str))]
 
+#[cfg(linux_kernel)]
+mod acl;
+#[cfg(linux_kernel)]
+mod atomic_write;
 mod chmodat;
+#[cfg(target_os = "linux")]
+mod copy_fd;
 mod cwd;
 #[cfg(not(target_os = "redox"))]
 mod dir;
+#[cfg(target_os = "linux")]
+mod fanotify;
 mod fcntl;
 #[cfg(not(any(
     target_os = "emscripten",
@@ -17,9 +25,15 @@ mod fcntl;
 )))]
 mod fcntl_lock;
 mod file;
+#[cfg(linux_kernel)]
+mod file_handle;
 #[cfg(not(target_os = "wasi"))]
 mod flock;
+#[cfg(linux_kernel)]
+mod fsopen;
 mod futimens;
+#[cfg(linux_kernel)]
+mod inotify;
 mod invalid_offset;
 #[cfg(not(target_os = "redox"))]
 mod ioctl;
@@ -27,6 +41,8 @@ mod linkat;
 mod long_paths;
 #[cfg(not(any(target_os = "haiku", target_os = "redox", target_os = "wasi")))]
 mod makedev;
+#[cfg(linux_kernel)]
+mod memfd;
 mod mkdirat;
 mod mknodat;
 #[cfg(linux_kernel)]
@@ -42,11 +58,17 @@ mod seals;
 #[cfg(not(any(target_os = "haiku", target_os = "redox", target_os = "wasi")))]
 mod statfs;
 #[cfg(linux_kernel)]
+mod statmount;
+#[cfg(linux_kernel)]
 mod statx;
 mod symlinkat;
 #[cfg(not(any(solarish, target_os = "redox", target_os = "wasi")))]
 mod sync;
+#[cfg(linux_kernel)]
+mod temp;
 mod utimensat;
+#[cfg(linux_kernel)]
+mod walk;
 #[cfg(any(apple, linux_kernel))]
 mod xattr;
 mod y2038;
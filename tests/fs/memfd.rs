@@ -0,0 +1,76 @@
+use rustix::fs::{memfd_create, MemfdFlags};
+use rustix::io;
+
+#[test]
+fn test_memfd_hugetlb_size() {
+    assert_eq!(
+        MemfdFlags::hugetlb_size(2 << 20),
+        Some(MemfdFlags::HUGETLB | MemfdFlags::HUGE_2MB)
+    );
+    assert_eq!(
+        MemfdFlags::hugetlb_size(1 << 30),
+        Some(MemfdFlags::HUGETLB | MemfdFlags::HUGE_1GB)
+    );
+    assert_eq!(
+        MemfdFlags::hugetlb_size(64 << 10),
+        Some(MemfdFlags::HUGETLB | MemfdFlags::HUGE_64KB)
+    );
+    assert_eq!(MemfdFlags::hugetlb_size(0), None);
+    assert_eq!(MemfdFlags::hugetlb_size(1), None);
+    assert_eq!(MemfdFlags::hugetlb_size(3 << 20), None);
+
+    let flags = MemfdFlags::hugetlb_size(16 << 30).unwrap() | MemfdFlags::CLOEXEC;
+    assert_eq!(flags.huge_page_size(), Some(16 << 30));
+    assert_eq!(MemfdFlags::HUGETLB.huge_page_size(), None);
+    assert_eq!(MemfdFlags::HUGE_2MB.huge_page_size(), None);
+
+    // Huge pages may not be configured, so just check that the kernel
+    // accepts the encoding, or rejects it in the expected ways.
+    match memfd_create("hugetlb", MemfdFlags::hugetlb_size(2 << 20).unwrap()) {
+        Ok(_) | Err(io::Errno::INVAL) | Err(io::Errno::NOSYS) | Err(io::Errno::NOENT) => {}
+        Err(err) => panic!("{:?}", err),
+    }
+}
+
+#[cfg(any(
+    target_arch = "aarch64",
+    target_arch = "riscv32",
+    target_arch = "riscv64",
+    target_arch = "x86",
+    target_arch = "x86_64",
+))]
+#[test]
+fn test_memfd_secret() {
+    use rustix::fs::{fstat, ftruncate, memfd_secret, SecretFlags};
+
+    let fd = match memfd_secret(SecretFlags::CLOEXEC) {
+        Ok(fd) => fd,
+        // `memfd_secret` is new in Linux 5.14, and may be disabled.
+        Err(io::Errno::NOSYS) | Err(io::Errno::PERM) => return,
+        Err(err) => panic!("{:?}", err),
+    };
+    ftruncate(&fd, 4096).unwrap();
+    assert_eq!(fstat(&fd).unwrap().st_size, 4096);
+
+    #[cfg(feature = "mm")]
+    unsafe {
+        use rustix::mm::{mmap, munmap, MapFlags, ProtFlags};
+
+        let addr = match mmap(
+            core::ptr::null_mut(),
+            4096,
+            ProtFlags::READ | ProtFlags::WRITE,
+            MapFlags::SHARED,
+            &fd,
+            0,
+        ) {
+            Ok(addr) => addr.cast::<u8>(),
+            // Secret memory counts against `RLIMIT_MEMLOCK`.
+            Err(io::Errno::AGAIN) | Err(io::Errno::NOMEM) => return,
+            Err(err) => panic!("{:?}", err),
+        };
+        addr.write(0xa5);
+        assert_eq!(addr.read(), 0xa5);
+        munmap(addr.cast(), 4096).unwrap();
+    }
+}
@@ -180,3 +180,142 @@ fn test_openat2() {
     )
     .unwrap();
 }
+
+#[test]
+fn test_openat_resolve() {
+    use rustix::fs::{fstat, openat_resolve, symlinkat, Stat};
+
+    fn same(a: &Stat, b: &Stat) -> bool {
+        a.st_dev == b.st_dev && a.st_ino == b.st_ino
+    }
+
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = openat(CWD, tmp.path(), OFlags::RDONLY, Mode::empty()).unwrap();
+    mkdirat(&dir, "a", Mode::RWXU).unwrap();
+    let file = openat(
+        &dir,
+        "a/file",
+        OFlags::WRONLY | OFlags::CREATE,
+        Mode::RUSR | Mode::WUSR,
+    )
+    .unwrap();
+    let file = fstat(&file).unwrap();
+    symlinkat("/a/file", &dir, "abs").unwrap();
+    symlinkat("a/file", &dir, "rel").unwrap();
+    symlinkat("a", &dir, "dirlink").unwrap();
+    symlinkat("../../a/file", &dir, "a/up").unwrap();
+    symlinkat("loop2", &dir, "loop1").unwrap();
+    symlinkat("loop1", &dir, "loop2").unwrap();
+    symlinkat("a/new", &dir, "dangling").unwrap();
+
+    let open = |path: &str, oflags: OFlags, resolve: ResolveFlags| {
+        // `openat2` requires the mode to be empty unless creating a file.
+        let mode = if oflags.contains(OFlags::CREATE) {
+            Mode::RUSR
+        } else {
+            Mode::empty()
+        };
+        openat_resolve(&dir, path, oflags | OFlags::CLOEXEC, mode, resolve)
+            .map(|fd| fstat(fd).unwrap())
+    };
+    let rdonly = OFlags::RDONLY;
+
+    // With no flags, everything resolves as usual.
+    let none = ResolveFlags::empty();
+    assert!(same(&open("rel", rdonly, none).unwrap(), &file));
+    assert!(same(&open("dirlink//file", rdonly, none).unwrap(), &file));
+    assert!(open("dirlink", OFlags::DIRECTORY, none).is_ok());
+    assert!(open("dirlink/", rdonly, none).is_ok());
+    assert_eq!(open("loop1", rdonly, none).unwrap_err(), io::Errno::LOOP);
+    assert_eq!(
+        open("a/file/", rdonly, none).unwrap_err(),
+        io::Errno::NOTDIR
+    );
+    assert_eq!(open("", rdonly, none).unwrap_err(), io::Errno::NOENT);
+
+    // `O_NOFOLLOW` applies to the last component only.
+    assert_eq!(
+        open("rel", OFlags::NOFOLLOW, none).unwrap_err(),
+        io::Errno::LOOP
+    );
+    let link = open("rel", OFlags::PATH | OFlags::NOFOLLOW, none).unwrap();
+    assert!(!same(&link, &file));
+
+    // `BENEATH` rejects anything which leaves the directory.
+    let beneath = ResolveFlags::BENEATH;
+    assert!(same(&open("a/../rel", rdonly, beneath).unwrap(), &file));
+    assert_eq!(open("..", rdonly, beneath).unwrap_err(), io::Errno::XDEV);
+    assert_eq!(open("abs", rdonly, beneath).unwrap_err(), io::Errno::XDEV);
+    assert_eq!(open("a/up", rdonly, beneath).unwrap_err(), io::Errno::XDEV);
+    assert_eq!(
+        open("/a/file", rdonly, beneath).unwrap_err(),
+        io::Errno::XDEV
+    );
+
+    // `IN_ROOT` treats the directory as the root.
+    let in_root = ResolveFlags::IN_ROOT;
+    assert!(same(&open("abs", rdonly, in_root).unwrap(), &file));
+    assert!(same(&open("a/up", rdonly, in_root).unwrap(), &file));
+    assert!(same(
+        &open("/../../a/file", rdonly, in_root).unwrap(),
+        &file
+    ));
+    let created = open("dangling", OFlags::WRONLY | OFlags::CREATE, in_root).unwrap();
+    let new = fstat(openat(&dir, "a/new", rdonly, Mode::empty()).unwrap()).unwrap();
+    assert!(same(&created, &new));
+
+    // `NO_SYMLINKS` rejects all symlinks, unless the last component is
+    // opened with `O_PATH | O_NOFOLLOW`.
+    let no_symlinks = ResolveFlags::NO_SYMLINKS;
+    assert_eq!(
+        open("rel", rdonly, no_symlinks).unwrap_err(),
+        io::Errno::LOOP
+    );
+    assert_eq!(
+        open("dirlink/file", rdonly, no_symlinks).unwrap_err(),
+        io::Errno::LOOP
+    );
+    assert!(open("rel", OFlags::PATH | OFlags::NOFOLLOW, no_symlinks).is_ok());
+    assert!(same(&open("a/file", rdonly, no_symlinks).unwrap(), &file));
+
+    // `BENEATH` and `IN_ROOT` can't be combined.
+    assert_eq!(
+        open("rel", rdonly, beneath | in_root).unwrap_err(),
+        io::Errno::INVAL
+    );
+
+    // `NO_XDEV` stops at mount points, and `NO_MAGICLINKS` rejects
+    // `/proc/self/fd/*`.
+    if let Ok(root_proc) = openat(CWD, "/proc", OFlags::RDONLY, Mode::empty()) {
+        let root = openat(CWD, "/", OFlags::RDONLY, Mode::empty()).unwrap();
+        if fstat(&root).unwrap().st_dev != fstat(&root_proc).unwrap().st_dev {
+            assert_eq!(
+                openat_resolve(&root, "proc", rdonly, Mode::empty(), ResolveFlags::NO_XDEV)
+                    .unwrap_err(),
+                io::Errno::XDEV
+            );
+        }
+
+        let magic = format!("/proc/self/fd/{}", dir.as_raw_fd());
+        assert!(openat_resolve(CWD, &magic, rdonly, Mode::empty(), none).is_ok());
+        assert_eq!(
+            openat_resolve(
+                CWD,
+                &magic,
+                rdonly,
+                Mode::empty(),
+                ResolveFlags::NO_MAGICLINKS
+            )
+            .unwrap_err(),
+            io::Errno::LOOP
+        );
+        assert!(openat_resolve(
+            CWD,
+            "/proc/self/fd",
+            rdonly,
+            Mode::empty(),
+            ResolveFlags::NO_MAGICLINKS
+        )
+        .is_ok());
+    }
+}
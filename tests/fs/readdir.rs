@@ -149,3 +149,65 @@ fn dir_from_openat() {
     .expect("open cwd as file");
     let _dir = Dir::read_from(dirfd).expect("construct Dir from dirfd");
 }
+
+#[test]
+#[cfg(linux_kernel)]
+fn owned_raw_dir_grows() {
+    use rustix::fs::OwnedRawDir;
+
+    let tmpdir = tempfile::tempdir().expect("construct tempdir");
+    let long_name = "x".repeat(200);
+    File::create(tmpdir.path().join(&long_name)).expect("create long file");
+    File::create(tmpdir.path().join("short")).expect("create short file");
+
+    // Start with a buffer too small for any entry, so that it has to grow.
+    let dirfd = File::open(tmpdir.path()).expect("open tempdir as file");
+    let mut names: Vec<String> = OwnedRawDir::with_capacity(dirfd, 1)
+        .map(|entry| {
+            let entry = entry.expect("non-error entry");
+            entry
+                .file_name()
+                .to_str()
+                .expect("utf8 filename")
+                .to_owned()
+        })
+        .collect();
+    names.sort();
+    assert_eq!(names, [".", "..", "short", &long_name]);
+}
+
+#[test]
+#[cfg(linux_kernel)]
+fn owned_raw_dir_seek() {
+    use rustix::fs::{FileType, OwnedRawDir};
+
+    let tmpdir = tempfile::tempdir().expect("construct tempdir");
+    for name in ["a", "b", "c", "d"] {
+        File::create(tmpdir.path().join(name)).expect("create file");
+    }
+    std::fs::create_dir(tmpdir.path().join("dir")).expect("create dir");
+
+    let dirfd = File::open(tmpdir.path()).expect("open tempdir as file");
+    let mut dir = OwnedRawDir::new(dirfd);
+    let entries: Vec<_> = dir.by_ref().map(Result::unwrap).collect();
+    assert_eq!(entries.len(), 7);
+    assert!(dir.next().is_none());
+    for entry in &entries {
+        let expected = match entry.file_name().to_bytes() {
+            b"." | b".." | b"dir" => FileType::Directory,
+            _ => FileType::RegularFile,
+        };
+        assert_eq!(entry.file_type(), expected);
+    }
+
+    // Seeking to an entry's cookie continues after that entry.
+    dir.seek(entries[2].next_entry_cookie()).unwrap();
+    let rest: Vec<_> = dir.by_ref().map(Result::unwrap).collect();
+    assert_eq!(rest, entries[3..]);
+
+    // The borrowing `read` agrees with the iterator.
+    dir.rewind().unwrap();
+    let first = dir.read().unwrap().unwrap();
+    assert_eq!(first.to_owned_entry(), entries[0]);
+    assert_eq!(dir.next().unwrap().unwrap(), entries[1]);
+}
@@ -50,3 +50,63 @@ fn test_readlinkat() {
     let target = readlinkat(&dir, "another", Vec::new()).unwrap();
     assert_eq!(target.to_string_lossy(), "link");
 }
+
+#[cfg(not(target_os = "redox"))]
+#[test]
+fn test_readlinkat_into() {
+    use core::mem::MaybeUninit;
+    use rustix::fs::{openat, readlinkat_into, symlinkat, Mode, OFlags, CWD};
+
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = openat(CWD, tmp.path(), OFlags::RDONLY, Mode::empty()).unwrap();
+
+    let _ = openat(&dir, "foo", OFlags::CREATE | OFlags::WRONLY, Mode::RUSR).unwrap();
+    symlinkat("foo", &dir, "link").unwrap();
+
+    let mut buf = [MaybeUninit::<u8>::uninit(); 16];
+    readlinkat_into(&dir, "absent", &mut buf).unwrap_err();
+    readlinkat_into(&dir, "foo", &mut buf).unwrap_err();
+
+    let target = readlinkat_into(&dir, "link", &mut buf).unwrap();
+    assert_eq!(target.to_bytes(), b"foo");
+
+    // The buffer needs room for the NUL terminator.
+    let mut buf = [MaybeUninit::<u8>::uninit(); 4];
+    assert_eq!(
+        readlinkat_into(&dir, "link", &mut buf).unwrap().to_bytes(),
+        b"foo"
+    );
+    let mut buf = [MaybeUninit::<u8>::uninit(); 3];
+    assert_eq!(
+        readlinkat_into(&dir, "link", &mut buf).unwrap_err(),
+        rustix::io::Errno::RANGE
+    );
+}
+
+#[test]
+fn test_readlink_into() {
+    use core::mem::MaybeUninit;
+    use rustix::fs::{readlink, readlink_into, symlinkat, CWD};
+
+    let tmp = tempfile::tempdir().unwrap();
+    let link = tmp.path().join("link");
+    symlinkat("some/target", CWD, &link).unwrap();
+
+    let mut buf = [MaybeUninit::<u8>::uninit(); 12];
+    assert_eq!(
+        readlink_into(&link, &mut buf).unwrap().to_bytes(),
+        b"some/target"
+    );
+    assert_eq!(
+        readlink(&link, Vec::new()).unwrap().to_bytes(),
+        b"some/target"
+    );
+
+    // The buffer needs room for the NUL terminator.
+    let mut buf = [MaybeUninit::<u8>::uninit(); 11];
+    assert_eq!(
+        readlink_into(&link, &mut buf).unwrap_err(),
+        rustix::io::Errno::RANGE
+    );
+    readlink_into(tmp.path().join("absent"), &mut buf).unwrap_err();
+}
@@ -0,0 +1,43 @@
+use rustix::fs::{listmount, statmount, ListmountFlags, StatmountFlags, LSMT_ROOT};
+use rustix::io;
+
+#[test]
+fn test_statmount() {
+    let mut ids = [0_u64; 256];
+    let n = match listmount(LSMT_ROOT, 0, &mut ids, ListmountFlags::empty()) {
+        Ok(n) => n,
+        // `listmount` is new in Linux 6.8.
+        Err(io::Errno::NOSYS) | Err(io::Errno::PERM) => return,
+        Err(err) => panic!("{:?}", err),
+    };
+    assert_ne!(n, 0);
+
+    // The first mount listed is the root of the mount namespace.
+    let root = statmount(
+        ids[0],
+        StatmountFlags::MNT_BASIC | StatmountFlags::MNT_POINT | StatmountFlags::FS_TYPE,
+    )
+    .unwrap();
+    assert!(root.mask().contains(StatmountFlags::MNT_BASIC));
+    assert_eq!(root.mnt_id(), ids[0]);
+    assert!(!root.fs_type().unwrap().to_bytes().is_empty());
+
+    // Fields that weren't requested aren't reported.
+    assert!(!root.mask().contains(StatmountFlags::MNT_ROOT));
+    assert_eq!(root.mnt_root(), None);
+
+    // Listing in reverse gives the same mounts.
+    let mut reversed = [0_u64; 256];
+    let m = listmount(LSMT_ROOT, 0, &mut reversed, ListmountFlags::REVERSE).unwrap();
+    if n < ids.len() && m < reversed.len() {
+        reversed[..m].reverse();
+        assert_eq!(ids[..n], reversed[..m]);
+    }
+
+    // Resuming after the last ID listed continues the listing.
+    if n > 1 {
+        let mut rest = [0_u64; 256];
+        let k = listmount(LSMT_ROOT, ids[0], &mut rest, ListmountFlags::empty()).unwrap();
+        assert_eq!(rest[..k.min(n - 1)], ids[1..1 + k.min(n - 1)]);
+    }
+}
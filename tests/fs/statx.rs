@@ -37,3 +37,112 @@ fn test_statx_reserved() {
         Err(err) => assert_eq!(err, rustix::io::Errno::INVAL),
     }
 }
+
+#[test]
+fn test_statx_layout() {
+    use rustix::fs::{Statx, StatxTimestamp};
+
+    assert_eq!(
+        core::mem::size_of::<Statx>(),
+        core::mem::size_of::<linux_raw_sys::general::statx>()
+    );
+    assert_eq!(
+        core::mem::size_of::<StatxTimestamp>(),
+        core::mem::size_of::<linux_raw_sys::general::statx_timestamp>()
+    );
+}
+
+#[test]
+fn test_statx_accessors() {
+    use rustix::fs::{fstat, AtFlags, FileType, StatxAttributes, StatxFlags};
+
+    let f = std::fs::File::open("Cargo.toml").unwrap();
+    let mask = StatxFlags::BASIC_STATS | StatxFlags::BTIME | StatxFlags::MNT_ID;
+    let statx = match rustix::fs::statx(&f, "", AtFlags::EMPTY_PATH, mask) {
+        Err(rustix::io::Errno::NOSYS) => return,
+        otherwise => otherwise.unwrap(),
+    };
+    let stat = fstat(&f).unwrap();
+
+    assert!(statx.has(StatxFlags::BASIC_STATS));
+    assert_eq!(statx.file_type(), Some(FileType::RegularFile));
+    assert_eq!(statx.ino(), Some(stat.st_ino as u64));
+    assert_eq!(statx.size(), Some(stat.st_size as u64));
+    assert_eq!(statx.nlink(), Some(stat.st_nlink as u32));
+    assert_eq!(statx.uid().unwrap().as_raw(), stat.st_uid);
+    assert_eq!(statx.gid().unwrap().as_raw(), stat.st_gid);
+    assert_eq!(statx.dev(), stat.st_dev as u64);
+    assert_eq!(statx.mtime().unwrap().tv_sec, stat.st_mtime as i64);
+
+    // We didn't ask for `STATX_DIOALIGN`, so it's probably not filled in;
+    // whatever the kernel did, the accessor agrees with the mask.
+    assert_eq!(
+        statx.dio_mem_align().is_some(),
+        statx.mask().contains(StatxFlags::DIOALIGN)
+    );
+
+    // Attributes are only reported if the filesystem supports them.
+    for attr in [
+        StatxAttributes::IMMUTABLE,
+        StatxAttributes::APPEND,
+        StatxAttributes::VERITY,
+        StatxAttributes::DAX,
+    ] {
+        match statx.attribute(attr) {
+            None => assert!(!statx.attributes_mask().contains(attr)),
+            Some(set) => assert_eq!(set, statx.attributes().contains(attr)),
+        }
+    }
+    assert!(statx.attributes_mask().contains(statx.attributes()));
+}
+
+#[test]
+fn test_statx_timestamp_conversions() {
+    use core::time::Duration;
+    use rustix::fs::StatxTimestamp;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let t = StatxTimestamp::new(1_000, 500);
+    assert_eq!(Duration::try_from(t), Ok(Duration::new(1_000, 500)));
+    assert_eq!(
+        SystemTime::try_from(t),
+        Ok(UNIX_EPOCH + Duration::new(1_000, 500))
+    );
+
+    // Timestamps before the epoch have a negative `tv_sec` and a positive
+    // `tv_nsec`.
+    let t = StatxTimestamp::new(-2, 250_000_000);
+    assert_eq!(Duration::try_from(t), Err(rustix::io::Errno::OVERFLOW));
+    assert_eq!(
+        SystemTime::try_from(t),
+        Ok(UNIX_EPOCH - Duration::from_millis(1_750))
+    );
+
+    // Timestamps outside the range of `SystemTime` fail rather than panic.
+    let t = StatxTimestamp::new(i64::MAX, u32::MAX);
+    assert_eq!(SystemTime::try_from(t), Err(rustix::io::Errno::OVERFLOW));
+}
+
+#[test]
+fn test_statx_or_statat() {
+    use rustix::fs::{statat, statx_or_statat, AtFlags, FileType, StatxFlags, CWD};
+
+    let statx = statx_or_statat(
+        CWD,
+        "Cargo.toml",
+        AtFlags::STATX_DONT_SYNC,
+        StatxFlags::BASIC_STATS,
+    )
+    .unwrap();
+    let stat = statat(CWD, "Cargo.toml", AtFlags::empty()).unwrap();
+    assert!(statx.has(StatxFlags::BASIC_STATS));
+    assert_eq!(statx.file_type(), Some(FileType::RegularFile));
+    assert_eq!(statx.ino(), Some(stat.st_ino as u64));
+    assert_eq!(statx.size(), Some(stat.st_size as u64));
+    assert_eq!(statx.dev(), stat.st_dev as u64);
+
+    assert_eq!(
+        statx_or_statat(CWD, "no-such-file", AtFlags::empty(), StatxFlags::TYPE).unwrap_err(),
+        rustix::io::Errno::NOENT
+    );
+}
@@ -9,3 +9,82 @@ fn test_syncfs() {
     let f = std::fs::File::open("Cargo.toml").unwrap();
     rustix::fs::syncfs(&f).unwrap();
 }
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_sync_file_range() {
+    use rustix::fs::{sync_file_range, SyncFileRangeFlags};
+    use std::io::Write;
+
+    let mut f = tempfile::tempfile().unwrap();
+    f.write_all(&[1; 16384]).unwrap();
+
+    // Start writeback of the first half, and then wait for all of it.
+    sync_file_range(&f, 0, 8192, SyncFileRangeFlags::WRITE).unwrap();
+    sync_file_range(
+        &f,
+        0,
+        0,
+        SyncFileRangeFlags::WAIT_BEFORE
+            | SyncFileRangeFlags::WRITE
+            | SyncFileRangeFlags::WAIT_AFTER,
+    )
+    .unwrap();
+
+    assert_eq!(
+        sync_file_range(&f, 0, 0, SyncFileRangeFlags::from_bits_retain(8)),
+        Err(rustix::io::Errno::INVAL)
+    );
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_readahead() {
+    let f = std::fs::File::open("Cargo.toml").unwrap();
+    rustix::fs::readahead(&f, 0, 4096).unwrap();
+    rustix::fs::readahead(&f, 1 << 40, 4096).unwrap();
+}
+
+#[cfg(all(target_os = "linux", feature = "param"))]
+#[test]
+fn test_cachestat() {
+    use rustix::fs::{cachestat, fsync};
+    use std::io::Write;
+
+    let mut f = tempfile::tempfile().unwrap();
+    f.write_all(&[1; 16384]).unwrap();
+
+    let cstat = match cachestat(&f, 0, 0) {
+        Ok(cstat) => cstat,
+        Err(rustix::io::Errno::NOSYS) => return,
+        Err(err) => panic!("{:?}", err),
+    };
+    // We just wrote the data, so it's all cached, and at least some of it is
+    // probably dirty.
+    let pages = 16384 / rustix::param::page_size() as u64;
+    assert_eq!(cstat.nr_cache, pages);
+    assert!(cstat.nr_dirty + cstat.nr_writeback <= pages);
+
+    fsync(&f).unwrap();
+    let cstat = cachestat(&f, 0, 0).unwrap();
+    assert_eq!(cstat.nr_cache, pages);
+    assert_eq!(cstat.nr_dirty, 0);
+
+    // Ranges are respected.
+    assert_eq!(cachestat(&f, 0, 1).unwrap().nr_cache, 1);
+}
+
+#[cfg(all(target_os = "linux", feature = "mm", feature = "param"))]
+#[test]
+fn test_cached_pages() {
+    use rustix::fs::cached_pages;
+    use std::io::Write;
+
+    let page_size = rustix::param::page_size();
+    let mut f = tempfile::tempfile().unwrap();
+    f.write_all(&vec![1; page_size * 4]).unwrap();
+
+    assert_eq!(cached_pages(&f, 0, 0).unwrap(), 4);
+    assert_eq!(cached_pages(&f, page_size as u64 + 1, 1).unwrap(), 1);
+    assert_eq!(cached_pages(&f, page_size as u64 * 10, 0).unwrap(), 0);
+}
@@ -0,0 +1,97 @@
+use rustix::fd::AsFd;
+use rustix::fs::{
+    fstat, mkdtempat, mkstempat, openat, statat, AtFlags, FileType, Mode, OFlags, CWD,
+};
+use rustix::io;
+
+#[test]
+fn test_mkstempat() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = openat(CWD, tmp.path(), OFlags::RDONLY, Mode::empty()).unwrap();
+
+    let file = mkstempat(dir.as_fd(), "prefix.XXXXXX").unwrap();
+    let name = file.path().to_str().unwrap().to_owned();
+    assert_eq!(name.len(), "prefix.XXXXXX".len());
+    assert!(name.starts_with("prefix."));
+    assert_ne!(name, "prefix.XXXXXX");
+    assert!(name[7..].bytes().all(|b| b.is_ascii_alphanumeric()));
+
+    // The file is open for reading and writing, and only the owner can
+    // access it.
+    assert_eq!(io::write(&file, b"hello").unwrap(), 5);
+    let stat = fstat(&file).unwrap();
+    assert_eq!(FileType::from_raw_mode(stat.st_mode), FileType::RegularFile);
+    assert_eq!(stat.st_mode & 0o777, 0o600);
+
+    // Another file gets a different name. The directory can be passed by
+    // reference, as well as by `BorrowedFd`.
+    let other = mkstempat(&dir, "prefix.XXXXXX").unwrap();
+    assert_ne!(other.path(), file.path());
+
+    // Dropping the guard removes the file.
+    drop(file);
+    assert_eq!(
+        statat(&dir, name.as_str(), AtFlags::empty()).unwrap_err(),
+        io::Errno::NOENT
+    );
+
+    // Keeping it doesn't.
+    let (fd, path) = other.keep();
+    statat(&dir, &path, AtFlags::empty()).unwrap();
+    assert_eq!(fstat(&fd).unwrap().st_size, 0);
+
+    // Templates need at least six `X`s.
+    assert_eq!(
+        mkstempat(dir.as_fd(), "prefix.XXXXX").unwrap_err(),
+        io::Errno::INVAL
+    );
+    assert_eq!(
+        mkstempat(dir.as_fd(), "XXXXXX.suffix").unwrap_err(),
+        io::Errno::INVAL
+    );
+
+    // Templates may have more `X`s, and be paths.
+    let template = tmp.path().join("long-XXXXXXXXXXXX");
+    let long = mkstempat(CWD, &template).unwrap();
+    let long_name = long.path().to_str().unwrap();
+    assert_eq!(long_name.len(), template.to_str().unwrap().len());
+    assert_ne!(long_name, template.to_str().unwrap());
+    assert!(long_name[long_name.len() - 12..]
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric()));
+}
+
+#[test]
+fn test_mkdtempat() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = openat(CWD, tmp.path(), OFlags::RDONLY, Mode::empty()).unwrap();
+
+    let temp_dir = mkdtempat(dir.as_fd(), "dir-XXXXXX").unwrap();
+    let stat = statat(&dir, temp_dir.path(), AtFlags::empty()).unwrap();
+    assert_eq!(FileType::from_raw_mode(stat.st_mode), FileType::Directory);
+    assert_eq!(stat.st_mode & 0o777, 0o700);
+
+    // Dropping the guard removes the directory and its contents.
+    let name = temp_dir.path().to_owned();
+    let sub = tmp.path().join(name.to_str().unwrap());
+    std::fs::write(sub.join("file"), b"contents").unwrap();
+    std::fs::create_dir(sub.join("nested")).unwrap();
+    drop(temp_dir);
+    assert!(!sub.exists());
+
+    let kept = mkdtempat(dir.as_fd(), "dir-XXXXXX").unwrap().keep();
+    statat(&dir, &kept, AtFlags::empty()).unwrap();
+
+    // The guard can own the directory's file descriptor.
+    let owned = mkdtempat(dir, "dir-XXXXXX").unwrap();
+    let sub = tmp.path().join(owned.path().to_str().unwrap());
+    assert!(sub.is_dir());
+    drop(owned);
+    assert!(!sub.exists());
+    let dir = openat(CWD, tmp.path(), OFlags::RDONLY, Mode::empty()).unwrap();
+
+    assert_eq!(
+        mkdtempat(dir.as_fd(), "dir-").unwrap_err(),
+        io::Errno::INVAL
+    );
+}
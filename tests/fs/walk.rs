@@ -0,0 +1,237 @@
+use rustix::fs::{
+    openat, remove_dir_all_at, walk, FileType, Mode, OFlags, SymlinkPolicy, WalkOptions, CWD,
+};
+use rustix::io;
+use std::fs::{create_dir, create_dir_all, rename, File};
+use std::os::unix::fs::symlink;
+use std::path::Path;
+
+/// Walk `root` and collect the paths and types of the entries.
+fn collect(root: &Path, options: WalkOptions) -> Vec<(String, FileType, usize)> {
+    let mut walk = walk(CWD, root, options).unwrap();
+    let mut entries = Vec::new();
+    while let Some(entry) = walk.next() {
+        let entry = entry.unwrap();
+        let path = entry.path().to_str().unwrap().to_owned();
+        assert!(path.ends_with(entry.file_name().to_str().unwrap()));
+        entries.push((path, entry.file_type(), entry.depth()));
+    }
+    entries
+}
+
+fn paths(entries: &[(String, FileType, usize)]) -> Vec<&str> {
+    entries.iter().map(|(path, _, _)| path.as_str()).collect()
+}
+
+fn position(entries: &[(String, FileType, usize)], path: &str) -> usize {
+    entries
+        .iter()
+        .position(|(p, _, _)| p == path)
+        .unwrap_or_else(|| panic!("{} not found", path))
+}
+
+#[test]
+fn test_walk() {
+    let tmp = tempfile::tempdir().unwrap();
+    create_dir_all(tmp.path().join("a/b/c")).unwrap();
+    File::create(tmp.path().join("a/file")).unwrap();
+    File::create(tmp.path().join("a/b/c/file")).unwrap();
+    symlink("b", tmp.path().join("a/link")).unwrap();
+
+    let entries = collect(tmp.path(), WalkOptions::default());
+    let mut sorted = paths(&entries);
+    sorted.sort_unstable();
+    assert_eq!(
+        sorted,
+        ["a", "a/b", "a/b/c", "a/b/c/file", "a/file", "a/link"]
+    );
+    assert_eq!(entries[position(&entries, "a/b/c")].2, 3);
+    assert_eq!(entries[position(&entries, "a/link")].1, FileType::Symlink);
+
+    // Directories come before their contents.
+    assert!(position(&entries, "a") < position(&entries, "a/b"));
+    assert!(position(&entries, "a/b") < position(&entries, "a/b/c/file"));
+
+    // Unless we ask for them to come after.
+    let options = WalkOptions {
+        contents_first: true,
+        ..WalkOptions::default()
+    };
+    let entries = collect(tmp.path(), options);
+    assert_eq!(entries.len(), 6);
+    assert!(position(&entries, "a") > position(&entries, "a/b"));
+    assert!(position(&entries, "a/b") > position(&entries, "a/b/c/file"));
+
+    // Filter by type, and limit the depth.
+    let options = WalkOptions {
+        file_types: Some(vec![FileType::RegularFile]),
+        ..WalkOptions::default()
+    };
+    let mut entries = collect(tmp.path(), options);
+    entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(paths(&entries), ["a/b/c/file", "a/file"]);
+
+    let options = WalkOptions {
+        max_depth: 2,
+        ..WalkOptions::default()
+    };
+    let entries = collect(tmp.path(), options);
+    assert!(entries.iter().all(|(_, _, depth)| *depth <= 2));
+    assert_eq!(entries.len(), 4);
+}
+
+#[test]
+fn test_walk_symlinks() {
+    let tmp = tempfile::tempdir().unwrap();
+    create_dir_all(tmp.path().join("dir/sub")).unwrap();
+    File::create(tmp.path().join("dir/sub/file")).unwrap();
+    symlink("dir/sub", tmp.path().join("link")).unwrap();
+    symlink("nowhere", tmp.path().join("dangling")).unwrap();
+
+    let options = WalkOptions {
+        symlinks: SymlinkPolicy::Skip,
+        ..WalkOptions::default()
+    };
+    let mut entries = collect(tmp.path(), options);
+    entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(paths(&entries), ["dir", "dir/sub", "dir/sub/file"]);
+
+    let options = WalkOptions {
+        symlinks: SymlinkPolicy::Follow,
+        ..WalkOptions::default()
+    };
+    let mut entries = collect(tmp.path(), options);
+    entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(
+        paths(&entries),
+        [
+            "dangling",
+            "dir",
+            "dir/sub",
+            "dir/sub/file",
+            "link",
+            "link/file"
+        ]
+    );
+    assert_eq!(entries[0].1, FileType::Symlink);
+    assert_eq!(entries[4].1, FileType::Directory);
+
+    // A symlink to an ancestor is reported as a loop, and the walk can
+    // continue past it.
+    symlink("..", tmp.path().join("dir/sub/up")).unwrap();
+    let options = WalkOptions {
+        symlinks: SymlinkPolicy::Follow,
+        ..WalkOptions::default()
+    };
+    let mut walk = walk(CWD, tmp.path(), options).unwrap();
+    let mut loops = 0;
+    let mut files = 0;
+    while let Some(entry) = walk.next() {
+        match entry {
+            Ok(entry) if entry.file_name().to_bytes() == b"file" => files += 1,
+            Ok(_) => (),
+            Err(io::Errno::LOOP) => loops += 1,
+            Err(err) => panic!("{:?}", err),
+        }
+    }
+    // "dir/sub/up" points back to "dir". "link/up" does too, but "dir" isn't
+    // an ancestor of "link", so that's not a loop until "link/up/sub/up".
+    assert_eq!(loops, 2);
+    assert_eq!(files, 3);
+}
+
+#[test]
+fn test_walk_max_open_fds() {
+    let tmp = tempfile::tempdir().unwrap();
+    let deep = tmp.path().join("0/1/2/3/4/5/6/7/8/9");
+    create_dir_all(&deep).unwrap();
+    for i in 0..10 {
+        let mut dir = tmp.path().to_owned();
+        for j in 0..=i {
+            dir.push(j.to_string());
+        }
+        File::create(dir.join("a")).unwrap();
+        File::create(dir.join("z")).unwrap();
+    }
+
+    let mut unbounded = collect(tmp.path(), WalkOptions::default());
+    let options = WalkOptions {
+        max_open_fds: 3,
+        ..WalkOptions::default()
+    };
+    let mut bounded = collect(tmp.path(), options);
+    assert_eq!(bounded.len(), 30);
+    unbounded.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    bounded.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(unbounded, bounded);
+}
+
+#[test]
+fn test_walk_reopen_checks_identity() {
+    let tmp = tempfile::tempdir().unwrap();
+    create_dir_all(tmp.path().join("a/b/c/d")).unwrap();
+    create_dir_all(tmp.path().join("other/b/c/d")).unwrap();
+
+    let options = WalkOptions {
+        max_open_fds: 3,
+        file_types: Some(vec![FileType::Directory]),
+        ..WalkOptions::default()
+    };
+    let mut walk = walk(CWD, tmp.path().join("a"), options).unwrap();
+    loop {
+        let entry = walk.next().unwrap().unwrap();
+        if entry.path().to_bytes() == b"b/c/d" {
+            break;
+        }
+    }
+
+    // "b" has been closed. Replace it while we're not looking.
+    rename(tmp.path().join("a/b"), tmp.path().join("old")).unwrap();
+    rename(tmp.path().join("other/b"), tmp.path().join("a/b")).unwrap();
+
+    let mut errors = Vec::new();
+    while let Some(entry) = walk.next() {
+        if let Err(err) = entry {
+            errors.push(err);
+        }
+    }
+    assert_eq!(errors, [io::Errno::STALE]);
+}
+
+#[test]
+fn test_remove_dir_all_at() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = openat(
+        CWD,
+        tmp.path(),
+        OFlags::RDONLY | OFlags::DIRECTORY | OFlags::CLOEXEC,
+        Mode::empty(),
+    )
+    .unwrap();
+
+    // Something outside the tree, which mustn't be removed.
+    create_dir(tmp.path().join("outside")).unwrap();
+    File::create(tmp.path().join("outside/precious")).unwrap();
+
+    create_dir_all(tmp.path().join("tree/a/b")).unwrap();
+    File::create(tmp.path().join("tree/a/b/file")).unwrap();
+    File::create(tmp.path().join("tree/file")).unwrap();
+    symlink("../../outside", tmp.path().join("tree/a/link")).unwrap();
+    symlink("../outside/precious", tmp.path().join("tree/file-link")).unwrap();
+
+    remove_dir_all_at(&dir, "tree").unwrap();
+    assert!(!tmp.path().join("tree").exists());
+    assert!(tmp.path().join("outside/precious").exists());
+
+    // A symlink at the top is removed, not followed.
+    symlink("outside", tmp.path().join("top-link")).unwrap();
+    remove_dir_all_at(&dir, "top-link").unwrap();
+    assert!(tmp.path().join("outside/precious").exists());
+    assert!(tmp.path().join("top-link").symlink_metadata().is_err());
+
+    assert_eq!(
+        remove_dir_all_at(&dir, "outside/precious"),
+        Err(io::Errno::NOTDIR)
+    );
+    assert_eq!(remove_dir_all_at(&dir, "missing"), Err(io::Errno::NOENT));
+}
@@ -119,3 +119,74 @@ fn xattr_basic() {
         enodata
     );
 }
+
+#[cfg(linux_kernel)]
+#[test]
+fn xattr_vec() {
+    use rustix::fs::{XattrFlags, XattrNamespace};
+
+    let tmp = tempfile::NamedTempFile::new().unwrap();
+    let path = tmp.path();
+
+    match rustix::fs::setxattr(path, "user.small", b"abc", XattrFlags::CREATE) {
+        Ok(()) => (),
+        // Some filesystems don't support user attributes.
+        Err(rustix::io::Errno::OPNOTSUPP) => return,
+        Err(err) => panic!("{:?}", err),
+    }
+    let large = vec![0xa5_u8; 3000];
+    rustix::fs::setxattr(path, "user.large", &large, XattrFlags::CREATE).unwrap();
+    rustix::fs::setxattr(path, "user.empty", b"", XattrFlags::CREATE).unwrap();
+
+    assert_eq!(
+        rustix::fs::getxattr_vec(path, "user.small").unwrap(),
+        b"abc"
+    );
+    assert_eq!(
+        rustix::fs::lgetxattr_vec(path, "user.large").unwrap(),
+        large
+    );
+    assert_eq!(
+        rustix::fs::fgetxattr_vec(tmp.as_file(), "user.empty").unwrap(),
+        b""
+    );
+    assert_eq!(
+        rustix::fs::getxattr_vec(path, "user.missing")
+            .unwrap_err()
+            .raw_os_error(),
+        libc::ENODATA
+    );
+
+    for list in [
+        rustix::fs::listxattr_names(path).unwrap(),
+        rustix::fs::llistxattr_names(path).unwrap(),
+        rustix::fs::flistxattr_names(tmp.as_file()).unwrap(),
+    ] {
+        let mut names: Vec<_> = list
+            .in_namespace(XattrNamespace::User)
+            .map(|name| name.to_str().unwrap())
+            .collect();
+        names.sort_unstable();
+        assert_eq!(names, ["user.empty", "user.large", "user.small"]);
+
+        // Other namespaces, such as `security.selinux`, may also be present.
+        assert!(list.iter().count() >= 3);
+        for name in &list {
+            if let Some(namespace) = XattrNamespace::of(name) {
+                assert!(name.to_bytes().starts_with(namespace.prefix().as_bytes()));
+            }
+        }
+        assert_eq!(list.in_namespace(XattrNamespace::Trusted).count(), 0);
+    }
+
+    rustix::fs::removexattr(path, "user.small").unwrap();
+    rustix::fs::removexattr(path, "user.large").unwrap();
+    rustix::fs::removexattr(path, "user.empty").unwrap();
+    assert_eq!(
+        rustix::fs::listxattr_names(path)
+            .unwrap()
+            .in_namespace(XattrNamespace::User)
+            .count(),
+        0
+    );
+}
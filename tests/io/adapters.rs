@@ -0,0 +1,126 @@
+use rustix::io::{BufFdWriter, FdReader, FdWriter, IoSlice};
+
+#[test]
+fn test_read_exact_write_all() {
+    let (reader, writer) = rustix::pipe::pipe().unwrap();
+    let mut writer = FdWriter::new(writer);
+    let mut reader = FdReader::new(reader);
+
+    writer.write_all(b"hello ").unwrap();
+    writer
+        .write_all_vectored(&mut [
+            IoSlice::new(b""),
+            IoSlice::new(b"wor"),
+            IoSlice::new(b""),
+            IoSlice::new(b"ld"),
+        ])
+        .unwrap();
+    writer.write_all_vectored(&mut []).unwrap();
+
+    let mut buf = [0_u8; 11];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"hello world");
+
+    writer.write_all(b"!").unwrap();
+    drop(writer);
+
+    // End of file before `buf` is full.
+    let mut buf = [0_u8; 2];
+    assert_eq!(reader.read_exact(&mut buf), Err(rustix::io::Errno::IO));
+}
+
+#[cfg(linux_kernel)]
+#[test]
+fn test_write_all_vectored_partial() {
+    use rustix::pipe::{pipe_with, PipeFlags};
+
+    let (reader, writer) = pipe_with(PipeFlags::NONBLOCK).unwrap();
+
+    // Together, these are bigger than the default pipe buffer size.
+    let a = vec![b'a'; 50000];
+    let b = vec![b'b'; 30000];
+    let c = vec![b'c'; 90000];
+
+    // Without polling, a full non-blocking pipe fails with `EAGAIN`.
+    let mut nonpolling = FdWriter::new(&writer);
+    assert_eq!(
+        nonpolling.write_all_vectored(&mut [IoSlice::new(&a), IoSlice::new(&b)]),
+        Err(rustix::io::Errno::AGAIN)
+    );
+    let mut drain = [0_u8; 8192];
+    while rustix::io::read(&reader, &mut drain).is_ok() {}
+
+    let thread = std::thread::spawn(move || {
+        let mut reader = FdReader::new(reader).poll_on_again(true);
+        let mut buf = vec![0_u8; 170000];
+        reader.read_exact(&mut buf).unwrap();
+        buf
+    });
+
+    let mut writer = FdWriter::new(writer).poll_on_again(true);
+    writer
+        .write_all_vectored(&mut [IoSlice::new(&a), IoSlice::new(&b), IoSlice::new(&c)])
+        .unwrap();
+
+    let buf = thread.join().unwrap();
+    assert_eq!(&buf[..50000], &a[..]);
+    assert_eq!(&buf[50000..80000], &b[..]);
+    assert_eq!(&buf[80000..], &c[..]);
+}
+
+#[test]
+fn test_buf_fd_writer() {
+    let (reader, writer) = rustix::pipe::pipe().unwrap();
+    let mut writer = BufFdWriter::with_capacity(8, FdWriter::new(writer));
+
+    writer.write_all(b"abc").unwrap();
+    writer.write_all(b"def").unwrap();
+    assert_eq!(writer.buffer(), b"abcdef");
+
+    // Doesn't fit, so the buffer is flushed first.
+    writer.write_all(b"ghi").unwrap();
+    assert_eq!(writer.buffer(), b"ghi");
+
+    // Too big for the buffer, so it's written directly.
+    writer.write_all(b"jklmnopqrstu").unwrap();
+    assert!(writer.buffer().is_empty());
+
+    writer.write_all(b"vw").unwrap();
+    let writer = writer.into_inner().unwrap();
+
+    let mut buf = [0_u8; 23];
+    FdReader::new(&reader).read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"abcdefghijklmnopqrstuvw");
+
+    // Dropping flushes.
+    let mut writer = BufFdWriter::new(writer);
+    writer.write_all(b"xyz").unwrap();
+    drop(writer);
+
+    let mut buf = [0_u8; 3];
+    FdReader::new(&reader).read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"xyz");
+}
+
+#[test]
+fn test_buf_fd_writer_into_inner_error() {
+    let (reader, writer) = rustix::pipe::pipe().unwrap();
+    let mut writer = BufFdWriter::new(FdWriter::new(writer));
+    writer.write_all(b"abc").unwrap();
+
+    // With the read end closed, flushing fails, and the writer is returned
+    // with its buffered data intact.
+    drop(reader);
+    let err = writer.into_inner().unwrap_err();
+    assert_eq!(err.error(), rustix::io::Errno::PIPE);
+    let writer = err.into_inner();
+    assert_eq!(writer.buffer(), b"abc");
+
+    // The data and the file descriptor can be recovered without flushing.
+    let (writer, buf) = writer.into_parts();
+    assert_eq!(buf, b"abc");
+    assert_eq!(
+        FdWriter::new(writer.into_inner()).write(b"abc"),
+        Err(rustix::io::Errno::PIPE)
+    );
+}
@@ -0,0 +1,62 @@
+use rustix::fd::{BorrowedFd, IntoRawFd, RawFd};
+use rustix::io::{self, close_range, fcntl_dupfd_cloexec, fcntl_getfd, CloseRangeFlags, FdFlags};
+
+/// Open `n` consecutive file descriptors starting at `first`, without
+/// `FD_CLOEXEC` set.
+///
+/// Tests in this file run in parallel in the same process, so each uses its
+/// own range, far above where other tests' file descriptors will be.
+fn open_range(first: RawFd, n: RawFd) -> Vec<RawFd> {
+    let file = std::fs::File::open("Cargo.toml").unwrap();
+    (first..first + n)
+        .map(|expected| {
+            let fd = fcntl_dupfd_cloexec(&file, expected).unwrap();
+            rustix::io::fcntl_setfd(&fd, FdFlags::empty()).unwrap();
+            let fd = fd.into_raw_fd();
+            assert_eq!(fd, expected);
+            fd
+        })
+        .collect()
+}
+
+fn getfd(fd: RawFd) -> io::Result<FdFlags> {
+    fcntl_getfd(unsafe { BorrowedFd::borrow_raw(fd) })
+}
+
+#[test]
+fn test_close_range() {
+    let fds = open_range(3000, 4);
+
+    // Close the middle two.
+    unsafe { close_range(3001, 3002, CloseRangeFlags::empty()).unwrap() };
+    assert_eq!(getfd(3000), Ok(FdFlags::empty()));
+    assert_eq!(getfd(3001), Err(io::Errno::BADF));
+    assert_eq!(getfd(3002), Err(io::Errno::BADF));
+    assert_eq!(getfd(3003), Ok(FdFlags::empty()));
+
+    // Ranges may include file descriptors which aren't open.
+    unsafe { close_range(fds[0] as u32, fds[3] as u32 + 50, CloseRangeFlags::empty()).unwrap() };
+    for fd in fds {
+        assert_eq!(getfd(fd), Err(io::Errno::BADF));
+    }
+
+    assert_eq!(
+        unsafe { close_range(3010, 3000, CloseRangeFlags::empty()) },
+        Err(io::Errno::INVAL)
+    );
+}
+
+#[test]
+fn test_close_range_cloexec() {
+    let fds = open_range(3100, 3);
+
+    unsafe { close_range(3100, 3101, CloseRangeFlags::CLOEXEC).unwrap() };
+    assert_eq!(getfd(3100), Ok(FdFlags::CLOEXEC));
+    assert_eq!(getfd(3101), Ok(FdFlags::CLOEXEC));
+    assert_eq!(getfd(3102), Ok(FdFlags::empty()));
+
+    unsafe { close_range(3100, 3102, CloseRangeFlags::empty()).unwrap() };
+    for fd in fds {
+        assert_eq!(getfd(fd), Err(io::Errno::BADF));
+    }
+}
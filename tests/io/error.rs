@@ -12,3 +12,83 @@ fn test_error() {
         windows_sys::Win32::Networking::WinSock::WSAEINVAL
     );
 }
+
+#[cfg(not(windows))]
+#[test]
+fn test_error_name() {
+    use rustix::io::Errno;
+
+    assert_eq!(Errno::NOENT.name(), Some("ENOENT"));
+    assert_eq!(
+        Errno::NOENT.description(),
+        Some("No such file or directory")
+    );
+    assert_eq!(Errno::from_name("ENOENT"), Some(Errno::NOENT));
+    assert_eq!(
+        Errno::from_raw_os_error(libc::EAGAIN).name(),
+        Some("EAGAIN")
+    );
+    assert_eq!(Errno::from_name("EWOULDBLOCK"), Some(Errno::WOULDBLOCK));
+
+    // Both of the names for "Operation not supported" are accepted, and
+    // Linux's primary name is the one that's returned.
+    assert_eq!(Errno::from_name("EOPNOTSUPP"), Some(Errno::OPNOTSUPP));
+    assert_eq!(Errno::from_name("ENOTSUP"), Some(Errno::NOTSUP));
+    #[cfg(linux_kernel)]
+    assert_eq!(Errno::OPNOTSUPP.name(), Some("EOPNOTSUPP"));
+    #[cfg(linux_kernel)]
+    assert_eq!(Errno::DEADLOCK.name(), Some("EDEADLK"));
+    assert_eq!(Errno::from_name("ENOENT\0"), None);
+
+    // Every named error code round-trips through its name, and agrees with
+    // libc's numbering.
+    for raw in 1..256 {
+        let errno = Errno::from_raw_os_error(raw);
+        if let Some(name) = errno.name() {
+            assert_eq!(Errno::from_name(name), Some(errno));
+            assert!(errno.description().is_some());
+        }
+    }
+
+    // Other C libraries' `strerror` messages differ from glibc's.
+    #[cfg(all(target_os = "linux", target_env = "gnu"))]
+    for raw in 1..134 {
+        let errno = Errno::from_raw_os_error(raw);
+        let message = unsafe { std::ffi::CStr::from_ptr(libc::strerror(raw)) };
+        let message = message.to_str().unwrap();
+        match errno.description() {
+            Some(description) => assert_eq!(description, message, "{:?}", errno.name()),
+            None => assert!(message.starts_with("Unknown error"), "{}", raw),
+        }
+    }
+}
+
+#[cfg(not(windows))]
+#[test]
+fn test_error_kind() {
+    use rustix::io::Errno;
+    use std::io::ErrorKind;
+
+    assert_eq!(Errno::NOENT.kind(), ErrorKind::NotFound);
+    assert_eq!(Errno::OPNOTSUPP.kind(), ErrorKind::Unsupported);
+    assert_eq!(Errno::NOBUFS.kind(), ErrorKind::OutOfMemory);
+    assert_eq!(Errno::ILSEQ.kind(), ErrorKind::InvalidData);
+    assert_eq!(
+        std::io::Error::from(Errno::NOENT).raw_os_error(),
+        Some(libc::ENOENT)
+    );
+
+    // Converting to a `std::io::Error` keeps the raw error code, including
+    // for error codes that `Errno::kind` categorizes differently than `std`.
+    assert_eq!(
+        std::io::Error::from(Errno::BADF).raw_os_error(),
+        Some(libc::EBADF)
+    );
+    for raw in 1..256 {
+        let errno = Errno::from_raw_os_error(raw);
+        let io_err = std::io::Error::from(errno);
+        assert_eq!(io_err.raw_os_error(), Some(raw), "{:?}", errno);
+        assert_eq!(Errno::from_io_error(&io_err), Some(errno));
+        assert_eq!(io_err.to_string(), errno.to_string());
+    }
+}
@@ -1,5 +1,11 @@
 //! Tests for [`rustix::io`].
 
+#[cfg(not(windows))]
+#[cfg(feature = "event")]
+#[cfg(feature = "pipe")]
+mod adapters;
+#[cfg(linux_kernel)]
+mod close_range;
 mod error;
 #[cfg(not(windows))]
 mod from_into;
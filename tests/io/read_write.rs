@@ -198,3 +198,34 @@ fn test_pwritev2() {
     .unwrap();
     assert_eq!(&buf, b"world");
 }
+
+#[cfg(feature = "fs")]
+#[test]
+fn test_readwrite_uninit() {
+    use core::mem::MaybeUninit;
+    use rustix::fs::{openat, seek, Mode, OFlags, SeekFrom, CWD};
+    use rustix::io::{pread_uninit, read_uninit, write};
+
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = openat(CWD, tmp.path(), OFlags::RDONLY, Mode::empty()).unwrap();
+    let foo = openat(
+        &dir,
+        "foo",
+        OFlags::RDWR | OFlags::CREATE | OFlags::TRUNC,
+        Mode::RUSR | Mode::WUSR,
+    )
+    .unwrap();
+
+    write(&foo, b"hello world").unwrap();
+    seek(&foo, SeekFrom::Start(0)).unwrap();
+
+    let mut buf = [MaybeUninit::<u8>::uninit(); 16];
+    let (init, uninit) = read_uninit(&foo, &mut buf).unwrap();
+    assert_eq!(init, b"hello world");
+    assert_eq!(uninit.len(), 5);
+
+    let mut buf = [MaybeUninit::<u8>::uninit(); 5];
+    let (init, uninit) = pread_uninit(&foo, &mut buf, 6).unwrap();
+    assert_eq!(init, b"world");
+    assert!(uninit.is_empty());
+}
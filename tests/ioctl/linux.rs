@@ -0,0 +1,99 @@
+use rustix::fd::AsFd;
+use rustix::io;
+use rustix::ioctl::{ioctl, opcode, Direction, Getter, NoArg, Opcode, Setter};
+use std::io::Write;
+use std::os::unix::net::UnixStream;
+
+#[cfg(not(any(
+    target_arch = "mips",
+    target_arch = "mips32r6",
+    target_arch = "mips64",
+    target_arch = "mips64r6",
+    target_arch = "powerpc",
+    target_arch = "powerpc64",
+    target_arch = "sparc",
+    target_arch = "sparc64",
+)))]
+#[test]
+fn test_opcode_encoding() {
+    // `BLKSSZGET`
+    assert_eq!(opcode::none(0x12, 104), 0x1268);
+    // `FS_IOC_GETFLAGS`
+    assert_eq!(opcode::read::<u64>(b'f', 1), 0x8008_6601);
+    // `TIOCSPTLCK`
+    assert_eq!(opcode::write::<i32>(b'T', 0x31), 0x4004_5431);
+    // `FS_IOC_FIEMAP`
+    assert_eq!(opcode::read_write::<[u64; 4]>(b'f', 11), 0xc020_660b);
+    assert_eq!(
+        opcode::from_components(Direction::Read, b'f', 1, 8),
+        opcode::read::<u64>(b'f', 1)
+    );
+}
+
+#[cfg(any(
+    target_arch = "mips",
+    target_arch = "mips32r6",
+    target_arch = "mips64",
+    target_arch = "mips64r6",
+    target_arch = "powerpc",
+    target_arch = "powerpc64",
+    target_arch = "sparc",
+    target_arch = "sparc64",
+))]
+#[test]
+fn test_opcode_encoding() {
+    // `BLKSSZGET`
+    assert_eq!(opcode::none(0x12, 104), 0x2000_1268);
+    // `FS_IOC_GETFLAGS`
+    assert_eq!(opcode::read::<u64>(b'f', 1), 0x4008_6601);
+    // `TIOCSPTLCK`
+    assert_eq!(opcode::write::<i32>(b'T', 0x31), 0x8004_5431);
+    // `FS_IOC_FIEMAP`
+    assert_eq!(opcode::read_write::<[u64; 4]>(b'f', 11), 0xc020_660b);
+}
+
+#[test]
+fn test_ioctl_getter_setter() {
+    let (mut a, b) = UnixStream::pair().unwrap();
+    a.write_all(b"abc").unwrap();
+
+    // `FIONREAD` predates `_IOC`, so use its raw value.
+    let nread = unsafe {
+        ioctl(
+            &b,
+            Getter::<{ libc::FIONREAD as Opcode }, libc::c_int>::new(),
+        )
+    }
+    .unwrap();
+    assert_eq!(nread, 3);
+
+    let mut buf = [0_u8; 8];
+    assert_eq!(io::read(&b, &mut buf).unwrap(), 3);
+
+    // With `FIONBIO` set, reading from the empty socket doesn't block.
+    unsafe {
+        ioctl(
+            &b,
+            Setter::<{ libc::FIONBIO as Opcode }, libc::c_int>::new(1),
+        )
+    }
+    .unwrap();
+    assert_eq!(io::read(&b, &mut buf), Err(io::Errno::WOULDBLOCK));
+}
+
+#[test]
+fn test_ioctl_no_arg() {
+    let (a, _b) = UnixStream::pair().unwrap();
+
+    unsafe { ioctl(a.as_fd(), NoArg::<{ libc::FIOCLEX as Opcode }>::new()) }.unwrap();
+    assert!(io::fcntl_getfd(&a).unwrap().contains(io::FdFlags::CLOEXEC));
+
+    unsafe { ioctl(a.as_fd(), NoArg::<{ libc::FIONCLEX as Opcode }>::new()) }.unwrap();
+    assert!(!io::fcntl_getfd(&a).unwrap().contains(io::FdFlags::CLOEXEC));
+
+    // Errors are reported as `Errno`s.
+    assert_eq!(
+        unsafe { ioctl(&a, NoArg::<{ opcode::none(0xfe, 0xfe) }>::new()) },
+        Err(io::Errno::NOTTY)
+    );
+}
@@ -0,0 +1,6 @@
+//! Tests for [`rustix::ioctl`].
+
+#![cfg(not(any(windows, target_os = "wasi")))]
+
+#[cfg(linux_kernel)]
+mod linux;
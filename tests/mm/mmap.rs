@@ -160,3 +160,41 @@ fn test_msync() {
         munmap(addr, 8192).unwrap();
     }
 }
+
+#[cfg(all(linux_kernel, feature = "param"))]
+#[test]
+fn test_mincore() {
+    use rustix::mm::{mincore, mmap_anonymous, munmap, MapFlags, ProtFlags};
+    use rustix::param::page_size;
+    use std::ptr::null_mut;
+
+    let page_size = page_size();
+    unsafe {
+        let addr = mmap_anonymous(
+            null_mut(),
+            page_size * 3,
+            ProtFlags::READ | ProtFlags::WRITE,
+            MapFlags::PRIVATE,
+        )
+        .unwrap();
+
+        // Only the page we touch is resident.
+        *addr.cast::<u8>().add(page_size) = 1;
+        let mut vec = [0xff_u8; 3];
+        mincore(addr, page_size * 3, &mut vec).unwrap();
+        assert_eq!(vec.map(|page| page & 1), [0, 1, 0]);
+
+        // A partial page at the end needs a byte too.
+        assert_eq!(
+            mincore(addr, page_size * 2 + 1, &mut vec[..2]),
+            Err(rustix::io::Errno::INVAL)
+        );
+        mincore(addr, page_size * 2 + 1, &mut vec).unwrap();
+
+        munmap(addr, page_size * 3).unwrap();
+        assert_eq!(
+            mincore(addr, page_size * 3, &mut vec),
+            Err(rustix::io::Errno::NOMEM)
+        );
+    }
+}
@@ -263,6 +263,89 @@ fn net_v4_sendto() -> std::io::Result<()> {
     Ok(())
 }
 
+/// Test `recv_uninit` and `recvfrom_uninit`.
+#[test]
+fn net_v4_recv_uninit() -> std::io::Result<()> {
+    use std::mem::MaybeUninit;
+
+    let localhost = IpAddr::V4(Ipv4Addr::LOCALHOST);
+    let addr = SocketAddr::new(localhost, 0);
+    let listener =
+        rustix::net::socket(AddressFamily::INET, SocketType::STREAM, Protocol::default())?;
+    rustix::net::bind(&listener, &addr).expect("bind");
+    rustix::net::listen(&listener, 1).expect("listen");
+
+    let local_addr = rustix::net::getsockname(&listener)?;
+    let sender = rustix::net::socket(AddressFamily::INET, SocketType::STREAM, Protocol::default())?;
+    rustix::net::connect_any(&sender, &local_addr).expect("connect");
+    let request = b"Hello, World!!!";
+    let n = rustix::net::send(&sender, request, SendFlags::empty()).expect("send");
+    assert_eq!(n, request.len());
+    let n = rustix::net::send(&sender, request, SendFlags::empty()).expect("send");
+    assert_eq!(n, request.len());
+    drop(sender);
+
+    let accepted = rustix::net::accept(&listener).expect("accept");
+
+    let mut response = [MaybeUninit::<u8>::uninit(); 4];
+    let (init, uninit, len) =
+        rustix::net::recv_uninit(&accepted, &mut response, RecvFlags::WAITALL).expect("recv");
+    assert_eq!(init, &request[..4]);
+    assert!(uninit.is_empty());
+    assert_eq!(len, 4);
+
+    let mut response = [MaybeUninit::<u8>::uninit(); 128];
+    let (init, uninit, len, from) =
+        rustix::net::recvfrom_uninit(&accepted, &mut response, RecvFlags::WAITALL).expect("recv");
+    assert_eq!(init, b"o, World!!!Hello, World!!!");
+    assert_eq!(uninit.len(), 128 - init.len());
+    assert_eq!(len, init.len());
+    assert!(from.is_none());
+
+    Ok(())
+}
+
+/// Test `recv_uninit` and `recvfrom_uninit` with `RecvFlags::TRUNC`, which
+/// makes Linux report the full length of a datagram that doesn't fit.
+#[cfg(linux_kernel)]
+#[test]
+fn net_v4_recv_uninit_trunc() -> std::io::Result<()> {
+    use std::mem::MaybeUninit;
+
+    let localhost = IpAddr::V4(Ipv4Addr::LOCALHOST);
+    let addr = SocketAddr::new(localhost, 0);
+    let receiver =
+        rustix::net::socket(AddressFamily::INET, SocketType::DGRAM, Protocol::default())?;
+    rustix::net::bind(&receiver, &addr).expect("bind");
+    let local_addr = rustix::net::getsockname(&receiver)?;
+
+    let sender = rustix::net::socket(AddressFamily::INET, SocketType::DGRAM, Protocol::default())?;
+    rustix::net::bind(&sender, &addr).expect("bind");
+    rustix::net::connect_any(&sender, &local_addr).expect("connect");
+    let request = b"Hello, World!!!";
+    for _ in 0..2 {
+        let n = rustix::net::send(&sender, request, SendFlags::empty()).expect("send");
+        assert_eq!(n, request.len());
+    }
+
+    let mut response = [MaybeUninit::<u8>::uninit(); 4];
+    let (init, uninit, len) =
+        rustix::net::recv_uninit(&receiver, &mut response, RecvFlags::TRUNC).expect("recv");
+    assert_eq!(init, &request[..4]);
+    assert!(uninit.is_empty());
+    assert_eq!(len, request.len());
+
+    let mut response = [MaybeUninit::<u8>::uninit(); 8];
+    let (init, uninit, len, from) =
+        rustix::net::recvfrom_uninit(&receiver, &mut response, RecvFlags::TRUNC).expect("recv");
+    assert_eq!(init, &request[..8]);
+    assert!(uninit.is_empty());
+    assert_eq!(len, request.len());
+    assert_eq!(from, Some(rustix::net::getsockname(&sender)?));
+
+    Ok(())
+}
+
 /// Similar, but with V6.
 #[test]
 fn net_v6_sendto() -> std::io::Result<()> {
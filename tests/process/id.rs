@@ -109,3 +109,19 @@ fn test_getsid() {
         process::getsid(Some(process::getpid()))
     );
 }
+
+#[test]
+fn test_getgroups_into() {
+    let groups = process::getgroups().unwrap();
+
+    let mut buf = vec![process::Gid::ROOT; groups.len()];
+    assert_eq!(process::getgroups_into(&mut buf).unwrap(), &groups[..]);
+
+    if !groups.is_empty() {
+        let mut buf = vec![process::Gid::ROOT; groups.len() - 1];
+        assert_eq!(
+            process::getgroups_into(&mut buf).unwrap_err(),
+            rustix::io::Errno::RANGE
+        );
+    }
+}
@@ -41,3 +41,22 @@ fn test_changing_working_directory() {
         "The cwd wasn't changed back to the its original position"
     );
 }
+
+#[test]
+fn test_getcwd_into() {
+    use core::mem::MaybeUninit;
+
+    let cwd = rustix::process::getcwd(Vec::new()).expect("get the cwd");
+
+    let mut buf = vec![MaybeUninit::<u8>::uninit(); cwd.as_bytes_with_nul().len()];
+    assert_eq!(
+        rustix::process::getcwd_into(&mut buf).expect("get the cwd"),
+        cwd.as_c_str()
+    );
+
+    let mut buf = vec![MaybeUninit::<u8>::uninit(); cwd.as_bytes().len()];
+    assert_eq!(
+        rustix::process::getcwd_into(&mut buf).unwrap_err(),
+        rustix::io::Errno::RANGE
+    );
+}
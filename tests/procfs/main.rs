@@ -4,3 +4,4 @@
 #![cfg(linux_kernel)]
 
 mod basic;
+mod mountinfo;
@@ -0,0 +1,56 @@
+use rustix::ffi::CString;
+use rustix::procfs::{mountinfo, MountInfo, MountOptionalField};
+
+#[test]
+fn test_mountinfo_parse() {
+    let info = MountInfo::parse(
+        b"36 35 98:0 /mnt1 /mnt\\0402 rw,noatime master:1 propagate_from:4 unbindable - ext3 /dev/root rw,errors=continue\n",
+    )
+    .unwrap();
+    assert_eq!(info.mount_id, 36);
+    assert_eq!(info.parent_id, 35);
+    assert_eq!((info.major, info.minor), (98, 0));
+    assert_eq!(info.root, CString::new("/mnt1").unwrap());
+    assert_eq!(info.mount_point, CString::new("/mnt 2").unwrap());
+    assert_eq!(info.mount_options, CString::new("rw,noatime").unwrap());
+    assert_eq!(
+        info.optional_fields,
+        [
+            MountOptionalField::Master(1),
+            MountOptionalField::PropagateFrom(4),
+            MountOptionalField::Unbindable,
+        ]
+    );
+    assert_eq!(info.fs_type, CString::new("ext3").unwrap());
+    assert_eq!(info.source, CString::new("/dev/root").unwrap());
+    assert_eq!(
+        info.super_options,
+        CString::new("rw,errors=continue").unwrap()
+    );
+
+    let info = MountInfo::parse(b"1 0 0:1 / / rw - rootfs a\\\\b\\134c rw").unwrap();
+    assert!(info.optional_fields.is_empty());
+    assert_eq!(info.source, CString::new("a\\\\b\\c").unwrap());
+
+    assert!(MountInfo::parse(b"").is_err());
+    assert!(MountInfo::parse(b"1 0 0:1 / / rw shared:1").is_err());
+    assert!(MountInfo::parse(b"1 0 0-1 / / rw - rootfs none rw").is_err());
+    assert!(MountInfo::parse(b"1 0 0:1 / / rw - rootfs none rw extra").is_err());
+}
+
+#[test]
+fn test_mountinfo() {
+    let mounts = mountinfo().unwrap();
+    assert!(mounts
+        .iter()
+        .any(|info| info.mount_point.as_bytes() == b"/"));
+
+    // Whichever way `mountinfo` got its answer, it matches the file.
+    let contents = std::fs::read("/proc/self/mountinfo").unwrap();
+    let parsed = contents
+        .split(|b| *b == b'\n')
+        .filter(|line| !line.is_empty())
+        .map(|line| MountInfo::parse(line).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(mounts, parsed);
+}
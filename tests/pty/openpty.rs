@@ -70,3 +70,37 @@ fn openpty_get_peer() -> io::Result<()> {
     assert_eq!(s, "Hello, world!\n");
     Ok(())
 }
+
+#[test]
+fn openpty_ptsname_into() -> io::Result<()> {
+    use core::mem::MaybeUninit;
+
+    let controller = openpt(OpenptFlags::RDWR | OpenptFlags::NOCTTY)?;
+    grantpt(&controller)?;
+    unlockpt(&controller)?;
+
+    let name = ptsname(&controller, Vec::new())?;
+
+    let mut buf = [MaybeUninit::<u8>::uninit(); 64];
+    assert_eq!(ptsname_into(&controller, &mut buf)?, name.as_c_str());
+
+    let mut buf = vec![MaybeUninit::<u8>::uninit(); name.as_bytes().len()];
+    assert_eq!(
+        ptsname_into(&controller, &mut buf).unwrap_err(),
+        rustix::io::Errno::RANGE
+    );
+
+    // `ttyname` of the user side is the name from `ptsname`.
+    #[cfg(all(feature = "termios", feature = "procfs"))]
+    {
+        let user = openat(CWD, &name, OFlags::RDWR | OFlags::NOCTTY, Mode::empty())?;
+        assert_eq!(rustix::termios::ttyname(&user, Vec::new())?, name);
+        let mut buf = [MaybeUninit::<u8>::uninit(); 64];
+        assert_eq!(
+            rustix::termios::ttyname_into(&user, &mut buf)?,
+            name.as_c_str()
+        );
+    }
+
+    Ok(())
+}
@@ -5,3 +5,13 @@ fn test_getrandom() {
     let mut buf = [0_u8; 256];
     let _ = getrandom(&mut buf, GetRandomFlags::empty());
 }
+
+#[test]
+fn test_getrandom_uninit() {
+    use core::mem::MaybeUninit;
+    use rustix::rand::getrandom_uninit;
+
+    let mut buf = [MaybeUninit::<u8>::uninit(); 256];
+    let (init, uninit) = getrandom_uninit(&mut buf, GetRandomFlags::empty()).unwrap();
+    assert_eq!(init.len() + uninit.len(), 256);
+}
@@ -16,6 +16,14 @@ fn test_name() {
     dbg!(name().unwrap());
 }
 
+#[test]
+fn test_name_into() {
+    let mut buf = [0xff_u8; 16];
+    let name = name_into(&mut buf).unwrap().to_owned();
+    assert!(name.to_bytes().len() < 16);
+    assert_eq!(name, rustix::thread::name().unwrap());
+}
+
 #[test]
 fn test_capability_is_in_bounding_set() {
     dbg!(capability_is_in_bounding_set(Capability::ChangeOwnership).unwrap());